@@ -0,0 +1,228 @@
+//! File paths and external asset dependency reporting
+//!
+//! `PathData` carries file paths through the graph so they can be resolved
+//! against the host's project root when projects move. Nodes also report the
+//! external files they depend on (textures, USD layers, caches) so the host can
+//! collect/archive projects and report missing files across all plugins without
+//! knowing each plugin's parameters.
+//!
+//! When a project or asset library moves, the host's "relink assets" operation
+//! builds a `RelinkRequest` (old root → new root) and sends it to every node
+//! through `PluginNode::relink_paths`.
+
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+
+/// File path value flowing between nodes
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct PathData {
+    /// Path exactly as entered/stored
+    pub raw: String,
+    /// Whether the path is relative (resolved against the project root)
+    pub is_relative: bool,
+}
+
+impl PathData {
+    /// Create path data, detecting whether the path is relative
+    pub fn new(raw: impl Into<String>) -> Self {
+        let raw = raw.into();
+        let is_relative = Path::new(&raw).is_relative();
+        Self { raw, is_relative }
+    }
+
+    /// Resolve against a project root (absolute paths are returned unchanged)
+    pub fn resolve(&self, project_root: &Path) -> PathBuf {
+        if self.is_relative {
+            project_root.join(&self.raw)
+        } else {
+            PathBuf::from(&self.raw)
+        }
+    }
+
+    /// Resolve using the host's path resolver
+    pub fn resolve_with(&self, resolver: &dyn PathResolver) -> PathBuf {
+        resolver.resolve(self)
+    }
+
+    /// Rewrite the path with a relink request (None if no root matches)
+    pub fn relinked(&self, request: &RelinkRequest) -> Option<PathData> {
+        request.rewrite(&self.raw).map(PathData::new)
+    }
+
+    /// Get the file extension, lowercased
+    pub fn extension(&self) -> Option<String> {
+        Path::new(&self.raw)
+            .extension()
+            .map(|e| e.to_string_lossy().to_lowercase())
+    }
+}
+
+/// Host-provided path resolution
+///
+/// The main application implements this trait so plugins can resolve relative
+/// paths without knowing where the project lives.
+pub trait PathResolver: Send + Sync {
+    /// Root directory of the current project (None for unsaved projects)
+    fn project_root(&self) -> Option<PathBuf>;
+
+    /// Resolve a path (default: join relative paths onto the project root)
+    fn resolve(&self, path: &PathData) -> PathBuf {
+        match self.project_root() {
+            Some(root) => path.resolve(&root),
+            None => PathBuf::from(&path.raw),
+        }
+    }
+}
+
+/// Kind of external asset a node depends on
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum AssetKind {
+    /// Image/texture file
+    Texture,
+    /// USD layer, payload or reference
+    USDLayer,
+    /// On-disk cache file
+    Cache,
+    /// Geometry file (OBJ, Alembic, ...)
+    Geometry,
+    /// Any other file type
+    Other(String),
+}
+
+/// Reference to an external file used by a node
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct AssetRef {
+    /// File path as stored in the node (may be relative to the project)
+    pub path: String,
+    /// Kind of asset
+    pub kind: AssetKind,
+    /// Parameter holding the path (lets the host relink it via `set_parameter`)
+    pub parameter: Option<String>,
+    /// Whether the node fails without this asset
+    pub required: bool,
+}
+
+impl AssetRef {
+    /// Create a required asset reference
+    pub fn new(path: impl Into<String>, kind: AssetKind) -> Self {
+        Self {
+            path: path.into(),
+            kind,
+            parameter: None,
+            required: true,
+        }
+    }
+
+    /// Create a texture reference
+    pub fn texture(path: impl Into<String>) -> Self {
+        Self::new(path, AssetKind::Texture)
+    }
+
+    /// Create a USD layer reference
+    pub fn usd_layer(path: impl Into<String>) -> Self {
+        Self::new(path, AssetKind::USDLayer)
+    }
+
+    /// Create a cache file reference (optional by default, caches can be rebuilt)
+    pub fn cache(path: impl Into<String>) -> Self {
+        Self::new(path, AssetKind::Cache).optional()
+    }
+
+    /// Record which parameter holds the path
+    pub fn with_parameter(mut self, parameter: impl Into<String>) -> Self {
+        self.parameter = Some(parameter.into());
+        self
+    }
+
+    /// Mark the asset as optional
+    pub fn optional(mut self) -> Self {
+        self.required = false;
+        self
+    }
+
+    /// Check if the file exists (relative paths are resolved against the working directory)
+    pub fn exists(&self) -> bool {
+        Path::new(&self.path).exists()
+    }
+
+    /// Check if the file exists, resolving relative paths against `base_dir`
+    pub fn exists_relative_to(&self, base_dir: &Path) -> bool {
+        let path = Path::new(&self.path);
+        if path.is_absolute() {
+            path.exists()
+        } else {
+            base_dir.join(path).exists()
+        }
+    }
+}
+
+/// Replacement of one directory root by another
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct PathRemap {
+    /// Root being replaced
+    pub from: String,
+    /// Root it is replaced with
+    pub to: String,
+}
+
+impl PathRemap {
+    /// Create a remap
+    pub fn new(from: impl Into<String>, to: impl Into<String>) -> Self {
+        Self {
+            from: from.into(),
+            to: to.into(),
+        }
+    }
+
+    /// Rewrite a path under `from` (None if it lies elsewhere)
+    ///
+    /// Roots match whole path components, so `/mnt/show` does not match
+    /// `/mnt/showcase/tex.png`.
+    pub fn apply(&self, path: &str) -> Option<String> {
+        let rest = Path::new(path).strip_prefix(&self.from).ok()?;
+        if rest.as_os_str().is_empty() {
+            Some(self.to.clone())
+        } else {
+            Some(Path::new(&self.to).join(rest).to_string_lossy().into_owned())
+        }
+    }
+}
+
+/// Bulk path rewrite sent by the host's "relink assets" operation
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RelinkRequest {
+    pub remaps: Vec<PathRemap>,
+}
+
+impl RelinkRequest {
+    /// Create an empty request
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a root replacement
+    pub fn remap(mut self, from: impl Into<String>, to: impl Into<String>) -> Self {
+        self.remaps.push(PathRemap::new(from, to));
+        self
+    }
+
+    /// Rewrite a path (None if no root matches)
+    ///
+    /// The most specific (longest) matching root wins.
+    pub fn rewrite(&self, path: &str) -> Option<String> {
+        self.remaps
+            .iter()
+            .filter_map(|remap| remap.apply(path).map(|rewritten| (remap.from.len(), rewritten)))
+            .max_by_key(|(len, _)| *len)
+            .map(|(_, rewritten)| rewritten)
+    }
+}
+
+/// One path changed by a relink, reported back to the host for undo and logging
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PathRewrite {
+    /// Parameter that held the path (None for paths in internal node state)
+    pub parameter: Option<String>,
+    pub old_path: String,
+    pub new_path: String,
+}
@@ -3,9 +3,12 @@
 //! This module provides plugins with access to the main application's
 //! unified caching system for performance optimization.
 
-use crate::NodeData;
+use crate::{NodeData, NodeId};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
+use std::fmt;
+use std::sync::{Arc, Mutex, RwLock};
+use std::time::Duration;
 
 /// Cache key for plugin data that integrates with the main application's cache system
 #[derive(Debug, Clone, Hash, PartialEq, Eq, Serialize, Deserialize)]
@@ -13,28 +16,38 @@ pub struct PluginCacheKey {
     /// Plugin identifier
     pub plugin_id: String,
     /// Node ID
-    pub node_id: u32,
+    pub node_id: NodeId,
     /// Optional stage identifier for multi-stage operations
     pub stage_id: Option<String>,
     /// Port or data identifier  
     pub port_index: usize,
+    /// Fingerprint of the inputs the data was computed from (None = not content-addressed)
+    /// 
+    /// Lookups with a different hash miss, so changed inputs never return stale data.
+    #[serde(default)]
+    pub input_hash: Option<u64>,
+    /// Animation frame the data belongs to (None = not frame-dependent)
+    #[serde(default)]
+    pub frame: Option<i32>,
 }
 
 impl PluginCacheKey {
     /// Create a cache key for a single-stage plugin node output
-    pub fn new(plugin_id: impl Into<String>, node_id: u32, port_index: usize) -> Self {
+    pub fn new(plugin_id: impl Into<String>, node_id: NodeId, port_index: usize) -> Self {
         Self {
             plugin_id: plugin_id.into(),
             node_id,
             stage_id: None,
             port_index,
+            input_hash: None,
+            frame: None,
         }
     }
     
     /// Create a cache key for a multi-stage plugin node output
     pub fn with_stage(
         plugin_id: impl Into<String>, 
-        node_id: u32, 
+        node_id: NodeId, 
         stage_id: impl Into<String>,
         port_index: usize
     ) -> Self {
@@ -43,9 +56,76 @@ impl PluginCacheKey {
             node_id,
             stage_id: Some(stage_id.into()),
             port_index,
+            input_hash: None,
+            frame: None,
         }
     }
     
+    /// Make this key content-addressed by an input fingerprint
+    pub fn with_input_hash(mut self, input_hash: u64) -> Self {
+        self.input_hash = Some(input_hash);
+        self
+    }
+    
+    /// Key the data by animation frame
+    pub fn with_frame(mut self, frame: i32) -> Self {
+        self.frame = Some(frame);
+        self
+    }
+    
+    /// Get the animation frame if this is a frame-indexed key
+    pub fn get_frame(&self) -> Option<i32> {
+        self.frame
+    }
+    
+    /// Make this key content-addressed by the node's inputs
+    pub fn with_inputs(self, inputs: &HashMap<String, NodeData>) -> Self {
+        self.with_input_hash(Self::hash_inputs(inputs))
+    }
+    
+    /// Compute a stable fingerprint of an input map (independent of iteration order)
+    pub fn hash_inputs(inputs: &HashMap<String, NodeData>) -> u64 {
+        crate::hashing::content_hash(inputs)
+    }
+    
+    /// Combine input fingerprints (e.g. from `NodeData::content_hash`) into one
+    /// 
+    /// Use this when only some inputs affect the cached data.
+    pub fn hash_fingerprints(fingerprints: &[(&str, u64)]) -> u64 {
+        let mut sorted = fingerprints.to_vec();
+        sorted.sort();
+        crate::hashing::combine_hashes(
+            sorted
+                .into_iter()
+                .flat_map(|(name, hash)| [crate::hashing::content_hash(name), hash]),
+        )
+    }
+    
+    /// Fingerprint the parameters that govern a stage (independent of order)
+    pub fn hash_parameters(parameters: &[(&str, &NodeData)]) -> u64 {
+        let fingerprints: Vec<(&str, u64)> = parameters
+            .iter()
+            .map(|(name, value)| (*name, value.content_hash()))
+            .collect();
+        Self::hash_fingerprints(&fingerprints)
+    }
+    
+    /// Identify the computation behind this key, ignoring which node owns it
+    /// 
+    /// Identical nodes (same plugin, stage, port, input hash and frame) get the
+    /// same value, for use with `ComputeDeduplicator`. Returns None for keys
+    /// without an input hash, which only identify a node, not its inputs.
+    pub fn content_key(&self) -> Option<u64> {
+        let input_hash = self.input_hash?;
+        Some(crate::hashing::combine_hashes([
+            crate::hashing::content_hash(&self.plugin_id),
+            crate::hashing::content_hash(&self.stage_id),
+            self.port_index as u64,
+            input_hash,
+            crate::hashing::content_hash(&self.frame),
+        ]))
+    }
+    
     /// Check if this is a stage-specific cache key
     pub fn has_stage(&self) -> bool {
         self.stage_id.is_some()
@@ -61,13 +141,17 @@ impl PluginCacheKey {
 #[derive(Debug, Clone)]
 pub enum PluginCacheKeyPattern {
     /// Match all outputs for a specific plugin node
-    Node(String, u32), // plugin_id, node_id
+    Node(String, NodeId), // plugin_id, node_id
     /// Match all outputs for a specific stage of a plugin node  
-    Stage(String, u32, String), // plugin_id, node_id, stage_id
+    Stage(String, NodeId, String), // plugin_id, node_id, stage_id
     /// Match a specific cache key exactly
     Exact(PluginCacheKey),
     /// Match all cache entries for a plugin
     Plugin(String), // plugin_id
+    /// Match the stages of a plugin node whose ID fits a glob (`*` and `?`)
+    StageGlob(String, NodeId, String), // plugin_id, node_id, pattern
+    /// Match the frame-indexed entries of a plugin node within an inclusive frame range
+    FrameRange(String, NodeId, i32, i32), // plugin_id, node_id, first, last
 }
 
 impl PluginCacheKeyPattern {
@@ -84,8 +168,54 @@ impl PluginCacheKeyPattern {
             },
             PluginCacheKeyPattern::Exact(exact_key) => key == exact_key,
             PluginCacheKeyPattern::Plugin(plugin_id) => key.plugin_id == *plugin_id,
+            PluginCacheKeyPattern::StageGlob(plugin_id, node_id, pattern) => {
+                key.plugin_id == *plugin_id
+                    && key.node_id == *node_id
+                    && key.stage_id.as_deref().is_some_and(|stage| glob_match(pattern, stage))
+            },
+            PluginCacheKeyPattern::FrameRange(plugin_id, node_id, first, last) => {
+                key.plugin_id == *plugin_id
+                    && key.node_id == *node_id
+                    && key.frame.is_some_and(|frame| (*first..=*last).contains(&frame))
+            },
+        }
+    }
+}
+
+/// Match text against a simple glob
+/// 
+/// `*` matches any run of characters (including `/`, so `"lod/*"` matches
+/// `"lod/0"` and `"lod/high/1"`), `?` matches exactly one character, and
+/// everything else matches literally.
+pub fn glob_match(pattern: &str, text: &str) -> bool {
+    let pattern: Vec<char> = pattern.chars().collect();
+    let text: Vec<char> = text.chars().collect();
+    let (mut p, mut t) = (0, 0);
+    // Position after the last `*` and the text position it is currently covering
+    let mut backtrack: Option<(usize, usize)> = None;
+    
+    while t < text.len() {
+        match pattern.get(p) {
+            Some('*') => {
+                backtrack = Some((p + 1, t));
+                p += 1;
+            }
+            Some(&c) if c == '?' || c == text[t] => {
+                p += 1;
+                t += 1;
+            }
+            _ => match backtrack {
+                // Let the last `*` swallow one more character
+                Some((star_p, star_t)) => {
+                    backtrack = Some((star_p, star_t + 1));
+                    p = star_p;
+                    t = star_t + 1;
+                }
+                None => return false,
+            },
         }
     }
+    pattern[p..].iter().all(|&c| c == '*')
 }
 
 /// Statistics about plugin cache performance
@@ -107,271 +237,2399 @@ pub struct PluginCacheStatistics {
     pub cache_invalidations: usize,
     /// Estimated memory usage (in bytes)
     pub estimated_memory_usage: usize,
+    /// Entries evicted by the plugin's cache policy
+    #[serde(default)]
+    pub evictions: usize,
+    /// Entries dropped because their TTL expired
+    #[serde(default)]
+    pub expirations: usize,
+    /// Breakdown by stage (entries without a stage use `stage_id: None`)
+    #[serde(default)]
+    pub stages: Vec<StageCacheStatistics>,
 }
 
 impl PluginCacheStatistics {
     /// Calculate cache hit ratio for this plugin
     pub fn hit_ratio(&self) -> f32 {
-        let total_accesses = self.cache_hits + self.cache_misses;
-        if total_accesses == 0 {
-            0.0
-        } else {
-            self.cache_hits as f32 / total_accesses as f32
-        }
+        hit_ratio(self.cache_hits, self.cache_misses)
     }
     
     /// Get average memory per entry for this plugin
     pub fn avg_memory_per_entry(&self) -> usize {
-        if self.total_entries == 0 {
-            0
-        } else {
-            self.estimated_memory_usage / self.total_entries
-        }
+        self.estimated_memory_usage
+            .checked_div(self.total_entries)
+            .unwrap_or(0)
+    }
+    
+    /// Get the breakdown for one stage (None = entries without a stage)
+    pub fn stage(&self, stage_id: Option<&str>) -> Option<&StageCacheStatistics> {
+        self.stages.iter().find(|s| s.stage_id.as_deref() == stage_id)
     }
 }
 
-/// Plugin cache interface
-/// 
-/// This trait provides plugins with access to the main application's
-/// caching system. The main application implements this trait and
-/// provides it to plugins during execution.
-pub trait PluginCache: Send + Sync {
-    /// Store data in the cache
-    fn insert(&mut self, key: PluginCacheKey, data: NodeData) -> Result<(), String>;
-    
-    /// Retrieve data from cache (returns reference)
-    fn get(&self, key: &PluginCacheKey) -> Option<&NodeData>;
-    
-    /// Retrieve and remove data from cache (for move semantics)
-    fn take(&mut self, key: &PluginCacheKey) -> Option<NodeData>;
+/// Fraction of accesses that were hits (0 when there were none)
+fn hit_ratio(hits: usize, misses: usize) -> f32 {
+    let total_accesses = hits + misses;
+    if total_accesses == 0 {
+        0.0
+    } else {
+        hits as f32 / total_accesses as f32
+    }
+}
+
+/// Cache statistics for one stage of a plugin
+#[derive(Debug, Default, Clone, PartialEq, Serialize, Deserialize)]
+pub struct StageCacheStatistics {
+    pub stage_id: Option<String>,
+    pub entries: usize,
+    pub cache_hits: usize,
+    pub cache_misses: usize,
+    /// Estimated memory usage (in bytes)
+    pub estimated_memory_usage: usize,
+}
+
+impl StageCacheStatistics {
+    /// Calculate cache hit ratio for this stage
+    pub fn hit_ratio(&self) -> f32 {
+        hit_ratio(self.cache_hits, self.cache_misses)
+    }
+}
+
+/// Cache statistics for one node's entries
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct NodeCacheStatistics {
+    pub plugin_id: String,
+    pub node_id: NodeId,
+    pub entries: usize,
+    pub cache_hits: usize,
+    pub cache_misses: usize,
+    /// Estimated memory usage (in bytes)
+    pub estimated_memory_usage: usize,
+    /// Breakdown by stage (entries without a stage use `stage_id: None`)
+    #[serde(default)]
+    pub stages: Vec<StageCacheStatistics>,
+}
+
+impl NodeCacheStatistics {
+    /// Calculate cache hit ratio for this node
+    pub fn hit_ratio(&self) -> f32 {
+        hit_ratio(self.cache_hits, self.cache_misses)
+    }
+}
+
+/// Host-wide cache statistics across all plugins and core nodes
+#[derive(Debug, Default, Clone, PartialEq, Serialize, Deserialize)]
+pub struct GlobalCacheStatistics {
+    pub total_entries: usize,
+    pub cache_hits: usize,
+    pub cache_misses: usize,
+    /// Estimated memory usage (in bytes)
+    pub estimated_memory_usage: usize,
+    /// Host memory budget for the cache (None = unlimited)
+    #[serde(default)]
+    pub memory_budget: Option<usize>,
+    #[serde(default)]
+    pub evictions: usize,
+    #[serde(default)]
+    pub expirations: usize,
+}
+
+impl GlobalCacheStatistics {
+    /// Calculate the host-wide cache hit ratio
+    pub fn hit_ratio(&self) -> f32 {
+        hit_ratio(self.cache_hits, self.cache_misses)
+    }
     
-    /// Check if a key exists in the cache
-    fn contains(&self, key: &PluginCacheKey) -> bool;
+    /// Fraction of the memory budget in use (None if unlimited)
+    pub fn budget_usage(&self) -> Option<f32> {
+        self.memory_budget
+            .filter(|budget| *budget > 0)
+            .map(|budget| self.estimated_memory_usage as f32 / budget as f32)
+    }
+}
+
+/// Statistics for every plugin in the cache, for dashboard panels
+/// 
+/// `sequence` identifies the state the report reflects; pass it to
+/// `PluginCache::statistics_since` to receive only what changed afterwards.
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+pub struct CacheStatisticsReport {
+    pub sequence: u64,
+    pub plugins: Vec<PluginCacheStatistics>,
+}
+
+impl CacheStatisticsReport {
+    /// Total estimated memory across all plugins
+    pub fn total_memory_usage(&self) -> usize {
+        self.plugins.iter().map(|p| p.estimated_memory_usage).sum()
+    }
     
-    /// Invalidate cache entries matching a pattern
-    fn invalidate(&mut self, pattern: &PluginCacheKeyPattern) -> usize;
+    /// Get a plugin's statistics
+    pub fn plugin(&self, plugin_id: &str) -> Option<&PluginCacheStatistics> {
+        self.plugins.iter().find(|p| p.plugin_id == plugin_id)
+    }
     
-    /// Clear all cache entries for a plugin
-    fn clear_plugin(&mut self, plugin_id: &str) -> usize;
+    /// Plugins sorted by memory usage, largest first
+    pub fn by_memory(&self) -> Vec<&PluginCacheStatistics> {
+        let mut plugins: Vec<&PluginCacheStatistics> = self.plugins.iter().collect();
+        plugins.sort_by_key(|p| std::cmp::Reverse(p.estimated_memory_usage));
+        plugins
+    }
     
-    /// Get cache statistics for a plugin
-    fn get_plugin_statistics(&self, plugin_id: &str) -> PluginCacheStatistics;
+    /// The `count` largest stages across all plugins, as (plugin_id, stage)
+    pub fn top_stages(&self, count: usize) -> Vec<(&str, &StageCacheStatistics)> {
+        let mut stages: Vec<(&str, &StageCacheStatistics)> = self
+            .plugins
+            .iter()
+            .flat_map(|p| p.stages.iter().map(move |s| (p.plugin_id.as_str(), s)))
+            .collect();
+        stages.sort_by_key(|(_, s)| std::cmp::Reverse(s.estimated_memory_usage));
+        stages.truncate(count);
+        stages
+    }
     
-    /// Get all cache keys for a plugin (for debugging/inspection)
-    fn get_plugin_keys(&self, plugin_id: &str) -> Vec<&PluginCacheKey>;
+    /// Bring the report up to date with a change set
+    /// 
+    /// Deltas older than the report are ignored.
+    pub fn apply(&mut self, delta: CacheStatisticsDelta) {
+        if delta.sequence <= self.sequence {
+            return;
+        }
+        self.plugins.retain(|p| !delta.removed_plugins.contains(&p.plugin_id));
+        for changed in delta.changed {
+            match self.plugins.iter_mut().find(|p| p.plugin_id == changed.plugin_id) {
+                Some(existing) => *existing = changed,
+                None => self.plugins.push(changed),
+            }
+        }
+        self.sequence = delta.sequence;
+    }
 }
 
-/// Plugin cache manager
-/// 
-/// This struct helps plugins manage their cache keys and provides
-/// convenient methods for common caching patterns.
-#[derive(Debug, Clone)]
-pub struct PluginCacheManager {
-    /// Plugin identifier
-    plugin_id: String,
-    /// Currently managed cache keys
-    managed_keys: Vec<PluginCacheKey>,
+/// Statistics that changed since a given sequence number
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+pub struct CacheStatisticsDelta {
+    /// Sequence number of the state after these changes
+    pub sequence: u64,
+    /// Full current statistics of every plugin that changed
+    pub changed: Vec<PluginCacheStatistics>,
+    /// Plugins that no longer have any entries or statistics
+    pub removed_plugins: Vec<String>,
 }
 
-impl PluginCacheManager {
-    /// Create a new cache manager for a plugin
-    pub fn new(plugin_id: impl Into<String>) -> Self {
+impl CacheStatisticsDelta {
+    /// Check if nothing changed
+    pub fn is_empty(&self) -> bool {
+        self.changed.is_empty() && self.removed_plugins.is_empty()
+    }
+}
+
+/// Order in which entries are evicted when a plugin exceeds its budget
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum EvictionStrategy {
+    /// Least recently used first
+    #[default]
+    Lru,
+    /// Least frequently used first
+    Lfu,
+    /// Oldest insertion first
+    Fifo,
+}
+
+/// Per-plugin cache budget, registered with `PluginCache::set_policy`
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct CachePolicy {
+    /// Maximum estimated memory for the plugin's entries (None = unlimited)
+    pub max_bytes: Option<usize>,
+    /// Maximum number of entries (None = unlimited)
+    pub max_entries: Option<usize>,
+    /// Which entries to drop first
+    pub eviction: EvictionStrategy,
+}
+
+impl CachePolicy {
+    /// Policy with a memory budget and LRU eviction
+    pub fn max_bytes(max_bytes: usize) -> Self {
         Self {
-            plugin_id: plugin_id.into(),
-            managed_keys: Vec::new(),
+            max_bytes: Some(max_bytes),
+            ..Self::default()
         }
     }
     
-    /// Create a cache key for this plugin
-    pub fn create_key(&self, node_id: u32, port_index: usize) -> PluginCacheKey {
-        PluginCacheKey::new(&self.plugin_id, node_id, port_index)
+    /// Limit the number of entries
+    pub fn with_max_entries(mut self, max_entries: usize) -> Self {
+        self.max_entries = Some(max_entries);
+        self
     }
     
-    /// Create a stage-specific cache key for this plugin
-    pub fn create_stage_key(
-        &self, 
-        node_id: u32, 
-        stage_id: impl Into<String>, 
-        port_index: usize
-    ) -> PluginCacheKey {
-        PluginCacheKey::with_stage(&self.plugin_id, node_id, stage_id, port_index)
+    /// Set the eviction strategy
+    pub fn with_eviction(mut self, eviction: EvictionStrategy) -> Self {
+        self.eviction = eviction;
+        self
     }
     
-    /// Store data and track the key
-    pub fn store(
-        &mut self, 
-        cache: &mut dyn PluginCache, 
-        key: PluginCacheKey, 
-        data: NodeData
-    ) -> Result<(), String> {
-        let result = cache.insert(key.clone(), data);
-        if result.is_ok() {
-            self.managed_keys.push(key);
+    /// Select entries to evict so the remaining ones fit the budget
+    /// 
+    /// Shared by hosts so every implementation enforces policies the same way.
+    /// Lower priorities go first, ordered by the eviction strategy within a
+    /// priority. Pinned entries are never selected, even if the budget stays
+    /// exceeded. Returns indices into `entries`, in eviction order.
+    pub fn entries_to_evict(&self, entries: &[CacheEntryInfo]) -> Vec<usize> {
+        let mut order: Vec<usize> = (0..entries.len()).filter(|&i| !entries[i].pinned).collect();
+        match self.eviction {
+            EvictionStrategy::Lru => order.sort_by_key(|&i| (entries[i].priority, entries[i].last_access)),
+            EvictionStrategy::Lfu => {
+                order.sort_by_key(|&i| (entries[i].priority, entries[i].access_count, entries[i].last_access))
+            }
+            EvictionStrategy::Fifo => order.sort_by_key(|&i| (entries[i].priority, entries[i].inserted_at)),
         }
-        result
-    }
-    
-    /// Invalidate all cache entries for a specific node
-    pub fn invalidate_node(&mut self, cache: &mut dyn PluginCache, node_id: u32) -> usize {
-        let pattern = PluginCacheKeyPattern::Node(self.plugin_id.clone(), node_id);
-        let invalidated = cache.invalidate(&pattern);
-        
-        // Remove invalidated keys from managed list
-        self.managed_keys.retain(|key| !pattern.matches(key));
         
-        invalidated
+        let mut bytes: usize = entries.iter().map(|e| e.size_bytes).sum();
+        let mut count = entries.len();
+        let mut victims = Vec::new();
+        for index in order {
+            let over_bytes = self.max_bytes.is_some_and(|max| bytes > max);
+            let over_count = self.max_entries.is_some_and(|max| count > max);
+            if !over_bytes && !over_count {
+                break;
+            }
+            bytes -= entries[index].size_bytes;
+            count -= 1;
+            victims.push(index);
+        }
+        victims
     }
-    
-    /// Invalidate all cache entries for a specific stage of a node
-    pub fn invalidate_stage(
-        &mut self, 
-        cache: &mut dyn PluginCache, 
-        node_id: u32, 
-        stage_id: impl Into<String>
-    ) -> usize {
-        let stage_id = stage_id.into();
-        let pattern = PluginCacheKeyPattern::Stage(self.plugin_id.clone(), node_id, stage_id);
-        let invalidated = cache.invalidate(&pattern);
-        
-        // Remove invalidated keys from managed list
-        self.managed_keys.retain(|key| !pattern.matches(key));
-        
-        invalidated
+}
+
+/// Bookkeeping for one cache entry, used to apply a `CachePolicy`
+/// 
+/// Times are host-defined monotonic counters (e.g. an access sequence number).
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct CacheEntryInfo {
+    /// Estimated memory size of the entry
+    pub size_bytes: usize,
+    /// When the entry was inserted
+    pub inserted_at: u64,
+    /// When the entry was last read
+    pub last_access: u64,
+    /// Number of reads
+    pub access_count: u64,
+    /// Eviction priority (see `PluginCache::set_priority`)
+    pub priority: CachePriority,
+    /// Whether the entry is exempt from eviction (see `PluginCache::pin`)
+    pub pinned: bool,
+}
+
+/// Format version written into `CacheSnapshot::version`
+pub const CACHE_SNAPSHOT_VERSION: u32 = 1;
+
+/// One persisted cache entry
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CacheSnapshotEntry {
+    pub key: PluginCacheKey,
+    pub data: NodeData,
+    /// Remaining time-to-live at export (None = no expiry)
+    #[serde(default)]
+    pub ttl: Option<Duration>,
+}
+
+/// Exported cache contents of one plugin, saved alongside the project file
+/// 
+/// Restoring a snapshot on load skips cold-start recomputation. Entries keyed
+/// by `input_hash` stay valid as long as their inputs are unchanged; plugins
+/// should prune other entries with `retain` before saving.
+/// 
+/// Snapshots written into a cache directory shared with other instances must be
+/// saved under a `DiskCacheLock` with `write_entry_atomic` (see `disk_cache`).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CacheSnapshot {
+    pub version: u32,
+    pub plugin_id: String,
+    pub entries: Vec<CacheSnapshotEntry>,
+}
+
+impl CacheSnapshot {
+    /// Create an empty snapshot for a plugin
+    pub fn new(plugin_id: impl Into<String>) -> Self {
+        Self {
+            version: CACHE_SNAPSHOT_VERSION,
+            plugin_id: plugin_id.into(),
+            entries: Vec::new(),
+        }
     }
     
-    /// Clear all cache entries for this plugin
-    pub fn clear_all(&mut self, cache: &mut dyn PluginCache) -> usize {
-        let cleared = cache.clear_plugin(&self.plugin_id);
-        self.managed_keys.clear();
-        cleared
+    /// Keep only the entries selected by `keep`
+    pub fn retain(&mut self, mut keep: impl FnMut(&PluginCacheKey, &NodeData) -> bool) {
+        self.entries.retain(|entry| keep(&entry.key, &entry.data));
     }
     
-    /// Get cache statistics for this plugin
-    pub fn get_statistics(&self, cache: &dyn PluginCache) -> PluginCacheStatistics {
-        cache.get_plugin_statistics(&self.plugin_id)
+    /// Number of entries
+    pub fn len(&self) -> usize {
+        self.entries.len()
     }
     
-    /// Get the plugin ID
-    pub fn plugin_id(&self) -> &str {
-        &self.plugin_id
+    /// Check if the snapshot has no entries
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
     }
     
-    /// Get the number of managed cache keys
-    pub fn managed_key_count(&self) -> usize {
-        self.managed_keys.len()
+    /// Check that the snapshot can be imported
+    /// 
+    /// Rejects snapshots from a newer format version and entries that belong to
+    /// another plugin.
+    pub fn validate(&self) -> Result<(), String> {
+        if self.version > CACHE_SNAPSHOT_VERSION {
+            return Err(format!(
+                "Cache snapshot version {} is newer than supported version {}",
+                self.version, CACHE_SNAPSHOT_VERSION
+            ));
+        }
+        if let Some(entry) = self.entries.iter().find(|e| e.key.plugin_id != self.plugin_id) {
+            return Err(format!(
+                "Cache snapshot for '{}' contains an entry for '{}'",
+                self.plugin_id, entry.key.plugin_id
+            ));
+        }
+        Ok(())
     }
-    
-    /// Get all managed cache keys
-    pub fn managed_keys(&self) -> &[PluginCacheKey] {
-        &self.managed_keys
+}
+
+/// Why the host removed an entry on its own
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum EvictionReason {
+    /// The plugin's `CachePolicy` budget was exceeded
+    Policy,
+    /// Freed under memory pressure (after `on_memory_pressure` was not enough)
+    MemoryPressure,
+    /// The entry's time-to-live ran out
+    Expired,
+}
+
+/// An entry the host evicted, reported through `NodeExecutionHooks::on_cache_evicted`
+/// 
+/// Entries removed by the plugin itself (`invalidate`, `take`, `clear_plugin`)
+/// are not reported.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CacheEviction {
+    pub key: PluginCacheKey,
+    pub reason: EvictionReason,
+    /// Estimated size of the evicted data
+    pub size_bytes: usize,
+}
+
+/// How urgently the host needs memory back
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub enum MemoryPressureLevel {
+    /// Trim low-priority data opportunistically
+    Low,
+    /// Drop anything that can be reconstructed
+    Moderate,
+    /// Drop everything possible; the host evicts blindly afterwards
+    Critical,
+}
+
+/// How valuable a cache entry is when memory runs short
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub enum CachePriority {
+    /// Cheap to rebuild; shed at any pressure level
+    Low,
+    /// Shed at moderate pressure
+    #[default]
+    Normal,
+    /// Expensive to rebuild (e.g. network loads); shed only at critical pressure
+    High,
+}
+
+impl CachePriority {
+    /// Check if entries of this priority should be shed at a pressure level
+    pub fn sheddable_at(&self, level: MemoryPressureLevel) -> bool {
+        match level {
+            MemoryPressureLevel::Low => *self == CachePriority::Low,
+            MemoryPressureLevel::Moderate => *self != CachePriority::High,
+            MemoryPressureLevel::Critical => true,
+        }
     }
 }
 
-/// Caching strategies for plugins
+/// Plugin cache interface
 /// 
-/// These provide common patterns that plugins can use for different
-/// types of caching scenarios.
-pub mod strategies {
-    use super::*;
+/// This trait provides plugins with access to the main application's
+/// caching system. The main application implements this trait and
+/// provides it to plugins during execution.
+pub trait PluginCache: Send + Sync {
+    /// Store data in the cache
+    fn insert(&mut self, key: PluginCacheKey, data: NodeData) -> Result<(), String>;
     
-    /// Simple single-output caching strategy
+    /// Store data that expires after `ttl`
     /// 
-    /// This is the most basic caching strategy where a plugin caches
-    /// the result of processing inputs to avoid recomputation.
-    pub struct SimpleCache {
-        manager: PluginCacheManager,
+    /// For data derived from network resources or live simulations. Once the TTL
+    /// has passed, `get`, `take` and `contains` treat the entry as a miss and the
+    /// host counts it in `PluginCacheStatistics::expirations`. The default
+    /// ignores the TTL and stores the entry like `insert`.
+    fn insert_with_ttl(&mut self, key: PluginCacheKey, data: NodeData, _ttl: Duration) -> Result<(), String> {
+        self.insert(key, data)
     }
     
-    impl SimpleCache {
-        pub fn new(plugin_id: impl Into<String>) -> Self {
-            Self {
-                manager: PluginCacheManager::new(plugin_id),
-            }
+    /// Retrieve data from cache (returns reference; expired entries are misses)
+    fn get(&self, key: &PluginCacheKey) -> Option<&NodeData>;
+    
+    /// Store several entries in one call
+    /// 
+    /// Hosts should override this to cross the plugin boundary once; the default
+    /// inserts one by one and stops at the first error.
+    fn insert_many(&mut self, entries: Vec<(PluginCacheKey, NodeData)>) -> Result<(), String> {
+        for (key, data) in entries {
+            self.insert(key, data)?;
         }
-        
-        /// Try to get cached result
-        pub fn get_cached<'a>(
+        Ok(())
+    }
+    
+    /// Retrieve several entries in one call (results are in key order)
+    fn get_many(&self, keys: &[PluginCacheKey]) -> Vec<Option<&NodeData>> {
+        keys.iter().map(|key| self.get(key)).collect()
+    }
+    
+    /// Retrieve and remove data from cache (for move semantics)
+    fn take(&mut self, key: &PluginCacheKey) -> Option<NodeData>;
+    
+    /// Check if a key exists in the cache
+    fn contains(&self, key: &PluginCacheKey) -> bool;
+    
+    /// Invalidate cache entries matching a pattern
+    fn invalidate(&mut self, pattern: &PluginCacheKeyPattern) -> usize;
+    
+    /// Clear all cache entries for a plugin
+    fn clear_plugin(&mut self, plugin_id: &str) -> usize;
+    
+    /// Copy a node's entries to another node of the same plugin
+    /// 
+    /// Used when a node is duplicated so the copy starts warm. Entries are
+    /// shared (not deep-copied) and keep their stage, port, input hash and
+    /// frame; only the node ID changes. Existing entries of `dst_node_id`
+    /// with the same key are replaced. Returns the number of entries copied.
+    fn copy_node_entries(&mut self, plugin_id: &str, src_node_id: NodeId, dst_node_id: NodeId) -> usize;
+    
+    /// Get cache statistics for a plugin
+    fn get_plugin_statistics(&self, plugin_id: &str) -> PluginCacheStatistics;
+    
+    /// Get statistics for every plugin, with per-stage breakdowns
+    fn all_statistics(&self) -> CacheStatisticsReport;
+    
+    /// Get the statistics that changed after `sequence`
+    /// 
+    /// Hosts bump a sequence number on every change that affects statistics and
+    /// remember which plugins changed, so dashboards can refresh cheaply by
+    /// feeding the result to `CacheStatisticsReport::apply`. A `sequence` older
+    /// than the host's retained history yields every plugin as changed.
+    fn statistics_since(&self, sequence: u64) -> CacheStatisticsDelta;
+    
+    /// Get cache statistics for one node of a plugin
+    /// 
+    /// The default builds entry counts and sizes from `get_plugin_keys` and
+    /// `entry_info`; hosts that track per-node hits and misses should override it.
+    fn get_node_statistics(&self, plugin_id: &str, node_id: NodeId) -> NodeCacheStatistics {
+        let mut statistics = NodeCacheStatistics {
+            plugin_id: plugin_id.to_string(),
+            node_id,
+            entries: 0,
+            cache_hits: 0,
+            cache_misses: 0,
+            estimated_memory_usage: 0,
+            stages: Vec::new(),
+        };
+        for key in self.get_plugin_keys(plugin_id).into_iter().filter(|key| key.node_id == node_id) {
+            let size = self.entry_info(key).map(|info| info.size_bytes).unwrap_or(0);
+            statistics.entries += 1;
+            statistics.estimated_memory_usage += size;
+            let stage = match statistics.stages.iter_mut().position(|s| s.stage_id == key.stage_id) {
+                Some(index) => &mut statistics.stages[index],
+                None => {
+                    statistics.stages.push(StageCacheStatistics {
+                        stage_id: key.stage_id.clone(),
+                        ..Default::default()
+                    });
+                    statistics.stages.last_mut().unwrap()
+                }
+            };
+            stage.entries += 1;
+            stage.estimated_memory_usage += size;
+        }
+        statistics
+    }
+    
+    /// Get host-wide cache statistics
+    /// 
+    /// The default sums `all_statistics`; the memory budget is unknown (None).
+    fn global_statistics(&self) -> GlobalCacheStatistics {
+        let report = self.all_statistics();
+        let mut global = GlobalCacheStatistics::default();
+        for plugin in &report.plugins {
+            global.total_entries += plugin.total_entries;
+            global.cache_hits += plugin.cache_hits;
+            global.cache_misses += plugin.cache_misses;
+            global.estimated_memory_usage += plugin.estimated_memory_usage;
+            global.evictions += plugin.evictions;
+            global.expirations += plugin.expirations;
+        }
+        global
+    }
+    
+    /// Get the `count` largest entries, largest first
+    /// 
+    /// With `plugin_id` set only that plugin's entries are considered; with
+    /// `None` the whole cache is (including other plugins' keys, for diagnosis).
+    /// The default collects sizes through `entry_info`.
+    fn largest_entries(&self, plugin_id: Option<&str>, count: usize) -> Vec<(PluginCacheKey, CacheEntryInfo)> {
+        let plugin_ids = match plugin_id {
+            Some(plugin_id) => vec![plugin_id.to_string()],
+            None => self.all_statistics().plugins.into_iter().map(|p| p.plugin_id).collect(),
+        };
+        let mut entries: Vec<(PluginCacheKey, CacheEntryInfo)> = plugin_ids
+            .iter()
+            .flat_map(|plugin_id| self.get_plugin_keys(plugin_id))
+            .filter_map(|key| self.entry_info(key).map(|info| (key.clone(), info)))
+            .collect();
+        entries.sort_by_key(|(_, info)| std::cmp::Reverse(info.size_bytes));
+        entries.truncate(count);
+        entries
+    }
+    
+    /// Get all cache keys for a plugin (for debugging/inspection)
+    fn get_plugin_keys(&self, plugin_id: &str) -> Vec<&PluginCacheKey>;
+    
+    /// Set the eviction policy and size budget for a plugin
+    /// 
+    /// The host enforces the policy on every insert, evicting the plugin's own
+    /// entries (see `CachePolicy::entries_to_evict`).
+    fn set_policy(&mut self, plugin_id: &str, policy: CachePolicy) -> Result<(), String>;
+    
+    /// Get the policy registered for a plugin
+    fn get_policy(&self, plugin_id: &str) -> Option<CachePolicy>;
+    
+    /// Set how valuable an entry is when the host has to evict
+    /// 
+    /// Policy and memory-pressure eviction drop lower priorities first. The
+    /// priority stays with the entry until it is removed. Returns false if the
+    /// key is not cached.
+    fn set_priority(&mut self, key: &PluginCacheKey, priority: CachePriority) -> bool;
+    
+    /// Exempt an entry from eviction (e.g. a parsed USD stage)
+    /// 
+    /// Pinned entries still count towards the plugin's budget and are removed
+    /// by explicit invalidation, but the host never evicts them. Returns false
+    /// if the key is not cached.
+    fn pin(&mut self, key: &PluginCacheKey) -> bool;
+    
+    /// Make a pinned entry evictable again (returns false if it was not pinned)
+    fn unpin(&mut self, key: &PluginCacheKey) -> bool;
+    
+    /// Get size and access bookkeeping for an entry (None if not cached)
+    fn entry_info(&self, key: &PluginCacheKey) -> Option<CacheEntryInfo>;
+    
+    /// Export all live entries of a plugin (expired entries are skipped)
+    fn export_plugin(&self, plugin_id: &str) -> CacheSnapshot;
+    
+    /// Restore entries from a snapshot, returning the number imported
+    /// 
+    /// Hosts must call `CacheSnapshot::validate` first. Existing entries with the
+    /// same keys are replaced, and the plugin's `CachePolicy` still applies.
+    fn import_plugin(&mut self, snapshot: CacheSnapshot) -> Result<usize, String>;
+    
+    /// Queue keys to be warmed in the background
+    /// 
+    /// At idle time the host calls `PluginNode::preload` on the owning node for
+    /// each queued key, so expensive loads happen before the user executes the
+    /// graph. Keys that are already cached or queued are skipped. Returns the
+    /// number of keys newly queued.
+    fn request_preload(&mut self, keys: Vec<PluginCacheKey>) -> usize;
+    
+    /// Retrieve shared data from cache
+    /// 
+    /// Unlike `get`, the result outlives the cache borrow. Hosts store entries as
+    /// `Arc<NodeData>` and must return the stored reference, so a hit is a
+    /// pointer copy rather than a clone of the payload.
+    fn get_shared(&self, key: &PluginCacheKey) -> Option<Arc<NodeData>>;
+    
+    /// Store shared data in the cache
+    /// 
+    /// Hosts must keep the reference rather than copying the data, so the
+    /// caller's handle and later `get_shared` hits point at the same value.
+    fn insert_shared(&mut self, key: PluginCacheKey, data: Arc<NodeData>) -> Result<(), String>;
+    
+    /// Retrieve several shared entries in one call (results are in key order)
+    fn get_many_shared(&self, keys: &[PluginCacheKey]) -> Vec<Option<Arc<NodeData>>> {
+        keys.iter().map(|key| self.get_shared(key)).collect()
+    }
+    
+    /// Apply a transaction all-or-nothing
+    /// 
+    /// Operations apply in order. If any of them fails (e.g. an insert larger
+    /// than the plugin's `CachePolicy` budget), the cache is left exactly as it
+    /// was. Readers never observe a partially applied transaction. Plugins
+    /// normally go through `transaction` instead of calling this directly.
+    fn commit(&mut self, transaction: CacheTransaction) -> Result<(), String>;
+    
+    /// Get a thread-safe handle to this cache for background work
+    /// 
+    /// `&mut dyn PluginCache` cannot leave the calling thread, so plugins that
+    /// populate entries from worker threads clone this handle into the task.
+    /// Returns None if the host does not support background access.
+    fn shared_handle(&self) -> Option<SharedCacheHandle> {
+        None
+    }
+}
+
+impl dyn PluginCache + '_ {
+    /// Return the cached entry, computing and inserting it on a miss
+    /// 
+    /// Replaces the get/compute/insert pattern without cloning the data. Errors
+    /// from `compute` are returned and nothing is cached. If the host evicts the
+    /// entry immediately (e.g. it exceeds the plugin's `CachePolicy`), an error
+    /// is returned; use `get_or_compute_shared` to keep the value in that case.
+    pub fn get_or_compute<F>(&mut self, key: &PluginCacheKey, compute: F) -> Result<&NodeData, String>
+    where
+        F: FnOnce() -> Result<NodeData, String>,
+    {
+        if !self.contains(key) {
+            let data = compute()?;
+            self.insert(key.clone(), data)?;
+        }
+        self.get(key)
+            .ok_or_else(|| format!("Cache entry for node {} was evicted on insert", key.node_id))
+    }
+    
+    /// Like `get_or_compute`, but returns a shared handle that outlives the cache borrow
+    pub fn get_or_compute_shared<F>(&mut self, key: &PluginCacheKey, compute: F) -> Result<Arc<NodeData>, String>
+    where
+        F: FnOnce() -> Result<NodeData, String>,
+    {
+        if let Some(data) = self.get_shared(key) {
+            return Ok(data);
+        }
+        let data = Arc::new(compute()?);
+        self.insert_shared(key.clone(), Arc::clone(&data))?;
+        Ok(data)
+    }
+    
+    /// Build a transaction in a closure and commit it all-or-nothing
+    /// 
+    /// ```rust,ignore
+    /// cache.transaction(|txn| {
+    ///     txn.insert(mesh_key, mesh);
+    ///     txn.insert(bounds_key, bounds);
+    ///     txn.invalidate(PluginCacheKeyPattern::Stage(plugin_id, node_id, "preview".into()));
+    /// })?;
+    /// ```
+    /// 
+    /// Nothing touches the cache until the closure returns, so a panic or
+    /// `txn.abort()` inside it leaves the cache unchanged. Returns whether the
+    /// transaction was committed.
+    pub fn transaction<F>(&mut self, build: F) -> Result<bool, String>
+    where
+        F: FnOnce(&mut CacheTransaction),
+    {
+        let mut transaction = CacheTransaction::new();
+        build(&mut transaction);
+        if transaction.is_aborted() {
+            return Ok(false);
+        }
+        if !transaction.is_empty() {
+            self.commit(transaction)?;
+        }
+        Ok(true)
+    }
+}
+
+/// Cache access with interior synchronization, usable from any thread
+/// 
+/// The host implements this for its cache. Every method takes `&self`, so a
+/// `SharedCacheHandle` can be cloned into background tasks that populate
+/// entries asynchronously. A `RwLock` around any `PluginCache` implements it.
+pub trait SharedPluginCache: Send + Sync {
+    /// Retrieve shared data from cache
+    fn get_shared(&self, key: &PluginCacheKey) -> Option<Arc<NodeData>>;
+    
+    /// Store shared data in the cache
+    fn insert_shared(&self, key: PluginCacheKey, data: Arc<NodeData>) -> Result<(), String>;
+    
+    /// Check if a key exists in cache
+    fn contains(&self, key: &PluginCacheKey) -> bool;
+    
+    /// Invalidate cache entries matching a pattern
+    fn invalidate(&self, pattern: &PluginCacheKeyPattern) -> usize;
+    
+    /// Apply a transaction all-or-nothing (see `PluginCache::commit`)
+    fn commit(&self, transaction: CacheTransaction) -> Result<(), String>;
+    
+    /// Store data in the cache
+    fn insert(&self, key: PluginCacheKey, data: NodeData) -> Result<(), String> {
+        self.insert_shared(key, Arc::new(data))
+    }
+}
+
+impl<C: PluginCache> SharedPluginCache for RwLock<C> {
+    fn get_shared(&self, key: &PluginCacheKey) -> Option<Arc<NodeData>> {
+        self.read().unwrap_or_else(|e| e.into_inner()).get_shared(key)
+    }
+    
+    fn insert_shared(&self, key: PluginCacheKey, data: Arc<NodeData>) -> Result<(), String> {
+        self.write().unwrap_or_else(|e| e.into_inner()).insert_shared(key, data)
+    }
+    
+    fn contains(&self, key: &PluginCacheKey) -> bool {
+        self.read().unwrap_or_else(|e| e.into_inner()).contains(key)
+    }
+    
+    fn invalidate(&self, pattern: &PluginCacheKeyPattern) -> usize {
+        self.write().unwrap_or_else(|e| e.into_inner()).invalidate(pattern)
+    }
+    
+    fn commit(&self, transaction: CacheTransaction) -> Result<(), String> {
+        self.write().unwrap_or_else(|e| e.into_inner()).commit(transaction)
+    }
+}
+
+/// Cloneable handle to a `SharedPluginCache`
+/// 
+/// Clones refer to the same cache.
+#[derive(Clone)]
+pub struct SharedCacheHandle(Arc<dyn SharedPluginCache>);
+
+impl SharedCacheHandle {
+    /// Wrap a shared cache
+    pub fn new(cache: Arc<dyn SharedPluginCache>) -> Self {
+        Self(cache)
+    }
+    
+    /// Return the cached entry, computing and inserting it on a miss
+    /// 
+    /// Unlike `PluginCache::get_or_compute_shared` no lock is held while
+    /// computing, so two threads may compute the same entry; use a
+    /// `ComputeDeduplicator` if that matters.
+    pub fn get_or_compute_shared<F>(&self, key: PluginCacheKey, compute: F) -> Result<Arc<NodeData>, String>
+    where
+        F: FnOnce() -> Result<NodeData, String>,
+    {
+        if let Some(data) = self.get_shared(&key) {
+            return Ok(data);
+        }
+        let data = Arc::new(compute()?);
+        self.insert_shared(key, data.clone())?;
+        Ok(data)
+    }
+}
+
+impl std::ops::Deref for SharedCacheHandle {
+    type Target = dyn SharedPluginCache;
+    
+    fn deref(&self) -> &Self::Target {
+        &*self.0
+    }
+}
+
+impl fmt::Debug for SharedCacheHandle {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("SharedCacheHandle")
+    }
+}
+
+/// One buffered operation of a `CacheTransaction`
+#[derive(Debug, Clone)]
+#[allow(clippy::large_enum_variant)]
+pub enum CacheOperation {
+    /// Store data (with an optional time-to-live)
+    Insert {
+        key: PluginCacheKey,
+        data: NodeData,
+        ttl: Option<Duration>,
+    },
+    /// Remove entries matching a pattern
+    Invalidate(PluginCacheKeyPattern),
+}
+
+/// Cache changes buffered for an all-or-nothing commit
+#[derive(Debug, Clone, Default)]
+pub struct CacheTransaction {
+    operations: Vec<CacheOperation>,
+    aborted: bool,
+}
+
+impl CacheTransaction {
+    /// Start an empty transaction
+    pub fn new() -> Self {
+        Self::default()
+    }
+    
+    /// Buffer an insert
+    pub fn insert(&mut self, key: PluginCacheKey, data: NodeData) {
+        self.operations.push(CacheOperation::Insert { key, data, ttl: None });
+    }
+    
+    /// Buffer an insert that expires after `ttl`
+    pub fn insert_with_ttl(&mut self, key: PluginCacheKey, data: NodeData, ttl: Duration) {
+        self.operations.push(CacheOperation::Insert { key, data, ttl: Some(ttl) });
+    }
+    
+    /// Buffer an invalidation
+    /// 
+    /// It applies to entries present at that point of the transaction, including
+    /// ones inserted earlier in it.
+    pub fn invalidate(&mut self, pattern: PluginCacheKeyPattern) {
+        self.operations.push(CacheOperation::Invalidate(pattern));
+    }
+    
+    /// Buffer removal of a single key
+    pub fn remove(&mut self, key: PluginCacheKey) {
+        self.invalidate(PluginCacheKeyPattern::Exact(key));
+    }
+    
+    /// Discard the transaction; nothing will be committed
+    pub fn abort(&mut self) {
+        self.aborted = true;
+        self.operations.clear();
+    }
+    
+    /// Check if the transaction was aborted
+    pub fn is_aborted(&self) -> bool {
+        self.aborted
+    }
+    
+    /// Number of buffered operations
+    pub fn len(&self) -> usize {
+        self.operations.len()
+    }
+    
+    /// Check if no operations are buffered
+    pub fn is_empty(&self) -> bool {
+        self.operations.is_empty()
+    }
+    
+    /// Buffered operations in order
+    pub fn operations(&self) -> &[CacheOperation] {
+        &self.operations
+    }
+    
+    /// Take the buffered operations (for hosts applying the transaction)
+    pub fn into_operations(self) -> Vec<CacheOperation> {
+        self.operations
+    }
+    
+    /// Keys still stored once the operations are applied in order
+    fn stored_keys(&self) -> Vec<PluginCacheKey> {
+        let mut keys: Vec<PluginCacheKey> = Vec::new();
+        for operation in &self.operations {
+            match operation {
+                CacheOperation::Insert { key, .. } => {
+                    if !keys.contains(key) {
+                        keys.push(key.clone());
+                    }
+                }
+                CacheOperation::Invalidate(pattern) => keys.retain(|key| !pattern.matches(key)),
+            }
+        }
+        keys
+    }
+}
+
+/// Plugin cache manager
+/// 
+/// This struct helps plugins manage their cache keys and provides
+/// convenient methods for common caching patterns.
+#[derive(Debug, Clone)]
+pub struct PluginCacheManager {
+    /// Plugin identifier
+    plugin_id: String,
+    /// Currently managed cache keys
+    managed_keys: Vec<PluginCacheKey>,
+}
+
+impl PluginCacheManager {
+    /// Create a new cache manager for a plugin
+    pub fn new(plugin_id: impl Into<String>) -> Self {
+        Self {
+            plugin_id: plugin_id.into(),
+            managed_keys: Vec::new(),
+        }
+    }
+    
+    /// Create a cache key for this plugin
+    pub fn create_key(&self, node_id: NodeId, port_index: usize) -> PluginCacheKey {
+        PluginCacheKey::new(&self.plugin_id, node_id, port_index)
+    }
+    
+    /// Create a content-addressed cache key that misses when the inputs change
+    pub fn create_content_key(
+        &self,
+        node_id: NodeId,
+        port_index: usize,
+        inputs: &HashMap<String, NodeData>
+    ) -> PluginCacheKey {
+        PluginCacheKey::new(&self.plugin_id, node_id, port_index).with_inputs(inputs)
+    }
+    
+    /// Create a stage-specific cache key for this plugin
+    pub fn create_stage_key(
+        &self, 
+        node_id: NodeId, 
+        stage_id: impl Into<String>, 
+        port_index: usize
+    ) -> PluginCacheKey {
+        PluginCacheKey::with_stage(&self.plugin_id, node_id, stage_id, port_index)
+    }
+    
+    /// Create a frame-indexed cache key for this plugin
+    pub fn create_frame_key(&self, node_id: NodeId, frame: i32, port_index: usize) -> PluginCacheKey {
+        PluginCacheKey::new(&self.plugin_id, node_id, port_index).with_frame(frame)
+    }
+    
+    /// Store data and track the key
+    pub fn store(
+        &mut self, 
+        cache: &mut dyn PluginCache, 
+        key: PluginCacheKey, 
+        data: NodeData
+    ) -> Result<(), String> {
+        let result = cache.insert(key.clone(), data);
+        if result.is_ok() {
+            self.managed_keys.push(key);
+        }
+        result
+    }
+    
+    /// Store shared data and track the key
+    pub fn store_shared(
+        &mut self,
+        cache: &mut dyn PluginCache,
+        key: PluginCacheKey,
+        data: Arc<NodeData>
+    ) -> Result<(), String> {
+        let result = cache.insert_shared(key.clone(), data);
+        if result.is_ok() {
+            self.track(&key);
+        }
+        result
+    }
+    
+    /// Commit a transaction and update tracking to match
+    /// 
+    /// On failure nothing was applied and tracking is left unchanged.
+    pub fn commit(&mut self, cache: &mut dyn PluginCache, transaction: CacheTransaction) -> Result<(), String> {
+        if transaction.is_aborted() || transaction.is_empty() {
+            return Ok(());
+        }
+        let stored = transaction.stored_keys();
+        let patterns: Vec<PluginCacheKeyPattern> = transaction
+            .operations()
+            .iter()
+            .filter_map(|operation| match operation {
+                CacheOperation::Invalidate(pattern) => Some(pattern.clone()),
+                CacheOperation::Insert { .. } => None,
+            })
+            .collect();
+        cache.commit(transaction)?;
+        
+        for pattern in &patterns {
+            self.managed_keys.retain(|key| !pattern.matches(key));
+        }
+        for key in &stored {
+            self.track(key);
+        }
+        Ok(())
+    }
+    
+    /// Store several entries with one cache call and track their keys
+    /// 
+    /// Use this for nodes that produce many outputs instead of storing port by port.
+    pub fn store_all(
+        &mut self,
+        cache: &mut dyn PluginCache,
+        entries: Vec<(PluginCacheKey, NodeData)>
+    ) -> Result<(), String> {
+        let keys: Vec<PluginCacheKey> = entries.iter().map(|(key, _)| key.clone()).collect();
+        let result = cache.insert_many(entries);
+        // Track whatever made it in, even if a later entry failed
+        for key in &keys {
+            if result.is_ok() || cache.contains(key) {
+                self.track(key);
+            }
+        }
+        result
+    }
+    
+    /// Store data with a time-to-live and track the key
+    pub fn store_with_ttl(
+        &mut self,
+        cache: &mut dyn PluginCache,
+        key: PluginCacheKey,
+        data: NodeData,
+        ttl: Duration
+    ) -> Result<(), String> {
+        let result = cache.insert_with_ttl(key.clone(), data, ttl);
+        if result.is_ok() {
+            self.track(&key);
+        }
+        result
+    }
+    
+    /// Get a cached entry, computing, storing and tracking it on a miss
+    pub fn get_or_compute<'a, F>(
+        &mut self,
+        cache: &'a mut dyn PluginCache,
+        key: PluginCacheKey,
+        compute: F
+    ) -> Result<&'a NodeData, String>
+    where
+        F: FnOnce() -> Result<NodeData, String>,
+    {
+        self.track(&key);
+        cache.get_or_compute(&key, compute)
+    }
+    
+    /// Shared-handle variant of `get_or_compute`
+    pub fn get_or_compute_shared<F>(
+        &mut self,
+        cache: &mut dyn PluginCache,
+        key: PluginCacheKey,
+        compute: F
+    ) -> Result<Arc<NodeData>, String>
+    where
+        F: FnOnce() -> Result<NodeData, String>,
+    {
+        self.track(&key);
+        cache.get_or_compute_shared(&key, compute)
+    }
+    
+    fn track(&mut self, key: &PluginCacheKey) {
+        if !self.managed_keys.contains(key) {
+            self.managed_keys.push(key.clone());
+        }
+    }
+    
+    /// Queue keys for background preloading (see `PluginCache::request_preload`)
+    pub fn request_preload(&self, cache: &mut dyn PluginCache, keys: Vec<PluginCacheKey>) -> usize {
+        let keys = keys.into_iter().filter(|key| key.plugin_id == self.plugin_id).collect();
+        cache.request_preload(keys)
+    }
+    
+    /// Export the managed entries selected by `keep`, for saving with the project
+    pub fn export(
+        &self,
+        cache: &dyn PluginCache,
+        mut keep: impl FnMut(&PluginCacheKey, &NodeData) -> bool
+    ) -> CacheSnapshot {
+        let mut snapshot = cache.export_plugin(&self.plugin_id);
+        snapshot.retain(|key, data| self.managed_keys.contains(key) && keep(key, data));
+        snapshot
+    }
+    
+    /// Restore a snapshot saved with `export` and track its keys
+    pub fn import(&mut self, cache: &mut dyn PluginCache, snapshot: CacheSnapshot) -> Result<usize, String> {
+        if snapshot.plugin_id != self.plugin_id {
+            return Err(format!(
+                "Cache snapshot for '{}' cannot be imported by '{}'",
+                snapshot.plugin_id, self.plugin_id
+            ));
+        }
+        snapshot.validate()?;
+        let keys: Vec<PluginCacheKey> = snapshot.entries.iter().map(|e| e.key.clone()).collect();
+        let imported = cache.import_plugin(snapshot)?;
+        for key in &keys {
+            self.track(key);
+        }
+        Ok(imported)
+    }
+    
+    /// Get the priority of an entry (Normal if it is not cached)
+    pub fn priority(&self, cache: &dyn PluginCache, key: &PluginCacheKey) -> CachePriority {
+        cache.entry_info(key).map(|info| info.priority).unwrap_or_default()
+    }
+    
+    /// Set an entry's priority for both `shed` and host eviction
+    pub fn prioritize(&self, cache: &mut dyn PluginCache, key: &PluginCacheKey, priority: CachePriority) -> bool {
+        cache.set_priority(key, priority)
+    }
+    
+    /// Pin an entry so neither the host nor `shed` drops it
+    pub fn pin(&self, cache: &mut dyn PluginCache, key: &PluginCacheKey) -> bool {
+        cache.pin(key)
+    }
+    
+    /// Make a pinned entry evictable again
+    pub fn unpin(&self, cache: &mut dyn PluginCache, key: &PluginCacheKey) -> bool {
+        cache.unpin(key)
+    }
+    
+    /// Drop managed entries in response to memory pressure
+    /// 
+    /// Sheds entries whose priority allows it at `level`, lowest priority and
+    /// least recently used first, until `bytes_requested` bytes are freed.
+    /// Pinned entries are skipped.
+    /// Returns the number of bytes freed. Call this from
+    /// `NodeExecutionHooks::on_memory_pressure`.
+    pub fn shed(&mut self, cache: &mut dyn PluginCache, level: MemoryPressureLevel, bytes_requested: usize) -> usize {
+        let mut candidates: Vec<(CachePriority, CacheEntryInfo, PluginCacheKey)> = self
+            .managed_keys
+            .iter()
+            .filter_map(|key| cache.entry_info(key).map(|info| (info.priority, info, key.clone())))
+            .filter(|(priority, info, _)| priority.sheddable_at(level) && !info.pinned)
+            .collect();
+        candidates.sort_by_key(|(priority, info, _)| (*priority, info.last_access));
+        
+        let mut freed = 0;
+        for (_, info, key) in candidates {
+            if freed >= bytes_requested {
+                break;
+            }
+            if cache.take(&key).is_some() {
+                freed += info.size_bytes;
+            }
+            self.managed_keys.retain(|k| *k != key);
+        }
+        freed
+    }
+    
+    /// Stop tracking keys the host evicted, returning how many were tracked
+    /// 
+    /// Call this from `NodeExecutionHooks::on_cache_evicted` so `managed_keys`
+    /// stays accurate.
+    pub fn handle_evictions(&mut self, evictions: &[CacheEviction]) -> usize {
+        let before = self.managed_keys.len();
+        self.managed_keys
+            .retain(|key| !evictions.iter().any(|eviction| eviction.key == *key));
+        before - self.managed_keys.len()
+    }
+    
+    /// Invalidate all cache entries for a specific node
+    pub fn invalidate_node(&mut self, cache: &mut dyn PluginCache, node_id: NodeId) -> usize {
+        let pattern = PluginCacheKeyPattern::Node(self.plugin_id.clone(), node_id);
+        let invalidated = cache.invalidate(&pattern);
+        
+        // Remove invalidated keys from managed list
+        self.managed_keys.retain(|key| !pattern.matches(key));
+        
+        invalidated
+    }
+    
+    /// Invalidate all cache entries for a specific stage of a node
+    pub fn invalidate_stage(
+        &mut self, 
+        cache: &mut dyn PluginCache, 
+        node_id: NodeId, 
+        stage_id: impl Into<String>
+    ) -> usize {
+        let stage_id = stage_id.into();
+        let pattern = PluginCacheKeyPattern::Stage(self.plugin_id.clone(), node_id, stage_id);
+        let invalidated = cache.invalidate(&pattern);
+        
+        // Remove invalidated keys from managed list
+        self.managed_keys.retain(|key| !pattern.matches(key));
+        
+        invalidated
+    }
+    
+    /// Invalidate every stage of a node whose ID matches a glob (see `glob_match`)
+    pub fn invalidate_stage_glob(
+        &mut self,
+        cache: &mut dyn PluginCache,
+        node_id: NodeId,
+        pattern: impl Into<String>
+    ) -> usize {
+        let pattern = PluginCacheKeyPattern::StageGlob(self.plugin_id.clone(), node_id, pattern.into());
+        let invalidated = cache.invalidate(&pattern);
+        
+        // Remove invalidated keys from managed list
+        self.managed_keys.retain(|key| !pattern.matches(key));
+        
+        invalidated
+    }
+    
+    /// Invalidate the frame-indexed entries of a node within an inclusive frame range
+    pub fn invalidate_frame_range(
+        &mut self,
+        cache: &mut dyn PluginCache,
+        node_id: NodeId,
+        first: i32,
+        last: i32
+    ) -> usize {
+        let pattern = PluginCacheKeyPattern::FrameRange(self.plugin_id.clone(), node_id, first, last);
+        let invalidated = cache.invalidate(&pattern);
+        
+        // Remove invalidated keys from managed list
+        self.managed_keys.retain(|key| !pattern.matches(key));
+        
+        invalidated
+    }
+    
+    /// Copy a node's entries to a duplicate of it and track the new keys
+    pub fn copy_node_entries(
+        &mut self,
+        cache: &mut dyn PluginCache,
+        src_node_id: NodeId,
+        dst_node_id: NodeId
+    ) -> usize {
+        let copied = cache.copy_node_entries(&self.plugin_id, src_node_id, dst_node_id);
+        
+        let copies: Vec<PluginCacheKey> = self
+            .managed_keys
+            .iter()
+            .filter(|key| key.node_id == src_node_id)
+            .map(|key| PluginCacheKey { node_id: dst_node_id, ..key.clone() })
+            .filter(|copy| cache.contains(copy))
+            .collect();
+        for copy in copies {
+            if !self.managed_keys.contains(&copy) {
+                self.managed_keys.push(copy);
+            }
+        }
+        
+        copied
+    }
+    
+    /// Clear all cache entries for this plugin
+    pub fn clear_all(&mut self, cache: &mut dyn PluginCache) -> usize {
+        let cleared = cache.clear_plugin(&self.plugin_id);
+        self.managed_keys.clear();
+        cleared
+    }
+    
+    /// Get cache statistics for this plugin
+    pub fn get_statistics(&self, cache: &dyn PluginCache) -> PluginCacheStatistics {
+        cache.get_plugin_statistics(&self.plugin_id)
+    }
+    
+    /// Get cache statistics for one node (e.g. for a cache-health readout in its panel)
+    pub fn get_node_statistics(&self, cache: &dyn PluginCache, node_id: NodeId) -> NodeCacheStatistics {
+        cache.get_node_statistics(&self.plugin_id, node_id)
+    }
+    
+    /// Get this plugin's `count` largest entries, largest first
+    pub fn largest_entries(&self, cache: &dyn PluginCache, count: usize) -> Vec<(PluginCacheKey, CacheEntryInfo)> {
+        cache.largest_entries(Some(&self.plugin_id), count)
+    }
+    
+    /// Get the plugin ID
+    pub fn plugin_id(&self) -> &str {
+        &self.plugin_id
+    }
+    
+    /// Get the number of managed cache keys
+    pub fn managed_key_count(&self) -> usize {
+        self.managed_keys.len()
+    }
+    
+    /// Get all managed cache keys
+    pub fn managed_keys(&self) -> &[PluginCacheKey] {
+        &self.managed_keys
+    }
+}
+
+/// Caching strategies for plugins
+/// 
+/// These provide common patterns that plugins can use for different
+/// types of caching scenarios.
+pub mod strategies {
+    use super::*;
+    
+    /// Simple single-output caching strategy
+    /// 
+    /// This is the most basic caching strategy where a plugin caches
+    /// the result of processing inputs to avoid recomputation.
+    pub struct SimpleCache {
+        manager: PluginCacheManager,
+    }
+    
+    impl SimpleCache {
+        pub fn new(plugin_id: impl Into<String>) -> Self {
+            Self {
+                manager: PluginCacheManager::new(plugin_id),
+            }
+        }
+        
+        /// Try to get cached result
+        pub fn get_cached<'a>(
+            &self, 
+            cache: &'a dyn PluginCache, 
+            node_id: NodeId, 
+            port_index: usize
+        ) -> Option<&'a NodeData> {
+            let key = self.manager.create_key(node_id, port_index);
+            cache.get(&key)
+        }
+        
+        /// Try to get a shared handle to the cached result
+        pub fn get_cached_shared(
+            &self,
+            cache: &dyn PluginCache,
+            node_id: NodeId,
+            port_index: usize
+        ) -> Option<Arc<NodeData>> {
+            let key = self.manager.create_key(node_id, port_index);
+            cache.get_shared(&key)
+        }
+        
+        /// Store result in cache
+        pub fn store_result(
+            &mut self, 
+            cache: &mut dyn PluginCache, 
+            node_id: NodeId, 
+            port_index: usize, 
+            data: NodeData
+        ) -> Result<(), String> {
+            let key = self.manager.create_key(node_id, port_index);
+            self.manager.store(cache, key, data)
+        }
+        
+        /// Get the cached result, computing and storing it on a miss
+        pub fn get_or_compute<'a, F>(
+            &mut self,
+            cache: &'a mut dyn PluginCache,
+            node_id: NodeId,
+            port_index: usize,
+            compute: F
+        ) -> Result<&'a NodeData, String>
+        where
+            F: FnOnce() -> Result<NodeData, String>,
+        {
+            let key = self.manager.create_key(node_id, port_index);
+            self.manager.get_or_compute(cache, key, compute)
+        }
+        
+        /// Shared-handle variant of `get_or_compute`
+        pub fn get_or_compute_shared<F>(
+            &mut self,
+            cache: &mut dyn PluginCache,
+            node_id: NodeId,
+            port_index: usize,
+            compute: F
+        ) -> Result<Arc<NodeData>, String>
+        where
+            F: FnOnce() -> Result<NodeData, String>,
+        {
+            let key = self.manager.create_key(node_id, port_index);
+            self.manager.get_or_compute_shared(cache, key, compute)
+        }
+        
+        /// Invalidate cache for a node
+        pub fn invalidate(&mut self, cache: &mut dyn PluginCache, node_id: NodeId) -> usize {
+            self.manager.invalidate_node(cache, node_id)
+        }
+        
+        /// Drop cached results under memory pressure (see `PluginCacheManager::shed`)
+        pub fn shed(&mut self, cache: &mut dyn PluginCache, level: MemoryPressureLevel, bytes_requested: usize) -> usize {
+            self.manager.shed(cache, level, bytes_requested)
+        }
+    }
+    
+    /// Input-sensitive caching for a pure function of the node's inputs
+    /// 
+    /// The result is cached under a hash of the inputs, so changed inputs are
+    /// recomputed and unchanged inputs hit, with no manual invalidation:
+    /// 
+    /// ```rust,ignore
+    /// let blur = Memoized::new("my_plugin", |inputs: &HashMap<String, NodeData>| {
+    ///     extract_inputs!(inputs => { image: ImageData, radius: f32 = 1.0 });
+    ///     Ok(NodeData::Image(gaussian_blur(&image, radius)))
+    /// });
+    /// let output = blur.call(cache, node_id, inputs)?;
+    /// ```
+    /// 
+    /// Parameters that affect the result must be part of the input map. Only
+    /// the latest result per node is kept.
+    pub struct Memoized<F> {
+        manager: PluginCacheManager,
+        function: F,
+        /// Input hash of each node's cached result
+        current: HashMap<NodeId, u64>,
+    }
+    
+    impl<F> Memoized<F>
+    where
+        F: Fn(&HashMap<String, NodeData>) -> Result<NodeData, String>,
+    {
+        pub fn new(plugin_id: impl Into<String>, function: F) -> Self {
+            Self {
+                manager: PluginCacheManager::new(plugin_id),
+                function,
+                current: HashMap::new(),
+            }
+        }
+        
+        /// Return the cached result for these inputs, calling the function on a miss
+        pub fn call(
+            &mut self,
+            cache: &mut dyn PluginCache,
+            node_id: NodeId,
+            inputs: &HashMap<String, NodeData>
+        ) -> Result<Arc<NodeData>, String> {
+            let key = self.manager.create_content_key(node_id, 0, inputs);
+            let input_hash = key.input_hash.unwrap_or_default();
+            if let Some(previous) = self.current.get(&node_id).copied().filter(|hash| *hash != input_hash) {
+                let mut superseded = CacheTransaction::new();
+                superseded.remove(self.manager.create_key(node_id, 0).with_input_hash(previous));
+                self.manager.commit(cache, superseded)?;
+            }
+            
+            let function = &self.function;
+            let result = self.manager.get_or_compute_shared(cache, key, || function(inputs))?;
+            self.current.insert(node_id, input_hash);
+            Ok(result)
+        }
+        
+        /// Drop a node's cached result
+        pub fn invalidate(&mut self, cache: &mut dyn PluginCache, node_id: NodeId) -> usize {
+            self.current.remove(&node_id);
+            self.manager.invalidate_node(cache, node_id)
+        }
+        
+        /// Drop cached results under memory pressure (see `PluginCacheManager::shed`)
+        pub fn shed(&mut self, cache: &mut dyn PluginCache, level: MemoryPressureLevel, bytes_requested: usize) -> usize {
+            self.manager.shed(cache, level, bytes_requested)
+        }
+    }
+    
+    /// Frame-indexed caching strategy for animated nodes
+    /// 
+    /// Keeps a sliding window of frames around the playhead: storing or
+    /// looking up a frame moves the window there, and frames that fall
+    /// outside it are dropped.
+    pub struct FrameCache {
+        manager: PluginCacheManager,
+        /// Frames kept before the current frame
+        before: u32,
+        /// Frames kept after the current frame
+        after: u32,
+    }
+    
+    impl FrameCache {
+        /// Create a frame cache keeping `before` frames behind and `after` frames ahead
+        pub fn new(plugin_id: impl Into<String>, before: u32, after: u32) -> Self {
+            Self {
+                manager: PluginCacheManager::new(plugin_id),
+                before,
+                after,
+            }
+        }
+        
+        /// Inclusive frame range kept around `frame`
+        pub fn window(&self, frame: i32) -> (i32, i32) {
+            (
+                frame.saturating_sub_unsigned(self.before),
+                frame.saturating_add_unsigned(self.after),
+            )
+        }
+        
+        /// Try to get a cached frame
+        pub fn get_frame<'a>(
+            &self,
+            cache: &'a dyn PluginCache,
+            node_id: NodeId,
+            frame: i32,
+            port_index: usize
+        ) -> Option<&'a NodeData> {
+            let key = self.manager.create_frame_key(node_id, frame, port_index);
+            cache.get(&key)
+        }
+        
+        /// Try to get a shared handle to a cached frame
+        pub fn get_frame_shared(
+            &self,
+            cache: &dyn PluginCache,
+            node_id: NodeId,
+            frame: i32,
+            port_index: usize
+        ) -> Option<Arc<NodeData>> {
+            let key = self.manager.create_frame_key(node_id, frame, port_index);
+            cache.get_shared(&key)
+        }
+        
+        /// Store a frame and slide the window to it
+        pub fn store_frame(
+            &mut self,
+            cache: &mut dyn PluginCache,
+            node_id: NodeId,
+            frame: i32,
+            port_index: usize,
+            data: NodeData
+        ) -> Result<(), String> {
+            self.set_current_frame(cache, node_id, frame);
+            let key = self.manager.create_frame_key(node_id, frame, port_index);
+            self.manager.store(cache, key, data)
+        }
+        
+        /// Get a cached frame, computing and storing it on a miss
+        pub fn get_or_compute_frame_shared<F>(
+            &mut self,
+            cache: &mut dyn PluginCache,
+            node_id: NodeId,
+            frame: i32,
+            port_index: usize,
+            compute: F
+        ) -> Result<Arc<NodeData>, String>
+        where
+            F: FnOnce() -> Result<NodeData, String>,
+        {
+            self.set_current_frame(cache, node_id, frame);
+            let key = self.manager.create_frame_key(node_id, frame, port_index);
+            self.manager.get_or_compute_shared(cache, key, compute)
+        }
+        
+        /// Move the window to `frame`, dropping frames outside it
+        /// 
+        /// Returns the number of entries dropped.
+        pub fn set_current_frame(&mut self, cache: &mut dyn PluginCache, node_id: NodeId, frame: i32) -> usize {
+            let (first, last) = self.window(frame);
+            let mut dropped = 0;
+            if first > i32::MIN {
+                dropped += self.manager.invalidate_frame_range(cache, node_id, i32::MIN, first - 1);
+            }
+            if last < i32::MAX {
+                dropped += self.manager.invalidate_frame_range(cache, node_id, last + 1, i32::MAX);
+            }
+            dropped
+        }
+        
+        /// Frames of a node this strategy has stored, in order
+        pub fn cached_frames(&self, node_id: NodeId) -> Vec<i32> {
+            let mut frames: Vec<i32> = self
+                .manager
+                .managed_keys()
+                .iter()
+                .filter(|key| key.node_id == node_id)
+                .filter_map(|key| key.frame)
+                .collect();
+            frames.sort_unstable();
+            frames.dedup();
+            frames
+        }
+        
+        /// Drop cached frames within an inclusive range (e.g. after a keyframe edit)
+        pub fn invalidate_frames(&mut self, cache: &mut dyn PluginCache, node_id: NodeId, first: i32, last: i32) -> usize {
+            self.manager.invalidate_frame_range(cache, node_id, first, last)
+        }
+        
+        /// Drop every cached frame of a node
+        pub fn invalidate(&mut self, cache: &mut dyn PluginCache, node_id: NodeId) -> usize {
+            self.manager.invalidate_node(cache, node_id)
+        }
+        
+        /// Drop cached frames under memory pressure (see `PluginCacheManager::shed`)
+        pub fn shed(&mut self, cache: &mut dyn PluginCache, level: MemoryPressureLevel, bytes_requested: usize) -> usize {
+            self.manager.shed(cache, level, bytes_requested)
+        }
+    }
+    
+    /// Produces one frame of a node's output (called on background threads)
+    pub type FrameProducer = dyn Fn(NodeId, i32) -> Result<NodeData, String> + Send + Sync;
+    
+    /// Background prefetch running for one node
+    struct PrefetchJob {
+        first: i32,
+        last: i32,
+        cancel: crate::CancellationToken,
+        done: Arc<std::sync::atomic::AtomicBool>,
+        /// Keys the worker stored, waiting to be tracked by the manager
+        stored: Arc<Mutex<Vec<PluginCacheKey>>>,
+        thread: Option<std::thread::JoinHandle<()>>,
+    }
+    
+    impl PrefetchJob {
+        /// Keys stored since the last call
+        fn take_stored(&self) -> Vec<PluginCacheKey> {
+            std::mem::take(&mut *self.stored.lock().unwrap_or_else(|e| e.into_inner()))
+        }
+        
+        /// Check if the worker has exited
+        fn is_done(&self) -> bool {
+            self.done.load(std::sync::atomic::Ordering::Acquire)
+        }
+        
+        /// Wait for the worker to exit, returning the keys it stored
+        /// 
+        /// The worker may be blocked on the host cache, so this must not be
+        /// called while a lock on that cache is held.
+        fn join(mut self) -> Vec<PluginCacheKey> {
+            if let Some(thread) = self.thread.take() {
+                let _ = thread.join();
+            }
+            self.take_stored()
+        }
+    }
+    
+    /// Marks a prefetch job done when the worker exits, even by panicking
+    struct DoneOnDrop(Arc<std::sync::atomic::AtomicBool>);
+    
+    impl Drop for DoneOnDrop {
+        fn drop(&mut self) {
+            self.0.store(true, std::sync::atomic::Ordering::Release);
+        }
+    }
+    
+    /// Frame cache that computes upcoming frames ahead of the playhead
+    /// 
+    /// The plugin registers a frame producer; every lookup of frame `f` also
+    /// schedules `f + 1 ..= f + lookahead` on a background thread, which stores
+    /// the results through the host's `PluginCache::shared_handle`. Without a
+    /// shared handle frames are only computed on demand. A frame requested
+    /// while it is being prefetched is not computed twice.
+    /// 
+    /// Prefetching runs forward only; seeking cancels the running prefetch.
+    /// Cancelling only signals the worker, since the caller usually holds the
+    /// cache lock the worker needs to finish its frame; cancelled workers are
+    /// joined by `wait_for_cancelled` or on drop. Prefetched frames are
+    /// tracked like foreground ones, so `shed` can evict them.
+    pub struct PrefetchCache {
+        frames: FrameCache,
+        producer: Arc<FrameProducer>,
+        lookahead: u32,
+        dedup: crate::ComputeDeduplicator,
+        jobs: HashMap<NodeId, PrefetchJob>,
+        /// Cancelled jobs whose workers may still be running
+        cancelled: Vec<PrefetchJob>,
+    }
+    
+    impl PrefetchCache {
+        /// Create a prefetch cache keeping `before` frames behind the playhead
+        /// and computing `lookahead` frames ahead of it
+        pub fn new<F>(plugin_id: impl Into<String>, before: u32, lookahead: u32, producer: F) -> Self
+        where
+            F: Fn(NodeId, i32) -> Result<NodeData, String> + Send + Sync + 'static,
+        {
+            Self {
+                frames: FrameCache::new(plugin_id, before, lookahead),
+                producer: Arc::new(producer),
+                lookahead,
+                dedup: crate::ComputeDeduplicator::new(),
+                jobs: HashMap::new(),
+                cancelled: Vec::new(),
+            }
+        }
+        
+        /// Get a frame (computing it on a miss) and prefetch the frames after it
+        pub fn get_frame(
+            &mut self,
+            cache: &mut dyn PluginCache,
+            node_id: NodeId,
+            frame: i32
+        ) -> Result<Arc<NodeData>, String> {
+            self.track_prefetched();
+            self.frames.set_current_frame(cache, node_id, frame);
+            let key = self.frames.manager.create_frame_key(node_id, frame, 0);
+            let data = match cache.get_shared(&key) {
+                Some(data) => data,
+                None => {
+                    let producer = &self.producer;
+                    let data = self.dedup.run(crate::hashing::content_hash(&key), || producer(node_id, frame))?;
+                    self.frames.manager.store_shared(cache, key, data.clone())?;
+                    data
+                }
+            };
+            self.schedule(cache, node_id, frame);
+            Ok(data)
+        }
+        
+        /// Check if a node's prefetch is still running
+        pub fn is_prefetching(&self, node_id: NodeId) -> bool {
+            self.jobs
+                .get(&node_id)
+                .is_some_and(|job| !job.is_done())
+        }
+        
+        /// Stop a node's prefetch without waiting for its worker
+        /// 
+        /// The worker exits after the frame it is computing, without storing it.
+        pub fn cancel(&mut self, node_id: NodeId) {
+            if let Some(job) = self.jobs.remove(&node_id) {
+                job.cancel.cancel();
+                self.cancelled.push(job);
+            }
+        }
+        
+        /// Wait for the workers of cancelled prefetches to exit
+        /// 
+        /// Must not be called while holding a lock on the host cache the
+        /// workers store into.
+        pub fn wait_for_cancelled(&mut self) {
+            for job in std::mem::take(&mut self.cancelled) {
+                for key in job.join() {
+                    self.frames.manager.track(&key);
+                }
+            }
+        }
+        
+        /// Frames of a node currently cached, in order
+        pub fn cached_frames(&self, cache: &dyn PluginCache, node_id: NodeId) -> Vec<i32> {
+            let mut frames: Vec<i32> = cache
+                .get_plugin_keys(self.frames.manager.plugin_id())
+                .into_iter()
+                .filter(|key| key.node_id == node_id)
+                .filter_map(|key| key.frame)
+                .collect();
+            frames.sort_unstable();
+            frames.dedup();
+            frames
+        }
+        
+        /// Stop prefetching and drop every cached frame of a node
+        /// 
+        /// Call this when the producer's inputs change.
+        pub fn invalidate(&mut self, cache: &mut dyn PluginCache, node_id: NodeId) -> usize {
+            self.cancel(node_id);
+            self.frames.invalidate(cache, node_id)
+        }
+        
+        /// Stop all prefetching and drop cached frames under memory pressure
+        pub fn shed(&mut self, cache: &mut dyn PluginCache, level: MemoryPressureLevel, bytes_requested: usize) -> usize {
+            let nodes: Vec<NodeId> = self.jobs.keys().copied().collect();
+            for node_id in nodes {
+                self.cancel(node_id);
+            }
+            self.frames.shed(cache, level, bytes_requested)
+        }
+        
+        /// Hand keys stored by prefetches to the manager, releasing exited workers
+        fn track_prefetched(&mut self) {
+            for job in self.jobs.values().chain(self.cancelled.iter()) {
+                for key in job.take_stored() {
+                    self.frames.manager.track(&key);
+                }
+            }
+            let (exited, running): (Vec<_>, Vec<_>) = std::mem::take(&mut self.cancelled)
+                .into_iter()
+                .partition(PrefetchJob::is_done);
+            self.cancelled = running;
+            for job in exited {
+                for key in job.join() {
+                    self.frames.manager.track(&key);
+                }
+            }
+        }
+        
+        /// Start prefetching after `frame` unless a running job already covers it
+        fn schedule(&mut self, cache: &dyn PluginCache, node_id: NodeId, frame: i32) {
+            if self.lookahead == 0 {
+                return;
+            }
+            let Some(first) = frame.checked_add(1) else { return };
+            let last = frame.saturating_add_unsigned(self.lookahead);
+            if let Some(job) = self.jobs.get(&node_id) {
+                if !job.is_done() && (job.first..=job.last).contains(&first) {
+                    return;
+                }
+            }
+            let Some(shared) = cache.shared_handle() else { return };
+            self.cancel(node_id);
+            
+            let mut job = PrefetchJob {
+                first,
+                last,
+                cancel: crate::CancellationToken::new(),
+                done: Arc::new(std::sync::atomic::AtomicBool::new(false)),
+                stored: Arc::new(Mutex::new(Vec::new())),
+                thread: None,
+            };
+            let cancel = job.cancel.clone();
+            let done = DoneOnDrop(job.done.clone());
+            let stored = job.stored.clone();
+            let producer = self.producer.clone();
+            let dedup = self.dedup.clone();
+            let plugin_id = self.frames.manager.plugin_id().to_string();
+            
+            job.thread = Some(std::thread::spawn(move || {
+                let _done = done;
+                for frame in first..=last {
+                    if cancel.is_cancelled() {
+                        break;
+                    }
+                    let key = PluginCacheKey::new(plugin_id.as_str(), node_id, 0).with_frame(frame);
+                    if shared.contains(&key) {
+                        continue;
+                    }
+                    // Failed frames are left for the foreground lookup to report
+                    if let Ok(data) = dedup.run(crate::hashing::content_hash(&key), || producer(node_id, frame)) {
+                        if !cancel.is_cancelled() && shared.insert_shared(key.clone(), data).is_ok() {
+                            stored.lock().unwrap_or_else(|e| e.into_inner()).push(key);
+                        }
+                    }
+                }
+            }));
+            self.jobs.insert(node_id, job);
+        }
+    }
+    
+    impl Drop for PrefetchCache {
+        fn drop(&mut self) {
+            // Signal every worker before waiting on any of them
+            for job in self.jobs.values() {
+                job.cancel.cancel();
+            }
+            for (_, job) in self.jobs.drain() {
+                job.join();
+            }
+            for job in self.cancelled.drain(..) {
+                job.join();
+            }
+        }
+    }
+    
+    /// Multi-stage caching strategy (like USD File Reader)
+    /// 
+    /// This strategy supports multiple stages of processing where each
+    /// stage can be cached independently. Stage 1 might load data from
+    /// disk, Stage 2 might process it, etc.
+    pub struct MultiStageCache {
+        manager: PluginCacheManager,
+    }
+    
+    impl MultiStageCache {
+        pub fn new(plugin_id: impl Into<String>) -> Self {
+            Self {
+                manager: PluginCacheManager::new(plugin_id),
+            }
+        }
+        
+        /// Try to get cached result for a specific stage
+        pub fn get_stage_cached<'a>(
             &self, 
             cache: &'a dyn PluginCache, 
-            node_id: u32, 
+            node_id: NodeId, 
+            stage_id: impl Into<String>,
+            port_index: usize
+        ) -> Option<&'a NodeData> {
+            let key = self.manager.create_stage_key(node_id, stage_id, port_index);
+            cache.get(&key)
+        }
+        
+        /// Try to get a shared handle to a stage's cached result
+        pub fn get_stage_cached_shared(
+            &self,
+            cache: &dyn PluginCache,
+            node_id: NodeId,
+            stage_id: impl Into<String>,
+            port_index: usize
+        ) -> Option<Arc<NodeData>> {
+            let key = self.manager.create_stage_key(node_id, stage_id, port_index);
+            cache.get_shared(&key)
+        }
+        
+        /// Store result for a specific stage
+        pub fn store_stage_result(
+            &mut self, 
+            cache: &mut dyn PluginCache, 
+            node_id: NodeId, 
+            stage_id: impl Into<String>,
+            port_index: usize, 
+            data: NodeData
+        ) -> Result<(), String> {
+            let key = self.manager.create_stage_key(node_id, stage_id, port_index);
+            self.manager.store(cache, key, data)
+        }
+        
+        /// Try to get a stage result computed with specific parameters
+        /// 
+        /// `param_hash` fingerprints the parameters that govern this stage (see
+        /// `PluginCacheKey::hash_parameters`). When any of them change the hash
+        /// changes and the lookup misses, so no invalidation is needed in
+        /// `on_parameter_changed`.
+        pub fn get_stage_cached_keyed<'a>(
+            &self,
+            cache: &'a dyn PluginCache,
+            node_id: NodeId,
+            stage_id: impl Into<String>,
+            port_index: usize,
+            param_hash: u64
+        ) -> Option<&'a NodeData> {
+            let key = self.manager.create_stage_key(node_id, stage_id, port_index).with_input_hash(param_hash);
+            cache.get(&key)
+        }
+        
+        /// Store a stage result under its parameter hash
+        /// 
+        /// Results stored for the same stage and port under other hashes are
+        /// removed, so old parameter values don't pile up in the cache.
+        pub fn store_stage_result_keyed(
+            &mut self,
+            cache: &mut dyn PluginCache,
+            node_id: NodeId,
+            stage_id: impl Into<String>,
+            port_index: usize,
+            param_hash: u64,
+            data: NodeData
+        ) -> Result<(), String> {
+            let key = self.manager.create_stage_key(node_id, stage_id, port_index).with_input_hash(param_hash);
+            let mut transaction = CacheTransaction::new();
+            for old in self.superseded_keys(&key) {
+                transaction.remove(old);
+            }
+            transaction.insert(key, data);
+            self.manager.commit(cache, transaction)
+        }
+        
+        /// Shared-handle variant of `store_stage_result_keyed`
+        /// 
+        /// Superseded results are removed after the new one is stored.
+        pub fn store_stage_result_keyed_shared(
+            &mut self,
+            cache: &mut dyn PluginCache,
+            node_id: NodeId,
+            stage_id: impl Into<String>,
+            port_index: usize,
+            param_hash: u64,
+            data: Arc<NodeData>
+        ) -> Result<(), String> {
+            let key = self.manager.create_stage_key(node_id, stage_id, port_index).with_input_hash(param_hash);
+            let superseded = self.superseded_keys(&key);
+            self.manager.store_shared(cache, key, data)?;
+            let mut transaction = CacheTransaction::new();
+            for old in superseded {
+                transaction.remove(old);
+            }
+            self.manager.commit(cache, transaction)
+        }
+        
+        /// Tracked keys for the same stage and port under a different hash
+        fn superseded_keys(&self, key: &PluginCacheKey) -> Vec<PluginCacheKey> {
+            self.manager
+                .managed_keys()
+                .iter()
+                .filter(|k| {
+                    k.node_id == key.node_id
+                        && k.stage_id == key.stage_id
+                        && k.port_index == key.port_index
+                        && k.input_hash != key.input_hash
+                })
+                .cloned()
+                .collect()
+        }
+        
+        /// Get the cached result for a stage, computing and storing it on a miss
+        pub fn get_or_compute_stage<'a, F>(
+            &mut self,
+            cache: &'a mut dyn PluginCache,
+            node_id: NodeId,
+            stage_id: impl Into<String>,
+            port_index: usize,
+            compute: F
+        ) -> Result<&'a NodeData, String>
+        where
+            F: FnOnce() -> Result<NodeData, String>,
+        {
+            let key = self.manager.create_stage_key(node_id, stage_id, port_index);
+            self.manager.get_or_compute(cache, key, compute)
+        }
+        
+        /// Shared-handle variant of `get_or_compute_stage`
+        pub fn get_or_compute_stage_shared<F>(
+            &mut self,
+            cache: &mut dyn PluginCache,
+            node_id: NodeId,
+            stage_id: impl Into<String>,
+            port_index: usize,
+            compute: F
+        ) -> Result<Arc<NodeData>, String>
+        where
+            F: FnOnce() -> Result<NodeData, String>,
+        {
+            let key = self.manager.create_stage_key(node_id, stage_id, port_index);
+            self.manager.get_or_compute_shared(cache, key, compute)
+        }
+        
+        /// Invalidate cache for a specific stage
+        pub fn invalidate_stage(
+            &mut self, 
+            cache: &mut dyn PluginCache, 
+            node_id: NodeId,
+            stage_id: impl Into<String>
+        ) -> usize {
+            self.manager.invalidate_stage(cache, node_id, stage_id)
+        }
+        
+        /// Invalidate the stages matching a glob (e.g. `"lod/*"`)
+        pub fn invalidate_stage_glob(
+            &mut self,
+            cache: &mut dyn PluginCache,
+            node_id: NodeId,
+            pattern: impl Into<String>
+        ) -> usize {
+            self.manager.invalidate_stage_glob(cache, node_id, pattern)
+        }
+        
+        /// Invalidate all stages for a node
+        pub fn invalidate_all_stages(&mut self, cache: &mut dyn PluginCache, node_id: NodeId) -> usize {
+            self.manager.invalidate_node(cache, node_id)
+        }
+        
+        /// Drop cached stages under memory pressure (see `PluginCacheManager::shed`)
+        pub fn shed(&mut self, cache: &mut dyn PluginCache, level: MemoryPressureLevel, bytes_requested: usize) -> usize {
+            self.manager.shed(cache, level, bytes_requested)
+        }
+    }
+    
+    /// Multi-stage caching with declared dependencies between stages
+    /// 
+    /// Invalidating a stage also invalidates every stage that depends on it,
+    /// directly or transitively:
+    /// 
+    /// ```
+    /// # use nodle_plugin_sdk::cache::strategies::DependentStageCache;
+    /// # use nodle_plugin_sdk::{NodeId, PluginCache};
+    /// # fn on_file_changed(cache: &mut dyn PluginCache, node_id: NodeId) -> Result<(), String> {
+    /// let mut cache_strategy = DependentStageCache::new("usd_reader");
+    /// cache_strategy.add_stage("load", &[])?;
+    /// cache_strategy.add_stage("process", &["load"])?;
+    /// // Also drops "process"
+    /// cache_strategy.invalidate_stage(cache, node_id, "load");
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub struct DependentStageCache {
+        stages: MultiStageCache,
+        /// Declared stages and their direct dependencies, in declaration order
+        dependencies: Vec<(String, Vec<String>)>,
+    }
+    
+    impl DependentStageCache {
+        pub fn new(plugin_id: impl Into<String>) -> Self {
+            Self {
+                stages: MultiStageCache::new(plugin_id),
+                dependencies: Vec::new(),
+            }
+        }
+        
+        /// Declare a stage and the stages it is computed from
+        /// 
+        /// Dependencies must already be declared, which rules out cycles.
+        pub fn add_stage(&mut self, stage_id: impl Into<String>, depends_on: &[&str]) -> Result<(), String> {
+            let stage_id = stage_id.into();
+            if self.has_stage(&stage_id) {
+                return Err(format!("Cache stage '{}' is already declared", stage_id));
+            }
+            if let Some(missing) = depends_on.iter().find(|dep| !self.has_stage(dep)) {
+                return Err(format!("Cache stage '{}' depends on undeclared stage '{}'", stage_id, missing));
+            }
+            let depends_on = depends_on.iter().map(|dep| dep.to_string()).collect();
+            self.dependencies.push((stage_id, depends_on));
+            Ok(())
+        }
+        
+        /// Check if a stage has been declared
+        pub fn has_stage(&self, stage_id: &str) -> bool {
+            self.dependencies.iter().any(|(stage, _)| stage == stage_id)
+        }
+        
+        /// Get the direct dependencies of a stage
+        pub fn dependencies_of(&self, stage_id: &str) -> Option<&[String]> {
+            self.dependencies
+                .iter()
+                .find(|(stage, _)| stage == stage_id)
+                .map(|(_, deps)| deps.as_slice())
+        }
+        
+        /// Get every stage that (transitively) depends on a stage, in declaration order
+        pub fn downstream_stages(&self, stage_id: &str) -> Vec<&str> {
+            let mut affected: Vec<&str> = Vec::new();
+            // Dependencies are always declared first, so one pass in order suffices
+            for (stage, deps) in &self.dependencies {
+                if deps.iter().any(|dep| dep == stage_id || affected.contains(&dep.as_str())) {
+                    affected.push(stage);
+                }
+            }
+            affected
+        }
+        
+        /// Try to get cached result for a specific stage
+        pub fn get_stage_cached<'a>(
+            &self,
+            cache: &'a dyn PluginCache,
+            node_id: NodeId,
+            stage_id: &str,
             port_index: usize
         ) -> Option<&'a NodeData> {
-            let key = self.manager.create_key(node_id, port_index);
-            cache.get(&key)
+            self.stages.get_stage_cached(cache, node_id, stage_id, port_index)
         }
         
-        /// Store result in cache
-        pub fn store_result(
-            &mut self, 
-            cache: &mut dyn PluginCache, 
-            node_id: u32, 
-            port_index: usize, 
+        /// Try to get a shared handle to a stage's cached result
+        pub fn get_stage_cached_shared(
+            &self,
+            cache: &dyn PluginCache,
+            node_id: NodeId,
+            stage_id: &str,
+            port_index: usize
+        ) -> Option<Arc<NodeData>> {
+            self.stages.get_stage_cached_shared(cache, node_id, stage_id, port_index)
+        }
+        
+        /// Store result for a declared stage
+        pub fn store_stage_result(
+            &mut self,
+            cache: &mut dyn PluginCache,
+            node_id: NodeId,
+            stage_id: &str,
+            port_index: usize,
             data: NodeData
         ) -> Result<(), String> {
-            let key = self.manager.create_key(node_id, port_index);
-            self.manager.store(cache, key, data)
+            self.check_stage(stage_id)?;
+            self.stages.store_stage_result(cache, node_id, stage_id, port_index, data)
         }
         
-        /// Invalidate cache for a node
-        pub fn invalidate(&mut self, cache: &mut dyn PluginCache, node_id: u32) -> usize {
-            self.manager.invalidate_node(cache, node_id)
+        /// Get the cached result for a declared stage, computing and storing it on a miss
+        pub fn get_or_compute_stage<'a, F>(
+            &mut self,
+            cache: &'a mut dyn PluginCache,
+            node_id: NodeId,
+            stage_id: &str,
+            port_index: usize,
+            compute: F
+        ) -> Result<&'a NodeData, String>
+        where
+            F: FnOnce() -> Result<NodeData, String>,
+        {
+            self.check_stage(stage_id)?;
+            self.stages.get_or_compute_stage(cache, node_id, stage_id, port_index, compute)
+        }
+        
+        /// Invalidate a stage and every stage downstream of it
+        pub fn invalidate_stage(&mut self, cache: &mut dyn PluginCache, node_id: NodeId, stage_id: &str) -> usize {
+            let mut stages = vec![stage_id.to_string()];
+            stages.extend(self.downstream_stages(stage_id).into_iter().map(str::to_string));
+            stages
+                .into_iter()
+                .map(|stage| self.stages.invalidate_stage(cache, node_id, stage))
+                .sum()
+        }
+        
+        /// Invalidate all stages for a node
+        pub fn invalidate_all_stages(&mut self, cache: &mut dyn PluginCache, node_id: NodeId) -> usize {
+            self.stages.invalidate_all_stages(cache, node_id)
+        }
+        
+        /// Drop cached stages under memory pressure (see `PluginCacheManager::shed`)
+        pub fn shed(&mut self, cache: &mut dyn PluginCache, level: MemoryPressureLevel, bytes_requested: usize) -> usize {
+            self.stages.shed(cache, level, bytes_requested)
+        }
+        
+        fn check_stage(&self, stage_id: &str) -> Result<(), String> {
+            if self.has_stage(stage_id) {
+                Ok(())
+            } else {
+                Err(format!("Cache stage '{}' has not been declared", stage_id))
+            }
         }
     }
     
-    /// Multi-stage caching strategy (like USD File Reader)
+    /// Inputs handed to a stage of a `StagePipeline`
+    pub struct StageInput<'a> {
+        /// Result of the previous stage (None for the first stage)
+        pub previous: Option<&'a NodeData>,
+        values: &'a HashMap<String, NodeData>,
+    }
+    
+    impl<'a> StageInput<'a> {
+        /// Read one of the values passed to `evaluate`
+        pub fn get(&self, name: &str) -> Option<&'a NodeData> {
+            self.values.get(name)
+        }
+    }
+    
+    /// Hit/miss counters for one pipeline stage
     /// 
-    /// This strategy supports multiple stages of processing where each
-    /// stage can be cached independently. Stage 1 might load data from
-    /// disk, Stage 2 might process it, etc.
-    pub struct MultiStageCache {
-        manager: PluginCacheManager,
+    /// A hit means the stage's result was read from the cache; stages before
+    /// it are skipped without being counted.
+    #[derive(Debug, Clone, Default, PartialEq)]
+    pub struct StageRunStatistics {
+        pub hits: usize,
+        pub misses: usize,
+        /// Time the stage took the last time it was computed
+        pub last_compute_time: Option<Duration>,
     }
     
-    impl MultiStageCache {
+    /// Declarative linear stage pipeline
+    /// 
+    /// Declare the stages once, with the inputs or parameters each one reads,
+    /// then let `evaluate` run only what is needed:
+    /// 
+    /// ```
+    /// # use nodle_plugin_sdk::cache::strategies::StagePipeline;
+    /// # use nodle_plugin_sdk::{NodeData, NodeId, PluginCache};
+    /// # use std::collections::HashMap;
+    /// # fn load(_path: Option<&NodeData>) -> Result<NodeData, String> { Ok(NodeData::Float(0.0)) }
+    /// # fn filter(mesh: &NodeData, _threshold: Option<&NodeData>) -> Result<NodeData, String> { Ok(mesh.clone()) }
+    /// # fn tessellate(mesh: &NodeData, _level: Option<&NodeData>) -> Result<NodeData, String> { Ok(mesh.clone()) }
+    /// # fn cook(cache: &mut dyn PluginCache, node_id: NodeId, values: HashMap<String, NodeData>) -> Result<(), String> {
+    /// let mut pipeline = StagePipeline::new("mesh_reader")
+    ///     .stages(["load", "filter", "tessellate"])
+    ///     .stage_inputs("load", &["file_path"])
+    ///     .stage_inputs("filter", &["threshold"])
+    ///     .stage_inputs("tessellate", &["level"]);
+    /// 
+    /// let mesh = pipeline.evaluate(cache, node_id, &values, |stage, input| match stage {
+    ///     "load" => load(input.get("file_path")),
+    ///     "filter" => filter(input.previous.unwrap(), input.get("threshold")),
+    ///     _ => tessellate(input.previous.unwrap(), input.get("level")),
+    /// })?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    /// 
+    /// Each stage is cached under a hash of its declared inputs chained with the
+    /// hashes of all earlier stages, so changing a value re-runs its stage and
+    /// everything after it, and nothing before it, with no explicit invalidation.
+    pub struct StagePipeline {
+        stages: MultiStageCache,
+        /// Stage IDs and the value names each one reads, in pipeline order
+        pipeline: Vec<(String, Vec<String>)>,
+        statistics: HashMap<String, StageRunStatistics>,
+        /// First declaration mistake, reported by `evaluate`
+        declaration_error: Option<String>,
+    }
+    
+    impl StagePipeline {
         pub fn new(plugin_id: impl Into<String>) -> Self {
             Self {
-                manager: PluginCacheManager::new(plugin_id),
+                stages: MultiStageCache::new(plugin_id),
+                pipeline: Vec::new(),
+                statistics: HashMap::new(),
+                declaration_error: None,
             }
         }
         
-        /// Try to get cached result for a specific stage
-        pub fn get_stage_cached<'a>(
-            &self, 
-            cache: &'a dyn PluginCache, 
-            node_id: u32, 
-            stage_id: impl Into<String>,
-            port_index: usize
-        ) -> Option<&'a NodeData> {
-            let key = self.manager.create_stage_key(node_id, stage_id, port_index);
-            cache.get(&key)
+        /// Declare the stages in execution order (replacing any previous declaration)
+        pub fn stages<I, S>(mut self, stages: I) -> Self
+        where
+            I: IntoIterator<Item = S>,
+            S: Into<String>,
+        {
+            self.pipeline = stages.into_iter().map(|stage| (stage.into(), Vec::new())).collect();
+            self.statistics.clear();
+            self
         }
         
-        /// Store result for a specific stage
-        pub fn store_stage_result(
-            &mut self, 
-            cache: &mut dyn PluginCache, 
-            node_id: u32, 
-            stage_id: impl Into<String>,
-            port_index: usize, 
-            data: NodeData
-        ) -> Result<(), String> {
-            let key = self.manager.create_stage_key(node_id, stage_id, port_index);
-            self.manager.store(cache, key, data)
+        /// Declare the values a stage reads
+        /// 
+        /// A stage not declared with `stages` is recorded as an error, which
+        /// `evaluate` and `check` return.
+        pub fn stage_inputs(mut self, stage_id: &str, inputs: &[&str]) -> Self {
+            match self.pipeline.iter_mut().find(|(stage, _)| stage == stage_id) {
+                Some((_, stage_inputs)) => {
+                    *stage_inputs = inputs.iter().map(|input| input.to_string()).collect();
+                }
+                None => {
+                    self.declaration_error
+                        .get_or_insert_with(|| format!("Pipeline stage '{}' has not been declared", stage_id));
+                }
+            }
+            self
         }
         
-        /// Invalidate cache for a specific stage
-        pub fn invalidate_stage(
-            &mut self, 
-            cache: &mut dyn PluginCache, 
-            node_id: u32,
-            stage_id: impl Into<String>
-        ) -> usize {
-            self.manager.invalidate_stage(cache, node_id, stage_id)
+        /// Check the declaration for mistakes (e.g. inputs of an undeclared stage)
+        pub fn check(&self) -> Result<(), String> {
+            match &self.declaration_error {
+                Some(error) => Err(error.clone()),
+                None if self.pipeline.is_empty() => Err("Pipeline has no stages".to_string()),
+                None => Ok(()),
+            }
         }
         
-        /// Invalidate all stages for a node
-        pub fn invalidate_all_stages(&mut self, cache: &mut dyn PluginCache, node_id: u32) -> usize {
-            self.manager.invalidate_node(cache, node_id)
+        /// Declared stage IDs in order
+        pub fn stage_ids(&self) -> impl Iterator<Item = &str> {
+            self.pipeline.iter().map(|(stage, _)| stage.as_str())
+        }
+        
+        /// Run the pipeline, computing only stages whose result is not cached
+        /// 
+        /// Starts from the last cached stage and computes every later stage in
+        /// order, storing each result. Returns the last stage's result.
+        pub fn evaluate<F>(
+            &mut self,
+            cache: &mut dyn PluginCache,
+            node_id: NodeId,
+            values: &HashMap<String, NodeData>,
+            mut compute: F
+        ) -> Result<Arc<NodeData>, String>
+        where
+            F: FnMut(&str, &StageInput) -> Result<NodeData, String>,
+        {
+            self.check()?;
+            let hashes = self.stage_hashes(values);
+            
+            // Find the latest stage that is already cached
+            let mut start = 0;
+            let mut previous: Option<Arc<NodeData>> = None;
+            for index in (0..self.pipeline.len()).rev() {
+                let key = self.stage_key(node_id, index, hashes[index]);
+                if let Some(data) = cache.get_shared(&key) {
+                    start = index + 1;
+                    previous = Some(data);
+                    break;
+                }
+            }
+            for (index, (stage, _)) in self.pipeline.iter().enumerate() {
+                let stats = self.statistics.entry(stage.clone()).or_default();
+                if index + 1 == start {
+                    stats.hits += 1;
+                } else if index >= start {
+                    stats.misses += 1;
+                }
+            }
+            
+            for (index, hash) in hashes.iter().enumerate().skip(start) {
+                let stage_id = self.pipeline[index].0.clone();
+                let started = std::time::Instant::now();
+                let input = StageInput {
+                    previous: previous.as_deref(),
+                    values,
+                };
+                let data = Arc::new(compute(&stage_id, &input)?);
+                if let Some(stats) = self.statistics.get_mut(&stage_id) {
+                    stats.last_compute_time = Some(started.elapsed());
+                }
+                self.stages
+                    .store_stage_result_keyed_shared(cache, node_id, stage_id, 0, *hash, Arc::clone(&data))?;
+                previous = Some(data);
+            }
+            previous.ok_or_else(|| "Pipeline produced no result".to_string())
+        }
+        
+        /// Hit/miss counters for a stage
+        pub fn statistics(&self, stage_id: &str) -> Option<&StageRunStatistics> {
+            self.statistics.get(stage_id)
+        }
+        
+        /// Drop every cached stage of a node
+        pub fn invalidate(&mut self, cache: &mut dyn PluginCache, node_id: NodeId) -> usize {
+            self.stages.invalidate_all_stages(cache, node_id)
+        }
+        
+        /// Drop cached stages under memory pressure (see `PluginCacheManager::shed`)
+        pub fn shed(&mut self, cache: &mut dyn PluginCache, level: MemoryPressureLevel, bytes_requested: usize) -> usize {
+            self.stages.shed(cache, level, bytes_requested)
+        }
+        
+        /// Chained hash per stage: its own inputs plus everything upstream
+        fn stage_hashes(&self, values: &HashMap<String, NodeData>) -> Vec<u64> {
+            let mut upstream = 0;
+            self.pipeline
+                .iter()
+                .map(|(_, inputs)| {
+                    let own: Vec<(&str, u64)> = inputs
+                        .iter()
+                        .map(|name| {
+                            // Tag presence so a missing input never matches a present one
+                            let hash = match values.get(name) {
+                                Some(value) => crate::hashing::combine_hashes([1, value.content_hash()]),
+                                None => crate::hashing::combine_hashes([0]),
+                            };
+                            (name.as_str(), hash)
+                        })
+                        .collect();
+                    upstream = crate::hashing::combine_hashes([upstream, PluginCacheKey::hash_fingerprints(&own)]);
+                    upstream
+                })
+                .collect()
+        }
+        
+        fn stage_key(&self, node_id: NodeId, index: usize, hash: u64) -> PluginCacheKey {
+            PluginCacheKey::with_stage(self.stages.manager.plugin_id(), node_id, self.pipeline[index].0.as_str(), 0)
+                .with_input_hash(hash)
         }
     }
 }
@@ -398,37 +2656,32 @@ pub mod examples {
         pub fn process_with_cache(
             &mut self,
             cache: &mut dyn PluginCache,
-            node_id: u32,
+            node_id: NodeId,
             inputs: &HashMap<String, NodeData>
         ) -> Result<HashMap<String, NodeData>, String> {
-            // Try to get cached result first
-            if let Some(cached_result) = self.cache_strategy.get_cached(cache, node_id, 0) {
-                println!("🔥 Cache hit for node {}", node_id);
-                let mut outputs = HashMap::new();
-                outputs.insert("output".to_string(), cached_result.clone());
-                return Ok(outputs);
-            }
-            
-            // Cache miss - perform expensive computation
-            println!("💾 Cache miss for node {} - computing", node_id);
-            
-            // Simulate expensive processing
-            let result = self.expensive_computation(inputs)?;
-            
-            // Store result in cache
-            self.cache_strategy.store_result(cache, node_id, 0, result.clone())?;
+            // Computation only runs on a cache miss
+            let result = self.cache_strategy.get_or_compute(cache, node_id, 0, || {
+                println!("💾 Cache miss for node {} - computing", node_id);
+                Self::expensive_computation(inputs)
+            })?;
             
             let mut outputs = HashMap::new();
-            outputs.insert("output".to_string(), result);
+            outputs.insert("output".to_string(), result.clone());
             Ok(outputs)
         }
         
-        fn expensive_computation(&self, _inputs: &HashMap<String, NodeData>) -> Result<NodeData, String> {
+        fn expensive_computation(_inputs: &HashMap<String, NodeData>) -> Result<NodeData, String> {
             // Simulate expensive work
             Ok(NodeData::String("Expensive result".to_string()))
         }
     }
     
+    impl Default for ExampleSimpleCachedPlugin {
+        fn default() -> Self {
+            Self::new()
+        }
+    }
+    
     /// Example plugin that uses multi-stage caching
     pub struct ExampleMultiStageCachedPlugin {
         cache_strategy: MultiStageCache,
@@ -444,7 +2697,7 @@ pub mod examples {
         pub fn process_with_multistage_cache(
             &mut self,
             cache: &mut dyn PluginCache,
-            node_id: u32,
+            node_id: NodeId,
             inputs: &HashMap<String, NodeData>
         ) -> Result<HashMap<String, NodeData>, String> {
             // Stage 1: Load/prepare data
@@ -485,15 +2738,658 @@ pub mod examples {
         }
         
         /// Invalidate only Stage 2 when parameters change (keeping Stage 1 intact)
-        pub fn on_parameters_changed(&mut self, cache: &mut dyn PluginCache, node_id: u32) {
+        pub fn on_parameters_changed(&mut self, cache: &mut dyn PluginCache, node_id: NodeId) {
             println!("🔧 Parameters changed - invalidating Stage 2 cache only");
             self.cache_strategy.invalidate_stage(cache, node_id, "stage2");
         }
         
         /// Invalidate Stage 1 when file path changes (which also invalidates Stage 2)
-        pub fn on_file_changed(&mut self, cache: &mut dyn PluginCache, node_id: u32) {
+        pub fn on_file_changed(&mut self, cache: &mut dyn PluginCache, node_id: NodeId) {
             println!("📁 File changed - invalidating all stages");
             self.cache_strategy.invalidate_all_stages(cache, node_id);
         }
     }
-}
\ No newline at end of file
+    
+    impl Default for ExampleMultiStageCachedPlugin {
+        fn default() -> Self {
+            Self::new()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::strategies::*;
+    use super::*;
+    use std::time::Instant;
+
+    /// In-memory host cache that enforces policies and snapshots like a real host
+    #[derive(Default)]
+    struct MemoryCache {
+        entries: HashMap<PluginCacheKey, (Arc<NodeData>, CacheEntryInfo, Option<Instant>)>,
+        policies: HashMap<String, CachePolicy>,
+        clock: u64,
+        preload: Vec<PluginCacheKey>,
+        shared: Option<SharedCacheHandle>,
+    }
+
+    impl MemoryCache {
+        /// Cache wrapped in a `RwLock`, handing out a shared handle to itself
+        fn shared() -> Arc<RwLock<MemoryCache>> {
+            let cache = Arc::new(RwLock::new(MemoryCache::default()));
+            let handle = SharedCacheHandle::new(cache.clone());
+            cache.write().unwrap().shared = Some(handle);
+            cache
+        }
+
+        fn store(&mut self, key: PluginCacheKey, data: Arc<NodeData>, expires: Option<Instant>) -> Result<(), String> {
+            self.clock += 1;
+            let info = CacheEntryInfo {
+                size_bytes: format!("{data:?}").len(),
+                inserted_at: self.clock,
+                last_access: self.clock,
+                ..Default::default()
+            };
+            self.entries.insert(key.clone(), (data, info, expires));
+            if let Some(policy) = self.policies.get(&key.plugin_id) {
+                let keys: Vec<PluginCacheKey> = self
+                    .entries
+                    .keys()
+                    .filter(|k| k.plugin_id == key.plugin_id)
+                    .cloned()
+                    .collect();
+                let infos: Vec<CacheEntryInfo> = keys.iter().map(|k| self.entries[k].1).collect();
+                for index in policy.entries_to_evict(&infos) {
+                    self.entries.remove(&keys[index]);
+                }
+            }
+            if self.entries.contains_key(&key) {
+                Ok(())
+            } else {
+                Err(format!("Entry for node {} exceeds the policy", key.node_id))
+            }
+        }
+
+        fn live(&self, key: &PluginCacheKey) -> Option<&(Arc<NodeData>, CacheEntryInfo, Option<Instant>)> {
+            self.entries
+                .get(key)
+                .filter(|(_, _, expires)| expires.map_or(true, |at| Instant::now() < at))
+        }
+    }
+
+    impl PluginCache for MemoryCache {
+        fn insert(&mut self, key: PluginCacheKey, data: NodeData) -> Result<(), String> {
+            self.store(key, Arc::new(data), None)
+        }
+
+        fn insert_with_ttl(&mut self, key: PluginCacheKey, data: NodeData, ttl: Duration) -> Result<(), String> {
+            self.store(key, Arc::new(data), Some(Instant::now() + ttl))
+        }
+
+        fn get(&self, key: &PluginCacheKey) -> Option<&NodeData> {
+            self.live(key).map(|(data, _, _)| &**data)
+        }
+
+        fn take(&mut self, key: &PluginCacheKey) -> Option<NodeData> {
+            self.live(key)?;
+            let (data, _, _) = self.entries.remove(key)?;
+            Some(Arc::try_unwrap(data).unwrap_or_else(|data| (*data).clone()))
+        }
+
+        fn contains(&self, key: &PluginCacheKey) -> bool {
+            self.live(key).is_some()
+        }
+
+        fn invalidate(&mut self, pattern: &PluginCacheKeyPattern) -> usize {
+            let before = self.entries.len();
+            self.entries.retain(|key, _| !pattern.matches(key));
+            before - self.entries.len()
+        }
+
+        fn clear_plugin(&mut self, plugin_id: &str) -> usize {
+            self.invalidate(&PluginCacheKeyPattern::Plugin(plugin_id.to_string()))
+        }
+
+        fn copy_node_entries(&mut self, plugin_id: &str, src_node_id: NodeId, dst_node_id: NodeId) -> usize {
+            let copies: Vec<(PluginCacheKey, Arc<NodeData>)> = self
+                .entries
+                .iter()
+                .filter(|(key, _)| key.plugin_id == plugin_id && key.node_id == src_node_id)
+                .map(|(key, (data, _, _))| (PluginCacheKey { node_id: dst_node_id, ..key.clone() }, data.clone()))
+                .collect();
+            let count = copies.len();
+            for (key, data) in copies {
+                let _ = self.store(key, data, None);
+            }
+            count
+        }
+
+        fn get_plugin_statistics(&self, plugin_id: &str) -> PluginCacheStatistics {
+            let mut statistics = PluginCacheStatistics {
+                plugin_id: plugin_id.to_string(),
+                ..Default::default()
+            };
+            for (key, (_, info, _)) in self.entries.iter().filter(|(key, _)| key.plugin_id == plugin_id) {
+                statistics.total_entries += 1;
+                if key.has_stage() {
+                    statistics.multi_stage_entries += 1;
+                } else {
+                    statistics.single_stage_entries += 1;
+                }
+                statistics.estimated_memory_usage += info.size_bytes;
+            }
+            statistics
+        }
+
+        fn all_statistics(&self) -> CacheStatisticsReport {
+            let mut plugin_ids: Vec<&str> = self.entries.keys().map(|key| key.plugin_id.as_str()).collect();
+            plugin_ids.sort_unstable();
+            plugin_ids.dedup();
+            CacheStatisticsReport {
+                sequence: self.clock,
+                plugins: plugin_ids.into_iter().map(|id| self.get_plugin_statistics(id)).collect(),
+            }
+        }
+
+        fn statistics_since(&self, _sequence: u64) -> CacheStatisticsDelta {
+            CacheStatisticsDelta {
+                sequence: self.clock,
+                changed: self.all_statistics().plugins,
+                removed_plugins: Vec::new(),
+            }
+        }
+
+        fn get_plugin_keys(&self, plugin_id: &str) -> Vec<&PluginCacheKey> {
+            self.entries.keys().filter(|key| key.plugin_id == plugin_id).collect()
+        }
+
+        fn set_policy(&mut self, plugin_id: &str, policy: CachePolicy) -> Result<(), String> {
+            self.policies.insert(plugin_id.to_string(), policy);
+            Ok(())
+        }
+
+        fn get_policy(&self, plugin_id: &str) -> Option<CachePolicy> {
+            self.policies.get(plugin_id).cloned()
+        }
+
+        fn set_priority(&mut self, key: &PluginCacheKey, priority: CachePriority) -> bool {
+            self.entries.get_mut(key).map(|(_, info, _)| info.priority = priority).is_some()
+        }
+
+        fn pin(&mut self, key: &PluginCacheKey) -> bool {
+            self.entries.get_mut(key).map(|(_, info, _)| info.pinned = true).is_some()
+        }
+
+        fn unpin(&mut self, key: &PluginCacheKey) -> bool {
+            self.entries
+                .get_mut(key)
+                .is_some_and(|(_, info, _)| std::mem::replace(&mut info.pinned, false))
+        }
+
+        fn entry_info(&self, key: &PluginCacheKey) -> Option<CacheEntryInfo> {
+            self.live(key).map(|(_, info, _)| *info)
+        }
+
+        fn export_plugin(&self, plugin_id: &str) -> CacheSnapshot {
+            let mut snapshot = CacheSnapshot::new(plugin_id);
+            for key in self.get_plugin_keys(plugin_id) {
+                if let Some((data, _, expires)) = self.live(key) {
+                    snapshot.entries.push(CacheSnapshotEntry {
+                        key: key.clone(),
+                        data: (**data).clone(),
+                        ttl: expires.map(|at| at.saturating_duration_since(Instant::now())),
+                    });
+                }
+            }
+            snapshot
+        }
+
+        fn import_plugin(&mut self, snapshot: CacheSnapshot) -> Result<usize, String> {
+            snapshot.validate()?;
+            let count = snapshot.len();
+            for entry in snapshot.entries {
+                let expires = entry.ttl.map(|ttl| Instant::now() + ttl);
+                self.store(entry.key, Arc::new(entry.data), expires)?;
+            }
+            Ok(count)
+        }
+
+        fn request_preload(&mut self, keys: Vec<PluginCacheKey>) -> usize {
+            let before = self.preload.len();
+            for key in keys {
+                if !self.entries.contains_key(&key) && !self.preload.contains(&key) {
+                    self.preload.push(key);
+                }
+            }
+            self.preload.len() - before
+        }
+
+        fn get_shared(&self, key: &PluginCacheKey) -> Option<Arc<NodeData>> {
+            self.live(key).map(|(data, _, _)| data.clone())
+        }
+
+        fn insert_shared(&mut self, key: PluginCacheKey, data: Arc<NodeData>) -> Result<(), String> {
+            self.store(key, data, None)
+        }
+
+        fn commit(&mut self, transaction: CacheTransaction) -> Result<(), String> {
+            let before = self.entries.clone();
+            for operation in transaction.into_operations() {
+                let result = match operation {
+                    CacheOperation::Insert { key, data, ttl } => {
+                        self.store(key, Arc::new(data), ttl.map(|ttl| Instant::now() + ttl))
+                    }
+                    CacheOperation::Invalidate(pattern) => {
+                        self.invalidate(&pattern);
+                        Ok(())
+                    }
+                };
+                if result.is_err() {
+                    self.entries = before;
+                    return result;
+                }
+            }
+            Ok(())
+        }
+
+        fn shared_handle(&self) -> Option<SharedCacheHandle> {
+            self.shared.clone()
+        }
+    }
+
+    #[test]
+    fn prefetch_seek_does_not_wait_for_a_worker_blocked_on_the_cache_lock() {
+        let cache = MemoryCache::shared();
+        let (sender, receiver) = std::sync::mpsc::channel();
+        let host = cache.clone();
+        std::thread::spawn(move || {
+            let mut prefetch = PrefetchCache::new("prefetch", 0, 4, |_, frame| Ok(NodeData::Integer(frame)));
+            let mut guard = host.write().unwrap();
+            prefetch.get_frame(&mut *guard, NodeId(1), 0).unwrap();
+            // The worker is now waiting for the lock this thread holds
+            std::thread::sleep(Duration::from_millis(50));
+            let data = prefetch.get_frame(&mut *guard, NodeId(1), 100).unwrap();
+            drop(guard);
+            prefetch.wait_for_cancelled();
+            sender.send(data).unwrap();
+        });
+        let data = receiver.recv_timeout(Duration::from_secs(10)).expect("seeking deadlocked on the cache lock");
+        assert!(matches!(*data, NodeData::Integer(100)));
+        let guard = cache.read().unwrap();
+        let key = PluginCacheKey::new("prefetch", NodeId(1), 0);
+        // The cancelled job exits without storing the frame it was computing
+        assert!(!guard.contains(&key.clone().with_frame(1)));
+        assert!(guard.contains(&key.with_frame(100)));
+    }
+
+    fn key(node: u64, port: usize) -> PluginCacheKey {
+        PluginCacheKey::new("test", NodeId(node), port)
+    }
+
+    fn inputs(pairs: &[(&str, i32)]) -> HashMap<String, NodeData> {
+        pairs.iter().map(|(name, value)| (name.to_string(), NodeData::Integer(*value))).collect()
+    }
+
+    fn info(priority: CachePriority, last_access: u64, pinned: bool) -> CacheEntryInfo {
+        CacheEntryInfo {
+            size_bytes: 10,
+            inserted_at: 10 - last_access,
+            last_access,
+            access_count: last_access,
+            priority,
+            pinned,
+        }
+    }
+
+    #[test]
+    fn glob_match_handles_stars_and_single_characters() {
+        let cases = [
+            ("lod/*", "lod/0", true),
+            ("lod/*", "lod/high/1", true),
+            ("lod/?", "lod/1", true),
+            ("lod/?", "lod/10", false),
+            ("*", "", true),
+            ("", "", true),
+            ("?", "", false),
+            ("a*b*c", "axxbyyc", true),
+            ("a*b", "ab", true),
+            ("a*b", "abc", false),
+            ("*x", "xyx", true),
+            ("*.usd", "scene.usda", false),
+        ];
+        for (pattern, text, expected) in cases {
+            assert_eq!(glob_match(pattern, text), expected, "{pattern:?} against {text:?}");
+        }
+    }
+
+    #[test]
+    fn entries_to_evict_orders_by_priority_then_strategy_and_skips_pinned() {
+        let entries = [
+            info(CachePriority::Normal, 5, false),
+            info(CachePriority::Low, 9, false),
+            info(CachePriority::Normal, 1, true),
+            info(CachePriority::High, 0, false),
+            info(CachePriority::Normal, 3, false),
+        ];
+        let lru = CachePolicy::default().with_max_entries(2);
+        assert_eq!(lru.entries_to_evict(&entries), vec![1, 4, 0]);
+        let fifo = lru.clone().with_eviction(EvictionStrategy::Fifo);
+        assert_eq!(fifo.entries_to_evict(&entries), vec![1, 0, 4]);
+        assert_eq!(CachePolicy::max_bytes(30).entries_to_evict(&entries), vec![1, 4]);
+        assert!(CachePolicy::max_bytes(50).entries_to_evict(&entries).is_empty());
+        // Pinned entries stay even when the budget remains exceeded
+        assert_eq!(CachePolicy::default().with_max_entries(0).entries_to_evict(&entries[2..3]), Vec::<usize>::new());
+    }
+
+    #[test]
+    fn host_eviction_respects_pins_and_priorities() {
+        let mut cache = MemoryCache::default();
+        let cache: &mut dyn PluginCache = &mut cache;
+        cache.set_policy("test", CachePolicy::default().with_max_entries(2)).unwrap();
+        cache.insert(key(1, 0), NodeData::Integer(0)).unwrap();
+        cache.insert(key(1, 1), NodeData::Integer(1)).unwrap();
+        assert!(cache.pin(&key(1, 0)));
+        assert!(cache.set_priority(&key(1, 1), CachePriority::High));
+        // The only evictable entry with the lowest priority is the new one
+        assert!(cache.insert(key(1, 2), NodeData::Integer(2)).is_err());
+        assert!(cache.contains(&key(1, 0)) && cache.contains(&key(1, 1)));
+
+        assert!(cache.unpin(&key(1, 0)));
+        assert!(!cache.unpin(&key(1, 0)));
+        cache.insert(key(1, 3), NodeData::Integer(3)).unwrap();
+        assert!(!cache.contains(&key(1, 0)));
+        assert!(cache.contains(&key(1, 1)) && cache.contains(&key(1, 3)));
+        assert!(!cache.pin(&key(1, 0)));
+    }
+
+    #[test]
+    fn transactions_apply_all_or_nothing() {
+        let mut host = MemoryCache::default();
+        let cache: &mut dyn PluginCache = &mut host;
+        cache.insert(key(1, 0), NodeData::Integer(0)).unwrap();
+
+        let committed = cache
+            .transaction(|txn| {
+                txn.insert(key(1, 1), NodeData::Integer(1));
+                txn.abort();
+            })
+            .unwrap();
+        assert!(!committed);
+        assert!(!cache.contains(&key(1, 1)));
+
+        let committed = cache
+            .transaction(|txn| {
+                txn.invalidate(PluginCacheKeyPattern::Node("test".into(), NodeId(1)));
+                txn.insert(key(1, 1), NodeData::Integer(1));
+            })
+            .unwrap();
+        assert!(committed);
+        assert!(!cache.contains(&key(1, 0)) && cache.contains(&key(1, 1)));
+
+        // A failing operation rolls back the ones before it
+        cache.set_policy("test", CachePolicy::default().with_max_entries(1)).unwrap();
+        assert!(cache.pin(&key(1, 1)));
+        let mut manager = PluginCacheManager::new("test");
+        let mut failing = CacheTransaction::new();
+        failing.insert(key(2, 0), NodeData::Integer(4));
+        assert!(manager.commit(cache, failing).is_err());
+        assert!(cache.contains(&key(1, 1)) && !cache.contains(&key(2, 0)));
+        assert_eq!(manager.managed_key_count(), 0);
+
+        let mut replacing = CacheTransaction::new();
+        replacing.remove(key(1, 1));
+        replacing.insert(key(1, 2), NodeData::Integer(2));
+        manager.commit(cache, replacing).unwrap();
+        assert!(!cache.contains(&key(1, 1)) && cache.contains(&key(1, 2)));
+        assert_eq!(manager.managed_keys(), &[key(1, 2)]);
+    }
+
+    #[test]
+    fn memoized_recomputes_on_changed_inputs_and_drops_the_superseded_result() {
+        let mut cache = MemoryCache::default();
+        let calls = std::cell::Cell::new(0);
+        let mut doubled = Memoized::new("test", |inputs: &HashMap<String, NodeData>| {
+            calls.set(calls.get() + 1);
+            match inputs.get("x") {
+                Some(NodeData::Integer(x)) => Ok(NodeData::Integer(x * 2)),
+                _ => Err("missing x".to_string()),
+            }
+        });
+
+        let first = doubled.call(&mut cache, NodeId(1), &inputs(&[("x", 1)])).unwrap();
+        let again = doubled.call(&mut cache, NodeId(1), &inputs(&[("x", 1)])).unwrap();
+        assert!(Arc::ptr_eq(&first, &again));
+        assert_eq!(calls.get(), 1);
+
+        let changed = doubled.call(&mut cache, NodeId(1), &inputs(&[("x", 2)])).unwrap();
+        assert!(matches!(*changed, NodeData::Integer(4)));
+        assert_eq!(calls.get(), 2);
+        assert_eq!(cache.get_plugin_keys("test").len(), 1);
+
+        // Other nodes keep their own result
+        doubled.call(&mut cache, NodeId(2), &inputs(&[("x", 1)])).unwrap();
+        assert_eq!(cache.get_plugin_keys("test").len(), 2);
+        assert!(doubled.call(&mut cache, NodeId(3), &inputs(&[])).is_err());
+        assert_eq!(cache.get_plugin_keys("test").len(), 2);
+    }
+
+    #[test]
+    fn frame_cache_keeps_the_window_around_the_current_frame() {
+        let mut cache = MemoryCache::default();
+        let mut frames = FrameCache::new("test", 1, 2);
+        for frame in 0..=10 {
+            frames.store_frame(&mut cache, NodeId(1), frame, 0, NodeData::Integer(frame)).unwrap();
+        }
+        assert_eq!(frames.cached_frames(NodeId(1)), vec![9, 10]);
+        assert_eq!(frames.set_current_frame(&mut cache, NodeId(1), 8), 0);
+        assert_eq!(frames.set_current_frame(&mut cache, NodeId(1), 7), 1);
+        assert_eq!(frames.cached_frames(NodeId(1)), vec![9]);
+
+        assert_eq!(frames.window(i32::MAX), (i32::MAX - 1, i32::MAX));
+        assert_eq!(frames.window(i32::MIN), (i32::MIN, i32::MIN + 2));
+        frames.store_frame(&mut cache, NodeId(1), i32::MAX, 0, NodeData::Integer(0)).unwrap();
+        frames.store_frame(&mut cache, NodeId(1), i32::MIN, 0, NodeData::Integer(0)).unwrap();
+        assert_eq!(frames.cached_frames(NodeId(1)), vec![i32::MIN]);
+    }
+
+    #[test]
+    fn dependent_stages_invalidate_everything_downstream() {
+        let mut cache = MemoryCache::default();
+        let mut stages = DependentStageCache::new("test");
+        stages.add_stage("load", &[]).unwrap();
+        stages.add_stage("process", &["load"]).unwrap();
+        stages.add_stage("preview", &["load"]).unwrap();
+        stages.add_stage("export", &["process"]).unwrap();
+        assert!(stages.add_stage("load", &[]).is_err());
+        assert!(stages.add_stage("mesh", &["missing"]).is_err());
+
+        assert_eq!(stages.downstream_stages("load"), vec!["process", "preview", "export"]);
+        assert_eq!(stages.downstream_stages("process"), vec!["export"]);
+        assert!(stages.downstream_stages("export").is_empty());
+
+        for stage in ["load", "process", "preview", "export"] {
+            stages.store_stage_result(&mut cache, NodeId(1), stage, 0, NodeData::Integer(0)).unwrap();
+        }
+        assert!(stages.store_stage_result(&mut cache, NodeId(1), "mesh", 0, NodeData::Integer(0)).is_err());
+        assert_eq!(stages.invalidate_stage(&mut cache, NodeId(1), "process"), 2);
+        assert!(stages.get_stage_cached(&cache, NodeId(1), "load", 0).is_some());
+        assert!(stages.get_stage_cached(&cache, NodeId(1), "preview", 0).is_some());
+        assert!(stages.get_stage_cached(&cache, NodeId(1), "export", 0).is_none());
+    }
+
+    #[test]
+    fn stage_pipeline_reruns_only_changed_stages_and_counts_hits() {
+        let mut cache = MemoryCache::default();
+        let mut pipeline = StagePipeline::new("test")
+            .stages(["load", "filter", "tessellate"])
+            .stage_inputs("load", &["file"])
+            .stage_inputs("filter", &["threshold"])
+            .stage_inputs("tessellate", &["level"]);
+        let mut runs: Vec<String> = Vec::new();
+        let mut evaluate = |pipeline: &mut StagePipeline, runs: &mut Vec<String>, values: &HashMap<String, NodeData>| {
+            pipeline
+                .evaluate(&mut cache, NodeId(1), values, |stage, input| {
+                    runs.push(stage.to_string());
+                    let previous = match input.previous {
+                        Some(NodeData::Integer(value)) => *value,
+                        _ => 0,
+                    };
+                    Ok(NodeData::Integer(previous * 10 + runs.len() as i32))
+                })
+                .unwrap()
+        };
+
+        let values = inputs(&[("file", 1), ("threshold", 2), ("level", 3)]);
+        let result = evaluate(&mut pipeline, &mut runs, &values);
+        assert!(matches!(*result, NodeData::Integer(123)));
+        assert_eq!(runs, ["load", "filter", "tessellate"]);
+
+        runs.clear();
+        evaluate(&mut pipeline, &mut runs, &values);
+        assert!(runs.is_empty());
+        let stats = pipeline.statistics("tessellate").unwrap();
+        assert_eq!((stats.hits, stats.misses), (1, 1));
+
+        runs.clear();
+        evaluate(&mut pipeline, &mut runs, &inputs(&[("file", 1), ("threshold", 5), ("level", 3)]));
+        assert_eq!(runs, ["filter", "tessellate"]);
+        let stats = pipeline.statistics("load").unwrap();
+        assert_eq!((stats.hits, stats.misses), (1, 1));
+
+        // A missing input never matches a present one
+        runs.clear();
+        evaluate(&mut pipeline, &mut runs, &inputs(&[("file", 1), ("threshold", 5)]));
+        assert_eq!(runs, ["tessellate"]);
+
+        runs.clear();
+        evaluate(&mut pipeline, &mut runs, &inputs(&[("file", 2), ("threshold", 5)]));
+        assert_eq!(runs, ["load", "filter", "tessellate"]);
+        // Superseded results of each stage are dropped
+        assert_eq!(cache.get_plugin_keys("test").len(), 3);
+
+        let broken = StagePipeline::new("test").stages(["load"]).stage_inputs("save", &["path"]);
+        assert!(broken.check().is_err());
+        assert!(StagePipeline::new("test").check().is_err());
+    }
+
+    #[test]
+    fn shed_frees_by_priority_and_skips_pinned_entries() {
+        let mut cache = MemoryCache::default();
+        let mut manager = PluginCacheManager::new("test");
+        let priorities = [CachePriority::Low, CachePriority::Normal, CachePriority::High, CachePriority::Low];
+        for (port, priority) in priorities.into_iter().enumerate() {
+            manager.store(&mut cache, key(1, port), NodeData::Integer(port as i32)).unwrap();
+            manager.prioritize(&mut cache, &key(1, port), priority);
+        }
+        manager.pin(&mut cache, &key(1, 3));
+        let size = cache.entry_info(&key(1, 0)).unwrap().size_bytes;
+
+        assert_eq!(manager.shed(&mut cache, MemoryPressureLevel::Low, usize::MAX), size);
+        assert!(!cache.contains(&key(1, 0)) && cache.contains(&key(1, 3)));
+        assert_eq!(manager.priority(&cache, &key(1, 2)), CachePriority::High);
+        assert_eq!(manager.shed(&mut cache, MemoryPressureLevel::Moderate, usize::MAX), size);
+        assert!(cache.contains(&key(1, 2)));
+        assert_eq!(manager.shed(&mut cache, MemoryPressureLevel::Critical, 1), size);
+        assert!(cache.contains(&key(1, 3)));
+        assert_eq!(manager.managed_keys(), &[key(1, 3)]);
+    }
+
+    #[test]
+    fn shed_stops_once_enough_bytes_are_freed() {
+        let mut cache = MemoryCache::default();
+        let mut manager = PluginCacheManager::new("test");
+        for port in 0..3 {
+            manager.store(&mut cache, key(1, port), NodeData::Integer(0)).unwrap();
+        }
+        let size = cache.entry_info(&key(1, 0)).unwrap().size_bytes;
+        assert_eq!(manager.shed(&mut cache, MemoryPressureLevel::Critical, size + 1), 2 * size);
+        // Least recently used first
+        assert!(!cache.contains(&key(1, 0)) && !cache.contains(&key(1, 1)) && cache.contains(&key(1, 2)));
+    }
+
+    #[test]
+    fn export_and_import_round_trip_managed_entries() {
+        let mut cache = MemoryCache::default();
+        let mut manager = PluginCacheManager::new("test");
+        manager.store(&mut cache, key(1, 0), NodeData::Integer(7)).unwrap();
+        manager
+            .store_with_ttl(&mut cache, key(1, 1), NodeData::String("net".into()), Duration::from_secs(60))
+            .unwrap();
+        cache.insert(key(2, 0), NodeData::Integer(9)).unwrap();
+
+        manager
+            .store_with_ttl(&mut cache, key(1, 1), NodeData::String("net".into()), Duration::from_secs(60))
+            .unwrap();
+        assert_eq!(manager.managed_key_count(), 2);
+
+        let snapshot = manager.export(&cache, |_, data| !matches!(data, NodeData::Integer(9)));
+        assert_eq!(snapshot.len(), 2);
+        assert!(snapshot.entries.iter().any(|entry| entry.ttl.is_some()));
+
+        let mut restored = MemoryCache::default();
+        let mut importer = PluginCacheManager::new("test");
+        assert_eq!(importer.import(&mut restored, snapshot.clone()).unwrap(), 2);
+        assert_eq!(importer.managed_key_count(), 2);
+        assert!(matches!(restored.get(&key(1, 0)), Some(NodeData::Integer(7))));
+        assert!(matches!(restored.get(&key(1, 1)), Some(NodeData::String(s)) if s == "net"));
+        assert!(!restored.contains(&key(2, 0)));
+
+        assert!(PluginCacheManager::new("other").import(&mut restored, snapshot.clone()).is_err());
+        let mut newer = snapshot;
+        newer.version = CACHE_SNAPSHOT_VERSION + 1;
+        assert!(importer.import(&mut restored, newer).is_err());
+    }
+
+    #[test]
+    fn copied_and_batch_stored_entries_are_tracked() {
+        let mut cache = MemoryCache::default();
+        let mut manager = PluginCacheManager::new("test");
+        manager
+            .store_all(&mut cache, vec![(key(1, 0), NodeData::Integer(0)), (key(1, 1), NodeData::Integer(1))])
+            .unwrap();
+        assert_eq!(manager.copy_node_entries(&mut cache, NodeId(1), NodeId(2)), 2);
+        assert_eq!(manager.managed_key_count(), 4);
+        assert!(matches!(cache.get(&key(2, 1)), Some(NodeData::Integer(1))));
+        assert_eq!(manager.get_node_statistics(&cache, NodeId(2)).entries, 2);
+
+        let evictions = [CacheEviction { key: key(2, 0), reason: EvictionReason::Policy, size_bytes: 0 }];
+        assert_eq!(manager.handle_evictions(&evictions), 1);
+        assert_eq!(manager.invalidate_node(&mut cache, NodeId(1)), 2);
+        assert_eq!(manager.managed_keys(), &[key(2, 1)]);
+    }
+
+    #[test]
+    fn node_statistics_default_groups_entries_by_stage() {
+        let mut cache = MemoryCache::default();
+        cache.insert(PluginCacheKey::with_stage("test", NodeId(1), "load", 0), NodeData::Integer(0)).unwrap();
+        cache.insert(PluginCacheKey::with_stage("test", NodeId(1), "load", 1), NodeData::Integer(0)).unwrap();
+        cache.insert(key(1, 0), NodeData::Integer(0)).unwrap();
+        cache.insert(key(2, 0), NodeData::Integer(0)).unwrap();
+
+        let statistics = cache.get_node_statistics("test", NodeId(1));
+        assert_eq!(statistics.entries, 3);
+        assert_eq!(statistics.stages.len(), 2);
+        let load = statistics.stages.iter().find(|s| s.stage_id.as_deref() == Some("load")).unwrap();
+        assert_eq!(load.entries, 2);
+        assert_eq!(cache.global_statistics().total_entries, 4);
+        assert_eq!(cache.largest_entries(Some("test"), 2).len(), 2);
+    }
+
+    #[test]
+    fn statistics_reports_ignore_stale_deltas() {
+        let mut report = CacheStatisticsReport::default();
+        let plugin = |id: &str, entries| PluginCacheStatistics {
+            plugin_id: id.to_string(),
+            total_entries: entries,
+            ..Default::default()
+        };
+        let delta = |sequence, changed, removed_plugins| CacheStatisticsDelta { sequence, changed, removed_plugins };
+        report.apply(delta(2, vec![plugin("a", 1), plugin("b", 2)], vec![]));
+        report.apply(delta(1, vec![plugin("a", 9)], vec![]));
+        assert_eq!(report.plugin("a").unwrap().total_entries, 1);
+        report.apply(delta(3, vec![plugin("a", 3)], vec!["b".to_string()]));
+        assert_eq!(report.plugin("a").unwrap().total_entries, 3);
+        assert!(report.plugin("b").is_none());
+        assert_eq!(report.sequence, 3);
+    }
+}
@@ -0,0 +1,217 @@
+//! Channel and AOV naming conventions
+//!
+//! Renderers, compositors and viewers exchange multi-channel images and need
+//! to agree on what a pass is called, how many channels it has and what the
+//! values mean. The standard AOVs are listed in `STANDARD_AOVS` under the
+//! names in `render::aov`.
+//!
+//! Channels are named `"<aov>.<channel>"` as in layered EXR files (`"normal.X"`),
+//! except the beauty pass, whose channels are the bare `R`, `G`, `B`, `A`.
+//! Custom AOV names are lowercase identifiers (`[a-z0-9_]`, starting with a
+//! letter), optionally namespaced by renderer (`"cycles:diffuse_direct"`).
+
+use crate::render::aov;
+use crate::{ColorSpace, ValidationError};
+
+/// What the values of an AOV mean
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum AovSemantic {
+    /// Scene-linear color (the beauty pass is premultiplied RGBA)
+    Color,
+    /// Camera-space distance along the view axis; background is `f32::INFINITY`
+    Depth,
+    /// World-space unit vector
+    Normal,
+    /// World-space position
+    Position,
+    /// Integer ID stored exactly in a float channel
+    Id,
+    /// Cryptomatte rank pairs (ID hash, coverage) per channel pair
+    Cryptomatte,
+}
+
+impl AovSemantic {
+    /// Color space the values are in (non-color data is `Raw`)
+    pub fn color_space(self) -> ColorSpace {
+        match self {
+            AovSemantic::Color => ColorSpace::LinearSrgb,
+            _ => ColorSpace::Raw,
+        }
+    }
+}
+
+/// Naming and meaning of one standard AOV
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct AovSpec {
+    /// AOV name
+    pub name: &'static str,
+    /// Channel suffixes, in order
+    pub channels: &'static [&'static str],
+    pub semantic: AovSemantic,
+    /// Other names readers should accept for this AOV
+    pub aliases: &'static [&'static str],
+}
+
+impl AovSpec {
+    /// Number of channels per pixel
+    pub fn channel_count(&self) -> usize {
+        self.channels.len()
+    }
+
+    /// Full channel names (e.g. `["normal.X", "normal.Y", "normal.Z"]`)
+    pub fn channel_names(&self) -> Vec<String> {
+        self.channels
+            .iter()
+            .map(|channel| channel_name(self.name, channel))
+            .collect()
+    }
+}
+
+const RGB: &[&str] = &["R", "G", "B"];
+const RGBA: &[&str] = &["R", "G", "B", "A"];
+const XYZ: &[&str] = &["X", "Y", "Z"];
+
+/// The standard AOVs
+pub const STANDARD_AOVS: &[AovSpec] = &[
+    AovSpec { name: aov::BEAUTY, channels: RGBA, semantic: AovSemantic::Color, aliases: &["rgba", "color", "combined"] },
+    AovSpec { name: aov::DEPTH, channels: &["Z"], semantic: AovSemantic::Depth, aliases: &["z", "Z"] },
+    AovSpec { name: aov::NORMAL, channels: XYZ, semantic: AovSemantic::Normal, aliases: &["N"] },
+    AovSpec { name: aov::POSITION, channels: XYZ, semantic: AovSemantic::Position, aliases: &["P"] },
+    AovSpec { name: aov::ALBEDO, channels: RGB, semantic: AovSemantic::Color, aliases: &["diffuse_color"] },
+    AovSpec { name: aov::OBJECT_ID, channels: &["id"], semantic: AovSemantic::Id, aliases: &["IndexOB"] },
+    AovSpec { name: aov::CRYPTO_OBJECT, channels: RGBA, semantic: AovSemantic::Cryptomatte, aliases: &["CryptoObject"] },
+    AovSpec { name: aov::CRYPTO_MATERIAL, channels: RGBA, semantic: AovSemantic::Cryptomatte, aliases: &["CryptoMaterial"] },
+    AovSpec { name: aov::CRYPTO_ASSET, channels: RGBA, semantic: AovSemantic::Cryptomatte, aliases: &["CryptoAsset"] },
+];
+
+/// Look up a standard AOV by name or alias
+///
+/// Cryptomatte rank layers (`"crypto_object01"`) resolve to their AOV.
+pub fn standard_aov(name: &str) -> Option<&'static AovSpec> {
+    let base = cryptomatte_base(name).unwrap_or(name);
+    STANDARD_AOVS
+        .iter()
+        .find(|spec| spec.name == base || spec.aliases.contains(&base))
+}
+
+/// Map an alias to the standard AOV name (other names are returned unchanged)
+pub fn canonical_aov_name(name: &str) -> &str {
+    match standard_aov(name) {
+        Some(spec) if cryptomatte_base(name).is_none() => spec.name,
+        _ => name,
+    }
+}
+
+/// Full name of one channel of an AOV
+pub fn channel_name(aov_name: &str, channel: &str) -> String {
+    if aov_name == aov::BEAUTY {
+        channel.to_string()
+    } else {
+        format!("{}.{}", aov_name, channel)
+    }
+}
+
+/// Split a full channel name into AOV and channel (`"normal.X"` → `("normal", "X")`)
+///
+/// Names without a layer belong to the beauty pass.
+pub fn split_channel_name(name: &str) -> (&str, &str) {
+    match name.rsplit_once('.') {
+        Some((aov_name, channel)) => (aov_name, channel),
+        None => (aov::BEAUTY, name),
+    }
+}
+
+/// Check an AOV name follows the conventions
+pub fn validate_aov_name(name: &str) -> Result<(), ValidationError> {
+    let invalid = || {
+        Err(ValidationError::InvalidValue(format!(
+            "AOV name '{}' must be a lowercase identifier, optionally namespaced ('renderer:name')",
+            name
+        )))
+    };
+    let local = match name.split_once(':') {
+        Some((namespace, local)) if is_identifier(namespace) => local,
+        Some(_) => return invalid(),
+        None => name,
+    };
+    if is_identifier(local) {
+        Ok(())
+    } else {
+        invalid()
+    }
+}
+
+/// Check a standard AOV is produced with its standard channel count
+///
+/// Custom AOVs accept any channel count.
+pub fn validate_aov_channels(name: &str, channels: u32) -> Result<(), ValidationError> {
+    match standard_aov(name) {
+        Some(spec) if spec.channel_count() != channels as usize => Err(ValidationError::LengthMismatch {
+            field: format!("{} channels", spec.name),
+            expected: spec.channel_count(),
+            actual: channels as usize,
+        }),
+        _ => Ok(()),
+    }
+}
+
+/// Name of a Cryptomatte rank layer (`"crypto_object"`, 1 → `"crypto_object01"`)
+///
+/// Each layer holds two ranks as (ID, coverage) pairs in RGBA.
+pub fn cryptomatte_layer(aov_name: &str, layer: usize) -> String {
+    format!("{}{:02}", aov_name, layer)
+}
+
+/// Cryptomatte ID of an object or material name
+///
+/// MurmurHash3 (32-bit, seed 0) of the name, with the exponent adjusted so the
+/// bits never form a denormal, infinity or NaN, as required by the Cryptomatte
+/// specification.
+pub fn cryptomatte_id(name: &str) -> f32 {
+    let mut hash = murmur3_32(name.as_bytes());
+    let exponent = (hash >> 23) & 0xff;
+    if exponent == 0 || exponent == 0xff {
+        hash ^= 1 << 23;
+    }
+    f32::from_bits(hash)
+}
+
+/// Strip the two-digit rank suffix from a Cryptomatte layer name
+fn cryptomatte_base(name: &str) -> Option<&str> {
+    let base = name.get(..name.len().checked_sub(2)?)?;
+    let is_crypto = [aov::CRYPTO_OBJECT, aov::CRYPTO_MATERIAL, aov::CRYPTO_ASSET].contains(&base);
+    (is_crypto && name[base.len()..].bytes().all(|b| b.is_ascii_digit())).then_some(base)
+}
+
+fn is_identifier(text: &str) -> bool {
+    let mut bytes = text.bytes();
+    bytes.next().is_some_and(|b| b.is_ascii_lowercase())
+        && bytes.all(|b| b.is_ascii_lowercase() || b.is_ascii_digit() || b == b'_')
+}
+
+fn murmur3_32(data: &[u8]) -> u32 {
+    const C1: u32 = 0xcc9e_2d51;
+    const C2: u32 = 0x1b87_3593;
+    let mut hash = 0u32;
+    let mut chunks = data.chunks_exact(4);
+    for chunk in &mut chunks {
+        let mut k = u32::from_le_bytes([chunk[0], chunk[1], chunk[2], chunk[3]]);
+        k = k.wrapping_mul(C1).rotate_left(15).wrapping_mul(C2);
+        hash ^= k;
+        hash = hash.rotate_left(13).wrapping_mul(5).wrapping_add(0xe654_6b64);
+    }
+    let tail = chunks.remainder();
+    if !tail.is_empty() {
+        let mut k = 0u32;
+        for (i, byte) in tail.iter().enumerate() {
+            k |= (*byte as u32) << (8 * i);
+        }
+        hash ^= k.wrapping_mul(C1).rotate_left(15).wrapping_mul(C2);
+    }
+    hash ^= data.len() as u32;
+    hash ^= hash >> 16;
+    hash = hash.wrapping_mul(0x85eb_ca6b);
+    hash ^= hash >> 13;
+    hash = hash.wrapping_mul(0xc2b2_ae35);
+    hash ^ (hash >> 16)
+}
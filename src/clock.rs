@@ -0,0 +1,310 @@
+//! Clock sources for realtime evaluation
+//!
+//! Graphs normally evaluate at the frame of the internal timeline. Realtime
+//! nodes (live video, broadcast graphics) can instead ask to be driven by an
+//! external clock through `NodeMetadata::clock`. The host reports which clock
+//! drove each evaluation, and its reading, in `ExecutionContext::clock`.
+
+use crate::DateTime;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+/// Where evaluation time comes from
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum ClockSource {
+    /// The host's internal timeline (playback and scrubbing)
+    #[default]
+    Timeline,
+    /// SMPTE linear timecode read from an input device
+    Ltc {
+        /// Host device identifier (None = default LTC input)
+        device: Option<String>,
+    },
+    /// The operating system's wall clock
+    System,
+    /// IEEE 1588 Precision Time Protocol
+    Ptp {
+        /// PTP domain number
+        domain: u8,
+    },
+}
+
+impl ClockSource {
+    /// Check if the clock runs independently of the user's timeline
+    pub fn is_external(&self) -> bool {
+        *self != ClockSource::Timeline
+    }
+}
+
+/// Frame rate as an exact ratio (e.g. 30000/1001 for 29.97)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct FrameRate {
+    pub numerator: u32,
+    pub denominator: u32,
+}
+
+impl FrameRate {
+    pub const FPS_23_976: FrameRate = FrameRate::new(24000, 1001);
+    pub const FPS_24: FrameRate = FrameRate::new(24, 1);
+    pub const FPS_25: FrameRate = FrameRate::new(25, 1);
+    pub const FPS_29_97: FrameRate = FrameRate::new(30000, 1001);
+    pub const FPS_30: FrameRate = FrameRate::new(30, 1);
+    pub const FPS_50: FrameRate = FrameRate::new(50, 1);
+    pub const FPS_59_94: FrameRate = FrameRate::new(60000, 1001);
+    pub const FPS_60: FrameRate = FrameRate::new(60, 1);
+
+    /// Create a frame rate
+    pub const fn new(numerator: u32, denominator: u32) -> Self {
+        Self { numerator, denominator }
+    }
+
+    /// Frames per second
+    pub fn fps(&self) -> f64 {
+        self.numerator as f64 / self.denominator as f64
+    }
+
+    /// Whole frames counted per timecode second (30 for 29.97)
+    pub fn timecode_base(&self) -> u32 {
+        self.fps().round() as u32
+    }
+
+    /// Check if this is an NTSC rate that uses drop-frame timecode (29.97, 59.94)
+    pub fn is_drop_frame_rate(&self) -> bool {
+        self.denominator == 1001 && self.timecode_base() % 30 == 0
+    }
+
+    /// Convert seconds to frames
+    pub fn seconds_to_frames(&self, seconds: f64) -> f64 {
+        seconds * self.fps()
+    }
+
+    /// Convert frames to seconds
+    pub fn frames_to_seconds(&self, frames: f64) -> f64 {
+        frames / self.fps()
+    }
+}
+
+impl Default for FrameRate {
+    fn default() -> Self {
+        Self::FPS_24
+    }
+}
+
+/// SMPTE timecode
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct Timecode {
+    pub hours: u32,
+    pub minutes: u32,
+    pub seconds: u32,
+    pub frames: u32,
+    /// Drop-frame counting (frame numbers 0 and 1 skipped most minutes)
+    pub drop_frame: bool,
+}
+
+impl Timecode {
+    /// Timecode of a frame count from midnight
+    ///
+    /// Drop-frame counting is used for 29.97 and 59.94.
+    pub fn from_frame(frame: u64, rate: FrameRate) -> Self {
+        let base = rate.timecode_base().max(1) as u64;
+        let drop_frame = rate.is_drop_frame_rate();
+        let mut frame = frame;
+        if drop_frame {
+            // Drop 2 (or 4 at 59.94) frame numbers every minute except every tenth
+            let dropped = base / 15;
+            let per_ten_minutes = base * 600 - dropped * 9;
+            let per_minute = base * 60 - dropped;
+            let tens = frame / per_ten_minutes;
+            let rest = frame % per_ten_minutes;
+            frame += dropped * 9 * tens;
+            if rest > dropped {
+                frame += dropped * ((rest - dropped) / per_minute);
+            }
+        }
+        Self {
+            hours: (frame / (base * 3600)) as u32,
+            minutes: (frame / (base * 60) % 60) as u32,
+            seconds: (frame / base % 60) as u32,
+            frames: (frame % base) as u32,
+            drop_frame,
+        }
+    }
+
+    /// Frame count from midnight (inverse of `from_frame`)
+    ///
+    /// Saturates instead of overflowing for out-of-range fields.
+    pub fn to_frame(&self, rate: FrameRate) -> u64 {
+        let base = rate.timecode_base().max(1) as u64;
+        let total_minutes = (self.hours as u64).saturating_mul(60).saturating_add(self.minutes as u64);
+        let mut frame = total_minutes
+            .saturating_mul(60)
+            .saturating_add(self.seconds as u64)
+            .saturating_mul(base)
+            .saturating_add(self.frames as u64);
+        if self.drop_frame {
+            let dropped = base / 15;
+            frame = frame.saturating_sub(dropped.saturating_mul(total_minutes - total_minutes / 10));
+        }
+        frame
+    }
+
+    /// Parse `"HH:MM:SS:FF"` (a `;` or `.` before the frames marks drop-frame)
+    ///
+    /// Hours must be 0-23, minutes and seconds 0-59.
+    pub fn parse(text: &str) -> Result<Self, String> {
+        let invalid = || format!("Invalid timecode '{}'", text);
+        let text = text.trim();
+        let split = text.rfind([':', ';', '.']).ok_or_else(invalid)?;
+        let drop_frame = text[split..].starts_with([';', '.']);
+        let mut fields = text[..split].split(':').chain([&text[split + 1..]]);
+        let mut field = || -> Result<u32, String> {
+            let field = fields.next().ok_or_else(invalid)?;
+            if field.is_empty() || !field.bytes().all(|b| b.is_ascii_digit()) {
+                return Err(invalid());
+            }
+            field.parse().map_err(|_| invalid())
+        };
+        let timecode = Self {
+            hours: field()?,
+            minutes: field()?,
+            seconds: field()?,
+            frames: field()?,
+            drop_frame,
+        };
+        if fields.next().is_some() || timecode.hours > 23 || timecode.minutes > 59 || timecode.seconds > 59 {
+            return Err(invalid());
+        }
+        Ok(timecode)
+    }
+
+    /// Timecode `frames` later (earlier if negative), wrapping at 24 hours
+    pub fn add_frames(&self, frames: i64, rate: FrameRate) -> Self {
+        let day = Self::frames_per_day(rate) as i64;
+        let frame = (self.to_frame(rate) as i64).saturating_add(frames).rem_euclid(day);
+        Self::from_frame(frame as u64, rate)
+    }
+
+    /// Frames from this timecode to `other` (negative if `other` is earlier)
+    pub fn frames_until(&self, other: &Timecode, rate: FrameRate) -> i64 {
+        (other.to_frame(rate) as i64).saturating_sub(self.to_frame(rate) as i64)
+    }
+
+    /// Number of frames in 24 hours of timecode
+    pub fn frames_per_day(rate: FrameRate) -> u64 {
+        let midnight = Self {
+            hours: 24,
+            drop_frame: rate.is_drop_frame_rate(),
+            ..Self::default()
+        };
+        midnight.to_frame(rate)
+    }
+}
+
+impl fmt::Display for Timecode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let separator = if self.drop_frame { ';' } else { ':' };
+        write!(
+            f,
+            "{:02}:{:02}:{:02}{}{:02}",
+            self.hours, self.minutes, self.seconds, separator, self.frames
+        )
+    }
+}
+
+/// Reading of the clock that drove an evaluation
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ClockSample {
+    pub source: ClockSource,
+    /// Evaluated frame (fractional for sub-frame evaluation)
+    pub frame: f64,
+    pub rate: FrameRate,
+    /// Timecode reported by the source (LTC) or derived by the host
+    #[serde(default)]
+    pub timecode: Option<Timecode>,
+    /// Wall-clock time of the reading, if the source provides one
+    #[serde(default)]
+    pub wall_time: Option<DateTime>,
+    /// Whether the host is locked to the source (false = free-running fallback)
+    pub locked: bool,
+}
+
+impl ClockSample {
+    /// Sample of the internal timeline
+    pub fn timeline(frame: f64, rate: FrameRate) -> Self {
+        Self {
+            source: ClockSource::Timeline,
+            frame,
+            rate,
+            timecode: None,
+            wall_time: None,
+            locked: true,
+        }
+    }
+
+    /// Evaluated time in seconds
+    pub fn seconds(&self) -> f64 {
+        self.rate.frames_to_seconds(self.frame)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_and_prints_timecode() {
+        let timecode = Timecode::parse("01:02:03:04").unwrap();
+        assert_eq!((timecode.hours, timecode.minutes, timecode.seconds, timecode.frames), (1, 2, 3, 4));
+        assert!(!timecode.drop_frame);
+        assert_eq!(timecode.to_string(), "01:02:03:04");
+        assert!(Timecode::parse("01:02:03;04").unwrap().drop_frame);
+        assert!(Timecode::parse("01:02:03.04").unwrap().drop_frame);
+        assert_eq!(Timecode::parse("00:59:59;29").unwrap().to_string(), "00:59:59;29");
+    }
+
+    #[test]
+    fn rejects_out_of_range_fields() {
+        let invalid = [
+            "24:00:00:00",
+            "4294967295:00:00:00",
+            "00:60:00:00",
+            "00:00:60:00",
+            "00:00:00",
+            "1:2:3:4:5",
+            "aa:00:00:00",
+            "",
+        ];
+        for text in invalid {
+            assert!(Timecode::parse(text).is_err(), "{}", text);
+        }
+    }
+
+    #[test]
+    fn frame_conversion_round_trips() {
+        for rate in [FrameRate::FPS_24, FrameRate::FPS_25, FrameRate::FPS_29_97, FrameRate::FPS_59_94] {
+            for frame in [0, 1, 1799, 1800, 17982, 107_892, 1_000_000] {
+                assert_eq!(Timecode::from_frame(frame, rate).to_frame(rate), frame, "{} at {:?}", frame, rate);
+            }
+        }
+    }
+
+    #[test]
+    fn drop_frame_skips_frame_numbers() {
+        // 29.97 drop-frame skips ;00 and ;01 at every minute but every tenth
+        assert_eq!(Timecode::from_frame(1800, FrameRate::FPS_29_97).to_string(), "00:01:00;02");
+        assert_eq!(Timecode::from_frame(17982, FrameRate::FPS_29_97).to_string(), "00:10:00;00");
+        assert_eq!(Timecode::frames_per_day(FrameRate::FPS_29_97), 2_589_408);
+        assert_eq!(Timecode::frames_per_day(FrameRate::FPS_24), 2_073_600);
+    }
+
+    #[test]
+    fn arithmetic_wraps_at_a_day() {
+        let rate = FrameRate::FPS_24;
+        let late = Timecode::parse("23:59:59:23").unwrap();
+        assert_eq!(late.add_frames(1, rate).to_string(), "00:00:00:00");
+        assert_eq!(Timecode::default().add_frames(-1, rate), late);
+        assert_eq!(Timecode::default().frames_until(&late, rate), 2_073_599);
+        let huge = Timecode { hours: u32::MAX, ..Timecode::default() };
+        assert!(huge.to_frame(rate) > 0);
+    }
+}
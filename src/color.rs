@@ -0,0 +1,100 @@
+//! Color space metadata and conversions
+//!
+//! Colors are ambiguous between sRGB-encoded and linear values, which breaks
+//! USD/MaterialX pipelines. Images carry a `ColorSpace`, and colors can be tagged
+//! with `NodeData::ColorIn`. Untagged `NodeData::Color` values are assumed to be
+//! sRGB-encoded, matching the UI color pickers that usually produce them.
+
+use serde::{Deserialize, Serialize};
+
+/// Color space of color or image data
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum ColorSpace {
+    /// sRGB primaries with the sRGB transfer function (display-referred)
+    #[default]
+    Srgb,
+    /// sRGB/Rec.709 primaries, linear (scene-referred)
+    LinearSrgb,
+    /// ACES AP1 primaries, linear
+    ACEScg,
+    /// Non-color data (normals, masks, roughness); never converted
+    Raw,
+}
+
+/// Linear Rec.709 -> ACEScg (AP1), Bradford-adapted D65 -> D60
+const REC709_TO_AP1: [[f32; 3]; 3] = [
+    [0.613_097, 0.339_523, 0.047_379],
+    [0.070_194, 0.916_354, 0.013_452],
+    [0.020_616, 0.109_570, 0.869_815],
+];
+
+/// ACEScg (AP1) -> linear Rec.709
+const AP1_TO_REC709: [[f32; 3]; 3] = [
+    [1.704_859, -0.621_715, -0.083_299],
+    [-0.130_078, 1.140_734, -0.010_560],
+    [-0.023_964, -0.128_975, 1.153_013],
+];
+
+impl ColorSpace {
+    /// Get a human-readable name for this color space
+    pub fn name(&self) -> &'static str {
+        match self {
+            ColorSpace::Srgb => "sRGB",
+            ColorSpace::LinearSrgb => "Linear sRGB",
+            ColorSpace::ACEScg => "ACEScg",
+            ColorSpace::Raw => "Raw",
+        }
+    }
+
+    /// Check if values in this space are linear
+    pub fn is_linear(&self) -> bool {
+        !matches!(self, ColorSpace::Srgb)
+    }
+
+    /// Convert an RGBA color from this space to `target` (alpha is untouched)
+    ///
+    /// Conversions to or from `Raw` return the value unchanged.
+    pub fn convert(&self, value: [f32; 4], target: ColorSpace) -> [f32; 4] {
+        if *self == target || *self == ColorSpace::Raw || target == ColorSpace::Raw {
+            return value;
+        }
+        let rgb = [value[0], value[1], value[2]];
+        let linear = match self {
+            ColorSpace::Srgb => rgb.map(srgb_to_linear),
+            ColorSpace::ACEScg => mul(&AP1_TO_REC709, rgb),
+            _ => rgb,
+        };
+        let out = match target {
+            ColorSpace::Srgb => linear.map(linear_to_srgb),
+            ColorSpace::ACEScg => mul(&REC709_TO_AP1, linear),
+            _ => linear,
+        };
+        [out[0], out[1], out[2], value[3]]
+    }
+}
+
+/// sRGB transfer function: encoded -> linear
+pub fn srgb_to_linear(value: f32) -> f32 {
+    if value <= 0.04045 {
+        value / 12.92
+    } else {
+        ((value + 0.055) / 1.055).powf(2.4)
+    }
+}
+
+/// sRGB transfer function: linear -> encoded
+pub fn linear_to_srgb(value: f32) -> f32 {
+    if value <= 0.003_130_8 {
+        value * 12.92
+    } else {
+        1.055 * value.powf(1.0 / 2.4) - 0.055
+    }
+}
+
+fn mul(m: &[[f32; 3]; 3], v: [f32; 3]) -> [f32; 3] {
+    [
+        m[0][0] * v[0] + m[0][1] * v[1] + m[0][2] * v[2],
+        m[1][0] * v[0] + m[1][1] * v[1] + m[1][2] * v[2],
+        m[2][0] * v[0] + m[2][1] * v[1] + m[2][2] * v[2],
+    ]
+}
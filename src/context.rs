@@ -0,0 +1,356 @@
+//! Execution context passed to nodes during evaluation
+//!
+//! The host builds an `ExecutionContext` for every cook and passes it to
+//! `PluginNode::process_with_context` and to the `*_with_context` execution
+//! hooks, so new evaluation settings can be added without changing method
+//! signatures.
+
+use crate::{
+    CancellationToken, ClockSample, GraphHandle, LimitExceeded, LogHandle, NodeId, ProgressHandle, ResourcePool,
+    SharedCacheHandle,
+};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+/// Evaluation quality requested by the host
+///
+/// Lets nodes such as USD readers load low-res payloads while the user is
+/// interacting and full geometry for final export. Ordered from cheapest to
+/// most accurate.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub enum EvaluationQuality {
+    /// Fastest possible result during interaction (proxies, bounding boxes)
+    Proxy,
+    /// Reduced quality for viewport preview
+    Preview,
+    /// Full quality for final output/export
+    #[default]
+    Final,
+}
+
+impl EvaluationQuality {
+    /// Pick the quality a node should use given the modes it honors
+    ///
+    /// Returns the requested quality if supported, otherwise the next higher
+    /// supported quality, falling back to `Final`.
+    pub fn resolve(self, supported: &[EvaluationQuality]) -> EvaluationQuality {
+        if supported.contains(&self) {
+            return self;
+        }
+        supported
+            .iter()
+            .copied()
+            .filter(|q| *q > self)
+            .min()
+            .unwrap_or(EvaluationQuality::Final)
+    }
+}
+
+/// Soft resource limits for a single cook
+///
+/// Declared in `NodeMetadata::limits`. They are cooperative: the host does not
+/// kill a node that exceeds them, but `ExecutionContext::check_limits` starts
+/// failing so long-running nodes can stop at the next safe point and return an
+/// error instead of freezing an interactive session.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ExecutionLimits {
+    /// Maximum wall-clock time of one cook
+    #[serde(default)]
+    pub max_cook_time: Option<Duration>,
+    /// Maximum memory the node may use during a cook, in bytes
+    #[serde(default)]
+    pub max_memory_bytes: Option<u64>,
+}
+
+impl ExecutionLimits {
+    /// No limits
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the maximum cook time in seconds
+    ///
+    /// Zero, negative, non-finite and out-of-range values remove the limit.
+    pub fn with_max_cook_seconds(mut self, seconds: f64) -> Self {
+        self.max_cook_time = if seconds > 0.0 {
+            Duration::try_from_secs_f64(seconds).ok()
+        } else {
+            None
+        };
+        self
+    }
+
+    /// Set the maximum memory in bytes
+    pub fn with_max_memory_bytes(mut self, bytes: u64) -> Self {
+        self.max_memory_bytes = Some(bytes);
+        self
+    }
+
+    /// Check if any limit is set
+    pub fn is_limited(&self) -> bool {
+        self.max_cook_time.is_some() || self.max_memory_bytes.is_some()
+    }
+}
+
+/// Who currently reads one of a node's outputs
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct OutputConsumers {
+    /// Number of downstream connections
+    pub connections: usize,
+    /// Whether a viewport is displaying this output
+    pub viewport: bool,
+}
+
+impl OutputConsumers {
+    /// Check if anything reads this output
+    pub fn is_used(&self) -> bool {
+        self.connections > 0 || self.viewport
+    }
+}
+
+/// Why the host is cooking a node
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum DirtyReason {
+    /// First cook since the node was created, loaded or its cache cleared
+    Initial,
+    /// A parameter of the node changed
+    ParameterChanged(String),
+    /// The data arriving on an input port changed (or it was connected or disconnected)
+    InputChanged(String),
+    /// The current frame changed and the node is time-dependent
+    TimeChanged,
+    /// A realtime node advanced its state in `NodeExecutionHooks::on_tick`
+    Tick,
+    /// The user or a plugin forced a recook
+    Forced,
+}
+
+/// Per-cook context provided by the host
+#[derive(Debug, Clone, Default)]
+pub struct ExecutionContext {
+    /// Node being cooked (always set by the host for hooks)
+    pub node_id: Option<NodeId>,
+    /// Evaluation this cook belongs to (see `on_graph_evaluation_started`)
+    pub evaluation_id: Option<u64>,
+    /// Frame being evaluated (fractional during sub-frame evaluation)
+    pub frame: Option<f64>,
+    /// Time being evaluated, in seconds
+    pub time: Option<f64>,
+    /// Why the node is being cooked (empty if the host does not track it)
+    pub dirty_reasons: Vec<DirtyReason>,
+    /// Read-only access to the graph (None if unsupported)
+    pub graph: Option<GraphHandle>,
+    /// Where to send log messages (shown in the host's log panel)
+    pub logger: LogHandle,
+    /// Active UI theme, for nodes that produce colored output (None if not set)
+    pub theme: Option<crate::theme::ThemeHandle>,
+    /// Evaluation quality, already resolved against the node's metadata
+    pub quality: EvaluationQuality,
+    /// Soft limits for this cook (see `check_limits`)
+    pub limits: ExecutionLimits,
+    /// When the cook started (None if the host does not track cook time)
+    pub started: Option<Instant>,
+    /// Memory used by the cook so far, shared between the node and the host
+    memory_used: Arc<AtomicU64>,
+    /// Where to report progress (shown on the node and in the status bar)
+    pub progress: ProgressHandle,
+    /// Set by the host when the user cancels the cook
+    pub cancellation: CancellationToken,
+    /// Consumers of each output, keyed by output name
+    ///
+    /// None if the host does not track connections, in which case every
+    /// output must be treated as used.
+    pub output_consumers: Option<HashMap<String, OutputConsumers>>,
+    /// Thread-safe cache handle for background work (None if unsupported)
+    pub cache: Option<SharedCacheHandle>,
+    /// Host pool for sharing open resources between nodes (None if unsupported)
+    pub resources: Option<ResourcePool>,
+    /// Clock that drove this evaluation and its reading (None if not reported)
+    ///
+    /// May differ from the clock requested in `NodeMetadata::clock` when the
+    /// host cannot lock to it; check `ClockSample::source` and `locked`.
+    pub clock: Option<ClockSample>,
+}
+
+impl ExecutionContext {
+    /// Create a context for a given quality
+    pub fn new(quality: EvaluationQuality) -> Self {
+        Self {
+            quality,
+            ..Default::default()
+        }
+    }
+
+    /// Set the node being cooked and the evaluation it belongs to
+    pub fn for_node(mut self, node_id: NodeId, evaluation_id: u64) -> Self {
+        self.node_id = Some(node_id);
+        self.evaluation_id = Some(evaluation_id);
+        self
+    }
+
+    /// Set the evaluated frame and time
+    pub fn with_time(mut self, frame: f64, seconds: f64) -> Self {
+        self.frame = Some(frame);
+        self.time = Some(seconds);
+        self
+    }
+
+    /// Record a reason the node is being cooked
+    pub fn with_dirty_reason(mut self, reason: DirtyReason) -> Self {
+        self.dirty_reasons.push(reason);
+        self
+    }
+
+    /// Give the node and hooks read-only access to the graph
+    pub fn with_graph(mut self, graph: GraphHandle) -> Self {
+        self.graph = Some(graph);
+        self
+    }
+
+    /// Give the node the active UI theme
+    pub fn with_theme(mut self, theme: crate::theme::ThemeHandle) -> Self {
+        self.theme = Some(theme);
+        self
+    }
+
+    /// Send log messages to the given logger
+    pub fn with_logger(mut self, logger: LogHandle) -> Self {
+        self.logger = logger;
+        self
+    }
+
+    /// Check if a parameter change is among the reasons for this cook
+    pub fn parameter_changed(&self, parameter: &str) -> bool {
+        self.dirty_reasons
+            .iter()
+            .any(|reason| matches!(reason, DirtyReason::ParameterChanged(p) if p == parameter))
+    }
+
+    /// Check if a change on an input port is among the reasons for this cook
+    pub fn input_changed(&self, port: &str) -> bool {
+        self.dirty_reasons
+            .iter()
+            .any(|reason| matches!(reason, DirtyReason::InputChanged(p) if p == port))
+    }
+
+    /// Apply limits and start the cook clock
+    pub fn with_limits(mut self, limits: ExecutionLimits) -> Self {
+        self.limits = limits;
+        self.started = Some(Instant::now());
+        self
+    }
+
+    /// Report progress and cancellation through the given handles
+    pub fn with_progress(mut self, progress: ProgressHandle, cancellation: CancellationToken) -> Self {
+        self.progress = progress;
+        self.cancellation = cancellation;
+        self
+    }
+
+    /// Give the node a thread-safe cache handle
+    pub fn with_cache(mut self, cache: SharedCacheHandle) -> Self {
+        self.cache = Some(cache);
+        self
+    }
+
+    /// Give the node access to the host's resource pool
+    pub fn with_resources(mut self, resources: ResourcePool) -> Self {
+        self.resources = Some(resources);
+        self
+    }
+
+    /// Report the clock driving this evaluation
+    pub fn with_clock(mut self, clock: ClockSample) -> Self {
+        self.clock = Some(clock);
+        self
+    }
+
+    /// Provide the consumers of each output
+    pub fn with_output_consumers(mut self, consumers: HashMap<String, OutputConsumers>) -> Self {
+        self.output_consumers = Some(consumers);
+        self
+    }
+
+    /// Consumers of an output (None if the host does not track connections)
+    ///
+    /// Outputs missing from a tracked map have no consumers.
+    pub fn consumers(&self, output: &str) -> Option<OutputConsumers> {
+        self.output_consumers
+            .as_ref()
+            .map(|outputs| outputs.get(output).copied().unwrap_or_default())
+    }
+
+    /// Check if an output needs computing
+    ///
+    /// Expensive optional outputs (debug meshes, previews) can be skipped
+    /// when this returns false. Always true if the host does not track
+    /// connections.
+    pub fn is_output_used(&self, output: &str) -> bool {
+        self.consumers(output).map_or(true, |c| c.is_used())
+    }
+
+    /// Check if a viewport is displaying an output
+    pub fn is_output_viewed(&self, output: &str) -> bool {
+        self.consumers(output).is_some_and(|c| c.viewport)
+    }
+
+    /// Check if the user cancelled the cook
+    pub fn is_cancelled(&self) -> bool {
+        self.cancellation.is_cancelled()
+    }
+
+    /// Check if this is a full-quality evaluation
+    pub fn is_final(&self) -> bool {
+        self.quality == EvaluationQuality::Final
+    }
+
+    /// Time since the cook started
+    pub fn elapsed(&self) -> Option<Duration> {
+        self.started.map(|started| started.elapsed())
+    }
+
+    /// Cook time left before the time limit (None if unlimited or untracked)
+    pub fn remaining_time(&self) -> Option<Duration> {
+        Some(self.limits.max_cook_time?.saturating_sub(self.elapsed()?))
+    }
+
+    /// Record the memory the cook is currently using
+    ///
+    /// Nodes call this as they allocate large buffers; hosts that measure
+    /// memory themselves may update it from another thread.
+    pub fn report_memory(&self, bytes: u64) {
+        self.memory_used.store(bytes, Ordering::Relaxed);
+    }
+
+    /// Memory last reported for this cook
+    pub fn memory_used(&self) -> u64 {
+        self.memory_used.load(Ordering::Relaxed)
+    }
+
+    /// Check the cook against its limits
+    ///
+    /// Call this at safe points (between chunks, frames or iterations) and
+    /// abort with the returned error when a limit is exceeded.
+    pub fn check_limits(&self) -> Result<(), LimitExceeded> {
+        if let (Some(limit), Some(elapsed)) = (self.limits.max_cook_time, self.elapsed()) {
+            if elapsed > limit {
+                return Err(LimitExceeded::CookTime { elapsed, limit });
+            }
+        }
+        if let Some(limit) = self.limits.max_memory_bytes {
+            let used = self.memory_used();
+            if used > limit {
+                return Err(LimitExceeded::Memory { used, limit });
+            }
+        }
+        Ok(())
+    }
+
+    /// Check if a limit is exceeded
+    pub fn limits_exceeded(&self) -> bool {
+        self.check_limits().is_err()
+    }
+}
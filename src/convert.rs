@@ -0,0 +1,178 @@
+//! Conversions between Rust values and `NodeData`
+//!
+//! `From` impls wrap plain values (`NodeData::from(1.0)`), and `TryFrom<&NodeData>`
+//! impls read them back with a descriptive `ConversionError`. The
+//! `extract_inputs!` macro builds on these to destructure a node's input map:
+//!
+//! ```
+//! # use nodle_plugin_sdk::{extract_inputs, NodeData, ProcessResult};
+//! # use std::collections::HashMap;
+//! # struct Circle;
+//! # impl Circle {
+//! fn try_process(&mut self, inputs: &HashMap<String, NodeData>) -> Result<ProcessResult, String> {
+//!     extract_inputs!(inputs => {
+//!         radius: f32,
+//!         segments as "Segments": i32 = 16,
+//!     });
+//!     // radius: f32 and segments: i32 are now in scope
+//! #   assert_eq!((radius, segments), (2.0, 16));
+//! #   Ok(ProcessResult::new())
+//! }
+//! # }
+//! # let inputs = HashMap::from([("radius".to_string(), NodeData::from(2.0f32))]);
+//! # Circle.try_process(&inputs).unwrap();
+//! # assert!(Circle.try_process(&HashMap::new()).is_err());
+//! ```
+
+use crate::{
+    ConversionError, DateTime, FrameRange, GeometryData, ImageData, InputError, JsonValue, LightData, MaterialData,
+    NodeData, PathData, SceneData, TableData, USDSceneData,
+};
+use crate::render::RenderSettings;
+use std::collections::HashMap;
+
+macro_rules! node_data_conversion {
+    ($ty:ty, $variant:ident) => {
+        impl From<$ty> for NodeData {
+            fn from(value: $ty) -> Self {
+                NodeData::$variant(value)
+            }
+        }
+
+        impl TryFrom<&NodeData> for $ty {
+            type Error = ConversionError;
+
+            fn try_from(data: &NodeData) -> Result<Self, ConversionError> {
+                match data {
+                    NodeData::$variant(value) => Ok(value.clone()),
+                    other => Err(ConversionError {
+                        expected: stringify!($variant),
+                        found: other.type_name(),
+                    }),
+                }
+            }
+        }
+    };
+}
+
+node_data_conversion!(f32, Float);
+node_data_conversion!(i32, Integer);
+node_data_conversion!(bool, Boolean);
+node_data_conversion!([f32; 3], Vector3);
+node_data_conversion!([f32; 4], Color);
+node_data_conversion!(PathData, Path);
+node_data_conversion!(DateTime, DateTime);
+node_data_conversion!(JsonValue, Json);
+node_data_conversion!(SceneData, Scene);
+node_data_conversion!(GeometryData, Geometry);
+node_data_conversion!(MaterialData, Material);
+node_data_conversion!(LightData, Light);
+node_data_conversion!(ImageData, Image);
+node_data_conversion!(USDSceneData, USDSceneData);
+node_data_conversion!(TableData, Table);
+node_data_conversion!(RenderSettings, RenderSettings);
+node_data_conversion!(crate::GradientData, Gradient);
+
+impl From<String> for NodeData {
+    fn from(value: String) -> Self {
+        NodeData::String(value)
+    }
+}
+
+impl From<&str> for NodeData {
+    fn from(value: &str) -> Self {
+        NodeData::String(value.to_string())
+    }
+}
+
+/// Accepts `String` and `Any`, matching `NodeData::as_string`
+impl TryFrom<&NodeData> for String {
+    type Error = ConversionError;
+
+    fn try_from(data: &NodeData) -> Result<Self, ConversionError> {
+        data.as_string().map(str::to_string).ok_or(ConversionError {
+            expected: "String",
+            found: data.type_name(),
+        })
+    }
+}
+
+/// Accepts `FrameRange` and range strings (see `FrameRange::parse`)
+impl TryFrom<&NodeData> for FrameRange {
+    type Error = ConversionError;
+
+    fn try_from(data: &NodeData) -> Result<Self, ConversionError> {
+        data.as_frame_range()
+            .or_else(|| data.as_string().and_then(|text| FrameRange::parse(text).ok()))
+            .ok_or(ConversionError {
+                expected: "FrameRange",
+                found: data.type_name(),
+            })
+    }
+}
+
+/// Read a required input, converting it to `T`
+pub fn extract_input<T>(inputs: &HashMap<String, NodeData>, port: &str) -> Result<T, InputError>
+where
+    T: for<'a> TryFrom<&'a NodeData, Error = ConversionError>,
+{
+    match inputs.get(port) {
+        None | Some(NodeData::None) => Err(InputError::Missing(port.to_string())),
+        Some(data) => convert_input(data, port),
+    }
+}
+
+/// Read an optional input, converting it to `T` (None if unconnected or empty)
+pub fn extract_optional_input<T>(inputs: &HashMap<String, NodeData>, port: &str) -> Result<Option<T>, InputError>
+where
+    T: for<'a> TryFrom<&'a NodeData, Error = ConversionError>,
+{
+    match inputs.get(port) {
+        None | Some(NodeData::None) => Ok(None),
+        Some(data) => convert_input(data, port).map(Some),
+    }
+}
+
+/// Read an input, falling back to `default` if unconnected or empty
+pub fn extract_input_or<T>(inputs: &HashMap<String, NodeData>, port: &str, default: T) -> Result<T, InputError>
+where
+    T: for<'a> TryFrom<&'a NodeData, Error = ConversionError>,
+{
+    extract_optional_input(inputs, port).map(|value| value.unwrap_or(default))
+}
+
+fn convert_input<T>(data: &NodeData, port: &str) -> Result<T, InputError>
+where
+    T: for<'a> TryFrom<&'a NodeData, Error = ConversionError>,
+{
+    if let NodeData::Error(error) = data {
+        return Err(InputError::Upstream(error.clone()));
+    }
+    T::try_from(data).map_err(|e| InputError::WrongType {
+        port: port.to_string(),
+        expected: e.expected,
+        found: e.found,
+    })
+}
+
+/// Destructure a node's input map into typed locals
+///
+/// Each entry is `name: Type`, optionally with a port name (`name as "Port": Type`)
+/// and a default for unconnected ports (`name: Type = value`). Without a port
+/// name the variable name is used. Failures return early with `?`, so the
+/// enclosing function must return a `Result` whose error converts from
+/// `InputError` (e.g. `Result<_, String>` in `try_process`).
+#[macro_export]
+macro_rules! extract_inputs {
+    ($inputs:expr => { $($name:ident $(as $port:literal)? : $ty:ty $(= $default:expr)?),* $(,)? }) => {
+        $(
+            let $name: $ty = $crate::extract_inputs!(
+                @get $inputs, $crate::extract_inputs!(@port $name $(, $port)?) $(, $default)?
+            )?;
+        )*
+    };
+    (@port $name:ident) => { stringify!($name) };
+    (@port $name:ident, $port:literal) => { $port };
+    (@get $inputs:expr, $port:expr) => { $crate::convert::extract_input($inputs, $port) };
+    (@get $inputs:expr, $port:expr, $default:expr) => { $crate::convert::extract_input_or($inputs, $port, $default) };
+}
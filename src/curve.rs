@@ -0,0 +1,580 @@
+//! Curve evaluation and interpolation
+//!
+//! Shared math for ramp widgets, animated parameters and curve geometry, so
+//! every plugin interpolates identically. Everything works on `CurvePoint`s:
+//! `f32` for scalar ramps, `[f32; 2]`/`[f32; 3]` for curve geometry and
+//! `[f32; 4]` for color ramps.
+//!
+//! - `Easing` shapes a 0-1 parameter (including CSS-style cubic-bezier timing).
+//! - `evaluate_curve` evaluates a whole control-point list with a `CurveBasis`.
+//! - `Ramp` is a sorted list of keys (ramp widget, keyframed channels).
+//!   `GradientData` is the color ramp edited by `UIElement::GradientEditor`.
+//! - `resample_polyline` redistributes points evenly by arc length.
+
+use serde::{Deserialize, Serialize};
+
+/// A value that can be interpolated
+pub trait CurvePoint: Copy {
+    /// `self + (other - self) * t`
+    fn lerp(self, other: Self, t: f32) -> Self;
+    /// Weighted sum of points (weights normally add up to 1)
+    fn weighted(points: &[Self], weights: &[f32]) -> Self;
+    /// Euclidean distance (used for arc-length resampling)
+    fn distance(self, other: Self) -> f32;
+}
+
+impl CurvePoint for f32 {
+    fn lerp(self, other: Self, t: f32) -> Self {
+        self + (other - self) * t
+    }
+
+    fn weighted(points: &[Self], weights: &[f32]) -> Self {
+        points.iter().zip(weights).map(|(p, w)| p * w).sum()
+    }
+
+    fn distance(self, other: Self) -> f32 {
+        (other - self).abs()
+    }
+}
+
+impl<const N: usize> CurvePoint for [f32; N] {
+    fn lerp(self, other: Self, t: f32) -> Self {
+        std::array::from_fn(|i| self[i] + (other[i] - self[i]) * t)
+    }
+
+    fn weighted(points: &[Self], weights: &[f32]) -> Self {
+        std::array::from_fn(|i| points.iter().zip(weights).map(|(p, w)| p[i] * w).sum())
+    }
+
+    fn distance(self, other: Self) -> f32 {
+        (0..N).map(|i| (other[i] - self[i]).powi(2)).sum::<f32>().sqrt()
+    }
+}
+
+/// Easing function applied to a 0-1 parameter
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub enum Easing {
+    #[default]
+    Linear,
+    /// Hold 0 until t reaches 1
+    Step,
+    /// Hermite smoothstep (3t² - 2t³)
+    SmoothStep,
+    QuadIn,
+    QuadOut,
+    QuadInOut,
+    CubicIn,
+    CubicOut,
+    CubicInOut,
+    SineIn,
+    SineOut,
+    SineInOut,
+    ExpoIn,
+    ExpoOut,
+    /// CSS `cubic-bezier(x1, y1, x2, y2)` timing curve
+    CubicBezier([f32; 4]),
+}
+
+impl Easing {
+    /// Map `t` (clamped to 0-1) through the easing curve
+    pub fn apply(&self, t: f32) -> f32 {
+        use std::f32::consts::PI;
+        let t = t.clamp(0.0, 1.0);
+        match *self {
+            Easing::Linear => t,
+            Easing::Step => {
+                if t >= 1.0 {
+                    1.0
+                } else {
+                    0.0
+                }
+            }
+            Easing::SmoothStep => t * t * (3.0 - 2.0 * t),
+            Easing::QuadIn => t * t,
+            Easing::QuadOut => 1.0 - (1.0 - t) * (1.0 - t),
+            Easing::QuadInOut => {
+                if t < 0.5 {
+                    2.0 * t * t
+                } else {
+                    1.0 - (-2.0 * t + 2.0).powi(2) / 2.0
+                }
+            }
+            Easing::CubicIn => t * t * t,
+            Easing::CubicOut => 1.0 - (1.0 - t).powi(3),
+            Easing::CubicInOut => {
+                if t < 0.5 {
+                    4.0 * t * t * t
+                } else {
+                    1.0 - (-2.0 * t + 2.0).powi(3) / 2.0
+                }
+            }
+            Easing::SineIn => 1.0 - (t * PI / 2.0).cos(),
+            Easing::SineOut => (t * PI / 2.0).sin(),
+            Easing::SineInOut => -((PI * t).cos() - 1.0) / 2.0,
+            Easing::ExpoIn => {
+                if t == 0.0 {
+                    0.0
+                } else {
+                    2f32.powf(10.0 * t - 10.0)
+                }
+            }
+            Easing::ExpoOut => {
+                if t == 1.0 {
+                    1.0
+                } else {
+                    1.0 - 2f32.powf(-10.0 * t)
+                }
+            }
+            Easing::CubicBezier([x1, y1, x2, y2]) => cubic_bezier_timing(x1, y1, x2, y2, t),
+        }
+    }
+}
+
+/// Evaluate a CSS-style timing curve from (0,0) to (1,1) at `x`
+///
+/// Solves the curve's x(s) = `x` for s, then returns y(s). `x1` and `x2` are
+/// clamped to 0-1 so the curve stays a function of x.
+pub fn cubic_bezier_timing(x1: f32, y1: f32, x2: f32, y2: f32, x: f32) -> f32 {
+    let (x1, x2) = (x1.clamp(0.0, 1.0), x2.clamp(0.0, 1.0));
+    let bezier = |a: f32, b: f32, s: f32| {
+        let inv = 1.0 - s;
+        3.0 * inv * inv * s * a + 3.0 * inv * s * s * b + s * s * s
+    };
+    let derivative = |a: f32, b: f32, s: f32| {
+        let inv = 1.0 - s;
+        3.0 * inv * inv * a + 6.0 * inv * s * (b - a) + 3.0 * s * s * (1.0 - b)
+    };
+
+    // Newton's method, falling back to bisection where the slope is flat
+    let mut s = x;
+    for _ in 0..8 {
+        let error = bezier(x1, x2, s) - x;
+        if error.abs() < 1e-6 {
+            return bezier(y1, y2, s);
+        }
+        let slope = derivative(x1, x2, s);
+        if slope.abs() < 1e-6 {
+            break;
+        }
+        s = (s - error / slope).clamp(0.0, 1.0);
+    }
+    let (mut low, mut high) = (0.0f32, 1.0f32);
+    s = x;
+    for _ in 0..32 {
+        let value = bezier(x1, x2, s);
+        if (value - x).abs() < 1e-6 {
+            break;
+        }
+        if value < x {
+            low = s;
+        } else {
+            high = s;
+        }
+        s = (low + high) * 0.5;
+    }
+    bezier(y1, y2, s)
+}
+
+/// Evaluate a cubic Bezier segment
+pub fn cubic_bezier<P: CurvePoint>(p0: P, p1: P, p2: P, p3: P, t: f32) -> P {
+    let inv = 1.0 - t;
+    P::weighted(
+        &[p0, p1, p2, p3],
+        &[inv * inv * inv, 3.0 * inv * inv * t, 3.0 * inv * t * t, t * t * t],
+    )
+}
+
+/// Evaluate a uniform Catmull-Rom segment between `p1` and `p2`
+///
+/// The curve passes through every control point.
+pub fn catmull_rom<P: CurvePoint>(p0: P, p1: P, p2: P, p3: P, t: f32) -> P {
+    let (t2, t3) = (t * t, t * t * t);
+    P::weighted(
+        &[p0, p1, p2, p3],
+        &[
+            0.5 * (-t3 + 2.0 * t2 - t),
+            0.5 * (3.0 * t3 - 5.0 * t2 + 2.0),
+            0.5 * (-3.0 * t3 + 4.0 * t2 + t),
+            0.5 * (t3 - t2),
+        ],
+    )
+}
+
+/// Evaluate a uniform cubic B-spline segment
+///
+/// The curve approximates the control points without passing through them.
+pub fn bspline<P: CurvePoint>(p0: P, p1: P, p2: P, p3: P, t: f32) -> P {
+    let (t2, t3) = (t * t, t * t * t);
+    let inv = 1.0 - t;
+    P::weighted(
+        &[p0, p1, p2, p3],
+        &[
+            inv * inv * inv / 6.0,
+            (3.0 * t3 - 6.0 * t2 + 4.0) / 6.0,
+            (-3.0 * t3 + 3.0 * t2 + 3.0 * t + 1.0) / 6.0,
+            t3 / 6.0,
+        ],
+    )
+}
+
+/// How control points define a curve
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum CurveBasis {
+    /// Straight segments between points
+    #[default]
+    Linear,
+    /// Piecewise cubic Bezier: point, handle, handle, point, handle, handle, point...
+    Bezier,
+    /// Interpolating spline through every point
+    CatmullRom,
+    /// Approximating uniform cubic B-spline (starts and ends near, not at, the end points)
+    BSpline,
+}
+
+impl CurveBasis {
+    /// Number of segments a control-point list forms
+    pub fn segment_count(&self, point_count: usize) -> usize {
+        match self {
+            CurveBasis::Bezier => point_count.saturating_sub(1) / 3,
+            _ => point_count.saturating_sub(1),
+        }
+    }
+}
+
+/// Evaluate a curve at `t` in 0-1 across all of its segments
+///
+/// Segments are spaced evenly in `t`, not by length; use `resample_polyline`
+/// on the result of `sample_curve` for even spacing. Catmull-Rom and B-spline
+/// curves duplicate their end points so they span the full point list.
+/// Returns None if there are no points (or fewer than 4 for `Bezier`).
+pub fn evaluate_curve<P: CurvePoint>(basis: CurveBasis, points: &[P], t: f32) -> Option<P> {
+    let segments = basis.segment_count(points.len());
+    if segments == 0 {
+        return match (basis, points.first()) {
+            (CurveBasis::Bezier, _) if points.len() > 1 => None,
+            (_, first) => first.copied(),
+        };
+    }
+    let scaled = t.clamp(0.0, 1.0) * segments as f32;
+    let segment = (scaled.floor() as usize).min(segments - 1);
+    let local = scaled - segment as f32;
+    let at = |i: isize| points[i.clamp(0, points.len() as isize - 1) as usize];
+    let i = segment as isize;
+
+    Some(match basis {
+        CurveBasis::Linear => at(i).lerp(at(i + 1), local),
+        CurveBasis::Bezier => {
+            let base = segment * 3;
+            cubic_bezier(points[base], points[base + 1], points[base + 2], points[base + 3], local)
+        }
+        CurveBasis::CatmullRom => catmull_rom(at(i - 1), at(i), at(i + 1), at(i + 2), local),
+        CurveBasis::BSpline => bspline(at(i - 1), at(i), at(i + 1), at(i + 2), local),
+    })
+}
+
+/// Sample a curve at `count` evenly spaced values of `t`
+pub fn sample_curve<P: CurvePoint>(basis: CurveBasis, points: &[P], count: usize) -> Vec<P> {
+    (0..count)
+        .filter_map(|i| {
+            let t = if count > 1 { i as f32 / (count - 1) as f32 } else { 0.0 };
+            evaluate_curve(basis, points, t)
+        })
+        .collect()
+}
+
+/// Redistribute a polyline into `count` points evenly spaced by arc length
+///
+/// The first and last points are kept.
+pub fn resample_polyline<P: CurvePoint>(points: &[P], count: usize) -> Vec<P> {
+    if points.len() < 2 || count < 2 {
+        return points.iter().take(count).copied().collect();
+    }
+    let mut lengths = Vec::with_capacity(points.len());
+    let mut total = 0.0;
+    lengths.push(0.0);
+    for pair in points.windows(2) {
+        total += pair[0].distance(pair[1]);
+        lengths.push(total);
+    }
+    if total <= f32::EPSILON {
+        return vec![points[0]; count];
+    }
+
+    let mut segment = 0;
+    (0..count)
+        .map(|i| {
+            let target = total * i as f32 / (count - 1) as f32;
+            while segment < points.len() - 2 && lengths[segment + 1] < target {
+                segment += 1;
+            }
+            let span = lengths[segment + 1] - lengths[segment];
+            let t = if span > f32::EPSILON { (target - lengths[segment]) / span } else { 0.0 };
+            points[segment].lerp(points[segment + 1], t.clamp(0.0, 1.0))
+        })
+        .collect()
+}
+
+/// Interpolation from a ramp key to the next one
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub enum RampInterpolation {
+    /// Hold the key's value until the next key
+    Constant,
+    #[default]
+    Linear,
+    /// Catmull-Rom through the neighbouring keys
+    Smooth,
+    /// Blend with an easing curve
+    Eased(Easing),
+}
+
+/// One key of a `Ramp`
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct RampKey<P> {
+    pub position: f32,
+    pub value: P,
+    /// Interpolation toward the next key
+    #[serde(default)]
+    pub interpolation: RampInterpolation,
+}
+
+/// Keyed 1D function (ramp widgets, animation channels)
+///
+/// Outside the key range the first/last value is held. Deserialized keys are
+/// sorted by position and keys at non-finite positions are dropped.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(from = "RampKeys<P>")]
+pub struct Ramp<P> {
+    keys: Vec<RampKey<P>>,
+}
+
+/// Serialized form of a `Ramp`, whose keys may be in any order
+#[derive(Deserialize)]
+struct RampKeys<P> {
+    keys: Vec<RampKey<P>>,
+}
+
+impl<P> From<RampKeys<P>> for Ramp<P> {
+    fn from(ramp: RampKeys<P>) -> Self {
+        Self::from_keys(ramp.keys)
+    }
+}
+
+impl<P> Default for Ramp<P> {
+    fn default() -> Self {
+        Self { keys: Vec::new() }
+    }
+}
+
+impl<P> Ramp<P> {
+    /// Create a ramp from keys in any order
+    ///
+    /// Keys are sorted by position (equal positions keep their order) and keys
+    /// at non-finite positions are dropped.
+    pub fn from_keys(mut keys: Vec<RampKey<P>>) -> Self {
+        keys.retain(|key| key.position.is_finite());
+        keys.sort_by(|a, b| a.position.total_cmp(&b.position));
+        Self { keys }
+    }
+}
+
+impl<P: CurvePoint> Ramp<P> {
+    /// Create an empty ramp
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a key, keeping keys sorted by position
+    pub fn with_key(mut self, position: f32, value: P, interpolation: RampInterpolation) -> Self {
+        self.add_key(position, value, interpolation);
+        self
+    }
+
+    /// Add a key, keeping keys sorted by position, and return its index
+    ///
+    /// A key at the same position as an existing one is inserted after it,
+    /// which gives a hard step at that position. Non-finite positions are
+    /// rejected (None).
+    pub fn add_key(&mut self, position: f32, value: P, interpolation: RampInterpolation) -> Option<usize> {
+        if !position.is_finite() {
+            return None;
+        }
+        let index = self.keys.partition_point(|k| k.position <= position);
+        self.keys.insert(index, RampKey { position, value, interpolation });
+        Some(index)
+    }
+
+    /// Remove a key by index
+    pub fn remove_key(&mut self, index: usize) -> Option<RampKey<P>> {
+        (index < self.keys.len()).then(|| self.keys.remove(index))
+    }
+
+    /// Move a key to a new position, returning its index after re-sorting
+    ///
+    /// Non-finite positions are rejected (None) and leave the key in place.
+    pub fn move_key(&mut self, index: usize, position: f32) -> Option<usize> {
+        if !position.is_finite() {
+            return None;
+        }
+        let mut key = self.remove_key(index)?;
+        key.position = position;
+        let index = self.keys.partition_point(|k| k.position <= position);
+        self.keys.insert(index, key);
+        Some(index)
+    }
+
+    /// Mutable access to a key's value and interpolation
+    ///
+    /// Use `move_key` to change the position, so keys stay sorted.
+    pub fn key_mut(&mut self, index: usize) -> Option<(&mut P, &mut RampInterpolation)> {
+        self.keys.get_mut(index).map(|key| (&mut key.value, &mut key.interpolation))
+    }
+
+    /// Keys sorted by position
+    pub fn keys(&self) -> &[RampKey<P>] {
+        &self.keys
+    }
+
+    /// Check if the ramp has no keys
+    pub fn is_empty(&self) -> bool {
+        self.keys.is_empty()
+    }
+
+    /// Evaluate the ramp at a position (None if it has no keys or the position is NaN)
+    pub fn evaluate(&self, position: f32) -> Option<P> {
+        if position.is_nan() {
+            return None;
+        }
+        let first = self.keys.first()?;
+        let last = self.keys.last()?;
+        if position <= first.position {
+            return Some(first.value);
+        }
+        if position >= last.position {
+            return Some(last.value);
+        }
+        let next = self.keys.partition_point(|k| k.position <= position).clamp(1, self.keys.len() - 1);
+        let (a, b) = (&self.keys[next - 1], &self.keys[next]);
+        let span = b.position - a.position;
+        let t = if span > f32::EPSILON { (position - a.position) / span } else { 1.0 };
+
+        Some(match a.interpolation {
+            RampInterpolation::Constant => a.value,
+            RampInterpolation::Linear => a.value.lerp(b.value, t),
+            RampInterpolation::Eased(easing) => a.value.lerp(b.value, easing.apply(t)),
+            RampInterpolation::Smooth => {
+                let before = self.keys[next.saturating_sub(2)].value;
+                let after = self.keys[(next + 1).min(self.keys.len() - 1)].value;
+                catmull_rom(before, a.value, b.value, after, t)
+            }
+        })
+    }
+
+    /// Sample the ramp at `count` evenly spaced positions across `range`
+    ///
+    /// Used to bake lookup tables (e.g. a color ramp texture).
+    pub fn sample(&self, range: (f32, f32), count: usize) -> Vec<P> {
+        (0..count)
+            .filter_map(|i| {
+                let t = if count > 1 { i as f32 / (count - 1) as f32 } else { 0.0 };
+                self.evaluate(range.0 + (range.1 - range.0) * t)
+            })
+            .collect()
+    }
+}
+
+/// Color ramp (RGBA stops at positions 0-1) flowing between nodes as `NodeData::Gradient`
+pub type GradientData = Ramp<[f32; 4]>;
+
+impl Ramp<[f32; 4]> {
+    /// Linear gradient between two colors over 0-1
+    pub fn two_color(start: [f32; 4], end: [f32; 4]) -> Self {
+        Self::new()
+            .with_key(0.0, start, RampInterpolation::Linear)
+            .with_key(1.0, end, RampInterpolation::Linear)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ramp() -> Ramp<f32> {
+        Ramp::new()
+            .with_key(0.0, 0.0, RampInterpolation::Linear)
+            .with_key(1.0, 10.0, RampInterpolation::Constant)
+            .with_key(2.0, 20.0, RampInterpolation::Linear)
+    }
+
+    #[test]
+    fn ramp_interpolates_and_holds_ends() {
+        let ramp = ramp();
+        assert_eq!(ramp.evaluate(0.5), Some(5.0));
+        assert_eq!(ramp.evaluate(1.5), Some(10.0));
+        assert_eq!(ramp.evaluate(-1.0), Some(0.0));
+        assert_eq!(ramp.evaluate(f32::INFINITY), Some(20.0));
+        assert_eq!(Ramp::<f32>::new().evaluate(0.5), None);
+        assert_eq!(ramp.sample((0.0, 2.0), 3), vec![0.0, 10.0, 20.0]);
+    }
+
+    #[test]
+    fn ramp_rejects_nan() {
+        let mut ramp = ramp();
+        assert_eq!(ramp.evaluate(f32::NAN), None);
+        assert_eq!(ramp.add_key(f32::NAN, 1.0, RampInterpolation::Linear), None);
+        assert_eq!(ramp.add_key(f32::INFINITY, 1.0, RampInterpolation::Linear), None);
+        assert_eq!(ramp.move_key(0, f32::NAN), None);
+        assert_eq!(ramp.keys().len(), 3);
+        assert_eq!(ramp.keys()[0].position, 0.0);
+    }
+
+    #[test]
+    fn ramp_keeps_keys_sorted() {
+        let mut ramp = ramp();
+        assert_eq!(ramp.move_key(0, 1.5), Some(1));
+        let positions: Vec<f32> = ramp.keys().iter().map(|k| k.position).collect();
+        assert_eq!(positions, vec![1.0, 1.5, 2.0]);
+        // Equal positions insert after the existing key, giving a step
+        assert_eq!(ramp.add_key(2.0, 30.0, RampInterpolation::Linear), Some(3));
+        assert_eq!(ramp.evaluate(2.0), Some(30.0));
+    }
+
+    #[test]
+    fn ramp_from_unsorted_keys() {
+        let key = |position: f32, value: f32| RampKey {
+            position,
+            value,
+            interpolation: RampInterpolation::Linear,
+        };
+        let ramp: Ramp<f32> = RampKeys {
+            keys: vec![key(2.0, 20.0), key(f32::NAN, 5.0), key(0.0, 0.0), key(2.0, 30.0)],
+        }
+        .into();
+        let values: Vec<f32> = ramp.keys().iter().map(|k| k.value).collect();
+        assert_eq!(values, vec![0.0, 20.0, 30.0]);
+        assert_eq!(ramp.evaluate(1.0), Some(10.0));
+    }
+
+    #[test]
+    fn easing_hits_endpoints() {
+        for easing in [
+            Easing::Linear,
+            Easing::SmoothStep,
+            Easing::QuadInOut,
+            Easing::CubicInOut,
+            Easing::SineInOut,
+            Easing::CubicBezier([0.25, 0.1, 0.25, 1.0]),
+        ] {
+            assert!(easing.apply(0.0).abs() < 1e-5, "{:?}", easing);
+            assert!((easing.apply(1.0) - 1.0).abs() < 1e-5, "{:?}", easing);
+        }
+    }
+
+    #[test]
+    fn curves_pass_through_control_points() {
+        assert_eq!(cubic_bezier(0.0f32, 1.0, 2.0, 3.0, 0.0), 0.0);
+        assert_eq!(cubic_bezier(0.0f32, 1.0, 2.0, 3.0, 1.0), 3.0);
+        assert!((catmull_rom(0.0f32, 1.0, 2.0, 3.0, 0.5) - 1.5).abs() < 1e-5);
+        let resampled = resample_polyline(&[[0.0f32, 0.0], [2.0, 0.0]], 3);
+        assert_eq!(resampled, vec![[0.0, 0.0], [1.0, 0.0], [2.0, 0.0]]);
+    }
+}
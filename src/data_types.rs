@@ -3,6 +3,7 @@
 //! This module provides all the data types that flow between nodes in the Nodle system,
 //! including complex 3D scene data, USD integration, and basic primitive types.
 
+use crate::{ColorSpace, DateTime, FrameRate, FrameRange, JsonValue, NodeErrorValue, PathData, TableData, SubdivisionSurface, UdimTexture, UVInterpolation, UVSet};
 use egui::Color32;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
@@ -32,10 +33,30 @@ pub enum NodeData {
     Integer(i32),
     Vector3([f32; 3]),
     Color([f32; 4]),
+    /// Color tagged with its color space
+    ColorIn { value: [f32; 4], space: ColorSpace },
     String(String),
+    /// File path with project-relative resolution
+    Path(PathData),
     Boolean(bool),
+    /// UTC timestamp with nanosecond precision
+    DateTime(DateTime),
+    /// Inclusive frame range sampled every `step` frames (see `FrameRange`)
+    FrameRange { start: i32, end: i32, step: i32 },
+    /// SMPTE timecode at a frame rate
+    Timecode { value: crate::Timecode, rate: FrameRate },
+    /// Color ramp with positioned stops
+    Gradient(crate::GradientData),
+    /// Loosely structured JSON value
+    Json(JsonValue),
+    /// Tabular data with typed columns
+    Table(TableData),
+    /// Render settings shared between settings and renderer nodes
+    RenderSettings(crate::render::RenderSettings),
     Any(String), // Generic reference/handle
     USDScene(String), // USD scene data as string for plugin interface
+    /// Error propagated from an upstream node
+    Error(NodeErrorValue),
     None, // Empty/null value
 }
 
@@ -65,9 +86,24 @@ impl NodeData {
     }
     
     /// Try to extract as color
+    /// 
+    /// `ColorIn` values are converted to sRGB, matching untagged `Color` values;
+    /// use `as_color_in` for another target space.
     pub fn as_color(&self) -> Option<[f32; 4]> {
         match self {
             NodeData::Color(c) => Some(*c),
+            NodeData::ColorIn { .. } => self.as_color_in(ColorSpace::Srgb),
+            _ => None,
+        }
+    }
+    
+    /// Try to extract as a color in a specific color space
+    /// 
+    /// Tagged colors are converted; untagged `Color` values are treated as sRGB.
+    pub fn as_color_in(&self, target: ColorSpace) -> Option<[f32; 4]> {
+        match self {
+            NodeData::Color(c) => Some(ColorSpace::Srgb.convert(*c, target)),
+            NodeData::ColorIn { value, space } => Some(space.convert(*value, target)),
             _ => None,
         }
     }
@@ -81,6 +117,14 @@ impl NodeData {
         }
     }
     
+    /// Try to extract as path data
+    pub fn as_path(&self) -> Option<&PathData> {
+        match self {
+            NodeData::Path(p) => Some(p),
+            _ => None,
+        }
+    }
+    
     /// Try to extract as boolean
     pub fn as_boolean(&self) -> Option<bool> {
         match self {
@@ -89,6 +133,62 @@ impl NodeData {
         }
     }
     
+    /// Try to extract as a timestamp
+    pub fn as_datetime(&self) -> Option<DateTime> {
+        match self {
+            NodeData::DateTime(t) => Some(*t),
+            _ => None,
+        }
+    }
+    
+    /// Try to extract as a frame range
+    pub fn as_frame_range(&self) -> Option<FrameRange> {
+        match self {
+            NodeData::FrameRange { start, end, step } => Some(FrameRange::new(*start, *end).with_step(*step)),
+            _ => None,
+        }
+    }
+    
+    /// Try to extract as a timecode and its frame rate
+    pub fn as_timecode(&self) -> Option<(crate::Timecode, FrameRate)> {
+        match self {
+            NodeData::Timecode { value, rate } => Some((*value, *rate)),
+            _ => None,
+        }
+    }
+    
+    /// Try to extract as a color gradient
+    pub fn as_gradient(&self) -> Option<&crate::GradientData> {
+        match self {
+            NodeData::Gradient(g) => Some(g),
+            _ => None,
+        }
+    }
+    
+    /// Try to extract as a JSON value
+    pub fn as_json(&self) -> Option<&JsonValue> {
+        match self {
+            NodeData::Json(j) => Some(j),
+            _ => None,
+        }
+    }
+    
+    /// Try to extract as table data
+    pub fn as_table(&self) -> Option<&TableData> {
+        match self {
+            NodeData::Table(t) => Some(t),
+            _ => None,
+        }
+    }
+    
+    /// Try to extract as render settings
+    pub fn as_render_settings(&self) -> Option<&crate::render::RenderSettings> {
+        match self {
+            NodeData::RenderSettings(settings) => Some(settings),
+            _ => None,
+        }
+    }
+    
     /// Try to extract as USD scene data
     pub fn as_usd_scene(&self) -> Option<&USDSceneData> {
         match self {
@@ -129,6 +229,57 @@ impl NodeData {
         }
     }
     
+    /// Create an error value
+    pub fn error(message: impl Into<String>) -> Self {
+        NodeData::Error(NodeErrorValue::new(message))
+    }
+    
+    /// Try to extract as an error
+    pub fn as_error(&self) -> Option<&NodeErrorValue> {
+        match self {
+            NodeData::Error(e) => Some(e),
+            _ => None,
+        }
+    }
+    
+    /// Check if this is an error value
+    pub fn is_error(&self) -> bool {
+        matches!(self, NodeData::Error(_))
+    }
+    
+    /// Get the name of this value's variant (for error messages)
+    pub fn type_name(&self) -> &'static str {
+        match self {
+            NodeData::Scene(_) => "Scene",
+            NodeData::Geometry(_) => "Geometry",
+            NodeData::Material(_) => "Material",
+            NodeData::Stage(_) => "Stage",
+            NodeData::USDSceneData(_) => "USDSceneData",
+            NodeData::USDScenegraphMetadata(_) => "USDScenegraphMetadata",
+            NodeData::Light(_) => "Light",
+            NodeData::Image(_) => "Image",
+            NodeData::Float(_) => "Float",
+            NodeData::Integer(_) => "Integer",
+            NodeData::Vector3(_) => "Vector3",
+            NodeData::Color(_) => "Color",
+            NodeData::ColorIn { .. } => "ColorIn",
+            NodeData::String(_) => "String",
+            NodeData::Path(_) => "Path",
+            NodeData::Boolean(_) => "Boolean",
+            NodeData::DateTime(_) => "DateTime",
+            NodeData::FrameRange { .. } => "FrameRange",
+            NodeData::Timecode { .. } => "Timecode",
+            NodeData::Gradient(_) => "Gradient",
+            NodeData::Json(_) => "Json",
+            NodeData::Table(_) => "Table",
+            NodeData::RenderSettings(_) => "RenderSettings",
+            NodeData::Any(_) => "Any",
+            NodeData::USDScene(_) => "USDScene",
+            NodeData::Error(_) => "Error",
+            NodeData::None => "None",
+        }
+    }
+    
     /// Check if this is a None/empty value
     pub fn is_none(&self) -> bool {
         matches!(self, NodeData::None)
@@ -148,8 +299,24 @@ pub enum DataType {
     Color,
     /// Text string
     String,
+    /// File path
+    Path,
     /// Boolean value
     Boolean,
+    /// UTC timestamp
+    DateTime,
+    /// Frame range
+    FrameRange,
+    /// SMPTE timecode
+    Timecode,
+    /// Color ramp
+    Gradient,
+    /// JSON value
+    Json,
+    /// Tabular data
+    Table,
+    /// Render settings
+    RenderSettings,
     /// Complete 3D scene
     Scene,
     /// Geometric data
@@ -184,7 +351,15 @@ impl DataType {
             DataType::Vector3 => "Vector3", 
             DataType::Color => "Color",
             DataType::String => "String",
+            DataType::Path => "Path",
             DataType::Boolean => "Boolean",
+            DataType::DateTime => "DateTime",
+            DataType::FrameRange => "Frame Range",
+            DataType::Timecode => "Timecode",
+            DataType::Gradient => "Gradient",
+            DataType::Json => "JSON",
+            DataType::Table => "Table",
+            DataType::RenderSettings => "Render Settings",
             DataType::Scene => "Scene",
             DataType::Geometry => "Geometry",
             DataType::Material => "Material",
@@ -198,14 +373,37 @@ impl DataType {
     }
     
     /// Get a color representing this data type
+    /// 
+    /// Returns the default dark-theme palette; use `themed_color` with the
+    /// host's theme (see `PluginHandle::theme`) to follow the active UI theme.
     pub fn color(&self) -> Color32 {
+        self.default_color()
+    }
+    
+    /// Get the color for this data type under a theme
+    pub fn themed_color(&self, theme: &dyn crate::theme::ThemeProvider) -> Color32 {
+        theme
+            .port_color(self)
+            .unwrap_or_else(|| crate::theme::adapt_color(self.default_color(), theme.variant()))
+    }
+    
+    /// Get the default (dark theme) color for this data type
+    pub fn default_color(&self) -> Color32 {
         match self {
             DataType::Float => Color32::from_rgb(100, 150, 255), // Blue
             DataType::Integer => Color32::from_rgb(80, 120, 200), // Dark blue
             DataType::Vector3 => Color32::from_rgb(255, 100, 100), // Red
             DataType::Color => Color32::from_rgb(255, 200, 100), // Orange
             DataType::String => Color32::from_rgb(100, 255, 100), // Green
+            DataType::Path => Color32::from_rgb(60, 200, 160), // Teal
             DataType::Boolean => Color32::from_rgb(255, 100, 255), // Magenta
+            DataType::DateTime => Color32::from_rgb(170, 200, 230), // Pale blue
+            DataType::FrameRange => Color32::from_rgb(190, 170, 230), // Lavender
+            DataType::Timecode => Color32::from_rgb(150, 180, 230), // Periwinkle
+            DataType::Gradient => Color32::from_rgb(240, 150, 190), // Rose
+            DataType::Json => Color32::from_rgb(220, 180, 120), // Tan
+            DataType::Table => Color32::from_rgb(130, 200, 200), // Light cyan
+            DataType::RenderSettings => Color32::from_rgb(230, 140, 90), // Copper
             DataType::Scene => Color32::from_rgb(180, 130, 70), // Brown
             DataType::Geometry => Color32::from_rgb(200, 100, 150), // Pink
             DataType::Material => Color32::from_rgb(150, 200, 100), // Light green
@@ -220,7 +418,7 @@ impl DataType {
 }
 
 /// Scene hierarchy data
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct SceneData {
     pub geometry: Vec<GeometryData>,
     pub materials: Vec<MaterialData>,
@@ -228,37 +426,242 @@ pub struct SceneData {
     pub transforms: HashMap<String, [[f32; 4]; 4]>, // Transform matrices
 }
 
-impl Default for SceneData {
-    fn default() -> Self {
-        Self {
-            geometry: Vec::new(),
-            materials: Vec::new(),
-            lights: Vec::new(),
-            transforms: HashMap::new(),
-        }
-    }
-}
-
 /// Geometry data for 3D objects
-#[derive(Debug, Clone, Serialize, Deserialize)]
+/// 
+/// `indices` are face-vertex indices. When `face_vertex_counts` is empty every
+/// face is a triangle; otherwise it lists the number of vertices of each face
+/// (n-gons), in order. `normals`/`uvs` are per-vertex, while the `face_varying_*`
+/// arrays hold one value per entry in `indices`. `uvs` is the primary UV set;
+/// further named sets live in `uv_sets`.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct GeometryData {
     pub id: String,
     pub vertices: Vec<[f32; 3]>,
     pub indices: Vec<u32>,
     pub normals: Vec<[f32; 3]>,
     pub uvs: Vec<[f32; 2]>,
+    /// Per-vertex colors (RGB), empty if not present
+    #[serde(default)]
+    pub colors: Vec<[f32; 3]>,
     pub material_id: Option<String>,
+    /// Number of vertices per face (empty = all triangles)
+    #[serde(default)]
+    pub face_vertex_counts: Vec<u32>,
+    /// Face-varying normals (one per face-vertex), empty if not present
+    #[serde(default)]
+    pub face_varying_normals: Vec<[f32; 3]>,
+    /// Face-varying UVs (one per face-vertex), empty if not present
+    #[serde(default)]
+    pub face_varying_uvs: Vec<[f32; 2]>,
+    /// Additional named UV sets (lightmap, secondary projections)
+    #[serde(default)]
+    pub uv_sets: Vec<UVSet>,
+    /// Subdivision scheme and creases (None = plain polygon mesh)
+    #[serde(default)]
+    pub subdivision: Option<SubdivisionSurface>,
+}
+
+impl GeometryData {
+    /// Check if every face is a triangle
+    pub fn is_triangulated(&self) -> bool {
+        self.face_vertex_counts.iter().all(|&count| count == 3)
+    }
+    
+    /// Get the number of faces
+    pub fn face_count(&self) -> usize {
+        if self.face_vertex_counts.is_empty() {
+            self.indices.len() / 3
+        } else {
+            self.face_vertex_counts.len()
+        }
+    }
+    
+    /// Iterate over faces as ranges into `indices`
+    pub fn face_ranges(&self) -> Vec<std::ops::Range<usize>> {
+        let mut ranges = Vec::with_capacity(self.face_count());
+        let mut start = 0;
+        if self.face_vertex_counts.is_empty() {
+            while start + 3 <= self.indices.len() {
+                ranges.push(start..start + 3);
+                start += 3;
+            }
+        } else {
+            for &count in &self.face_vertex_counts {
+                ranges.push(start..start + count as usize);
+                start += count as usize;
+            }
+        }
+        ranges
+    }
+    
+    /// Return a triangulated copy of this geometry (fan triangulation)
+    /// 
+    /// Face-varying attributes are carried over to the new triangle corners.
+    /// Faces with fewer than three vertices, faces running past the end of
+    /// `indices` and faces referencing missing vertices are dropped; use
+    /// `validate` to report them instead.
+    pub fn triangulate(&self) -> GeometryData {
+        let vertex_count = self.vertices.len();
+        let mut corners = Vec::with_capacity(self.indices.len());
+        for range in self.face_ranges() {
+            let valid = range.len() >= 3
+                && self
+                    .indices
+                    .get(range.clone())
+                    .is_some_and(|face| face.iter().all(|&i| (i as usize) < vertex_count));
+            if !valid {
+                continue;
+            }
+            for i in 1..range.len() - 1 {
+                corners.push(range.start);
+                corners.push(range.start + i);
+                corners.push(range.start + i + 1);
+            }
+        }
+        
+        GeometryData {
+            indices: corners.iter().map(|&c| self.indices[c]).collect(),
+            face_vertex_counts: Vec::new(),
+            face_varying_normals: pick(&self.face_varying_normals, &corners),
+            face_varying_uvs: pick(&self.face_varying_uvs, &corners),
+            uv_sets: self
+                .uv_sets
+                .iter()
+                .map(|set| match set.interpolation {
+                    UVInterpolation::Vertex => set.clone(),
+                    UVInterpolation::FaceVarying => UVSet {
+                        uvs: pick(&set.uvs, &corners),
+                        ..set.clone()
+                    },
+                })
+                .collect(),
+            ..self.clone()
+        }
+    }
+}
+
+impl GeometryData {
+    /// Get UVs by set name (`PRIMARY_UV_SET` returns `uvs`/`face_varying_uvs`)
+    pub fn uv_set(&self, name: &str) -> Option<(&[[f32; 2]], UVInterpolation)> {
+        if name == crate::uv::PRIMARY_UV_SET {
+            return if !self.uvs.is_empty() {
+                Some((&self.uvs, UVInterpolation::Vertex))
+            } else if !self.face_varying_uvs.is_empty() {
+                Some((&self.face_varying_uvs, UVInterpolation::FaceVarying))
+            } else {
+                None
+            };
+        }
+        self.uv_sets
+            .iter()
+            .find(|set| set.name == name)
+            .map(|set| (set.uvs.as_slice(), set.interpolation))
+    }
+    
+    /// Add or replace a named UV set
+    pub fn set_uv_set(&mut self, set: UVSet) {
+        match self.uv_sets.iter_mut().find(|existing| existing.name == set.name) {
+            Some(existing) => *existing = set,
+            None => self.uv_sets.push(set),
+        }
+    }
+}
+
+/// Select face-varying values for triangle corners (empty stays empty)
+fn pick<T: Copy>(values: &[T], corners: &[usize]) -> Vec<T> {
+    if values.is_empty() {
+        Vec::new()
+    } else {
+        corners.iter().filter_map(|&c| values.get(c).copied()).collect()
+    }
 }
 
 /// Material and shading data
+/// 
+/// This is the single material definition used by both node data and viewport scenes.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct MaterialData {
     pub id: String,
+    /// Material display name
+    #[serde(default)]
+    pub name: String,
     pub base_color: [f32; 4],
     pub metallic: f32,
     pub roughness: f32,
+    /// Emission color (RGB)
+    #[serde(default)]
+    pub emission: [f32; 3],
+    #[serde(alias = "normal_texture")]
     pub normal_map: Option<String>,
+    #[serde(alias = "diffuse_texture")]
     pub diffuse_map: Option<String>,
+    #[serde(default, alias = "roughness_texture")]
+    pub roughness_map: Option<String>,
+    #[serde(default, alias = "metallic_texture")]
+    pub metallic_map: Option<String>,
+    /// UV set used by each texture slot (e.g. "normal_map" -> "lightmap")
+    /// 
+    /// Slots without an entry use the primary set.
+    #[serde(default)]
+    pub uv_set_bindings: HashMap<String, String>,
+    /// UDIM texture sets by texture slot (the slot's map path holds the pattern)
+    #[serde(default)]
+    pub udim_textures: HashMap<String, UdimTexture>,
+}
+
+impl MaterialData {
+    /// Diffuse texture path (formerly `viewport::MaterialData::diffuse_texture`)
+    #[deprecated(note = "use the `diffuse_map` field")]
+    pub fn diffuse_texture(&self) -> Option<&str> {
+        self.diffuse_map.as_deref()
+    }
+    
+    /// Normal texture path (formerly `viewport::MaterialData::normal_texture`)
+    #[deprecated(note = "use the `normal_map` field")]
+    pub fn normal_texture(&self) -> Option<&str> {
+        self.normal_map.as_deref()
+    }
+    
+    /// Roughness texture path (formerly `viewport::MaterialData::roughness_texture`)
+    #[deprecated(note = "use the `roughness_map` field")]
+    pub fn roughness_texture(&self) -> Option<&str> {
+        self.roughness_map.as_deref()
+    }
+    
+    /// Metallic texture path (formerly `viewport::MaterialData::metallic_texture`)
+    #[deprecated(note = "use the `metallic_map` field")]
+    pub fn metallic_texture(&self) -> Option<&str> {
+        self.metallic_map.as_deref()
+    }
+    
+    /// Get the UV set name a texture slot samples from
+    pub fn uv_set_for(&self, slot: &str) -> &str {
+        self.uv_set_bindings
+            .get(slot)
+            .map(String::as_str)
+            .unwrap_or(crate::uv::PRIMARY_UV_SET)
+    }
+    
+    /// Get the UDIM texture set bound to a texture slot
+    pub fn udim_texture(&self, slot: &str) -> Option<&UdimTexture> {
+        self.udim_textures.get(slot)
+    }
+    
+    /// Resolve the texture file a slot samples at a UV coordinate
+    /// 
+    /// UDIM slots pick the tile under `uv`; plain slots return their map path.
+    pub fn texture_path_for_uv(&self, slot: &str, uv: [f32; 2]) -> Option<&str> {
+        if let Some(texture) = self.udim_textures.get(slot) {
+            return texture.path_for_uv(uv);
+        }
+        match slot {
+            "diffuse_map" => self.diffuse_map.as_deref(),
+            "normal_map" => self.normal_map.as_deref(),
+            "roughness_map" => self.roughness_map.as_deref(),
+            "metallic_map" => self.metallic_map.as_deref(),
+            _ => None,
+        }
+    }
 }
 
 /// USD stage reference data
@@ -270,6 +673,8 @@ pub struct StageData {
 }
 
 /// Lighting data
+/// 
+/// This is the single light definition used by both node data and viewport scenes.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct LightData {
     pub id: String,
@@ -277,6 +682,34 @@ pub struct LightData {
     pub position: [f32; 3],
     pub color: [f32; 3],
     pub intensity: f32,
+    /// Light range for point/spot/area lights (None = unlimited)
+    #[serde(default)]
+    pub range: Option<f32>,
+    /// Which prims this light illuminates
+    #[serde(default)]
+    pub linking: LightLinking,
+}
+
+impl LightData {
+    /// Light direction (formerly `viewport::LightData::direction`)
+    #[deprecated(note = "use `light_type.direction()`")]
+    pub fn direction(&self) -> Option<[f32; 3]> {
+        self.light_type.direction()
+    }
+    
+    /// Spot cone angle in radians (formerly `viewport::LightData::spot_angle`)
+    #[deprecated(note = "match on `LightType::Spot { cone_angle, .. }`")]
+    pub fn spot_angle(&self) -> Option<f32> {
+        match self.light_type {
+            LightType::Spot { cone_angle, .. } => Some(cone_angle),
+            _ => None,
+        }
+    }
+    
+    /// Check if this light illuminates the prim/mesh at `path`
+    pub fn illuminates(&self, path: &str) -> bool {
+        self.linking.illuminates(path)
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -284,9 +717,89 @@ pub enum LightType {
     Point,
     Directional { direction: [f32; 3] },
     Spot { direction: [f32; 3], cone_angle: f32 },
+    Area { direction: [f32; 3], size: [f32; 2] },
+}
+
+impl LightType {
+    /// Get the light direction (None for point lights)
+    pub fn direction(&self) -> Option<[f32; 3]> {
+        match self {
+            LightType::Point => None,
+            LightType::Directional { direction }
+            | LightType::Spot { direction, .. }
+            | LightType::Area { direction, .. } => Some(*direction),
+        }
+    }
+}
+
+/// Light-linking include/exclude sets
+/// 
+/// Follows USD collection semantics: a path matches an entry if it is that prim
+/// or one of its descendants, and the most specific (longest) matching entry
+/// wins. An empty include set means "include everything", so the default links
+/// the light to the whole scene.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LightLinking {
+    /// Prim paths the light illuminates (empty = all)
+    #[serde(default)]
+    pub include: Vec<String>,
+    /// Prim paths the light does not illuminate
+    #[serde(default)]
+    pub exclude: Vec<String>,
+}
+
+impl LightLinking {
+    /// Link to every prim except the excluded ones
+    pub fn all() -> Self {
+        Self::default()
+    }
+    
+    /// Add a prim path (and its descendants) to the include set
+    pub fn include(mut self, path: impl Into<String>) -> Self {
+        self.include.push(path.into());
+        self
+    }
+    
+    /// Add a prim path (and its descendants) to the exclude set
+    pub fn exclude(mut self, path: impl Into<String>) -> Self {
+        self.exclude.push(path.into());
+        self
+    }
+    
+    /// Check if the light links to everything (no include or exclude entries)
+    pub fn is_default(&self) -> bool {
+        self.include.is_empty() && self.exclude.is_empty()
+    }
+    
+    /// Check if a prim/mesh path is illuminated
+    pub fn illuminates(&self, path: &str) -> bool {
+        let best_match = |paths: &[String]| {
+            paths
+                .iter()
+                .filter(|p| path_contains(p, path))
+                .map(|p| p.trim_end_matches('/').len())
+                .max()
+        };
+        let included = if self.include.is_empty() { Some(0) } else { best_match(&self.include) };
+        match (included, best_match(&self.exclude)) {
+            (None, _) => false,
+            (Some(_), None) => true,
+            (Some(include), Some(exclude)) => include > exclude,
+        }
+    }
+}
+
+/// Check if `path` is `root` or a descendant of it
+fn path_contains(root: &str, path: &str) -> bool {
+    let root = root.trim_end_matches('/');
+    root.is_empty()
+        || path == root
+        || (path.starts_with(root) && path.as_bytes().get(root.len()) == Some(&b'/'))
 }
 
 /// Image/texture data
+/// 
+/// Images can be file references (`file_path`) or carry real pixel data in `pixels`.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ImageData {
     pub id: String,
@@ -294,13 +807,195 @@ pub struct ImageData {
     pub width: u32,
     pub height: u32,
     pub format: ImageFormat,
+    /// In-memory pixel data (None for file-only references)
+    #[serde(default)]
+    pub pixels: Option<PixelBuffer>,
+    /// Color space of the pixel values
+    #[serde(default)]
+    pub color_space: ColorSpace,
+    /// Reduced-resolution levels below the base image (level 1 first)
+    #[serde(default)]
+    pub mip_levels: Vec<MipLevel>,
+    /// Tile set for UDIM file references (`file_path` holds the pattern)
+    #[serde(default)]
+    pub udim: Option<UdimTexture>,
 }
 
+/// One reduced-resolution level of a mipmapped image
 #[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MipLevel {
+    pub width: u32,
+    pub height: u32,
+    pub pixels: PixelBuffer,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub enum ImageFormat {
     RGB8,
     RGBA8,
     HDR,
+    /// 4 x 32-bit float channels
+    RGBA32F,
+    /// Single 16-bit float channel (IEEE half, little-endian)
+    R16F,
+}
+
+impl ImageFormat {
+    /// Number of channels per pixel
+    pub fn channels(&self) -> usize {
+        match self {
+            ImageFormat::RGB8 | ImageFormat::HDR => 3,
+            ImageFormat::RGBA8 | ImageFormat::RGBA32F => 4,
+            ImageFormat::R16F => 1,
+        }
+    }
+    
+    /// Number of bytes per pixel
+    pub fn bytes_per_pixel(&self) -> usize {
+        match self {
+            ImageFormat::RGB8 => 3,
+            ImageFormat::RGBA8 => 4,
+            ImageFormat::HDR => 12, // RGB 32-bit float
+            ImageFormat::RGBA32F => 16,
+            ImageFormat::R16F => 2,
+        }
+    }
+    
+    /// Check if this is a floating point format
+    pub fn is_float(&self) -> bool {
+        matches!(self, ImageFormat::HDR | ImageFormat::RGBA32F | ImageFormat::R16F)
+    }
+}
+
+/// Raw pixel storage for `ImageData`
+/// 
+/// Rows are stored top to bottom; each row starts `row_stride` bytes after the
+/// previous one (`row_stride` >= width * bytes per pixel, allowing padding).
+/// Multi-byte channels are little-endian.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PixelBuffer {
+    pub data: Vec<u8>,
+    pub row_stride: usize,
+}
+
+impl ImageData {
+    /// Create an image with tightly packed pixel data
+    pub fn from_pixels(
+        id: impl Into<String>,
+        width: u32,
+        height: u32,
+        format: ImageFormat,
+        data: Vec<u8>,
+    ) -> Result<Self, String> {
+        let row_stride = width as usize * format.bytes_per_pixel();
+        let image = Self {
+            id: id.into(),
+            file_path: None,
+            width,
+            height,
+            format,
+            pixels: Some(PixelBuffer { data, row_stride }),
+            color_space: if format.is_float() { ColorSpace::LinearSrgb } else { ColorSpace::Srgb },
+            mip_levels: Vec::new(),
+            udim: None,
+        };
+        image.validate_pixels()?;
+        Ok(image)
+    }
+    
+    /// Create a file reference to a UDIM texture set, discovering its tiles
+    /// 
+    /// `width`/`height` are the largest tile resolution known so far.
+    pub fn from_udim_pattern(id: impl Into<String>, pattern: impl Into<String>) -> Result<Self, String> {
+        let texture = UdimTexture::discover(pattern)?;
+        let (width, height) = texture.max_resolution();
+        Ok(Self {
+            id: id.into(),
+            file_path: Some(texture.pattern.clone()),
+            width,
+            height,
+            format: ImageFormat::RGBA8,
+            pixels: None,
+            color_space: ColorSpace::default(),
+            mip_levels: Vec::new(),
+            udim: Some(texture),
+        })
+    }
+    
+    /// Check if this image references a UDIM texture set
+    pub fn is_udim(&self) -> bool {
+        self.udim.is_some()
+    }
+    
+    /// Create an RGBA32F image from float values (4 per pixel)
+    pub fn from_rgba32f(id: impl Into<String>, width: u32, height: u32, values: &[f32]) -> Result<Self, String> {
+        let data = values.iter().flat_map(|v| v.to_le_bytes()).collect();
+        Self::from_pixels(id, width, height, ImageFormat::RGBA32F, data)
+    }
+    
+    /// Check that the pixel buffer is large enough for the image dimensions
+    pub fn validate_pixels(&self) -> Result<(), String> {
+        let Some(pixels) = &self.pixels else {
+            return Ok(());
+        };
+        let row_bytes = self.width as usize * self.format.bytes_per_pixel();
+        if pixels.row_stride < row_bytes {
+            return Err(format!("Row stride {} is smaller than row size {}", pixels.row_stride, row_bytes));
+        }
+        let required = match self.height as usize {
+            0 => 0,
+            h => pixels.row_stride * (h - 1) + row_bytes,
+        };
+        if pixels.data.len() < required {
+            return Err(format!("Pixel buffer has {} bytes, expected at least {}", pixels.data.len(), required));
+        }
+        Ok(())
+    }
+    
+    /// Get the raw bytes of a single pixel
+    pub fn pixel_bytes(&self, x: u32, y: u32) -> Option<&[u8]> {
+        if x >= self.width || y >= self.height {
+            return None;
+        }
+        let pixels = self.pixels.as_ref()?;
+        let bpp = self.format.bytes_per_pixel();
+        let start = y as usize * pixels.row_stride + x as usize * bpp;
+        pixels.data.get(start..start + bpp)
+    }
+    
+    /// Read a pixel as RGBA float (8-bit formats are normalized to 0-1)
+    pub fn pixel_rgba(&self, x: u32, y: u32) -> Option<[f32; 4]> {
+        let bytes = self.pixel_bytes(x, y)?;
+        let f32_at = |i: usize| f32::from_le_bytes([bytes[i], bytes[i + 1], bytes[i + 2], bytes[i + 3]]);
+        Some(match self.format {
+            ImageFormat::RGB8 => [bytes[0] as f32 / 255.0, bytes[1] as f32 / 255.0, bytes[2] as f32 / 255.0, 1.0],
+            ImageFormat::RGBA8 => [
+                bytes[0] as f32 / 255.0,
+                bytes[1] as f32 / 255.0,
+                bytes[2] as f32 / 255.0,
+                bytes[3] as f32 / 255.0,
+            ],
+            ImageFormat::HDR => [f32_at(0), f32_at(4), f32_at(8), 1.0],
+            ImageFormat::RGBA32F => [f32_at(0), f32_at(4), f32_at(8), f32_at(12)],
+            ImageFormat::R16F => {
+                let v = half_to_f32(u16::from_le_bytes([bytes[0], bytes[1]]));
+                [v, v, v, 1.0]
+            }
+        })
+    }
+}
+
+/// Convert IEEE 754 half-precision bits to f32
+pub fn half_to_f32(bits: u16) -> f32 {
+    let sign = if bits & 0x8000 != 0 { -1.0 } else { 1.0 };
+    let exponent = ((bits >> 10) & 0x1f) as i32;
+    let mantissa = (bits & 0x3ff) as f32;
+    match exponent {
+        0 => sign * mantissa * 2f32.powi(-24),
+        0x1f if mantissa == 0.0 => sign * f32::INFINITY,
+        0x1f => f32::NAN,
+        _ => sign * (1.0 + mantissa / 1024.0) * 2f32.powi(exponent - 15),
+    }
 }
 
 // USD-specific data structures
@@ -345,8 +1040,11 @@ pub struct USDMeshGeometry {
     pub indices: Vec<u32>,
     /// Vertex normals
     pub normals: Vec<[f32; 3]>,
-    /// UV coordinates
+    /// UV coordinates (primary set)
     pub uvs: Vec<[f32; 2]>,
+    /// Additional named UV sets
+    #[serde(default)]
+    pub uv_sets: Vec<UVSet>,
     /// Vertex colors (if present)
     pub vertex_colors: Vec<[f32; 3]>,
     /// Transform matrix
@@ -355,6 +1053,44 @@ pub struct USDMeshGeometry {
     pub material_path: Option<String>,
     /// Custom attributes/primvars
     pub primvars: HashMap<String, USDPrimvar>,
+    /// Sub-frame samples for motion blur (empty for static meshes)
+    #[serde(default)]
+    pub motion_samples: Vec<MotionSample>,
+    /// Subdivision metadata and control cage (None = plain polygon mesh)
+    #[serde(default)]
+    pub subdivision: Option<SubdivisionSurface>,
+}
+
+/// Sub-frame geometry sample for motion blur
+/// 
+/// Convention shared by `USDMeshGeometry` and viewport `MeshData`: samples are
+/// sorted by `time`, which is an offset in frames from the evaluated frame
+/// (e.g. -0.25 for shutter open, 0.25 for shutter close). `positions` must have
+/// the same length as the mesh vertices (topology does not change between
+/// samples); leave it empty for transform-only motion.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MotionSample {
+    /// Time offset in frames relative to the evaluated frame
+    pub time: f32,
+    /// Vertex positions at this time (empty = same as the mesh)
+    pub positions: Vec<[f32; 3]>,
+    /// Transform matrix at this time
+    pub transform: [[f32; 4]; 4],
+}
+
+impl MotionSample {
+    /// Get the shutter interval covered by a set of samples
+    pub fn shutter_range(samples: &[MotionSample]) -> Option<(f32, f32)> {
+        let first = samples.first()?.time;
+        Some(samples.iter().fold((first, first), |(open, close), s| {
+            (open.min(s.time), close.max(s.time))
+        }))
+    }
+    
+    /// Sort samples by time as required by the convention
+    pub fn sort_samples(samples: &mut [MotionSample]) {
+        samples.sort_by(|a, b| a.time.total_cmp(&b.time));
+    }
 }
 
 /// USD primvar (primitive variable) data
@@ -394,6 +1130,9 @@ pub struct USDLight {
     pub color: [f32; 3],
     pub intensity: f32,
     pub exposure: f32,
+    /// Light-linking include/exclude sets (`collection:lightLink`)
+    #[serde(default)]
+    pub linking: LightLinking,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -418,6 +1157,9 @@ pub struct USDMaterial {
     pub emission_color: [f32; 3],
     pub normal_map: Option<String>,
     pub diffuse_map: Option<String>,
+    /// UV set used by each texture slot (from the texture readers' `varname`)
+    #[serde(default)]
+    pub uv_set_bindings: HashMap<String, String>,
 }
 
 /// Lightweight USD metadata for scenegraph display (no heavy geometry data)
@@ -446,4 +1188,69 @@ pub struct USDPrimInfo {
     pub has_material: bool,
     pub vertex_count: Option<usize>,
     pub triangle_count: Option<usize>,
-}
\ No newline at end of file
+    /// Unloaded payload under this prim (resolved on demand via `USDPayloadResolver`)
+    #[serde(default)]
+    pub payload: Option<crate::usd::USDPayloadRef>,
+}
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn quad_and_triangle() -> GeometryData {
+        GeometryData {
+            vertices: vec![[0.0, 0.0, 0.0], [1.0, 0.0, 0.0], [1.0, 1.0, 0.0], [0.0, 1.0, 0.0], [2.0, 0.0, 0.0]],
+            indices: vec![0, 1, 2, 3, 1, 4, 2],
+            face_vertex_counts: vec![4, 3],
+            face_varying_uvs: (0..7).map(|i| [i as f32, 0.0]).collect(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn triangulate_fans_ngons_and_carries_face_varying_attributes() {
+        let triangles = quad_and_triangle().triangulate();
+        assert_eq!(triangles.indices, vec![0, 1, 2, 0, 2, 3, 1, 4, 2]);
+        assert!(triangles.face_vertex_counts.is_empty());
+        let corners: Vec<f32> = triangles.face_varying_uvs.iter().map(|uv| uv[0]).collect();
+        assert_eq!(corners, vec![0.0, 1.0, 2.0, 0.0, 2.0, 3.0, 4.0, 5.0, 6.0]);
+    }
+
+    #[test]
+    fn triangulate_drops_faces_past_the_end_of_indices() {
+        let mut geometry = quad_and_triangle();
+        geometry.face_vertex_counts = vec![4, 3, 5];
+        assert_eq!(geometry.triangulate().indices, vec![0, 1, 2, 0, 2, 3, 1, 4, 2]);
+    }
+
+    #[test]
+    fn triangulate_drops_degenerate_faces_and_missing_vertices() {
+        let mut geometry = quad_and_triangle();
+        geometry.indices = vec![0, 1, 2, 9, 1, 4, 2];
+        assert_eq!(geometry.triangulate().indices, vec![1, 4, 2]);
+
+        geometry.indices = vec![0, 1, 2, 3, 1, 4, 2];
+        geometry.face_vertex_counts = vec![2, 5];
+        assert_eq!(geometry.triangulate().indices, vec![2, 3, 1, 2, 1, 4, 2, 4, 2]);
+    }
+
+    #[test]
+    fn as_color_accepts_tagged_colors() {
+        let tagged = NodeData::ColorIn { value: [0.5, 0.25, 0.0, 1.0], space: ColorSpace::ACEScg };
+        let converted = ColorSpace::ACEScg.convert([0.5, 0.25, 0.0, 1.0], ColorSpace::Srgb);
+        assert_eq!(tagged.as_color(), Some(converted));
+        assert_ne!(converted, [0.5, 0.25, 0.0, 1.0]);
+        let srgb = NodeData::ColorIn { value: [0.5, 0.25, 0.0, 1.0], space: ColorSpace::Srgb };
+        assert_eq!(srgb.as_color(), Some([0.5, 0.25, 0.0, 1.0]));
+        assert_eq!(NodeData::Float(1.0).as_color(), None);
+    }
+
+    #[test]
+    fn triangulate_drops_incomplete_trailing_triangle() {
+        let geometry = GeometryData {
+            vertices: vec![[0.0; 3]; 3],
+            indices: vec![0, 1, 2, 2],
+            ..Default::default()
+        };
+        assert_eq!(geometry.triangulate().indices, vec![0, 1, 2]);
+    }
+}
@@ -0,0 +1,258 @@
+//! UTC timestamps
+//!
+//! `DateTime` is stored as seconds and nanoseconds since the Unix epoch, always
+//! in UTC, so values compare and serialize identically across hosts and plugins.
+//! Pipeline nodes (render-farm submitters, file watchers) pass these through
+//! `NodeData::DateTime`.
+
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+const NANOS_PER_SECOND: i128 = 1_000_000_000;
+
+/// A UTC point in time with nanosecond precision
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub struct DateTime {
+    /// Whole seconds since 1970-01-01T00:00:00Z (negative before the epoch)
+    pub seconds: i64,
+    /// Nanoseconds within the second (always < 1_000_000_000)
+    pub nanos: u32,
+}
+
+impl DateTime {
+    /// The Unix epoch (1970-01-01T00:00:00Z)
+    pub const UNIX_EPOCH: DateTime = DateTime { seconds: 0, nanos: 0 };
+
+    /// Create a timestamp from seconds and nanoseconds since the epoch
+    ///
+    /// Nanoseconds beyond one second carry into `seconds`.
+    pub fn from_unix(seconds: i64, nanos: u32) -> Self {
+        Self {
+            seconds: seconds + (nanos / 1_000_000_000) as i64,
+            nanos: nanos % 1_000_000_000,
+        }
+    }
+
+    /// Create a timestamp from nanoseconds since the epoch
+    pub fn from_unix_nanos(nanos: i128) -> Self {
+        Self {
+            seconds: nanos.div_euclid(NANOS_PER_SECOND) as i64,
+            nanos: nanos.rem_euclid(NANOS_PER_SECOND) as u32,
+        }
+    }
+
+    /// Get nanoseconds since the epoch
+    pub fn unix_nanos(&self) -> i128 {
+        self.seconds as i128 * NANOS_PER_SECOND + self.nanos as i128
+    }
+
+    /// Current system time
+    pub fn now() -> Self {
+        Self::from(SystemTime::now())
+    }
+
+    /// Create a timestamp from a calendar date and time (UTC)
+    ///
+    /// Returns None for out-of-range fields (e.g. month 13 or February 30).
+    pub fn from_ymd_hms(year: i32, month: u32, day: u32, hour: u32, minute: u32, second: u32) -> Option<Self> {
+        if !(1..=12).contains(&month)
+            || day == 0
+            || day > days_in_month(year, month)
+            || hour > 23
+            || minute > 59
+            || second > 59
+        {
+            return None;
+        }
+        let days = days_from_civil(year, month, day);
+        Some(Self {
+            seconds: days * 86_400 + (hour * 3600 + minute * 60 + second) as i64,
+            nanos: 0,
+        })
+    }
+
+    /// Split into (year, month, day, hour, minute, second) in UTC
+    pub fn to_ymd_hms(&self) -> (i32, u32, u32, u32, u32, u32) {
+        let days = self.seconds.div_euclid(86_400);
+        let secs_of_day = self.seconds.rem_euclid(86_400) as u32;
+        let (year, month, day) = civil_from_days(days);
+        (year, month, day, secs_of_day / 3600, secs_of_day / 60 % 60, secs_of_day % 60)
+    }
+
+    /// Signed duration from `earlier` to `self` in nanoseconds
+    pub fn nanos_since(&self, earlier: &DateTime) -> i128 {
+        self.unix_nanos() - earlier.unix_nanos()
+    }
+
+    /// Duration since `earlier` (None if `earlier` is later than `self`)
+    pub fn duration_since(&self, earlier: &DateTime) -> Option<Duration> {
+        let nanos = self.nanos_since(earlier);
+        if nanos < 0 {
+            return None;
+        }
+        Some(Duration::new(
+            (nanos / NANOS_PER_SECOND) as u64,
+            (nanos % NANOS_PER_SECOND) as u32,
+        ))
+    }
+
+    /// Check if this timestamp is strictly before another
+    pub fn is_before(&self, other: &DateTime) -> bool {
+        self < other
+    }
+
+    /// Check if this timestamp is strictly after another
+    pub fn is_after(&self, other: &DateTime) -> bool {
+        self > other
+    }
+
+    /// Check if two timestamps are within `tolerance` of each other
+    ///
+    /// Useful when comparing file modification times from filesystems with
+    /// coarser resolution.
+    pub fn within(&self, other: &DateTime, tolerance: Duration) -> bool {
+        self.nanos_since(other).unsigned_abs() <= tolerance.as_nanos()
+    }
+
+    /// Add a duration
+    pub fn add(&self, duration: Duration) -> Self {
+        Self::from_unix_nanos(self.unix_nanos() + duration.as_nanos() as i128)
+    }
+
+    /// Subtract a duration
+    pub fn sub(&self, duration: Duration) -> Self {
+        Self::from_unix_nanos(self.unix_nanos() - duration.as_nanos() as i128)
+    }
+
+    /// Format as RFC 3339 (e.g. `2024-05-01T12:30:00.000000001Z`)
+    ///
+    /// The fractional part is omitted when nanoseconds are zero.
+    pub fn to_rfc3339(&self) -> String {
+        let (year, month, day, hour, minute, second) = self.to_ymd_hms();
+        let mut out = format!(
+            "{:04}-{:02}-{:02}T{:02}:{:02}:{:02}",
+            year, month, day, hour, minute, second
+        );
+        if self.nanos != 0 {
+            out.push_str(&format!(".{:09}", self.nanos));
+        }
+        out.push('Z');
+        out
+    }
+
+    /// Parse an RFC 3339 timestamp (`YYYY-MM-DDTHH:MM:SS[.fraction](Z|±HH:MM)`)
+    pub fn parse_rfc3339(s: &str) -> Result<Self, String> {
+        let err = || format!("Invalid RFC 3339 timestamp: {}", s);
+        let bytes = s.as_bytes();
+        if bytes.len() < 20 || bytes[4] != b'-' || bytes[7] != b'-' || !matches!(bytes[10], b'T' | b't' | b' ')
+            || bytes[13] != b':' || bytes[16] != b':'
+        {
+            return Err(err());
+        }
+        let num = |range: std::ops::Range<usize>| -> Result<u32, String> {
+            s.get(range).and_then(|v| v.parse().ok()).ok_or_else(err)
+        };
+        let year = num(0..4)? as i32;
+        let base = Self::from_ymd_hms(year, num(5..7)?, num(8..10)?, num(11..13)?, num(14..16)?, num(17..19)?)
+            .ok_or_else(err)?;
+
+        let mut rest = &s[19..];
+        let mut nanos = 0u32;
+        if let Some(fraction) = rest.strip_prefix('.') {
+            let digits = fraction.bytes().take_while(|b| b.is_ascii_digit()).count();
+            if digits == 0 {
+                return Err(err());
+            }
+            // Keep nanosecond precision, ignore anything finer
+            for (i, b) in fraction.bytes().take(digits.min(9)).enumerate() {
+                nanos += (b - b'0') as u32 * 10u32.pow(8 - i as u32);
+            }
+            rest = &fraction[digits..];
+        }
+
+        let offset_seconds = match rest {
+            "Z" | "z" => 0,
+            _ if rest.len() == 6 && matches!(rest.as_bytes()[0], b'+' | b'-') && rest.as_bytes()[3] == b':' => {
+                let hours = rest[1..3].parse::<i64>().map_err(|_| err())?;
+                let minutes = rest[4..6].parse::<i64>().map_err(|_| err())?;
+                let offset = hours * 3600 + minutes * 60;
+                if rest.starts_with('-') { -offset } else { offset }
+            }
+            _ => return Err(err()),
+        };
+
+        Ok(Self {
+            seconds: base.seconds - offset_seconds,
+            nanos,
+        })
+    }
+}
+
+impl fmt::Display for DateTime {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.to_rfc3339())
+    }
+}
+
+impl From<SystemTime> for DateTime {
+    fn from(time: SystemTime) -> Self {
+        match time.duration_since(UNIX_EPOCH) {
+            Ok(after) => Self::from_unix_nanos(after.as_nanos() as i128),
+            Err(before) => Self::from_unix_nanos(-(before.duration().as_nanos() as i128)),
+        }
+    }
+}
+
+impl From<DateTime> for SystemTime {
+    fn from(time: DateTime) -> Self {
+        let nanos = time.unix_nanos();
+        let magnitude = Duration::new(
+            (nanos.unsigned_abs() / NANOS_PER_SECOND as u128) as u64,
+            (nanos.unsigned_abs() % NANOS_PER_SECOND as u128) as u32,
+        );
+        if nanos >= 0 {
+            UNIX_EPOCH + magnitude
+        } else {
+            UNIX_EPOCH - magnitude
+        }
+    }
+}
+
+fn is_leap_year(year: i32) -> bool {
+    (year % 4 == 0 && year % 100 != 0) || year % 400 == 0
+}
+
+fn days_in_month(year: i32, month: u32) -> u32 {
+    match month {
+        2 if is_leap_year(year) => 29,
+        2 => 28,
+        4 | 6 | 9 | 11 => 30,
+        _ => 31,
+    }
+}
+
+/// Days since the epoch for a proleptic Gregorian date (Howard Hinnant's algorithm)
+fn days_from_civil(year: i32, month: u32, day: u32) -> i64 {
+    let year = if month <= 2 { year as i64 - 1 } else { year as i64 };
+    let era = year.div_euclid(400);
+    let year_of_era = year - era * 400;
+    let month = month as i64;
+    let day_of_year = (153 * (if month > 2 { month - 3 } else { month + 9 }) + 2) / 5 + day as i64 - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+    era * 146_097 + day_of_era - 719_468
+}
+
+/// Proleptic Gregorian date for days since the epoch
+fn civil_from_days(days: i64) -> (i32, u32, u32) {
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let day_of_era = z - era * 146_097;
+    let year_of_era = (day_of_era - day_of_era / 1460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let mp = (5 * day_of_year + 2) / 153;
+    let day = (day_of_year - (153 * mp + 2) / 5 + 1) as u32;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 } as u32;
+    let year = year_of_era + era * 400 + if month <= 2 { 1 } else { 0 };
+    (year as i32, month, day)
+}
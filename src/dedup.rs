@@ -0,0 +1,177 @@
+//! Deduplication of identical in-flight computations
+//!
+//! During the first cook of a graph, two nodes reading the same file with the
+//! same parameters would each miss the cache and both start a multi-gigabyte
+//! load. A `ComputeDeduplicator` shared between the plugin's nodes (e.g. kept
+//! in a `PluginState`) lets the first request compute while identical
+//! requests wait for its result:
+//!
+//! ```rust,ignore
+//! let key = PluginCacheKey::with_stage("usd", node_id, "load", 0)
+//!     .with_input_hash(file_hash);
+//! let content_key = key.content_key().ok_or("load key has no input hash")?;
+//! let stage = dedup.run(content_key, || load_stage(&path))?;
+//! cache.insert_shared(key, stage.clone())?;
+//! ```
+//!
+//! Keys identify the computation, not the node, so use
+//! `PluginCacheKey::content_key` rather than hashing the whole cache key.
+//! Keys without an input hash have no content key and must not be shared.
+
+use crate::NodeData;
+use std::collections::HashMap;
+use std::sync::{Arc, Condvar, Mutex};
+use std::thread::ThreadId;
+
+type ComputeResult = Result<Arc<NodeData>, String>;
+
+/// One computation in progress and the slot its result is published to
+struct InFlight {
+    /// Thread running the computation
+    leader: ThreadId,
+    result: Mutex<Option<ComputeResult>>,
+    done: Condvar,
+}
+
+impl InFlight {
+    fn new() -> Self {
+        Self {
+            leader: std::thread::current().id(),
+            result: Mutex::new(None),
+            done: Condvar::new(),
+        }
+    }
+
+    fn publish(&self, result: ComputeResult) {
+        *self.result.lock().unwrap_or_else(|e| e.into_inner()) = Some(result);
+        self.done.notify_all();
+    }
+
+    fn wait(&self) -> ComputeResult {
+        let mut result = self.result.lock().unwrap_or_else(|e| e.into_inner());
+        loop {
+            if let Some(result) = result.as_ref() {
+                return result.clone();
+            }
+            result = self.done.wait(result).unwrap_or_else(|e| e.into_inner());
+        }
+    }
+}
+
+/// Coordinates identical computations so only one of them runs
+///
+/// Clones share the same set of in-flight computations. Results are not kept
+/// once the computation finishes; store them in the cache.
+#[derive(Clone, Default)]
+pub struct ComputeDeduplicator {
+    in_flight: Arc<Mutex<HashMap<u64, Arc<InFlight>>>>,
+}
+
+impl ComputeDeduplicator {
+    /// Create a deduplicator with nothing in flight
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Run `compute` for `key`, or wait for the identical computation already running
+    ///
+    /// Waiters receive the same shared result (or the same error). If the
+    /// computing thread panics, waiters get an error and the panic continues
+    /// on the computing thread. A `compute` that calls `run` again with the
+    /// same key gets an error instead of waiting for itself forever.
+    pub fn run<F>(&self, key: u64, compute: F) -> Result<Arc<NodeData>, String>
+    where
+        F: FnOnce() -> Result<NodeData, String>,
+    {
+        let (slot, leader) = {
+            let mut in_flight = self.in_flight.lock().unwrap_or_else(|e| e.into_inner());
+            match in_flight.get(&key) {
+                Some(slot) if slot.leader == std::thread::current().id() => {
+                    return Err(format!("Re-entrant computation for key {:016x}", key));
+                }
+                Some(slot) => (slot.clone(), false),
+                None => {
+                    let slot = Arc::new(InFlight::new());
+                    in_flight.insert(key, slot.clone());
+                    (slot, true)
+                }
+            }
+        };
+        if !leader {
+            return slot.wait();
+        }
+
+        let mut guard = LeaderGuard {
+            deduplicator: self,
+            key,
+            slot,
+            result: None,
+        };
+        let result = compute().map(Arc::new);
+        guard.result = Some(result.clone());
+        result
+    }
+
+    /// Check if a computation for `key` is running
+    pub fn is_in_flight(&self, key: u64) -> bool {
+        self.in_flight.lock().unwrap_or_else(|e| e.into_inner()).contains_key(&key)
+    }
+
+    /// Number of computations running
+    pub fn in_flight_count(&self) -> usize {
+        self.in_flight.lock().unwrap_or_else(|e| e.into_inner()).len()
+    }
+}
+
+/// Publishes the leader's result and clears the slot, even if `compute` panicked
+struct LeaderGuard<'a> {
+    deduplicator: &'a ComputeDeduplicator,
+    key: u64,
+    slot: Arc<InFlight>,
+    result: Option<ComputeResult>,
+}
+
+impl Drop for LeaderGuard<'_> {
+    fn drop(&mut self) {
+        self.deduplicator
+            .in_flight
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .remove(&self.key);
+        let result = self
+            .result
+            .take()
+            .unwrap_or_else(|| Err("Deduplicated computation panicked".to_string()));
+        self.slot.publish(result);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::PluginCacheKey;
+
+    #[test]
+    fn content_key_requires_input_hash() {
+        let a = PluginCacheKey::with_stage("usd", crate::NodeId(1), "load", 0);
+        let b = PluginCacheKey::with_stage("usd", crate::NodeId(2), "load", 0);
+        assert_eq!(a.content_key(), None);
+        assert_eq!(
+            a.with_input_hash(7).content_key(),
+            b.clone().with_input_hash(7).content_key()
+        );
+        assert_ne!(b.clone().with_input_hash(7).content_key(), b.with_input_hash(8).content_key());
+    }
+
+    #[test]
+    fn reentrant_run_fails_instead_of_deadlocking() {
+        let dedup = ComputeDeduplicator::new();
+        let result = dedup.run(1, || {
+            let inner = dedup.run(1, || Ok(NodeData::Integer(0)));
+            assert!(inner.is_err());
+            Ok(NodeData::Integer(1))
+        });
+        assert!(matches!(result.as_deref(), Ok(NodeData::Integer(1))));
+        assert_eq!(dedup.in_flight_count(), 0);
+    }
+}
@@ -0,0 +1,456 @@
+//! Coordinating shared on-disk caches
+//!
+//! When two instances share a project (two editors, or an editor and a headless
+//! batch job, possibly on different machines over a network share), their
+//! plugins' disk caches live in the same directory. The protocol here keeps
+//! them from corrupting each other:
+//!
+//! - **Ownership**: only the instance holding a directory's `DiskCacheLock`
+//!   may write, evict or clear entries. The lock is an advisory `.lock` file
+//!   created atomically and holding a `CacheLockOwner` record, so it works on
+//!   network filesystems where OS file locks are unreliable.
+//! - **Liveness**: the owner calls `heartbeat` periodically. A lock whose
+//!   heartbeat is older than `LockOptions::stale_after` belongs to a crashed
+//!   instance and is broken by the next `acquire`.
+//! - **Readers**: other instances may read without the lock. Owners write
+//!   entries with `write_entry_atomic` (write to a temporary file, then rename),
+//!   so readers see either the old file, the new file, or no file. A missing
+//!   file is a cache miss, never an error.
+//!
+//! ```rust,ignore
+//! let instance = CacheInstance::current("editor-1");
+//! match DiskCacheLock::acquire(&cache_dir, &instance, &LockOptions::default()) {
+//!     Ok(lock) => write_entry_atomic(&lock.entry_path("mesh.bin"), &bytes)?,
+//!     Err(CacheLockError::Held(owner)) => { /* read-only: another instance owns it */ }
+//!     Err(e) => return Err(e.to_string()),
+//! }
+//! ```
+
+use crate::{CacheLockError, DateTime, JsonValue};
+use std::collections::BTreeMap;
+use std::fs::{self, OpenOptions};
+use std::io::{ErrorKind, Write};
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+
+/// Name of the lock file inside a cache directory
+pub const CACHE_LOCK_FILE: &str = ".lock";
+
+/// Identity of a running host instance
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct CacheInstance {
+    /// Unique per running instance (e.g. a UUID chosen at startup)
+    pub instance_id: String,
+    /// Machine name, shown when reporting who holds a lock
+    pub host: String,
+    pub pid: u32,
+}
+
+impl CacheInstance {
+    /// Describe the current process
+    pub fn current(instance_id: impl Into<String>) -> Self {
+        let host = std::env::var("HOSTNAME")
+            .or_else(|_| std::env::var("COMPUTERNAME"))
+            .unwrap_or_else(|_| "unknown".to_string());
+        Self {
+            instance_id: instance_id.into(),
+            host,
+            pid: std::process::id(),
+        }
+    }
+}
+
+/// Contents of a cache directory's lock file
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CacheLockOwner {
+    pub instance: CacheInstance,
+    pub acquired_at: DateTime,
+    /// Last time the owner proved it was alive
+    pub heartbeat: DateTime,
+}
+
+impl CacheLockOwner {
+    /// Check if the owner has missed its heartbeat for longer than `stale_after`
+    pub fn is_stale(&self, now: DateTime, stale_after: Duration) -> bool {
+        now.duration_since(&self.heartbeat).is_some_and(|age| age > stale_after)
+    }
+
+    /// Check if this record was written by the same lock acquisition
+    fn same_acquisition(&self, other: &CacheLockOwner) -> bool {
+        self.instance == other.instance && self.acquired_at == other.acquired_at
+    }
+
+    fn to_json(&self) -> JsonValue {
+        let time = |t: DateTime| {
+            JsonValue::Array(vec![JsonValue::from(t.seconds), JsonValue::from(t.nanos as i64)])
+        };
+        let mut object = BTreeMap::new();
+        object.insert("instance_id".to_string(), JsonValue::from(self.instance.instance_id.as_str()));
+        object.insert("host".to_string(), JsonValue::from(self.instance.host.as_str()));
+        object.insert("pid".to_string(), JsonValue::from(self.instance.pid as i64));
+        object.insert("acquired_at".to_string(), time(self.acquired_at));
+        object.insert("heartbeat".to_string(), time(self.heartbeat));
+        JsonValue::Object(object)
+    }
+
+    fn from_json(value: &JsonValue) -> Option<Self> {
+        let time = |key: &str| -> Option<DateTime> {
+            let seconds = value.get_pointer(&format!("/{}/0", key))?.as_i64()?;
+            let nanos = value.get_pointer(&format!("/{}/1", key))?.as_i64()?;
+            Some(DateTime::from_unix(seconds, nanos as u32))
+        };
+        Some(Self {
+            instance: CacheInstance {
+                instance_id: value.get("instance_id")?.as_str()?.to_string(),
+                host: value.get("host")?.as_str()?.to_string(),
+                pid: value.get("pid")?.as_i64()? as u32,
+            },
+            acquired_at: time("acquired_at")?,
+            heartbeat: time("heartbeat")?,
+        })
+    }
+}
+
+/// How `DiskCacheLock::acquire` waits for and breaks locks
+#[derive(Debug, Clone, PartialEq)]
+pub struct LockOptions {
+    /// Heartbeat age after which a lock counts as abandoned
+    ///
+    /// Keep this several times longer than the heartbeat interval.
+    pub stale_after: Duration,
+    /// How long to keep retrying while another live instance holds the lock
+    pub wait: Duration,
+    pub poll_interval: Duration,
+}
+
+impl Default for LockOptions {
+    fn default() -> Self {
+        Self {
+            stale_after: Duration::from_secs(30),
+            wait: Duration::ZERO,
+            poll_interval: Duration::from_millis(100),
+        }
+    }
+}
+
+/// Ownership of a shared cache directory
+///
+/// The lock file is removed when this is dropped.
+#[derive(Debug)]
+pub struct DiskCacheLock {
+    dir: PathBuf,
+    owner: CacheLockOwner,
+}
+
+impl DiskCacheLock {
+    /// Take ownership of a cache directory, creating it if needed
+    ///
+    /// Breaks a stale lock, and retries a live one until `options.wait` runs out.
+    pub fn acquire(
+        dir: impl AsRef<Path>,
+        instance: &CacheInstance,
+        options: &LockOptions,
+    ) -> Result<Self, CacheLockError> {
+        let dir = dir.as_ref();
+        fs::create_dir_all(dir).map_err(|e| io_error(dir, e))?;
+        let deadline = Instant::now() + options.wait;
+        loop {
+            match Self::try_acquire(dir, instance, options.stale_after) {
+                Err(CacheLockError::Held(_)) if Instant::now() < deadline => {
+                    std::thread::sleep(options.poll_interval);
+                }
+                result => return result,
+            }
+        }
+    }
+
+    /// Try to take ownership once without waiting
+    pub fn try_acquire(
+        dir: impl AsRef<Path>,
+        instance: &CacheInstance,
+        stale_after: Duration,
+    ) -> Result<Self, CacheLockError> {
+        let dir = dir.as_ref();
+        let path = dir.join(CACHE_LOCK_FILE);
+        let now = DateTime::now();
+        let owner = CacheLockOwner {
+            instance: instance.clone(),
+            acquired_at: now,
+            heartbeat: now,
+        };
+
+        match create_lock_file(&path, &owner) {
+            Ok(()) => return Ok(Self { dir: dir.to_path_buf(), owner }),
+            Err(e) if e.kind() == ErrorKind::AlreadyExists => {}
+            Err(e) => return Err(io_error(&path, e)),
+        }
+
+        let Some(current) = read_owner_settled(dir)? else {
+            // Still unreadable: a writer crashed mid-write, or it was released just now
+            return match fs::metadata(&path) {
+                Ok(meta) if meta.modified().ok().and_then(|t| t.elapsed().ok()).is_some_and(|age| age > stale_after) => {
+                    break_lock(&path, None).map_err(|e| io_error(&path, e))?;
+                    Self::try_acquire(dir, instance, stale_after)
+                }
+                Ok(_) => Err(CacheLockError::Corrupt(path)),
+                Err(e) if e.kind() == ErrorKind::NotFound => Self::try_acquire(dir, instance, stale_after),
+                Err(e) => Err(io_error(&path, e)),
+            };
+        };
+        if current.instance.instance_id == instance.instance_id {
+            return Err(CacheLockError::AlreadyOwned);
+        }
+        if !current.is_stale(now, stale_after) {
+            return Err(CacheLockError::Held(current));
+        }
+
+        match break_lock(&path, Some(&current)) {
+            Ok(true) => {}
+            // Someone else broke and re-took it first
+            Ok(false) => return Err(CacheLockError::Held(read_owner(dir)?.unwrap_or(current))),
+            Err(e) => return Err(io_error(&path, e)),
+        }
+        match create_lock_file(&path, &owner) {
+            Ok(()) => Ok(Self { dir: dir.to_path_buf(), owner }),
+            Err(e) if e.kind() == ErrorKind::AlreadyExists => {
+                Err(CacheLockError::Held(read_owner(dir)?.unwrap_or(current)))
+            }
+            Err(e) => Err(io_error(&path, e)),
+        }
+    }
+
+    /// The locked cache directory
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    /// The owner record this lock wrote
+    pub fn owner(&self) -> &CacheLockOwner {
+        &self.owner
+    }
+
+    /// Path of an entry inside the cache directory
+    pub fn entry_path(&self, name: &str) -> PathBuf {
+        self.dir.join(name)
+    }
+
+    /// Refresh the heartbeat so other instances don't treat the lock as stale
+    ///
+    /// Fails with `CacheLockError::Lost` if another instance broke the lock
+    /// (e.g. after this process was suspended); stop writing in that case.
+    ///
+    /// The lock file is renamed aside and checked before the new record is
+    /// linked in, so a lock re-taken by another instance is never overwritten.
+    /// An instance acquiring in that short window wins the lock instead.
+    pub fn heartbeat(&mut self) -> Result<(), CacheLockError> {
+        let mut owner = self.owner.clone();
+        owner.heartbeat = DateTime::now();
+        let path = self.dir.join(CACHE_LOCK_FILE);
+        let staged = side_path(&path, "heartbeat");
+        write_entry_atomic(&staged, owner.to_json().to_json_string().as_bytes())
+            .map_err(CacheLockError::Io)?;
+
+        let result = match move_aside(&path) {
+            Ok(Some((aside, Some(current)))) if current.same_acquisition(&self.owner) => {
+                match fs::hard_link(&staged, &path) {
+                    Ok(()) => {
+                        let _ = fs::remove_file(&aside);
+                        Ok(())
+                    }
+                    Err(e) if e.kind() == ErrorKind::AlreadyExists => {
+                        let _ = fs::remove_file(&aside);
+                        Err(CacheLockError::Lost(read_owner(&self.dir)?))
+                    }
+                    Err(e) => {
+                        restore_aside(&path, &aside);
+                        Err(io_error(&path, e))
+                    }
+                }
+            }
+            Ok(Some((aside, current))) => {
+                restore_aside(&path, &aside);
+                Err(CacheLockError::Lost(current))
+            }
+            Ok(None) => Err(CacheLockError::Lost(None)),
+            Err(e) => Err(io_error(&path, e)),
+        };
+        let _ = fs::remove_file(&staged);
+        result?;
+        self.owner = owner;
+        Ok(())
+    }
+
+    /// Check that the lock file still names this acquisition
+    pub fn check_owned(&self) -> Result<(), CacheLockError> {
+        match read_owner(&self.dir)? {
+            Some(current) if current.same_acquisition(&self.owner) => Ok(()),
+            current => Err(CacheLockError::Lost(current)),
+        }
+    }
+
+    /// Give up ownership
+    pub fn release(self) -> Result<(), CacheLockError> {
+        self.remove_if_owned()
+    }
+
+    fn remove_if_owned(&self) -> Result<(), CacheLockError> {
+        // Claim the file first so a lock re-taken by another instance survives
+        let path = self.dir.join(CACHE_LOCK_FILE);
+        match break_lock(&path, Some(&self.owner)) {
+            Ok(true) => Ok(()),
+            Ok(false) => Err(CacheLockError::Lost(read_owner(&self.dir)?)),
+            Err(e) => Err(io_error(&path, e)),
+        }
+    }
+}
+
+impl Drop for DiskCacheLock {
+    fn drop(&mut self) {
+        let _ = self.remove_if_owned();
+    }
+}
+
+/// Read the current owner of a cache directory (None if unlocked or unreadable)
+pub fn read_owner(dir: impl AsRef<Path>) -> Result<Option<CacheLockOwner>, CacheLockError> {
+    let path = dir.as_ref().join(CACHE_LOCK_FILE);
+    match fs::read_to_string(&path) {
+        Ok(text) => Ok(JsonValue::parse(&text).ok().and_then(|json| CacheLockOwner::from_json(&json))),
+        Err(e) if e.kind() == ErrorKind::NotFound => Ok(None),
+        Err(e) => Err(io_error(&path, e)),
+    }
+}
+
+/// Delays between re-reads of an unreadable lock record
+const UNREADABLE_RETRY_DELAYS: [Duration; 3] = [
+    Duration::from_millis(5),
+    Duration::from_millis(20),
+    Duration::from_millis(50),
+];
+
+/// Read the owner, giving a writer that is mid-write a moment to finish
+fn read_owner_settled(dir: &Path) -> Result<Option<CacheLockOwner>, CacheLockError> {
+    let path = dir.join(CACHE_LOCK_FILE);
+    let mut owner = read_owner(dir)?;
+    for delay in UNREADABLE_RETRY_DELAYS {
+        if owner.is_some() || !path.exists() {
+            break;
+        }
+        std::thread::sleep(delay);
+        owner = read_owner(dir)?;
+    }
+    Ok(owner)
+}
+
+/// Write a file so concurrent readers never see it half-written
+///
+/// Same as `file_io::atomic_write` without progress reporting.
+pub fn write_entry_atomic(path: impl AsRef<Path>, data: &[u8]) -> Result<(), String> {
+    crate::file_io::atomic_write(path, data, &crate::NoProgress, &crate::CancellationToken::new())
+        .map_err(String::from)
+}
+
+fn create_lock_file(path: &Path, owner: &CacheLockOwner) -> std::io::Result<()> {
+    let mut file = OpenOptions::new().write(true).create_new(true).open(path)?;
+    file.write_all(owner.to_json().to_json_string().as_bytes())?;
+    file.sync_all()
+}
+
+/// Remove a lock file that is believed stale
+///
+/// The file is first renamed aside so only one instance can take it. If the
+/// renamed record is not the one judged stale, another instance re-took the
+/// lock in between; it is put back and `false` is returned.
+fn break_lock(path: &Path, stale: Option<&CacheLockOwner>) -> std::io::Result<bool> {
+    let Some((aside, taken)) = move_aside(path)? else {
+        return Ok(true);
+    };
+    let expected = match (stale, &taken) {
+        (Some(stale), Some(taken)) => stale.same_acquisition(taken),
+        (None, None) => true,
+        _ => false,
+    };
+    if !expected {
+        restore_aside(path, &aside);
+        return Ok(false);
+    }
+    fs::remove_file(&aside)?;
+    Ok(true)
+}
+
+/// Atomically take the lock file out of place and read the record it held
+///
+/// Returns None if there was no lock file.
+fn move_aside(path: &Path) -> std::io::Result<Option<(PathBuf, Option<CacheLockOwner>)>> {
+    let aside = side_path(path, "broken");
+    match fs::rename(path, &aside) {
+        Ok(()) => {}
+        Err(e) if e.kind() == ErrorKind::NotFound => return Ok(None),
+        Err(e) => return Err(e),
+    }
+    let taken = fs::read_to_string(&aside)
+        .ok()
+        .and_then(|text| JsonValue::parse(&text).ok())
+        .and_then(|json| CacheLockOwner::from_json(&json));
+    Ok(Some((aside, taken)))
+}
+
+/// Put a file taken by `move_aside` back, unless yet another lock appeared meanwhile
+fn restore_aside(path: &Path, aside: &Path) {
+    if fs::hard_link(aside, path).is_ok() {
+        let _ = fs::remove_file(aside);
+    }
+}
+
+fn side_path(path: &Path, tag: &str) -> PathBuf {
+    path.with_file_name(format!(
+        "{}.{}.{}.{}",
+        CACHE_LOCK_FILE,
+        tag,
+        std::process::id(),
+        DateTime::now().unix_nanos()
+    ))
+}
+
+fn io_error(path: &Path, error: std::io::Error) -> CacheLockError {
+    CacheLockError::Io(format!("{}: {}", path.display(), error))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn temp_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("nodle-disk-cache-{}-{}", name, std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        dir
+    }
+
+    #[test]
+    fn heartbeat_and_drop_leave_a_retaken_lock_alone() {
+        let dir = temp_dir("retaken");
+        let mut lock = DiskCacheLock::acquire(&dir, &CacheInstance::current("a"), &LockOptions::default()).unwrap();
+        lock.heartbeat().unwrap();
+        assert_eq!(read_owner(&dir).unwrap().as_ref(), Some(lock.owner()));
+
+        // Another instance breaks and re-takes the lock behind our back
+        fs::remove_file(dir.join(CACHE_LOCK_FILE)).unwrap();
+        let other = DiskCacheLock::try_acquire(&dir, &CacheInstance::current("b"), Duration::from_secs(30)).unwrap();
+        assert!(matches!(lock.heartbeat(), Err(CacheLockError::Lost(Some(_)))));
+        drop(lock);
+        assert_eq!(read_owner(&dir).unwrap().as_ref(), Some(other.owner()));
+
+        drop(other);
+        assert_eq!(read_owner(&dir).unwrap(), None);
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn fresh_unreadable_lock_is_corrupt_after_retrying() {
+        let dir = temp_dir("corrupt");
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(dir.join(CACHE_LOCK_FILE), "{\"instance_id\"").unwrap();
+        let result = DiskCacheLock::try_acquire(&dir, &CacheInstance::current("a"), Duration::from_secs(30));
+        assert!(matches!(result, Err(CacheLockError::Corrupt(_))));
+        let _ = fs::remove_dir_all(&dir);
+    }
+}
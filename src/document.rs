@@ -0,0 +1,150 @@
+//! Undoable document edits from plugins
+//!
+//! "Auto-setup" tools in a node's parameter panel often need to touch more than
+//! the node itself: set parameters on other nodes, wire connections. The host
+//! passes a `DocumentEditApi` to `PluginNode::handle_ui_action_with_document`,
+//! and the plugin submits an `EditTransaction`. The host applies the whole
+//! transaction atomically and records it as a single labelled step in its undo
+//! stack, exactly like a native operation.
+//!
+//! ```rust,ignore
+//! fn handle_ui_action_with_document(
+//!     &mut self,
+//!     action: UIAction,
+//!     document: &mut dyn DocumentEditApi,
+//! ) -> Vec<ParameterChange> {
+//!     if let UIAction::ButtonClicked { action } = &action {
+//!         if action == "auto_setup" {
+//!             let Some(id) = self.node_id() else { return Vec::new() };
+//!             let edit = document
+//!                 .find_nodes("Light")
+//!                 .into_iter()
+//!                 .fold(EditTransaction::new("Link Lights"), |edit, light| {
+//!                     edit.connect(light, "Light", id, "Lights")
+//!                 });
+//!             let _ = document.apply(edit);
+//!         }
+//!     }
+//!     Vec::new()
+//! }
+//! ```
+
+use crate::{NodeData, NodeId, PluginError};
+
+/// A single change to the node graph document
+#[derive(Debug, Clone)]
+#[allow(clippy::large_enum_variant)]
+pub enum DocumentEdit {
+    /// Set a parameter on any node
+    SetParameter {
+        node: NodeId,
+        parameter: String,
+        value: NodeData,
+    },
+    /// Connect an output port to an input port (replacing an existing input connection)
+    Connect {
+        from_node: NodeId,
+        from_port: String,
+        to_node: NodeId,
+        to_port: String,
+    },
+    /// Remove a connection
+    Disconnect {
+        from_node: NodeId,
+        from_port: String,
+        to_node: NodeId,
+        to_port: String,
+    },
+}
+
+/// A labelled group of edits applied as one undo step
+#[derive(Debug, Clone)]
+pub struct EditTransaction {
+    /// Label shown in the host's undo history (e.g. "Auto Setup Lights")
+    pub label: String,
+    pub edits: Vec<DocumentEdit>,
+}
+
+impl EditTransaction {
+    /// Start an empty transaction
+    pub fn new(label: impl Into<String>) -> Self {
+        Self {
+            label: label.into(),
+            edits: Vec::new(),
+        }
+    }
+
+    /// Add a parameter change
+    pub fn set_parameter(mut self, node: NodeId, parameter: impl Into<String>, value: NodeData) -> Self {
+        self.edits.push(DocumentEdit::SetParameter {
+            node,
+            parameter: parameter.into(),
+            value,
+        });
+        self
+    }
+
+    /// Add a connection
+    pub fn connect(
+        mut self,
+        from_node: NodeId,
+        from_port: impl Into<String>,
+        to_node: NodeId,
+        to_port: impl Into<String>,
+    ) -> Self {
+        self.edits.push(DocumentEdit::Connect {
+            from_node,
+            from_port: from_port.into(),
+            to_node,
+            to_port: to_port.into(),
+        });
+        self
+    }
+
+    /// Remove a connection
+    pub fn disconnect(
+        mut self,
+        from_node: NodeId,
+        from_port: impl Into<String>,
+        to_node: NodeId,
+        to_port: impl Into<String>,
+    ) -> Self {
+        self.edits.push(DocumentEdit::Disconnect {
+            from_node,
+            from_port: from_port.into(),
+            to_node,
+            to_port: to_port.into(),
+        });
+        self
+    }
+
+    /// Check if the transaction contains no edits
+    pub fn is_empty(&self) -> bool {
+        self.edits.is_empty()
+    }
+}
+
+/// Host-provided access to the node graph document
+///
+/// The host implements this trait and hands it to plugins while handling UI
+/// actions. It must not be stored beyond the call.
+pub trait DocumentEditApi {
+    /// Apply a transaction as a single undoable action
+    ///
+    /// Either every edit is applied or none is (e.g. on an unknown node, port
+    /// or a type-incompatible connection). Empty transactions are ignored and
+    /// do not create an undo step.
+    fn apply(&mut self, transaction: EditTransaction) -> Result<(), PluginError>;
+
+    /// Read a parameter of any node
+    fn get_parameter(&self, node: NodeId, parameter: &str) -> Option<NodeData>;
+
+    /// Get the type ID of a node (as registered in `NodeMetadata::node_type`)
+    fn node_type(&self, node: NodeId) -> Option<String>;
+
+    /// Find all nodes of a type
+    fn find_nodes(&self, node_type: &str) -> Vec<NodeId>;
+
+    /// Get the nodes and output ports connected to an input port
+    fn input_connections(&self, node: NodeId, port: &str) -> Vec<(NodeId, String)>;
+}
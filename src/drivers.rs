@@ -0,0 +1,295 @@
+//! Parameter drivers
+//!
+//! A driver computes one parameter from other parameters and/or time. Plugins
+//! register drivers for their nodes, and the host evaluates them before cooking.
+//! Dependencies are recorded automatically from the parameters each driver reads,
+//! so the host knows which drivers to re-run when a parameter changes.
+
+use crate::{NodeData, ParameterChange, ValidationError};
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::sync::Arc;
+
+/// Driver function signature
+pub type DriverFn = Arc<dyn Fn(&DriverContext) -> Option<NodeData> + Send + Sync>;
+
+/// Evaluation context passed to driver functions
+pub struct DriverContext<'a> {
+    parameters: &'a HashMap<String, NodeData>,
+    time: f32,
+    frame: i32,
+    accessed: RefCell<Vec<String>>,
+    used_time: RefCell<bool>,
+}
+
+impl<'a> DriverContext<'a> {
+    /// Create a context over the current parameter values
+    pub fn new(parameters: &'a HashMap<String, NodeData>, time: f32, frame: i32) -> Self {
+        Self {
+            parameters,
+            time,
+            frame,
+            accessed: RefCell::new(Vec::new()),
+            used_time: RefCell::new(false),
+        }
+    }
+
+    /// Read another parameter (recorded as a dependency)
+    pub fn param(&self, name: &str) -> Option<&'a NodeData> {
+        let mut accessed = self.accessed.borrow_mut();
+        if !accessed.iter().any(|n| n == name) {
+            accessed.push(name.to_string());
+        }
+        self.parameters.get(name)
+    }
+
+    /// Current time in seconds (marks the driver as time-dependent)
+    pub fn time(&self) -> f32 {
+        *self.used_time.borrow_mut() = true;
+        self.time
+    }
+
+    /// Current frame (marks the driver as time-dependent)
+    pub fn frame(&self) -> i32 {
+        *self.used_time.borrow_mut() = true;
+        self.frame
+    }
+}
+
+/// A registered driver for one parameter
+#[derive(Clone)]
+pub struct ParameterDriver {
+    /// Parameter written by this driver
+    pub target: String,
+    /// Parameters read during the last evaluation
+    pub dependencies: Vec<String>,
+    /// Whether the last evaluation read the time or frame
+    pub time_dependent: bool,
+    function: DriverFn,
+}
+
+impl std::fmt::Debug for ParameterDriver {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ParameterDriver")
+            .field("target", &self.target)
+            .field("dependencies", &self.dependencies)
+            .field("time_dependent", &self.time_dependent)
+            .finish()
+    }
+}
+
+/// Collection of drivers for a node
+///
+/// ```rust,ignore
+/// drivers.register_driver("radius", |ctx| {
+///     Some(NodeData::Float(ctx.param("diameter")?.as_float()? * 0.5))
+/// });
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct DriverRegistry {
+    drivers: Vec<ParameterDriver>,
+}
+
+impl DriverRegistry {
+    /// Create an empty registry
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Register a driver for a parameter (replaces any existing driver for it)
+    pub fn register_driver<F>(&mut self, target: impl Into<String>, function: F)
+    where
+        F: Fn(&DriverContext) -> Option<NodeData> + Send + Sync + 'static,
+    {
+        let target = target.into();
+        self.drivers.retain(|d| d.target != target);
+        self.drivers.push(ParameterDriver {
+            target,
+            dependencies: Vec::new(),
+            time_dependent: false,
+            function: Arc::new(function),
+        });
+    }
+
+    /// Remove the driver for a parameter
+    pub fn unregister_driver(&mut self, target: &str) -> bool {
+        let before = self.drivers.len();
+        self.drivers.retain(|d| d.target != target);
+        self.drivers.len() != before
+    }
+
+    /// Check if a parameter is driven
+    pub fn is_driven(&self, parameter: &str) -> bool {
+        self.drivers.iter().any(|d| d.target == parameter)
+    }
+
+    /// Get all registered drivers
+    pub fn drivers(&self) -> &[ParameterDriver] {
+        &self.drivers
+    }
+
+    /// Evaluate all drivers in dependency order
+    ///
+    /// A driver runs after the drivers of every parameter it reads, so it sees
+    /// their results; independent drivers keep their registration order.
+    /// Dependencies are only known once a driver has run, so evaluation repeats
+    /// until the order settles. Returns the parameter changes the host should
+    /// apply; drivers returning `None` leave their target untouched.
+    ///
+    /// Fails without changes if drivers read each other's targets in a cycle.
+    pub fn evaluate(
+        &mut self,
+        parameters: &HashMap<String, NodeData>,
+        time: f32,
+        frame: i32,
+    ) -> Result<Vec<ParameterChange>, ValidationError> {
+        let mut order = self.evaluation_order()?;
+
+        // Each pass can only reveal new dependencies, so this settles quickly
+        for _ in 0..=self.drivers.len() {
+            let changes = self.evaluate_in(&order, parameters, time, frame);
+            let next = self.evaluation_order()?;
+            if next == order {
+                return Ok(changes);
+            }
+            order = next;
+        }
+
+        Ok(self.evaluate_in(&order, parameters, time, frame))
+    }
+
+    fn evaluate_in(
+        &mut self,
+        order: &[usize],
+        parameters: &HashMap<String, NodeData>,
+        time: f32,
+        frame: i32,
+    ) -> Vec<ParameterChange> {
+        let mut working = parameters.clone();
+        let mut changes = Vec::new();
+
+        for &index in order {
+            let driver = &mut self.drivers[index];
+            let ctx = DriverContext::new(&working, time, frame);
+            let result = (driver.function)(&ctx);
+            driver.dependencies = ctx.accessed.into_inner();
+            driver.time_dependent = ctx.used_time.into_inner();
+
+            if let Some(value) = result {
+                working.insert(driver.target.clone(), value.clone());
+                changes.push(ParameterChange {
+                    parameter: driver.target.clone(),
+                    value,
+                });
+            }
+        }
+
+        changes
+    }
+
+    /// Order drivers so each runs after the drivers it read from last time
+    fn evaluation_order(&self) -> Result<Vec<usize>, ValidationError> {
+        let count = self.drivers.len();
+        // upstream[i] = drivers whose targets driver i reads (excluding itself)
+        let upstream: Vec<Vec<usize>> = self
+            .drivers
+            .iter()
+            .enumerate()
+            .map(|(i, driver)| {
+                (0..count)
+                    .filter(|&j| j != i && driver.dependencies.contains(&self.drivers[j].target))
+                    .collect()
+            })
+            .collect();
+
+        let mut done = vec![false; count];
+        let mut order = Vec::with_capacity(count);
+        while order.len() < count {
+            let Some(next) = (0..count).find(|&i| !done[i] && upstream[i].iter().all(|&j| done[j])) else {
+                let mut cycle: Vec<&str> = (0..count)
+                    .filter(|&i| !done[i])
+                    .map(|i| self.drivers[i].target.as_str())
+                    .collect();
+                cycle.sort_unstable();
+                return Err(ValidationError::InvalidValue(format!(
+                    "driver dependency cycle between {}",
+                    cycle.join(", ")
+                )));
+            };
+            done[next] = true;
+            order.push(next);
+        }
+        Ok(order)
+    }
+
+    /// Get the parameters whose drivers read `parameter` (for invalidation)
+    pub fn dependents_of(&self, parameter: &str) -> Vec<&str> {
+        self.drivers
+            .iter()
+            .filter(|d| d.dependencies.iter().any(|dep| dep == parameter))
+            .map(|d| d.target.as_str())
+            .collect()
+    }
+
+    /// Check if any driver depends on time (needs re-evaluation on frame change)
+    pub fn is_time_dependent(&self) -> bool {
+        self.drivers.iter().any(|d| d.time_dependent)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn float(parameters: &HashMap<String, NodeData>, changes: &[ParameterChange], name: &str) -> Option<f32> {
+        changes
+            .iter()
+            .rev()
+            .find(|c| c.parameter == name)
+            .map(|c| &c.value)
+            .or_else(|| parameters.get(name))
+            .and_then(|v| v.as_float())
+    }
+
+    #[test]
+    fn drivers_run_after_the_drivers_they_read() {
+        let mut drivers = DriverRegistry::new();
+        // Registered before the driver of "diameter" that it reads
+        drivers.register_driver("radius", |ctx| Some(NodeData::Float(ctx.param("diameter")?.as_float()? * 0.5)));
+        drivers.register_driver("diameter", |ctx| Some(NodeData::Float(ctx.param("size")?.as_float()? * 2.0)));
+
+        let parameters = HashMap::from([
+            ("size".to_string(), NodeData::Float(3.0)),
+            ("diameter".to_string(), NodeData::Float(1.0)),
+        ]);
+        let changes = drivers.evaluate(&parameters, 0.0, 0).unwrap();
+        assert_eq!(float(&parameters, &changes, "diameter"), Some(6.0));
+        assert_eq!(float(&parameters, &changes, "radius"), Some(3.0));
+        assert_eq!(drivers.dependents_of("diameter"), vec!["radius"]);
+    }
+
+    #[test]
+    fn dependency_cycles_are_rejected() {
+        let mut drivers = DriverRegistry::new();
+        drivers.register_driver("a", |ctx| ctx.param("b").cloned());
+        drivers.register_driver("b", |ctx| ctx.param("a").cloned());
+        drivers.register_driver("c", |_| Some(NodeData::Float(1.0)));
+
+        let parameters = HashMap::from([
+            ("a".to_string(), NodeData::Float(1.0)),
+            ("b".to_string(), NodeData::Float(2.0)),
+        ]);
+        let error = drivers.evaluate(&parameters, 0.0, 0).unwrap_err();
+        assert_eq!(error, ValidationError::InvalidValue("driver dependency cycle between a, b".to_string()));
+    }
+
+    #[test]
+    fn drivers_may_read_their_own_target() {
+        let mut drivers = DriverRegistry::new();
+        drivers.register_driver("x", |ctx| Some(NodeData::Float(ctx.param("x")?.as_float()? + 1.0)));
+
+        let parameters = HashMap::from([("x".to_string(), NodeData::Float(1.0))]);
+        let changes = drivers.evaluate(&parameters, 0.0, 0).unwrap();
+        assert_eq!(float(&parameters, &changes, "x"), Some(2.0));
+    }
+}
@@ -1,5 +1,8 @@
 //! Plugin system errors
 
+use crate::{CacheLockOwner, NodeId};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 use std::fmt;
 
 /// Errors that can occur in the plugin system
@@ -29,4 +32,314 @@ impl fmt::Display for PluginError {
     }
 }
 
-impl std::error::Error for PluginError {}
\ No newline at end of file
+impl std::error::Error for PluginError {}
+
+/// Errors found while validating data before it reaches the host
+#[derive(Debug, Clone, PartialEq)]
+pub enum ValidationError {
+    /// A required field was not provided
+    MissingField(String),
+    /// An attribute array does not match the expected length
+    LengthMismatch {
+        field: String,
+        expected: usize,
+        actual: usize,
+    },
+    /// An index refers past the end of the vertex array
+    IndexOutOfBounds { index: u32, vertex_count: usize },
+    /// Index count is not a multiple of three
+    InvalidIndexCount(usize),
+    /// A value is out of range or otherwise invalid
+    InvalidValue(String),
+}
+
+impl fmt::Display for ValidationError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ValidationError::MissingField(field) => write!(f, "Missing required field: {}", field),
+            ValidationError::LengthMismatch { field, expected, actual } => {
+                write!(f, "Length mismatch for {}: expected {}, got {}", field, expected, actual)
+            }
+            ValidationError::IndexOutOfBounds { index, vertex_count } => {
+                write!(f, "Index {} out of bounds for {} vertices", index, vertex_count)
+            }
+            ValidationError::InvalidIndexCount(count) => {
+                write!(f, "Index count {} is not a multiple of 3", count)
+            }
+            ValidationError::InvalidValue(msg) => write!(f, "Invalid value: {}", msg),
+        }
+    }
+}
+
+impl std::error::Error for ValidationError {}
+
+/// Errors found while validating geometry
+#[derive(Debug, Clone, PartialEq)]
+pub enum GeometryError {
+    /// An index refers past the end of the vertex array
+    IndexOutOfBounds { index: u32, vertex_count: usize },
+    /// Triangle index count is not a multiple of three
+    InvalidIndexCount(usize),
+    /// Face vertex counts do not add up to the number of indices
+    FaceCountMismatch { expected: usize, actual: usize },
+    /// A face has fewer than three vertices
+    DegenerateFace(usize),
+    /// An attribute array does not match the expected length
+    AttributeLengthMismatch {
+        attribute: String,
+        expected: usize,
+        actual: usize,
+    },
+    /// A vertex position is NaN or infinite
+    NonFiniteVertex(usize),
+    /// A required attribute is missing
+    MissingAttribute(String),
+}
+
+impl fmt::Display for GeometryError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            GeometryError::IndexOutOfBounds { index, vertex_count } => {
+                write!(f, "Index {} out of bounds for {} vertices", index, vertex_count)
+            }
+            GeometryError::InvalidIndexCount(count) => {
+                write!(f, "Index count {} is not a multiple of 3", count)
+            }
+            GeometryError::FaceCountMismatch { expected, actual } => {
+                write!(f, "Face vertex counts sum to {} but there are {} indices", expected, actual)
+            }
+            GeometryError::DegenerateFace(face) => write!(f, "Face {} has fewer than 3 vertices", face),
+            GeometryError::AttributeLengthMismatch { attribute, expected, actual } => {
+                write!(f, "Attribute {} has {} values, expected {}", attribute, actual, expected)
+            }
+            GeometryError::NonFiniteVertex(vertex) => write!(f, "Vertex {} is not finite", vertex),
+            GeometryError::MissingAttribute(attribute) => write!(f, "Missing attribute: {}", attribute),
+        }
+    }
+}
+
+impl std::error::Error for GeometryError {}
+
+/// A `NodeData` value did not have the requested type
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ConversionError {
+    /// Type that was requested
+    pub expected: &'static str,
+    /// Variant that was found
+    pub found: &'static str,
+}
+
+impl fmt::Display for ConversionError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Expected {}, found {}", self.expected, self.found)
+    }
+}
+
+impl std::error::Error for ConversionError {}
+
+/// Errors reading typed values from a node's input map
+#[derive(Debug, Clone, PartialEq)]
+pub enum InputError {
+    /// The input port has no value
+    Missing(String),
+    /// The input port has a value of the wrong type
+    WrongType {
+        port: String,
+        expected: &'static str,
+        found: &'static str,
+    },
+    /// The input carries an error from an upstream node
+    Upstream(NodeErrorValue),
+}
+
+impl fmt::Display for InputError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            InputError::Missing(port) => write!(f, "Missing input '{}'", port),
+            InputError::WrongType { port, expected, found } => {
+                write!(f, "Input '{}' expected {}, found {}", port, expected, found)
+            }
+            InputError::Upstream(error) => write!(f, "Upstream error: {}", error),
+        }
+    }
+}
+
+impl std::error::Error for InputError {}
+
+impl From<InputError> for String {
+    fn from(error: InputError) -> Self {
+        error.to_string()
+    }
+}
+
+/// A cook went past one of its node's `ExecutionLimits`
+#[derive(Debug, Clone, PartialEq)]
+pub enum LimitExceeded {
+    CookTime {
+        elapsed: std::time::Duration,
+        limit: std::time::Duration,
+    },
+    Memory { used: u64, limit: u64 },
+}
+
+impl fmt::Display for LimitExceeded {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            LimitExceeded::CookTime { elapsed, limit } => write!(
+                f,
+                "Cook time limit exceeded: {:.1}s of {:.1}s",
+                elapsed.as_secs_f64(),
+                limit.as_secs_f64()
+            ),
+            LimitExceeded::Memory { used, limit } => {
+                write!(f, "Memory limit exceeded: {} of {} bytes", used, limit)
+            }
+        }
+    }
+}
+
+impl std::error::Error for LimitExceeded {}
+
+impl From<LimitExceeded> for String {
+    fn from(error: LimitExceeded) -> Self {
+        error.to_string()
+    }
+}
+
+/// Errors from the `file_io` helpers
+#[derive(Debug, Clone, PartialEq)]
+pub enum FileIoError {
+    /// The operation's `CancellationToken` was cancelled
+    Cancelled,
+    /// The file's checksum did not match the expected value
+    ChecksumMismatch { expected: u64, actual: u64 },
+    /// Filesystem error
+    Io(String),
+}
+
+impl fmt::Display for FileIoError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            FileIoError::Cancelled => write!(f, "Cancelled"),
+            FileIoError::ChecksumMismatch { expected, actual } => {
+                write!(f, "Checksum mismatch: expected {:016x}, got {:016x}", expected, actual)
+            }
+            FileIoError::Io(msg) => write!(f, "I/O error: {}", msg),
+        }
+    }
+}
+
+impl std::error::Error for FileIoError {}
+
+impl From<FileIoError> for String {
+    fn from(error: FileIoError) -> Self {
+        error.to_string()
+    }
+}
+
+/// Errors taking or keeping ownership of a shared disk cache
+#[derive(Debug, Clone, PartialEq)]
+pub enum CacheLockError {
+    /// Another live instance owns the cache
+    Held(CacheLockOwner),
+    /// This instance already holds the lock
+    AlreadyOwned,
+    /// The lock was broken by another instance (its current owner, if any)
+    Lost(Option<CacheLockOwner>),
+    /// The lock file exists but cannot be parsed and is not old enough to break
+    Corrupt(std::path::PathBuf),
+    /// Filesystem error
+    Io(String),
+}
+
+impl fmt::Display for CacheLockError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CacheLockError::Held(owner) => write!(
+                f,
+                "Cache is owned by instance {} on {} (pid {})",
+                owner.instance.instance_id, owner.instance.host, owner.instance.pid
+            ),
+            CacheLockError::AlreadyOwned => write!(f, "Cache is already owned by this instance"),
+            CacheLockError::Lost(Some(owner)) => write!(
+                f,
+                "Cache lock was taken over by instance {} on {}",
+                owner.instance.instance_id, owner.instance.host
+            ),
+            CacheLockError::Lost(None) => write!(f, "Cache lock was removed by another instance"),
+            CacheLockError::Corrupt(path) => write!(f, "Unreadable cache lock file: {}", path.display()),
+            CacheLockError::Io(msg) => write!(f, "Cache lock I/O error: {}", msg),
+        }
+    }
+}
+
+impl std::error::Error for CacheLockError {}
+
+/// Severity of an error flowing through the graph
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub enum ErrorSeverity {
+    /// Output is usable but suspect
+    Warning,
+    /// Node failed to produce valid output
+    #[default]
+    Error,
+    /// Evaluation of the whole graph should stop
+    Fatal,
+}
+
+/// Error value carried by `NodeData::Error`
+/// 
+/// When a node fails it outputs this instead of empty data, so downstream nodes
+/// can pass it along and viewer nodes can display where the failure came from.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct NodeErrorValue {
+    /// Human-readable message
+    pub message: String,
+    /// Node where the error originated (None if unknown)
+    pub source_node: Option<NodeId>,
+    /// How serious the error is
+    #[serde(default)]
+    pub severity: ErrorSeverity,
+}
+
+impl NodeErrorValue {
+    /// Create an error with `Error` severity
+    pub fn new(message: impl Into<String>) -> Self {
+        Self {
+            message: message.into(),
+            source_node: None,
+            severity: ErrorSeverity::Error,
+        }
+    }
+    
+    /// Set the node the error originated from
+    pub fn from_node(mut self, node_id: NodeId) -> Self {
+        self.source_node = Some(node_id);
+        self
+    }
+    
+    /// Set the severity
+    pub fn with_severity(mut self, severity: ErrorSeverity) -> Self {
+        self.severity = severity;
+        self
+    }
+    
+    /// Find the most severe error among a node's inputs, for propagation
+    /// 
+    /// Nodes typically return this error on all outputs instead of processing.
+    pub fn from_inputs(inputs: &HashMap<String, crate::NodeData>) -> Option<&NodeErrorValue> {
+        inputs
+            .values()
+            .filter_map(|data| data.as_error())
+            .max_by_key(|error| error.severity)
+    }
+}
+
+impl fmt::Display for NodeErrorValue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.source_node {
+            Some(node) => write!(f, "{:?} from node {}: {}", self.severity, node, self.message),
+            None => write!(f, "{:?}: {}", self.severity, self.message),
+        }
+    }
+}
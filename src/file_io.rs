@@ -0,0 +1,143 @@
+//! File IO with progress, cancellation and checksums
+//!
+//! Reader and writer plugins (USD, images, caches) should use these instead of
+//! `std::fs::read`/`write` for anything large, so every plugin reports progress
+//! and honors cancellation the same way:
+//!
+//! ```rust,ignore
+//! let bytes = read_file_with_progress(&path, &ctx.progress, &ctx.cancellation)?;
+//! ```
+//!
+//! Checksums are FNV-1a 64 (`crate::hashing`), meant for detecting corrupt or
+//! truncated files, not for security.
+
+use crate::hashing::ContentHasher;
+use crate::{CancellationToken, FileIoError, ProgressReporter};
+use std::fs::{self, File};
+use std::io::{Read, Write};
+use std::path::Path;
+
+/// Size of the chunks files are read and written in
+pub const IO_CHUNK_SIZE: usize = 1 << 20;
+
+/// Read a whole file, reporting progress and checking for cancellation per chunk
+pub fn read_file_with_progress(
+    path: impl AsRef<Path>,
+    progress: &dyn ProgressReporter,
+    cancel: &CancellationToken,
+) -> Result<Vec<u8>, FileIoError> {
+    read_file_with_checksum(path, progress, cancel).map(|(data, _)| data)
+}
+
+/// Read a whole file and compute its checksum in the same pass
+pub fn read_file_with_checksum(
+    path: impl AsRef<Path>,
+    progress: &dyn ProgressReporter,
+    cancel: &CancellationToken,
+) -> Result<(Vec<u8>, u64), FileIoError> {
+    let path = path.as_ref();
+    let mut file = File::open(path).map_err(|e| io_error(path, e))?;
+    let total = file.metadata().map(|m| m.len()).unwrap_or(0);
+    let message = format!("Reading {}", path.display());
+    let mut data = Vec::with_capacity(total as usize);
+    let mut hasher = ContentHasher::new();
+    let mut chunk = vec![0u8; IO_CHUNK_SIZE];
+
+    progress.report(0.0, &message);
+    loop {
+        if cancel.is_cancelled() {
+            return Err(FileIoError::Cancelled);
+        }
+        let read = file.read(&mut chunk).map_err(|e| io_error(path, e))?;
+        if read == 0 {
+            break;
+        }
+        hasher.write(&chunk[..read]);
+        data.extend_from_slice(&chunk[..read]);
+        if total > 0 {
+            progress.report((data.len() as f64 / total as f64).min(1.0) as f32, &message);
+        }
+    }
+    progress.report(1.0, &message);
+    Ok((data, hasher.finish()))
+}
+
+/// Read a whole file and fail if its checksum differs from `expected`
+pub fn read_file_verified(
+    path: impl AsRef<Path>,
+    expected: u64,
+    progress: &dyn ProgressReporter,
+    cancel: &CancellationToken,
+) -> Result<Vec<u8>, FileIoError> {
+    let (data, actual) = read_file_with_checksum(path, progress, cancel)?;
+    if actual != expected {
+        return Err(FileIoError::ChecksumMismatch { expected, actual });
+    }
+    Ok(data)
+}
+
+/// Checksum of in-memory data, matching `read_file_with_checksum`
+pub fn checksum(data: &[u8]) -> u64 {
+    let mut hasher = ContentHasher::new();
+    hasher.write(data);
+    hasher.finish()
+}
+
+/// Write a file so readers never see it half-written
+///
+/// The data goes to a uniquely named temporary file in the same directory,
+/// which is synced and then renamed over `path`. On error or cancellation the
+/// temporary file is removed and `path` is left untouched.
+pub fn atomic_write(
+    path: impl AsRef<Path>,
+    data: &[u8],
+    progress: &dyn ProgressReporter,
+    cancel: &CancellationToken,
+) -> Result<(), FileIoError> {
+    let path = path.as_ref();
+    let name = path
+        .file_name()
+        .and_then(|name| name.to_str())
+        .ok_or_else(|| FileIoError::Io(format!("Invalid file path: {}", path.display())))?;
+    let temp = path.with_file_name(format!(
+        ".{}.{}.{}.tmp",
+        name,
+        std::process::id(),
+        crate::DateTime::now().unix_nanos()
+    ));
+
+    let result = write_chunks(&temp, data, progress, cancel, path)
+        .and_then(|()| fs::rename(&temp, path).map_err(|e| io_error(path, e)));
+    if result.is_err() {
+        let _ = fs::remove_file(&temp);
+    }
+    result
+}
+
+fn write_chunks(
+    temp: &Path,
+    data: &[u8],
+    progress: &dyn ProgressReporter,
+    cancel: &CancellationToken,
+    target: &Path,
+) -> Result<(), FileIoError> {
+    let message = format!("Writing {}", target.display());
+    let mut file = File::create(temp).map_err(|e| io_error(temp, e))?;
+    let mut written = 0;
+    progress.report(0.0, &message);
+    for chunk in data.chunks(IO_CHUNK_SIZE) {
+        if cancel.is_cancelled() {
+            return Err(FileIoError::Cancelled);
+        }
+        file.write_all(chunk).map_err(|e| io_error(temp, e))?;
+        written += chunk.len();
+        progress.report(written as f32 / data.len() as f32, &message);
+    }
+    file.sync_all().map_err(|e| io_error(temp, e))?;
+    progress.report(1.0, &message);
+    Ok(())
+}
+
+fn io_error(path: &Path, error: std::io::Error) -> FileIoError {
+    FileIoError::Io(format!("{}: {}", path.display(), error))
+}
@@ -0,0 +1,213 @@
+//! Progressive frame-buffer tiles
+//!
+//! Renderer plugins stream `TileData` through `TileSink::on_tile`. Each tile
+//! holds the running average of `sample_count` samples for its region, so tiles
+//! from any renderer can be accumulated into a `FrameBuffer` and displayed with
+//! the same code.
+
+use crate::ImageData;
+
+/// Rectangular pixel region of a frame
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct TileRegion {
+    /// Left edge in pixels
+    pub x: u32,
+    /// Top edge in pixels
+    pub y: u32,
+    pub width: u32,
+    pub height: u32,
+}
+
+impl TileRegion {
+    /// Create a region
+    pub fn new(x: u32, y: u32, width: u32, height: u32) -> Self {
+        Self { x, y, width, height }
+    }
+
+    /// Number of pixels in the region
+    pub fn pixel_count(&self) -> usize {
+        self.width as usize * self.height as usize
+    }
+
+    /// Check if a frame pixel lies inside the region
+    pub fn contains(&self, x: u32, y: u32) -> bool {
+        x >= self.x && y >= self.y && x - self.x < self.width && y - self.y < self.height
+    }
+
+    /// Split a frame into tiles of at most `tile_size` pixels, row by row
+    pub fn grid(width: u32, height: u32, tile_size: u32) -> Vec<TileRegion> {
+        let tile_size = tile_size.max(1);
+        let mut regions = Vec::new();
+        for y in (0..height).step_by(tile_size as usize) {
+            for x in (0..width).step_by(tile_size as usize) {
+                regions.push(TileRegion::new(x, y, tile_size.min(width - x), tile_size.min(height - y)));
+            }
+        }
+        regions
+    }
+}
+
+/// A block of rendered pixels for one AOV
+#[derive(Debug, Clone)]
+pub struct TileData {
+    /// AOV the pixels belong to
+    pub aov: String,
+    /// Frame region covered by the tile
+    pub region: TileRegion,
+    /// Channels per pixel
+    pub channels: u32,
+    /// Row-major pixel values, `region.pixel_count() * channels` long
+    ///
+    /// Values are the average of `sample_count` samples.
+    pub pixels: Vec<f32>,
+    /// Samples per pixel averaged into `pixels`
+    pub sample_count: u32,
+}
+
+impl TileData {
+    /// Create a zeroed tile with no samples
+    pub fn new(aov: impl Into<String>, region: TileRegion, channels: u32) -> Self {
+        Self {
+            aov: aov.into(),
+            region,
+            channels,
+            pixels: vec![0.0; region.pixel_count() * channels as usize],
+            sample_count: 0,
+        }
+    }
+
+    /// Check that the pixel array matches the region and channel count
+    pub fn is_valid(&self) -> bool {
+        self.pixels.len() == self.region.pixel_count() * self.channels as usize
+    }
+
+    /// Get the channels of a pixel in tile-local coordinates
+    pub fn pixel(&self, x: u32, y: u32) -> Option<&[f32]> {
+        if x >= self.region.width || y >= self.region.height {
+            return None;
+        }
+        let channels = self.channels as usize;
+        let start = (y as usize * self.region.width as usize + x as usize) * channels;
+        self.pixels.get(start..start + channels)
+    }
+
+    /// Merge another pass over the same region, weighting by sample counts
+    pub fn accumulate(&mut self, other: &TileData) -> Result<(), String> {
+        if other.region != self.region || other.channels != self.channels || !other.is_valid() {
+            return Err(format!(
+                "Cannot accumulate tile {:?}x{} into {:?}x{}",
+                other.region, other.channels, self.region, self.channels
+            ));
+        }
+        let total = self.sample_count + other.sample_count;
+        if total == 0 {
+            return Ok(());
+        }
+        let weight = other.sample_count as f32 / total as f32;
+        for (value, new) in self.pixels.iter_mut().zip(&other.pixels) {
+            *value += (new - *value) * weight;
+        }
+        self.sample_count = total;
+        Ok(())
+    }
+}
+
+/// Full-frame accumulation buffer for one AOV
+#[derive(Debug, Clone)]
+pub struct FrameBuffer {
+    pub width: u32,
+    pub height: u32,
+    pub channels: u32,
+    /// Row-major averaged pixel values
+    pub pixels: Vec<f32>,
+    /// Samples accumulated per pixel
+    pub samples: Vec<u32>,
+}
+
+impl FrameBuffer {
+    /// Create an empty frame buffer
+    pub fn new(width: u32, height: u32, channels: u32) -> Self {
+        let pixel_count = width as usize * height as usize;
+        Self {
+            width,
+            height,
+            channels,
+            pixels: vec![0.0; pixel_count * channels as usize],
+            samples: vec![0; pixel_count],
+        }
+    }
+
+    /// Reset all pixels and sample counts (e.g. after a scene change)
+    pub fn clear(&mut self) {
+        self.pixels.fill(0.0);
+        self.samples.fill(0);
+    }
+
+    /// Accumulate a progressive tile (sample-weighted running average)
+    pub fn accumulate_tile(&mut self, tile: &TileData) {
+        self.write_tile(tile, true);
+    }
+
+    /// Overwrite a region with a tile (for renderers that send converged pixels)
+    pub fn replace_tile(&mut self, tile: &TileData) {
+        self.write_tile(tile, false);
+    }
+
+    /// Average samples per pixel across the frame
+    pub fn average_samples(&self) -> f32 {
+        if self.samples.is_empty() {
+            return 0.0;
+        }
+        self.samples.iter().map(|&s| s as f64).sum::<f64>() as f32 / self.samples.len() as f32
+    }
+
+    /// Convert to RGBA32F image data for viewer panels
+    ///
+    /// Missing channels are filled (single-channel AOVs become grayscale, alpha
+    /// defaults to 1).
+    pub fn to_image(&self, id: impl Into<String>) -> Result<ImageData, String> {
+        let channels = self.channels as usize;
+        let rgba: Vec<f32> = self
+            .pixels
+            .chunks_exact(channels.max(1))
+            .flat_map(|p| match channels {
+                1 => [p[0], p[0], p[0], 1.0],
+                2 => [p[0], p[1], 0.0, 1.0],
+                3 => [p[0], p[1], p[2], 1.0],
+                _ => [p[0], p[1], p[2], p[3]],
+            })
+            .collect();
+        ImageData::from_rgba32f(id, self.width, self.height, &rgba)
+    }
+
+    fn write_tile(&mut self, tile: &TileData, accumulate: bool) {
+        if tile.channels != self.channels || !tile.is_valid() {
+            return;
+        }
+        let channels = self.channels as usize;
+        for ty in 0..tile.region.height {
+            let y = tile.region.y + ty;
+            if y >= self.height {
+                break;
+            }
+            for tx in 0..tile.region.width {
+                let x = tile.region.x + tx;
+                if x >= self.width {
+                    break;
+                }
+                let index = y as usize * self.width as usize + x as usize;
+                let Some(src) = tile.pixel(tx, ty) else {
+                    continue;
+                };
+                let dst = &mut self.pixels[index * channels..(index + 1) * channels];
+                let previous = self.samples[index];
+                let total = if accumulate { previous + tile.sample_count } else { tile.sample_count };
+                let weight = if !accumulate || total == 0 { 1.0 } else { tile.sample_count as f32 / total as f32 };
+                for (value, new) in dst.iter_mut().zip(src) {
+                    *value += (new - *value) * weight;
+                }
+                self.samples[index] = total;
+            }
+        }
+    }
+}
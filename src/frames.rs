@@ -0,0 +1,175 @@
+//! Frame ranges
+//!
+//! Exporters, cache writers and playback nodes pass frame ranges as
+//! `NodeData::FrameRange` rather than strings. `FrameRange::parse` reads the
+//! common text forms once for everyone:
+//!
+//! - `"1001"`: a single frame
+//! - `"1001-1100"`: every frame from 1001 to 1100, inclusive
+//! - `"1001-1100x2"` or `"1001:1100:2"`: every second frame
+//!
+//! Negative frames are allowed (`"-10--1"`).
+
+use crate::NodeData;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+/// Inclusive range of frames with a step
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct FrameRange {
+    pub start: i32,
+    /// Last frame of the range (included if reached by the step)
+    pub end: i32,
+    /// Frames between samples (at least 1)
+    pub step: i32,
+}
+
+impl FrameRange {
+    /// Every frame from `start` to `end`, inclusive
+    pub fn new(start: i32, end: i32) -> Self {
+        Self { start, end, step: 1 }
+    }
+
+    /// A single frame
+    pub fn single(frame: i32) -> Self {
+        Self::new(frame, frame)
+    }
+
+    /// Sample every `step` frames
+    pub fn with_step(mut self, step: i32) -> Self {
+        self.step = step.max(1);
+        self
+    }
+
+    /// Parse `"1001"`, `"1001-1100"`, `"1001-1100x2"` or `"1001:1100:2"`
+    pub fn parse(text: &str) -> Result<Self, String> {
+        let invalid = || format!("Invalid frame range '{}'", text);
+        let number = |part: &str| part.trim().parse::<i32>().map_err(|_| invalid());
+        let text = text.trim();
+        let (range, step) = if text.contains(':') {
+            let mut parts = text.splitn(3, ':');
+            let start = parts.next().unwrap_or_default();
+            let end = parts.next().ok_or_else(invalid)?;
+            let range = (number(start)?, number(end)?);
+            (range, parts.next().map(number).transpose()?)
+        } else {
+            let (range, step) = match text.split_once(['x', 'X']) {
+                Some((range, step)) => (range, Some(number(step)?)),
+                None => (text, None),
+            };
+            // Skip a leading sign so "-10--1" splits after "-10"
+            let range = match range.get(1..).and_then(|rest| rest.find('-')) {
+                Some(i) => (number(&range[..=i])?, number(&range[i + 2..])?),
+                None => {
+                    let frame = number(range)?;
+                    (frame, frame)
+                }
+            };
+            (range, step)
+        };
+        match step {
+            Some(step) if step < 1 => Err(format!("Frame range step must be positive in '{}'", text)),
+            _ => Ok(Self::new(range.0, range.1).with_step(step.unwrap_or(1))),
+        }
+    }
+
+    /// Number of frames sampled
+    pub fn len(&self) -> usize {
+        if self.is_empty() {
+            0
+        } else {
+            // i64, since end - start overflows i32 for ranges spanning i32::MIN..i32::MAX
+            ((self.end as i64 - self.start as i64) / self.step.max(1) as i64) as usize + 1
+        }
+    }
+
+    /// Check if the range samples no frames (`end` before `start`)
+    pub fn is_empty(&self) -> bool {
+        self.end < self.start
+    }
+
+    /// Check if a frame is sampled
+    pub fn contains(&self, frame: i32) -> bool {
+        !self.is_empty()
+            && (self.start..=self.end).contains(&frame)
+            && (frame as i64 - self.start as i64) % self.step.max(1) as i64 == 0
+    }
+
+    /// Last frame actually sampled (`end` may fall between steps)
+    pub fn last(&self) -> Option<i32> {
+        (!self.is_empty()).then(|| (self.start as i64 + (self.len() as i64 - 1) * self.step.max(1) as i64) as i32)
+    }
+
+    /// Sampled frames in order
+    pub fn frames(&self) -> impl Iterator<Item = i32> {
+        (self.start..=self.end).step_by(self.step.max(1) as usize)
+    }
+}
+
+impl fmt::Display for FrameRange {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.start == self.end {
+            write!(f, "{}", self.start)
+        } else if self.step > 1 {
+            write!(f, "{}-{}x{}", self.start, self.end, self.step)
+        } else {
+            write!(f, "{}-{}", self.start, self.end)
+        }
+    }
+}
+
+impl From<FrameRange> for NodeData {
+    fn from(range: FrameRange) -> Self {
+        NodeData::FrameRange {
+            start: range.start,
+            end: range.end,
+            step: range.step,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_common_forms() {
+        assert_eq!(FrameRange::parse("1001").unwrap(), FrameRange::single(1001));
+        assert_eq!(FrameRange::parse(" 1001-1100 ").unwrap(), FrameRange::new(1001, 1100));
+        assert_eq!(FrameRange::parse("1001-1100x2").unwrap(), FrameRange::new(1001, 1100).with_step(2));
+        assert_eq!(FrameRange::parse("1001:1100:2").unwrap(), FrameRange::new(1001, 1100).with_step(2));
+        assert_eq!(FrameRange::parse("-10--1").unwrap(), FrameRange::new(-10, -1));
+        assert!(FrameRange::parse("1-10x0").is_err());
+        assert!(FrameRange::parse("a-b").is_err());
+        assert!(FrameRange::parse("").is_err());
+    }
+
+    #[test]
+    fn display_round_trips() {
+        for range in [FrameRange::single(5), FrameRange::new(-10, -1), FrameRange::new(1, 9).with_step(3)] {
+            assert_eq!(FrameRange::parse(&range.to_string()).unwrap(), range);
+        }
+    }
+
+    #[test]
+    fn samples_with_step() {
+        let range = FrameRange::new(1, 10).with_step(3);
+        assert_eq!(range.len(), 4);
+        assert_eq!(range.last(), Some(10));
+        assert_eq!(range.frames().collect::<Vec<_>>(), vec![1, 4, 7, 10]);
+        assert!(range.contains(7) && !range.contains(8) && !range.contains(13));
+        assert!(FrameRange::new(5, 1).is_empty());
+        assert_eq!(FrameRange::new(5, 1).len(), 0);
+    }
+
+    #[test]
+    fn full_i32_range_does_not_overflow() {
+        let range = FrameRange::new(i32::MIN, i32::MAX);
+        assert_eq!(range.len(), u32::MAX as usize + 1);
+        assert!(range.contains(i32::MAX));
+        assert_eq!(range.last(), Some(i32::MAX));
+        let stepped = range.with_step(2);
+        assert!(stepped.contains(i32::MAX - 1) && !stepped.contains(i32::MAX));
+        assert_eq!(stepped.last(), Some(i32::MAX - 1));
+    }
+}
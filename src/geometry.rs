@@ -0,0 +1,203 @@
+//! Geometry validation and attribute generation
+//!
+//! Common mesh utilities so geometry plugins don't each reimplement index bounds
+//! checking, normal computation, tangents and bounding boxes.
+//!
+//! The generation utilities assume the geometry passes `validate()`.
+
+use crate::{GeometryData, GeometryError, UVInterpolation};
+
+impl GeometryData {
+    /// Validate indices, face counts and attribute lengths
+    pub fn validate(&self) -> Result<(), GeometryError> {
+        let vertex_count = self.vertices.len();
+
+        if let Some(vertex) = self.vertices.iter().position(|v| v.iter().any(|c| !c.is_finite())) {
+            return Err(GeometryError::NonFiniteVertex(vertex));
+        }
+
+        if self.face_vertex_counts.is_empty() {
+            if self.indices.len() % 3 != 0 {
+                return Err(GeometryError::InvalidIndexCount(self.indices.len()));
+            }
+        } else {
+            if let Some(face) = self.face_vertex_counts.iter().position(|&c| c < 3) {
+                return Err(GeometryError::DegenerateFace(face));
+            }
+            let expected: usize = self.face_vertex_counts.iter().map(|&c| c as usize).sum();
+            if expected != self.indices.len() {
+                return Err(GeometryError::FaceCountMismatch {
+                    expected,
+                    actual: self.indices.len(),
+                });
+            }
+        }
+
+        if let Some(&index) = self.indices.iter().find(|&&i| i as usize >= vertex_count) {
+            return Err(GeometryError::IndexOutOfBounds { index, vertex_count });
+        }
+
+        let face_vertex_count = self.indices.len();
+        check_length("normals", self.normals.len(), vertex_count)?;
+        check_length("uvs", self.uvs.len(), vertex_count)?;
+        check_length("colors", self.colors.len(), vertex_count)?;
+        check_length("face_varying_normals", self.face_varying_normals.len(), face_vertex_count)?;
+        check_length("face_varying_uvs", self.face_varying_uvs.len(), face_vertex_count)?;
+        for set in &self.uv_sets {
+            let expected = match set.interpolation {
+                UVInterpolation::Vertex => vertex_count,
+                UVInterpolation::FaceVarying => face_vertex_count,
+            };
+            check_length(&format!("uv_sets.{}", set.name), set.uvs.len(), expected)?;
+        }
+
+        Ok(())
+    }
+
+    /// Replace `normals` with area-weighted smooth per-vertex normals
+    pub fn compute_smooth_normals(&mut self) {
+        let mut normals = vec![[0.0f32; 3]; self.vertices.len()];
+        for range in self.face_ranges() {
+            let face = &self.indices[range];
+            // Newell's method gives an area-weighted normal for any planar polygon
+            let normal = self.face_normal_unnormalized(face);
+            for &index in face {
+                if let Some(n) = normals.get_mut(index as usize) {
+                    *n = add(*n, normal);
+                }
+            }
+        }
+        self.normals = normals.into_iter().map(normalize).collect();
+    }
+
+    /// Replace `face_varying_normals` with flat (per-face) normals
+    pub fn compute_flat_normals(&mut self) {
+        let mut normals = Vec::with_capacity(self.indices.len());
+        for range in self.face_ranges() {
+            let face = &self.indices[range];
+            let normal = normalize(self.face_normal_unnormalized(face));
+            normals.extend(std::iter::repeat(normal).take(face.len()));
+        }
+        self.face_varying_normals = normals;
+    }
+
+    /// Compute per-vertex tangents (xyz + handedness in w) from normals and UVs
+    pub fn compute_tangents(&self) -> Result<Vec<[f32; 4]>, GeometryError> {
+        if self.uvs.len() != self.vertices.len() {
+            return Err(GeometryError::MissingAttribute("uvs".to_string()));
+        }
+        if self.normals.len() != self.vertices.len() {
+            return Err(GeometryError::MissingAttribute("normals".to_string()));
+        }
+
+        let mut tangents = vec![[0.0f32; 3]; self.vertices.len()];
+        let mut bitangents = vec![[0.0f32; 3]; self.vertices.len()];
+        let triangles = self.triangulate();
+        for tri in triangles.indices.chunks_exact(3) {
+            let (a, b, c) = (tri[0] as usize, tri[1] as usize, tri[2] as usize);
+            let e1 = sub(self.vertices[b], self.vertices[a]);
+            let e2 = sub(self.vertices[c], self.vertices[a]);
+            let du1 = self.uvs[b][0] - self.uvs[a][0];
+            let dv1 = self.uvs[b][1] - self.uvs[a][1];
+            let du2 = self.uvs[c][0] - self.uvs[a][0];
+            let dv2 = self.uvs[c][1] - self.uvs[a][1];
+            let det = du1 * dv2 - du2 * dv1;
+            if det.abs() < f32::EPSILON {
+                continue;
+            }
+            let r = 1.0 / det;
+            let t = scale(sub(scale(e1, dv2), scale(e2, dv1)), r);
+            let bt = scale(sub(scale(e2, du1), scale(e1, du2)), r);
+            for &i in &[a, b, c] {
+                tangents[i] = add(tangents[i], t);
+                bitangents[i] = add(bitangents[i], bt);
+            }
+        }
+
+        Ok(tangents
+            .iter()
+            .zip(&bitangents)
+            .zip(&self.normals)
+            .map(|((&t, &bt), &n)| {
+                // Gram-Schmidt orthogonalize against the normal
+                let t = normalize(sub(t, scale(n, dot(n, t))));
+                let w = if dot(cross(n, t), bt) < 0.0 { -1.0 } else { 1.0 };
+                [t[0], t[1], t[2], w]
+            })
+            .collect())
+    }
+
+    /// Compute the axis-aligned bounding box (None if there are no vertices)
+    pub fn bounding_box(&self) -> Option<([f32; 3], [f32; 3])> {
+        let mut vertices = self.vertices.iter();
+        let first = *vertices.next()?;
+        Some(vertices.fold((first, first), |(mut min, mut max), v| {
+            for i in 0..3 {
+                min[i] = min[i].min(v[i]);
+                max[i] = max[i].max(v[i]);
+            }
+            (min, max)
+        }))
+    }
+
+    fn face_normal_unnormalized(&self, face: &[u32]) -> [f32; 3] {
+        let mut normal = [0.0f32; 3];
+        for (i, &index) in face.iter().enumerate() {
+            let (Some(current), Some(next)) = (
+                self.vertices.get(index as usize),
+                self.vertices.get(face[(i + 1) % face.len()] as usize),
+            ) else {
+                continue;
+            };
+            normal[0] += (current[1] - next[1]) * (current[2] + next[2]);
+            normal[1] += (current[2] - next[2]) * (current[0] + next[0]);
+            normal[2] += (current[0] - next[0]) * (current[1] + next[1]);
+        }
+        normal
+    }
+}
+
+/// Optional attributes must be empty or match the expected length
+fn check_length(attribute: &str, actual: usize, expected: usize) -> Result<(), GeometryError> {
+    if actual != 0 && actual != expected {
+        return Err(GeometryError::AttributeLengthMismatch {
+            attribute: attribute.to_string(),
+            expected,
+            actual,
+        });
+    }
+    Ok(())
+}
+
+fn add(a: [f32; 3], b: [f32; 3]) -> [f32; 3] {
+    [a[0] + b[0], a[1] + b[1], a[2] + b[2]]
+}
+
+fn sub(a: [f32; 3], b: [f32; 3]) -> [f32; 3] {
+    [a[0] - b[0], a[1] - b[1], a[2] - b[2]]
+}
+
+fn scale(a: [f32; 3], s: f32) -> [f32; 3] {
+    [a[0] * s, a[1] * s, a[2] * s]
+}
+
+fn dot(a: [f32; 3], b: [f32; 3]) -> f32 {
+    a[0] * b[0] + a[1] * b[1] + a[2] * b[2]
+}
+
+fn cross(a: [f32; 3], b: [f32; 3]) -> [f32; 3] {
+    [
+        a[1] * b[2] - a[2] * b[1],
+        a[2] * b[0] - a[0] * b[2],
+        a[0] * b[1] - a[1] * b[0],
+    ]
+}
+
+fn normalize(a: [f32; 3]) -> [f32; 3] {
+    let len = dot(a, a).sqrt();
+    if len > f32::EPSILON {
+        scale(a, 1.0 / len)
+    } else {
+        [0.0, 0.0, 0.0]
+    }
+}
@@ -0,0 +1,220 @@
+//! Read-only graph introspection
+//!
+//! Hooks such as `on_input_connection_added` receive bare node IDs. A
+//! `GraphHandle` lets plugins look those nodes up (type, metadata,
+//! parameters) and walk the topology, e.g. to invalidate caches only when an
+//! upstream USD reader changes. The host hands it to execution hooks through
+//! `NodeExecutionHooks::attach_graph` and to cooks through
+//! `ExecutionContext::graph`:
+//!
+//! ```rust,ignore
+//! fn on_input_connection_added(&mut self, _: &PluginHandle, node_id: NodeId, _: &str, source: NodeId) -> Result<(), String> {
+//!     let Some(graph) = &self.graph else { return Ok(()) };
+//!     if graph.node_type(source).as_deref() == Some("usd_file_reader") {
+//!         self.invalidate(node_id);
+//!     }
+//!     Ok(())
+//! }
+//! ```
+//!
+//! The handle reflects the graph at the time of the call; do not cache
+//! answers across edits.
+//!
+//! It is also the entry point for bug-report snapshots: a node handling a
+//! "Report issue" action calls `graph.capture_snapshot(node_id, &options)`.
+
+use crate::{GraphSnapshot, NodeData, NodeId, NodeMetadata, PluginError, SnapshotOptions};
+use std::collections::{HashSet, VecDeque};
+use std::fmt;
+use std::sync::Arc;
+
+/// Connection from an output port to an input port
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Connection {
+    pub from_node: NodeId,
+    pub from_port: String,
+    pub to_node: NodeId,
+    pub to_port: String,
+}
+
+/// Read-only queries on the node graph (implemented by the host)
+pub trait GraphQuery: Send + Sync {
+    /// Read a parameter of any node
+    fn get_parameter(&self, node: NodeId, parameter: &str) -> Option<NodeData>;
+
+    /// Get the type ID of a node (as registered in `NodeMetadata::node_type`)
+    fn node_type(&self, node: NodeId) -> Option<String>;
+
+    /// Get the metadata of a node's type (None for unknown nodes)
+    fn node_metadata(&self, node: NodeId) -> Option<NodeMetadata>;
+
+    /// Find all nodes of a type
+    fn find_nodes(&self, node_type: &str) -> Vec<NodeId>;
+
+    /// Get every connection into or out of a node
+    fn connections(&self, node: NodeId) -> Vec<Connection>;
+
+    /// Get the nodes and output ports connected to an input port
+    fn input_connections(&self, node: NodeId, port: &str) -> Vec<(NodeId, String)> {
+        self.connections(node)
+            .into_iter()
+            .filter(|c| c.to_node == node && c.to_port == port)
+            .map(|c| (c.from_node, c.from_port))
+            .collect()
+    }
+
+    /// Get the nodes feeding a node's inputs directly
+    fn upstream(&self, node: NodeId) -> Vec<NodeId> {
+        unique(self.connections(node).into_iter().filter(|c| c.to_node == node).map(|c| c.from_node))
+    }
+
+    /// Get the nodes reading a node's outputs directly
+    fn downstream(&self, node: NodeId) -> Vec<NodeId> {
+        unique(self.connections(node).into_iter().filter(|c| c.from_node == node).map(|c| c.to_node))
+    }
+
+    /// Get every node a node depends on, nearest first
+    fn all_upstream(&self, node: NodeId) -> Vec<NodeId> {
+        let mut seen = HashSet::from([node]);
+        let mut queue = VecDeque::from([node]);
+        let mut result = Vec::new();
+        while let Some(current) = queue.pop_front() {
+            for next in self.upstream(current) {
+                if seen.insert(next) {
+                    result.push(next);
+                    queue.push_back(next);
+                }
+            }
+        }
+        result
+    }
+
+    /// Capture an anonymized snapshot of the sub-graph around a node
+    ///
+    /// Redaction from `options` is already applied. Hosts that do not support
+    /// snapshots keep the default, which returns an error.
+    fn capture_snapshot(&self, _node: NodeId, _options: &SnapshotOptions) -> Result<GraphSnapshot, PluginError> {
+        Err(PluginError::Other("Graph snapshots are not supported by this host".to_string()))
+    }
+
+    /// Capture a snapshot and serialize it as JSON
+    fn export_snapshot_json(&self, node: NodeId, options: &SnapshotOptions) -> Result<String, PluginError> {
+        let snapshot = self.capture_snapshot(node, options)?;
+        crate::JsonValue::from_serialize(&snapshot)
+            .map(|json| json.to_json_string())
+            .map_err(PluginError::Other)
+    }
+}
+
+fn unique(nodes: impl Iterator<Item = NodeId>) -> Vec<NodeId> {
+    let mut seen = HashSet::new();
+    nodes.filter(|node| seen.insert(*node)).collect()
+}
+
+/// Cloneable handle to the host's graph queries
+#[derive(Clone)]
+pub struct GraphHandle(Arc<dyn GraphQuery>);
+
+impl GraphHandle {
+    /// Wrap a graph query implementation
+    pub fn new(graph: impl GraphQuery + 'static) -> Self {
+        Self(Arc::new(graph))
+    }
+}
+
+impl std::ops::Deref for GraphHandle {
+    type Target = dyn GraphQuery;
+
+    fn deref(&self) -> &Self::Target {
+        self.0.as_ref()
+    }
+}
+
+impl fmt::Debug for GraphHandle {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("GraphHandle")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    /// Single-node graph whose host supports snapshots
+    struct OneNode;
+
+    impl GraphQuery for OneNode {
+        fn get_parameter(&self, _node: NodeId, _parameter: &str) -> Option<NodeData> {
+            None
+        }
+
+        fn node_type(&self, _node: NodeId) -> Option<String> {
+            Some("blur".to_string())
+        }
+
+        fn node_metadata(&self, _node: NodeId) -> Option<NodeMetadata> {
+            None
+        }
+
+        fn find_nodes(&self, _node_type: &str) -> Vec<NodeId> {
+            vec![NodeId(3)]
+        }
+
+        fn connections(&self, _node: NodeId) -> Vec<Connection> {
+            Vec::new()
+        }
+
+        fn capture_snapshot(&self, node: NodeId, options: &SnapshotOptions) -> Result<GraphSnapshot, PluginError> {
+            let mut snapshot = GraphSnapshot::new("1.0", node);
+            snapshot.nodes.push(crate::snapshot::SnapshotNode {
+                node_id: node,
+                node_type: "blur".to_string(),
+                parameters: HashMap::from([
+                    ("radius".to_string(), NodeData::Float(1.5)),
+                    ("mask".to_string(), NodeData::String("/tmp/mask.png".to_string())),
+                ]),
+            });
+            snapshot.redact(options);
+            Ok(snapshot)
+        }
+    }
+
+    struct NoSnapshots;
+
+    impl GraphQuery for NoSnapshots {
+        fn get_parameter(&self, _node: NodeId, _parameter: &str) -> Option<NodeData> {
+            None
+        }
+
+        fn node_type(&self, _node: NodeId) -> Option<String> {
+            None
+        }
+
+        fn node_metadata(&self, _node: NodeId) -> Option<NodeMetadata> {
+            None
+        }
+
+        fn find_nodes(&self, _node_type: &str) -> Vec<NodeId> {
+            Vec::new()
+        }
+
+        fn connections(&self, _node: NodeId) -> Vec<Connection> {
+            Vec::new()
+        }
+    }
+
+    #[test]
+    fn snapshot_json_serializes_the_captured_snapshot() {
+        let text = OneNode.export_snapshot_json(NodeId(3), &SnapshotOptions::redacted()).unwrap();
+        let json = crate::JsonValue::parse(&text).unwrap();
+        assert_eq!(json.get_pointer("/root_node").and_then(crate::JsonValue::as_i64), Some(3));
+        assert_eq!(json.get_pointer("/host_version").and_then(crate::JsonValue::as_str), Some("1.0"));
+        assert_eq!(json.get_pointer("/nodes/0/parameters/radius/Float").and_then(crate::JsonValue::as_f64), Some(1.5));
+        assert_eq!(
+            json.get_pointer("/nodes/0/parameters/mask/String").and_then(crate::JsonValue::as_str),
+            Some(crate::snapshot::REDACTED)
+        );
+        assert!(NoSnapshots.export_snapshot_json(NodeId(3), &SnapshotOptions::default()).is_err());
+    }
+}
@@ -0,0 +1,89 @@
+//! Panic isolation for plugin entry points
+//!
+//! A panic unwinding out of a plugin into the host is undefined behavior across
+//! `extern "C"` and would otherwise abort the whole editor. The handle types use
+//! these helpers to run plugin code under `catch_unwind` and turn panics into
+//! `PluginError::Other`.
+//!
+//! By default the error only carries the panic message. Hosts that want the
+//! panic location and a backtrace as well call `install_panic_hook` once at
+//! startup; it chains to the previously installed hook, and backtraces are
+//! captured according to the usual `RUST_BACKTRACE`/`RUST_LIB_BACKTRACE`
+//! settings.
+
+use crate::PluginError;
+use std::backtrace::Backtrace;
+use std::cell::RefCell;
+use std::panic::{self, AssertUnwindSafe};
+use std::sync::Once;
+
+thread_local! {
+    static LAST_PANIC: RefCell<Option<String>> = const { RefCell::new(None) };
+}
+
+static INSTALL_HOOK: Once = Once::new();
+
+/// Install the panic hook that records panic details for `catch_plugin_panic`
+///
+/// Opt-in: the host calls this once, typically before loading plugins. The
+/// previous hook still runs afterwards, and calling this again has no effect.
+pub fn install_panic_hook() {
+    INSTALL_HOOK.call_once(|| {
+        let previous = panic::take_hook();
+        panic::set_hook(Box::new(move |info| {
+            let location = info
+                .location()
+                .map(|l| format!(" at {}:{}", l.file(), l.line()))
+                .unwrap_or_default();
+            let details = format!(
+                "{}{}\n{}",
+                panic_message(info.payload()),
+                location,
+                Backtrace::capture()
+            );
+            LAST_PANIC.with(|last| *last.borrow_mut() = Some(details));
+            previous(info);
+        }));
+    });
+}
+
+/// Run plugin code, converting a panic into `PluginError::Other`
+///
+/// `context` names the entry point (e.g. "process") and is included in the error.
+pub fn catch_plugin_panic<R>(context: &str, f: impl FnOnce() -> R) -> Result<R, PluginError> {
+    LAST_PANIC.with(|last| last.borrow_mut().take());
+
+    panic::catch_unwind(AssertUnwindSafe(f)).map_err(|payload| {
+        let details = LAST_PANIC
+            .with(|last| last.borrow_mut().take())
+            .unwrap_or_else(|| panic_message(payload.as_ref()));
+        PluginError::Other(format!("Plugin panicked in {}: {}", context, details))
+    })
+}
+
+fn panic_message(payload: &(dyn std::any::Any + Send)) -> String {
+    if let Some(s) = payload.downcast_ref::<&str>() {
+        s.to_string()
+    } else if let Some(s) = payload.downcast_ref::<String>() {
+        s.clone()
+    } else {
+        "unknown panic payload".to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn panics_become_plugin_errors() {
+        let error = catch_plugin_panic("process", || -> i32 { panic!("bad input") }).unwrap_err();
+        match error {
+            PluginError::Other(message) => {
+                assert!(message.starts_with("Plugin panicked in process: bad input"), "{}", message)
+            }
+            other => panic!("unexpected error {:?}", other),
+        }
+        assert_eq!(catch_plugin_panic("process", || 7).unwrap(), 7);
+    }
+}
@@ -0,0 +1,420 @@
+//! Stable content hashing
+//!
+//! Hashes any `Serialize` value (including `NodeData`) with FNV-1a so the result
+//! is stable across runs, platforms and compiler versions. Map entries are
+//! combined order-independently, so `HashMap` iteration order does not matter.
+//! Used for execution provenance and content-addressed cache keys.
+
+use serde::ser::{self, Serialize};
+use std::fmt;
+
+const FNV_OFFSET: u64 = 0xcbf2_9ce4_8422_2325;
+const FNV_PRIME: u64 = 0x0000_0100_0000_01b3;
+
+/// Compute a stable 64-bit content hash of a value
+pub fn content_hash<T: Serialize + ?Sized>(value: &T) -> u64 {
+    let mut hasher = ContentHasher::new();
+    // The hashing serializer never fails
+    let _ = value.serialize(&mut hasher);
+    hasher.finish()
+}
+
+/// Combine several hashes into one (order-dependent)
+pub fn combine_hashes(hashes: impl IntoIterator<Item = u64>) -> u64 {
+    let mut hasher = ContentHasher::new();
+    for hash in hashes {
+        hasher.write(&hash.to_le_bytes());
+    }
+    hasher.finish()
+}
+
+/// FNV-1a hasher that can also act as a serde serializer
+#[derive(Debug, Clone)]
+pub struct ContentHasher {
+    state: u64,
+}
+
+impl ContentHasher {
+    /// Create a hasher with the FNV offset basis
+    pub fn new() -> Self {
+        Self { state: FNV_OFFSET }
+    }
+
+    /// Feed bytes into the hash
+    pub fn write(&mut self, bytes: &[u8]) {
+        for &byte in bytes {
+            self.state ^= byte as u64;
+            self.state = self.state.wrapping_mul(FNV_PRIME);
+        }
+    }
+
+    /// Get the current hash value
+    pub fn finish(&self) -> u64 {
+        self.state
+    }
+
+    fn tag(&mut self, tag: u8) {
+        self.write(&[tag]);
+    }
+
+    fn write_len(&mut self, len: usize) {
+        self.write(&(len as u64).to_le_bytes());
+    }
+
+    fn write_str(&mut self, s: &str) {
+        self.write_len(s.len());
+        self.write(s.as_bytes());
+    }
+
+    fn write_f64(&mut self, v: f64) {
+        // Canonicalize -0.0 and NaN so equal-looking values hash equally
+        let v = if v == 0.0 { 0.0 } else if v.is_nan() { f64::NAN } else { v };
+        self.write(&v.to_bits().to_le_bytes());
+    }
+}
+
+impl Default for ContentHasher {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl std::hash::Hasher for ContentHasher {
+    fn finish(&self) -> u64 {
+        self.state
+    }
+
+    fn write(&mut self, bytes: &[u8]) {
+        ContentHasher::write(self, bytes);
+    }
+}
+
+/// Error type for the hashing serializer (never produced in practice)
+#[derive(Debug)]
+pub struct HashError(String);
+
+impl fmt::Display for HashError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "hash error: {}", self.0)
+    }
+}
+
+impl std::error::Error for HashError {}
+
+impl ser::Error for HashError {
+    fn custom<T: fmt::Display>(msg: T) -> Self {
+        HashError(msg.to_string())
+    }
+}
+
+impl<'a> ser::Serializer for &'a mut ContentHasher {
+    type Ok = ();
+    type Error = HashError;
+    type SerializeSeq = Self;
+    type SerializeTuple = Self;
+    type SerializeTupleStruct = Self;
+    type SerializeTupleVariant = Self;
+    type SerializeMap = MapHasher<'a>;
+    type SerializeStruct = Self;
+    type SerializeStructVariant = Self;
+
+    fn serialize_bool(self, v: bool) -> Result<(), HashError> {
+        self.tag(1);
+        self.write(&[v as u8]);
+        Ok(())
+    }
+
+    fn serialize_i8(self, v: i8) -> Result<(), HashError> {
+        self.serialize_i64(v as i64)
+    }
+
+    fn serialize_i16(self, v: i16) -> Result<(), HashError> {
+        self.serialize_i64(v as i64)
+    }
+
+    fn serialize_i32(self, v: i32) -> Result<(), HashError> {
+        self.serialize_i64(v as i64)
+    }
+
+    fn serialize_i64(self, v: i64) -> Result<(), HashError> {
+        self.tag(2);
+        self.write(&v.to_le_bytes());
+        Ok(())
+    }
+
+    fn serialize_u8(self, v: u8) -> Result<(), HashError> {
+        self.serialize_u64(v as u64)
+    }
+
+    fn serialize_u16(self, v: u16) -> Result<(), HashError> {
+        self.serialize_u64(v as u64)
+    }
+
+    fn serialize_u32(self, v: u32) -> Result<(), HashError> {
+        self.serialize_u64(v as u64)
+    }
+
+    fn serialize_u64(self, v: u64) -> Result<(), HashError> {
+        self.tag(3);
+        self.write(&v.to_le_bytes());
+        Ok(())
+    }
+
+    fn serialize_f32(self, v: f32) -> Result<(), HashError> {
+        self.serialize_f64(v as f64)
+    }
+
+    fn serialize_f64(self, v: f64) -> Result<(), HashError> {
+        self.tag(4);
+        self.write_f64(v);
+        Ok(())
+    }
+
+    fn serialize_char(self, v: char) -> Result<(), HashError> {
+        self.tag(5);
+        self.write(&(v as u32).to_le_bytes());
+        Ok(())
+    }
+
+    fn serialize_str(self, v: &str) -> Result<(), HashError> {
+        self.tag(6);
+        self.write_str(v);
+        Ok(())
+    }
+
+    fn serialize_bytes(self, v: &[u8]) -> Result<(), HashError> {
+        self.tag(7);
+        self.write_len(v.len());
+        self.write(v);
+        Ok(())
+    }
+
+    fn serialize_none(self) -> Result<(), HashError> {
+        self.tag(8);
+        Ok(())
+    }
+
+    fn serialize_some<T: Serialize + ?Sized>(self, value: &T) -> Result<(), HashError> {
+        self.tag(9);
+        value.serialize(self)
+    }
+
+    fn serialize_unit(self) -> Result<(), HashError> {
+        self.tag(10);
+        Ok(())
+    }
+
+    fn serialize_unit_struct(self, name: &'static str) -> Result<(), HashError> {
+        self.tag(11);
+        self.write_str(name);
+        Ok(())
+    }
+
+    fn serialize_unit_variant(self, _name: &'static str, index: u32, _variant: &'static str) -> Result<(), HashError> {
+        self.tag(12);
+        self.write(&index.to_le_bytes());
+        Ok(())
+    }
+
+    fn serialize_newtype_struct<T: Serialize + ?Sized>(self, _name: &'static str, value: &T) -> Result<(), HashError> {
+        value.serialize(self)
+    }
+
+    fn serialize_newtype_variant<T: Serialize + ?Sized>(
+        self,
+        _name: &'static str,
+        index: u32,
+        _variant: &'static str,
+        value: &T,
+    ) -> Result<(), HashError> {
+        self.tag(13);
+        self.write(&index.to_le_bytes());
+        value.serialize(self)
+    }
+
+    fn serialize_seq(self, len: Option<usize>) -> Result<Self, HashError> {
+        self.tag(14);
+        self.write_len(len.unwrap_or(0));
+        Ok(self)
+    }
+
+    fn serialize_tuple(self, len: usize) -> Result<Self, HashError> {
+        self.tag(15);
+        self.write_len(len);
+        Ok(self)
+    }
+
+    fn serialize_tuple_struct(self, _name: &'static str, len: usize) -> Result<Self, HashError> {
+        self.serialize_tuple(len)
+    }
+
+    fn serialize_tuple_variant(
+        self,
+        _name: &'static str,
+        index: u32,
+        _variant: &'static str,
+        len: usize,
+    ) -> Result<Self, HashError> {
+        self.tag(16);
+        self.write(&index.to_le_bytes());
+        self.write_len(len);
+        Ok(self)
+    }
+
+    fn serialize_map(self, _len: Option<usize>) -> Result<MapHasher<'a>, HashError> {
+        self.tag(17);
+        Ok(MapHasher {
+            parent: self,
+            accumulated: 0,
+            count: 0,
+            entry: ContentHasher::new(),
+        })
+    }
+
+    fn serialize_struct(self, _name: &'static str, len: usize) -> Result<Self, HashError> {
+        self.tag(18);
+        self.write_len(len);
+        Ok(self)
+    }
+
+    fn serialize_struct_variant(
+        self,
+        _name: &'static str,
+        index: u32,
+        _variant: &'static str,
+        len: usize,
+    ) -> Result<Self, HashError> {
+        self.tag(19);
+        self.write(&index.to_le_bytes());
+        self.write_len(len);
+        Ok(self)
+    }
+}
+
+impl ser::SerializeSeq for &mut ContentHasher {
+    type Ok = ();
+    type Error = HashError;
+
+    fn serialize_element<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), HashError> {
+        value.serialize(&mut **self)
+    }
+
+    fn end(self) -> Result<(), HashError> {
+        Ok(())
+    }
+}
+
+impl ser::SerializeTuple for &mut ContentHasher {
+    type Ok = ();
+    type Error = HashError;
+
+    fn serialize_element<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), HashError> {
+        value.serialize(&mut **self)
+    }
+
+    fn end(self) -> Result<(), HashError> {
+        Ok(())
+    }
+}
+
+impl ser::SerializeTupleStruct for &mut ContentHasher {
+    type Ok = ();
+    type Error = HashError;
+
+    fn serialize_field<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), HashError> {
+        value.serialize(&mut **self)
+    }
+
+    fn end(self) -> Result<(), HashError> {
+        Ok(())
+    }
+}
+
+impl ser::SerializeTupleVariant for &mut ContentHasher {
+    type Ok = ();
+    type Error = HashError;
+
+    fn serialize_field<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), HashError> {
+        value.serialize(&mut **self)
+    }
+
+    fn end(self) -> Result<(), HashError> {
+        Ok(())
+    }
+}
+
+impl ser::SerializeStruct for &mut ContentHasher {
+    type Ok = ();
+    type Error = HashError;
+
+    fn serialize_field<T: Serialize + ?Sized>(&mut self, key: &'static str, value: &T) -> Result<(), HashError> {
+        self.write_str(key);
+        value.serialize(&mut **self)
+    }
+
+    fn end(self) -> Result<(), HashError> {
+        Ok(())
+    }
+}
+
+impl ser::SerializeStructVariant for &mut ContentHasher {
+    type Ok = ();
+    type Error = HashError;
+
+    fn serialize_field<T: Serialize + ?Sized>(&mut self, key: &'static str, value: &T) -> Result<(), HashError> {
+        self.write_str(key);
+        value.serialize(&mut **self)
+    }
+
+    fn end(self) -> Result<(), HashError> {
+        Ok(())
+    }
+}
+
+/// Map serializer that combines entries independently of iteration order
+pub struct MapHasher<'a> {
+    parent: &'a mut ContentHasher,
+    accumulated: u64,
+    count: usize,
+    entry: ContentHasher,
+}
+
+impl ser::SerializeMap for MapHasher<'_> {
+    type Ok = ();
+    type Error = HashError;
+
+    fn serialize_key<T: Serialize + ?Sized>(&mut self, key: &T) -> Result<(), HashError> {
+        self.entry = ContentHasher::new();
+        key.serialize(&mut self.entry)
+    }
+
+    fn serialize_value<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), HashError> {
+        value.serialize(&mut self.entry)?;
+        self.accumulated = self.accumulated.wrapping_add(self.entry.finish());
+        self.count += 1;
+        Ok(())
+    }
+
+    fn end(self) -> Result<(), HashError> {
+        self.parent.write_len(self.count);
+        self.parent.write(&self.accumulated.to_le_bytes());
+        Ok(())
+    }
+}
+
+impl crate::NodeData {
+    /// Compute a stable content hash of this value
+    pub fn content_hash(&self) -> u64 {
+        content_hash(self)
+    }
+}
+
+/// Hash every entry of a port map by name
+pub fn hash_port_map(
+    ports: &std::collections::HashMap<String, crate::NodeData>,
+) -> std::collections::HashMap<String, u64> {
+    ports
+        .iter()
+        .map(|(name, data)| (name.clone(), data.content_hash()))
+        .collect()
+}
@@ -0,0 +1,219 @@
+//! Node health self-checks
+//!
+//! A node can look fine in the graph and still fail at render time: a texture
+//! was deleted, a license expired, a cache was written by an older plugin
+//! version, the farm machine has no GPU. `PluginNode::self_check` lets a node
+//! report such problems on demand, so the host's "validate scene" command can
+//! cover plugin nodes without cooking them:
+//!
+//! ```rust,ignore
+//! let mut report = HealthReport::new();
+//! for (node_id, node_type, handle) in &nodes {
+//!     report.add_result(*node_id, node_type, unsafe { handle.safe_self_check() });
+//! }
+//! if !report.is_healthy() {
+//!     for entry in report.problems() {
+//!         println!("{} ({}): {}", entry.node_type, entry.node_id, entry.issue);
+//!     }
+//! }
+//! ```
+
+use crate::{AssetRef, ErrorSeverity, NodeId, PluginError};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::path::Path;
+
+/// What kind of problem a node found
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum HealthIssueKind {
+    /// A file the node reads does not exist
+    MissingFile { path: String },
+    /// A license is missing, expired or about to expire
+    License,
+    /// Cached data was written by an incompatible version or is corrupt
+    IncompatibleCache,
+    /// The node needs a GPU and none is usable
+    GpuUnavailable,
+    /// Any other problem, with a plugin-defined code
+    Other(String),
+}
+
+/// Problem reported by `PluginNode::self_check`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct HealthIssue {
+    pub kind: HealthIssueKind,
+    /// Human-readable description (e.g. "license expires in 3 days")
+    pub message: String,
+    /// `Warning` if the node still cooks, `Error` if it will fail
+    pub severity: ErrorSeverity,
+    /// Parameter the user should look at (None = whole node)
+    #[serde(default)]
+    pub parameter: Option<String>,
+}
+
+impl HealthIssue {
+    /// Create an issue with `Error` severity
+    pub fn new(kind: HealthIssueKind, message: impl Into<String>) -> Self {
+        Self {
+            kind,
+            message: message.into(),
+            severity: ErrorSeverity::Error,
+            parameter: None,
+        }
+    }
+
+    /// Missing file
+    pub fn missing_file(path: impl Into<String>) -> Self {
+        let path = path.into();
+        let message = format!("File not found: {}", path);
+        Self::new(HealthIssueKind::MissingFile { path }, message)
+    }
+
+    /// License problem
+    pub fn license(message: impl Into<String>) -> Self {
+        Self::new(HealthIssueKind::License, message)
+    }
+
+    /// Incompatible or corrupt cached data
+    pub fn incompatible_cache(message: impl Into<String>) -> Self {
+        Self::new(HealthIssueKind::IncompatibleCache, message)
+    }
+
+    /// No usable GPU
+    pub fn gpu_unavailable(message: impl Into<String>) -> Self {
+        Self::new(HealthIssueKind::GpuUnavailable, message)
+    }
+
+    /// Downgrade to a warning
+    pub fn warning(self) -> Self {
+        self.with_severity(ErrorSeverity::Warning)
+    }
+
+    /// Set the severity
+    pub fn with_severity(mut self, severity: ErrorSeverity) -> Self {
+        self.severity = severity;
+        self
+    }
+
+    /// Record which parameter the issue concerns
+    pub fn with_parameter(mut self, parameter: impl Into<String>) -> Self {
+        self.parameter = Some(parameter.into());
+        self
+    }
+
+    /// Check if the node is expected to fail because of this issue
+    pub fn is_error(&self) -> bool {
+        self.severity >= ErrorSeverity::Error
+    }
+}
+
+impl fmt::Display for HealthIssue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.parameter {
+            Some(parameter) => write!(f, "{}: {}", parameter, self.message),
+            None => write!(f, "{}", self.message),
+        }
+    }
+}
+
+/// Report missing asset files
+///
+/// Relative paths are resolved against `base_dir` (the working directory if
+/// None). Missing required assets are errors, missing optional ones warnings.
+/// Nodes can return this from `self_check` together with their own checks.
+pub fn check_assets(assets: &[AssetRef], base_dir: Option<&Path>) -> Vec<HealthIssue> {
+    assets
+        .iter()
+        .filter(|asset| match base_dir {
+            Some(dir) => !asset.exists_relative_to(dir),
+            None => !asset.exists(),
+        })
+        .map(|asset| {
+            let mut issue = HealthIssue::missing_file(asset.path.as_str());
+            if !asset.required {
+                issue = issue.warning();
+            }
+            match &asset.parameter {
+                Some(parameter) => issue.with_parameter(parameter.as_str()),
+                None => issue,
+            }
+        })
+        .collect()
+}
+
+/// Issue found on one node of the graph
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct NodeHealthIssue {
+    pub node_id: NodeId,
+    /// Node type, for display
+    pub node_type: String,
+    pub issue: HealthIssue,
+}
+
+/// Results of running `self_check` across a graph
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct HealthReport {
+    pub entries: Vec<NodeHealthIssue>,
+    /// Number of nodes checked, including healthy ones
+    pub nodes_checked: usize,
+}
+
+impl HealthReport {
+    /// Create an empty report
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add the issues of one checked node
+    pub fn add(&mut self, node_id: NodeId, node_type: &str, issues: Vec<HealthIssue>) {
+        self.nodes_checked += 1;
+        self.entries.extend(issues.into_iter().map(|issue| NodeHealthIssue {
+            node_id,
+            node_type: node_type.to_string(),
+            issue,
+        }));
+    }
+
+    /// Add the outcome of `PluginNodeHandle::safe_self_check`
+    ///
+    /// A check that failed (e.g. panicked) is recorded as an error on the node.
+    pub fn add_result(&mut self, node_id: NodeId, node_type: &str, result: Result<Vec<HealthIssue>, PluginError>) {
+        let issues = result.unwrap_or_else(|error| {
+            vec![HealthIssue::new(
+                HealthIssueKind::Other("self_check_failed".to_string()),
+                format!("Self-check failed: {}", error),
+            )]
+        });
+        self.add(node_id, node_type, issues);
+    }
+
+    /// Check if no node reported an error (warnings are allowed)
+    pub fn is_healthy(&self) -> bool {
+        !self.entries.iter().any(|entry| entry.issue.is_error())
+    }
+
+    /// Most severe issue level in the report (None if there are no issues)
+    pub fn worst_severity(&self) -> Option<ErrorSeverity> {
+        self.entries.iter().map(|entry| entry.issue.severity).max()
+    }
+
+    /// Issues reported by one node
+    pub fn issues_for(&self, node_id: NodeId) -> impl Iterator<Item = &HealthIssue> {
+        self.entries
+            .iter()
+            .filter(move |entry| entry.node_id == node_id)
+            .map(|entry| &entry.issue)
+    }
+
+    /// Issues sorted most severe first, for display
+    pub fn problems(&self) -> Vec<&NodeHealthIssue> {
+        let mut entries: Vec<&NodeHealthIssue> = self.entries.iter().collect();
+        entries.sort_by_key(|entry| std::cmp::Reverse(entry.issue.severity));
+        entries
+    }
+
+    /// Check if no issues were reported
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+}
@@ -3,8 +3,27 @@
 //! This module provides a trait-based system for plugins to handle their own
 //! cache clearing and resource management during the execution lifecycle.
 
-use crate::{NodeData, PluginHandle};
-use std::collections::HashMap;
+use crate::{NodeData, NodeId, PluginError, PluginHandle};
+use std::collections::{HashMap, HashSet};
+use std::sync::Arc;
+use std::time::Duration;
+
+/// Priority of hooks that clear or invalidate caches (run first)
+pub const HOOK_PRIORITY_CACHE: i32 = -100;
+
+/// Priority of hooks that do not override `NodeExecutionHooks::priority`
+pub const HOOK_PRIORITY_DEFAULT: i32 = 0;
+
+/// Priority of hooks that validate inputs or state (run after cache hooks)
+pub const HOOK_PRIORITY_VALIDATION: i32 = 100;
+
+/// Sort hooks into the order the host calls them
+/// 
+/// Lower priorities run first; hooks with equal priority keep their
+/// registration order. The same order is used for every callback.
+pub fn sort_hooks(hooks: &mut [Box<dyn NodeExecutionHooks>]) {
+    hooks.sort_by_key(|hooks| hooks.priority());
+}
 
 /// Trait for node-specific execution lifecycle hooks
 /// 
@@ -15,6 +34,29 @@ use std::collections::HashMap;
 /// - React to connection changes for cache invalidation
 /// - Perform custom setup/teardown operations
 pub trait NodeExecutionHooks: Send + Sync {
+    /// Position of these hooks among others applying to the same node
+    /// 
+    /// When several hook implementations apply to one node, the host calls
+    /// them in ascending priority, ties in registration order (see
+    /// `sort_hooks`). Cache-clearing hooks should return `HOOK_PRIORITY_CACHE`
+    /// and validation hooks `HOOK_PRIORITY_VALIDATION` so caches are always
+    /// cleared before anything is validated.
+    fn priority(&self) -> i32 {
+        HOOK_PRIORITY_DEFAULT
+    }
+    
+    /// Receive read-only access to the node graph
+    /// 
+    /// Called by the host once after registering the hooks (and again for
+    /// each clone made with `clone_box`). Store the handle to look up nodes
+    /// passed to other hooks, such as the source of `on_input_connection_added`.
+    /// 
+    /// # Arguments
+    /// * `graph` - Handle to the host's graph queries
+    fn attach_graph(&mut self, _graph: crate::graph::GraphHandle) {
+        // Default: the hooks do not need graph access
+    }
+    
     /// Called before node execution - handle cache clearing and preparation
     /// 
     /// This is where plugins should clear any internal caches, validate inputs,
@@ -31,14 +73,88 @@ pub trait NodeExecutionHooks: Send + Sync {
     /// * `Err(String)` with error message if preparation failed
     fn before_execution(
         &mut self, 
-        plugin_handle: &PluginHandle,
-        node_id: u32,
-        connections: &HashMap<String, NodeData>
+        _plugin_handle: &PluginHandle,
+        _node_id: NodeId,
+        _connections: &HashMap<String, NodeData>
     ) -> Result<(), String> {
         // Default: no special handling
         Ok(())
     }
     
+    /// Start slow preparation without blocking the host (optional)
+    /// 
+    /// Called before `before_execution`. Return a `PreparationToken` for work
+    /// that takes more than a few milliseconds (mounting volumes, warming
+    /// caches) and run it in the background; the host polls it through
+    /// `poll_preparation` and keeps the UI responsive meanwhile. See the
+    /// `preparation` module for the full protocol.
+    /// 
+    /// # Arguments
+    /// * `plugin_handle` - Handle to the plugin instance
+    /// * `node_id` - ID of the node being executed
+    /// * `connections` - Current input connections and their data
+    /// 
+    /// # Returns
+    /// * `Ok(Some(token))` if preparation was started
+    /// * `Ok(None)` if there is nothing to prepare
+    /// * `Err(String)` with error message if preparation could not be started
+    fn begin_preparation(
+        &mut self,
+        _plugin_handle: &PluginHandle,
+        _node_id: NodeId,
+        _connections: &HashMap<String, NodeData>
+    ) -> Result<Option<crate::preparation::PreparationToken>, String> {
+        // Default: everything is done in before_execution
+        Ok(None)
+    }
+    
+    /// Check on preparation started by `begin_preparation`
+    /// 
+    /// The host calls this once per UI frame until the result is finished.
+    /// Override it to drive preparation that is not running on its own
+    /// thread, e.g. by checking a pending network request.
+    /// 
+    /// # Arguments
+    /// * `plugin_handle` - Handle to the plugin instance
+    /// * `node_id` - ID of the node being prepared
+    /// * `token` - Token returned by `begin_preparation`
+    /// 
+    /// # Returns
+    /// * Current state of the preparation
+    fn poll_preparation(
+        &mut self,
+        _plugin_handle: &PluginHandle,
+        _node_id: NodeId,
+        token: &crate::preparation::PreparationToken
+    ) -> crate::preparation::PreparationStatus {
+        // Default: the token is finished by its worker
+        token.status()
+    }
+    
+    /// Called before node execution, with the host's execution context
+    /// 
+    /// Hosts call this instead of `before_execution`; the default forwards to
+    /// `before_execution` with `ctx.node_id`. Override it to use the rest of
+    /// the context (frame, dirty reasons, logger, graph).
+    /// 
+    /// # Arguments
+    /// * `plugin_handle` - Handle to the plugin instance
+    /// * `connections` - Current input connections and their data
+    /// * `ctx` - Context of the cook (`node_id` is always set)
+    /// 
+    /// # Returns
+    /// * `Ok(())` if preparation succeeded
+    /// * `Err(String)` with error message if preparation failed
+    fn before_execution_with_context(
+        &mut self,
+        plugin_handle: &PluginHandle,
+        connections: &HashMap<String, NodeData>,
+        ctx: &crate::context::ExecutionContext
+    ) -> Result<(), String> {
+        let node_id = ctx.node_id.ok_or("Execution context has no node ID")?;
+        self.before_execution(plugin_handle, node_id, connections)
+    }
+    
     /// Called after successful node execution - handle caching and cleanup
     /// 
     /// This is where plugins can cache results, update internal state,
@@ -54,14 +170,118 @@ pub trait NodeExecutionHooks: Send + Sync {
     /// * `Err(String)` with error message if post-processing failed
     fn after_execution(
         &mut self, 
-        plugin_handle: &PluginHandle,
-        node_id: u32,
-        outputs: &HashMap<String, NodeData>
+        _plugin_handle: &PluginHandle,
+        _node_id: NodeId,
+        _outputs: &HashMap<String, NodeData>
     ) -> Result<(), String> {
         // Default: no special handling
         Ok(())
     }
     
+    /// Called after successful node execution with provenance information
+    /// 
+    /// Provides the evaluated time/frame range and content hashes of every input
+    /// and output, so plugins (e.g. render-farm submitters) can record exactly
+    /// what was cooked. The host calls this instead of `after_execution`; the
+    /// default implementation forwards to `after_execution`.
+    /// 
+    /// # Arguments
+    /// * `plugin_handle` - Handle to the plugin instance
+    /// * `node_id` - ID of the node that was executed
+    /// * `outputs` - The output data produced by execution
+    /// * `provenance` - Evaluated time and input/output hashes
+    fn after_execution_with_provenance(
+        &mut self,
+        plugin_handle: &PluginHandle,
+        node_id: NodeId,
+        outputs: &HashMap<String, NodeData>,
+        _provenance: &ExecutionProvenance
+    ) -> Result<(), String> {
+        self.after_execution(plugin_handle, node_id, outputs)
+    }
+    
+    /// Called after successful node execution, with the host's execution context
+    /// 
+    /// Hosts call this instead of `after_execution`. The default forwards to
+    /// `after_execution_with_provenance` if the host computed provenance and
+    /// to `after_execution` otherwise.
+    /// 
+    /// # Arguments
+    /// * `plugin_handle` - Handle to the plugin instance
+    /// * `outputs` - The output data produced by execution
+    /// * `provenance` - What produced the outputs, if the host tracks it
+    /// * `ctx` - Context of the cook (`node_id` is always set)
+    /// 
+    /// # Returns
+    /// * `Ok(())` if post-processing succeeded
+    /// * `Err(String)` with error message if post-processing failed
+    fn after_execution_with_context(
+        &mut self,
+        plugin_handle: &PluginHandle,
+        outputs: &HashMap<String, NodeData>,
+        provenance: Option<&ExecutionProvenance>,
+        ctx: &crate::context::ExecutionContext
+    ) -> Result<(), String> {
+        let node_id = ctx.node_id.ok_or("Execution context has no node ID")?;
+        match provenance {
+            Some(provenance) => self.after_execution_with_provenance(plugin_handle, node_id, outputs, provenance),
+            None => self.after_execution(plugin_handle, node_id, outputs),
+        }
+    }
+    
+    /// Called when cooking the node failed - clean up and decide what happens next
+    /// 
+    /// Use this to discard partial state left by the failed cook and to retry
+    /// transient failures such as flaky network reads (see `RetryPolicy`).
+    /// The host cooks the node again after the requested delay, calling
+    /// `before_execution` again first; `attempt` counts cooks of the current
+    /// evaluation.
+    /// 
+    /// # Arguments
+    /// * `plugin_handle` - Handle to the plugin instance
+    /// * `node_id` - ID of the node that failed
+    /// * `error` - Error returned by the cook (panics are reported as errors too)
+    /// * `attempt` - Number of the failed attempt, starting at 1
+    /// 
+    /// # Returns
+    /// * What the host should do next
+    fn on_execution_failed(
+        &mut self,
+        _plugin_handle: &PluginHandle,
+        _node_id: NodeId,
+        _error: &PluginError,
+        _attempt: u32
+    ) -> FailureAction {
+        // Default: fail the node and let the error flow downstream
+        FailureAction::Skip
+    }
+    
+    /// Called when cooking the node failed, with the host's execution context
+    /// 
+    /// Hosts call this instead of `on_execution_failed`; the default forwards
+    /// to it with `ctx.node_id`, skipping the node if the ID is missing.
+    /// 
+    /// # Arguments
+    /// * `plugin_handle` - Handle to the plugin instance
+    /// * `error` - Error returned by the cook
+    /// * `attempt` - Number of the failed attempt, starting at 1
+    /// * `ctx` - Context of the cook (`node_id` is always set)
+    /// 
+    /// # Returns
+    /// * What the host should do next
+    fn on_execution_failed_with_context(
+        &mut self,
+        plugin_handle: &PluginHandle,
+        error: &PluginError,
+        attempt: u32,
+        ctx: &crate::context::ExecutionContext
+    ) -> FailureAction {
+        match ctx.node_id {
+            Some(node_id) => self.on_execution_failed(plugin_handle, node_id, error, attempt),
+            None => FailureAction::Skip,
+        }
+    }
+    
     /// Called when node is removed from graph - handle cleanup
     /// 
     /// This is where plugins should clean up any resources, caches, or
@@ -76,13 +296,91 @@ pub trait NodeExecutionHooks: Send + Sync {
     /// * `Err(String)` with error message if cleanup failed
     fn on_node_removed(
         &mut self, 
-        plugin_handle: &PluginHandle,
-        node_id: u32
+        _plugin_handle: &PluginHandle,
+        _node_id: NodeId
     ) -> Result<(), String> {
         // Default: no special handling
         Ok(())
     }
     
+    /// Called when the project is saved - return opaque per-node state to persist
+    /// 
+    /// For state beyond parameters that is expensive or impossible to rebuild
+    /// (accumulated simulation, learned LUTs). The host stores the bytes in
+    /// the project file with the node and hands them back to `on_load`. Keep
+    /// a version marker in the bytes so later plugin versions can read or
+    /// discard old state.
+    /// 
+    /// # Arguments
+    /// * `plugin_handle` - Handle to the plugin instance
+    /// * `node_id` - ID of the node being saved
+    /// 
+    /// # Returns
+    /// * `Ok(bytes)` with the state to store (empty stores nothing)
+    /// * `Err(String)` with error message if the state could not be written;
+    ///   the project is still saved, without state for this node
+    fn on_save(
+        &mut self,
+        _plugin_handle: &PluginHandle,
+        _node_id: NodeId
+    ) -> Result<Vec<u8>, String> {
+        // Default: no state beyond parameters
+        Ok(Vec::new())
+    }
+    
+    /// Called when a project is loaded - restore state returned by `on_save`
+    /// 
+    /// The host calls this after the node was created, its parameters set and
+    /// `PluginNode::on_created` called, and only if state was stored for it.
+    /// 
+    /// # Arguments
+    /// * `plugin_handle` - Handle to the plugin instance
+    /// * `node_id` - ID of the node being loaded
+    /// * `state` - Bytes returned by `on_save` when the project was saved
+    /// 
+    /// # Returns
+    /// * `Ok(())` if the state was restored
+    /// * `Err(String)` with error message if the state could not be read; the
+    ///   node is kept and starts without its saved state
+    fn on_load(
+        &mut self,
+        _plugin_handle: &PluginHandle,
+        _node_id: NodeId,
+        _state: &[u8]
+    ) -> Result<(), String> {
+        // Default: no state beyond parameters
+        Ok(())
+    }
+    
+    /// Called after a node of this plugin was duplicated (copy/paste or duplicate)
+    /// 
+    /// Plugins whose cached data depends only on parameters and inputs (which
+    /// the duplicate shares) can copy the entries with
+    /// `PluginCache::copy_node_entries` so the new node starts warm instead of
+    /// reloading everything. Per-node state kept outside the node (e.g. in a
+    /// `PluginState` map keyed by node ID) should be copied here too; state
+    /// inside the node is carried over by `PluginNode::duplicate`.
+    /// 
+    /// # Arguments
+    /// * `plugin_handle` - Handle to the plugin instance
+    /// * `cache` - The host cache the plugin's entries live in
+    /// * `source_node_id` - ID of the node that was duplicated
+    /// * `new_node_id` - ID of the duplicate
+    /// 
+    /// # Returns
+    /// * `Ok(())` if handling succeeded
+    /// * `Err(String)` with error message if handling failed
+    fn on_node_duplicated(
+        &mut self,
+        _plugin_handle: &PluginHandle,
+        _cache: &mut dyn crate::cache::PluginCache,
+        _source_node_id: NodeId,
+        _new_node_id: NodeId
+    ) -> Result<(), String> {
+        // Default: the duplicate starts with an empty cache
+        Ok(())
+    }
+    
     /// Called when a connection is added TO this node (this node receives new input)
     /// 
     /// This allows plugins to invalidate caches or update internal state when
@@ -99,10 +397,10 @@ pub trait NodeExecutionHooks: Send + Sync {
     /// * `Err(String)` with error message if handling failed
     fn on_input_connection_added(
         &mut self, 
-        plugin_handle: &PluginHandle,
-        node_id: u32,
-        input_port: &str,
-        source_node_id: u32
+        _plugin_handle: &PluginHandle,
+        _node_id: NodeId,
+        _input_port: &str,
+        _source_node_id: NodeId
     ) -> Result<(), String> {
         // Default: no special handling
         Ok(())
@@ -124,10 +422,10 @@ pub trait NodeExecutionHooks: Send + Sync {
     /// * `Err(String)` with error message if handling failed
     fn on_input_connection_removed(
         &mut self, 
-        plugin_handle: &PluginHandle,
-        node_id: u32,
-        input_port: &str,
-        source_node_id: u32
+        _plugin_handle: &PluginHandle,
+        _node_id: NodeId,
+        _input_port: &str,
+        _source_node_id: NodeId
     ) -> Result<(), String> {
         // Default: no special handling
         Ok(())
@@ -150,16 +448,334 @@ pub trait NodeExecutionHooks: Send + Sync {
     /// * `Err(String)` with error message if handling failed
     fn on_parameter_changed(
         &mut self,
-        plugin_handle: &PluginHandle,
-        node_id: u32,
-        parameter_name: &str,
-        old_value: &NodeData,
-        new_value: &NodeData
+        _plugin_handle: &PluginHandle,
+        _node_id: NodeId,
+        _parameter_name: &str,
+        _old_value: &NodeData,
+        _new_value: &NodeData
+    ) -> Result<(), String> {
+        // Default: no special handling
+        Ok(())
+    }
+    
+    /// Called after the host undid a history step that touched this node
+    /// 
+    /// The host has already restored the node's parameters through
+    /// `set_parameter`, which does not go through `on_parameter_changed`. Use
+    /// this to bring caches and internal state derived from those parameters
+    /// back in sync.
+    /// 
+    /// # Arguments
+    /// * `plugin_handle` - Handle to the plugin instance
+    /// * `node_id` - ID of the node the step touched
+    /// * `change_description` - Label of the undone step in the host's history
+    ///   (e.g. "Set Roughness")
+    /// 
+    /// # Returns
+    /// * `Ok(())` if handling succeeded
+    /// * `Err(String)` with error message if handling failed
+    fn on_undo(
+        &mut self,
+        _plugin_handle: &PluginHandle,
+        _node_id: NodeId,
+        _change_description: &str
+    ) -> Result<(), String> {
+        // Default: no special handling
+        Ok(())
+    }
+    
+    /// Called after the host redid a history step that touched this node
+    /// 
+    /// Counterpart of `on_undo`; the node's parameters already hold the
+    /// redone values.
+    /// 
+    /// # Arguments
+    /// * `plugin_handle` - Handle to the plugin instance
+    /// * `node_id` - ID of the node the step touched
+    /// * `change_description` - Label of the redone step in the host's history
+    /// 
+    /// # Returns
+    /// * `Ok(())` if handling succeeded
+    /// * `Err(String)` with error message if handling failed
+    fn on_redo(
+        &mut self,
+        _plugin_handle: &PluginHandle,
+        _node_id: NodeId,
+        _change_description: &str
     ) -> Result<(), String> {
         // Default: no special handling
         Ok(())
     }
     
+    /// Called when a panel showing this node is opened
+    /// 
+    /// Nodes can allocate heavy per-panel resources (GPU-side scenes, preview
+    /// renderers) here instead of keeping them alive while nobody looks. A
+    /// node may have several panels open at once; each open and close is
+    /// reported separately.
+    /// 
+    /// # Arguments
+    /// * `plugin_handle` - Handle to the plugin instance
+    /// * `node_id` - ID of the node the panel shows
+    /// * `panel_type` - Type of the opened panel
+    /// 
+    /// # Returns
+    /// * `Ok(())` if handling succeeded
+    /// * `Err(String)` with error message if handling failed
+    fn on_panel_opened(
+        &mut self,
+        _plugin_handle: &PluginHandle,
+        _node_id: NodeId,
+        _panel_type: crate::ui::PanelType
+    ) -> Result<(), String> {
+        // Default: no per-panel resources
+        Ok(())
+    }
+    
+    /// Called when a panel showing this node is closed
+    /// 
+    /// Release resources allocated in `on_panel_opened`. Also called for every
+    /// open panel before the node is removed.
+    /// 
+    /// # Arguments
+    /// * `plugin_handle` - Handle to the plugin instance
+    /// * `node_id` - ID of the node the panel showed
+    /// * `panel_type` - Type of the closed panel
+    /// 
+    /// # Returns
+    /// * `Ok(())` if handling succeeded
+    /// * `Err(String)` with error message if handling failed
+    fn on_panel_closed(
+        &mut self,
+        _plugin_handle: &PluginHandle,
+        _node_id: NodeId,
+        _panel_type: crate::ui::PanelType
+    ) -> Result<(), String> {
+        // Default: no per-panel resources
+        Ok(())
+    }
+    
+    /// Called when the user bypasses a node or turns bypass off
+    /// 
+    /// While bypassed the node is not cooked; the host routes inputs to
+    /// outputs as declared by `PluginNode::bypass_mapping`. Plugins can release
+    /// resources held for the node here and rebuild them when bypass is
+    /// turned off.
+    /// 
+    /// # Arguments
+    /// * `plugin_handle` - Handle to the plugin instance
+    /// * `node_id` - ID of the node being bypassed
+    /// * `bypassed` - `true` if bypass was turned on, `false` if turned off
+    /// 
+    /// # Returns
+    /// * `Ok(())` if handling succeeded
+    /// * `Err(String)` with error message if handling failed
+    fn on_node_bypassed(
+        &mut self,
+        _plugin_handle: &PluginHandle,
+        _node_id: NodeId,
+        _bypassed: bool
+    ) -> Result<(), String> {
+        // Default: no special handling
+        Ok(())
+    }
+    
+    /// Called when the user switches the active workspace (e.g. "3D", "MaterialX")
+    /// 
+    /// The host calls this for each of the plugin's nodes in the graph. Nodes
+    /// can adjust defaults or hide parameters that do not apply; the next
+    /// `get_parameter_ui` call should reflect the change. Workspace names
+    /// match `NodeMetadata::workspace_compatibility`.
+    /// 
+    /// # Arguments
+    /// * `plugin_handle` - Handle to the plugin instance
+    /// * `node_id` - ID of the node being notified
+    /// * `workspace` - Name of the now active workspace
+    /// 
+    /// # Returns
+    /// * `Ok(())` if handling succeeded
+    /// * `Err(String)` with error message if handling failed
+    fn on_workspace_changed(
+        &mut self,
+        _plugin_handle: &PluginHandle,
+        _node_id: NodeId,
+        _workspace: &str
+    ) -> Result<(), String> {
+        // Default: no special handling
+        Ok(())
+    }
+    
+    /// Called once per UI frame for `ExecutionMode::Realtime` nodes
+    /// 
+    /// Simulation and playback nodes advance their internal state here by
+    /// `delta_seconds`. The host ticks all realtime nodes before evaluating
+    /// the graph for the UI frame and recooks those that report a change,
+    /// with `DirtyReason::Tick`. Keep it cheap: it runs on the UI thread.
+    /// 
+    /// # Arguments
+    /// * `plugin_handle` - Handle to the plugin instance
+    /// * `node_id` - ID of the node being ticked
+    /// * `delta_seconds` - Wall-clock time since the node's previous tick
+    ///   (0.0 on the first tick after the node was created or resumed)
+    /// 
+    /// # Returns
+    /// * `Ok(true)` if the node's state changed and it should be recooked
+    /// * `Ok(false)` if nothing changed
+    /// * `Err(String)` with error message if the tick failed
+    fn on_tick(
+        &mut self,
+        _plugin_handle: &PluginHandle,
+        _node_id: NodeId,
+        _delta_seconds: f64
+    ) -> Result<bool, String> {
+        // Default: recook every frame, as realtime nodes always did
+        Ok(true)
+    }
+    
+    /// Called when the current frame changes
+    /// 
+    /// The host calls this for each of the plugin's nodes on playback,
+    /// scrubbing and frame jumps, before re-evaluating them, so time-dependent
+    /// nodes can drop per-frame state (e.g. `FrameCache::set_current_frame`)
+    /// without polling the timeline. Frames are fractional during sub-frame
+    /// evaluation.
+    /// 
+    /// # Arguments
+    /// * `plugin_handle` - Handle to the plugin instance
+    /// * `node_id` - ID of the node being notified
+    /// * `old_frame` - Previous current frame
+    /// * `new_frame` - New current frame
+    /// 
+    /// # Returns
+    /// * `Ok(())` if handling succeeded
+    /// * `Err(String)` with error message if handling failed
+    fn on_frame_changed(
+        &mut self,
+        _plugin_handle: &PluginHandle,
+        _node_id: NodeId,
+        _old_frame: f64,
+        _new_frame: f64
+    ) -> Result<(), String> {
+        // Default: not time-dependent
+        Ok(())
+    }
+    
+    /// Called once before the host cooks any node of an evaluation
+    /// 
+    /// Use this for per-evaluation setup shared by all of the plugin's nodes,
+    /// such as creating a GPU context, instead of repeating it in every
+    /// `before_execution`. The host only calls this when at least one node
+    /// handled by these hooks is dirty, and calls `on_graph_evaluation_finished`
+    /// with the same ID afterwards if this returns `Ok`.
+    /// 
+    /// # Arguments
+    /// * `plugin_handle` - Handle to the plugin instance
+    /// * `evaluation_id` - Unique ID of the evaluation (increasing per session)
+    /// * `dirty_nodes` - Nodes handled by these hooks that will be cooked
+    /// 
+    /// # Returns
+    /// * `Ok(())` if setup succeeded
+    /// * `Err(String)` to fail the evaluation of `dirty_nodes`
+    fn on_graph_evaluation_started(
+        &mut self,
+        _plugin_handle: &PluginHandle,
+        _evaluation_id: u64,
+        _dirty_nodes: &HashSet<NodeId>
+    ) -> Result<(), String> {
+        // Default: no per-evaluation setup
+        Ok(())
+    }
+    
+    /// Called once after the last node of an evaluation was cooked
+    /// 
+    /// Also called when the evaluation was cancelled or failed, so resources
+    /// created in `on_graph_evaluation_started` can always be released.
+    /// 
+    /// # Arguments
+    /// * `plugin_handle` - Handle to the plugin instance
+    /// * `evaluation_id` - ID passed to `on_graph_evaluation_started`
+    /// * `dirty_nodes` - Nodes handled by these hooks that were scheduled to cook
+    /// 
+    /// # Returns
+    /// * `Ok(())` if teardown succeeded
+    /// * `Err(String)` with error message if teardown failed
+    fn on_graph_evaluation_finished(
+        &mut self,
+        _plugin_handle: &PluginHandle,
+        _evaluation_id: u64,
+        _dirty_nodes: &HashSet<NodeId>
+    ) -> Result<(), String> {
+        // Default: no per-evaluation teardown
+        Ok(())
+    }
+    
+    /// Called when the selection in the host's node editor changes
+    /// 
+    /// Called once per registered hooks instance rather than per node, so
+    /// plugin panels (tree views, inspectors) can follow what the user
+    /// selected. The selection includes nodes of every plugin and of the host.
+    /// 
+    /// # Arguments
+    /// * `plugin_handle` - Handle to the plugin instance
+    /// * `selected_node_ids` - Selected nodes in selection order (the last one
+    ///   is the most recently selected); empty when the selection was cleared
+    /// 
+    /// # Returns
+    /// * `Ok(())` if handling succeeded
+    /// * `Err(String)` with error message if handling failed
+    fn on_selection_changed(
+        &mut self,
+        _plugin_handle: &PluginHandle,
+        _selected_node_ids: &[NodeId]
+    ) -> Result<(), String> {
+        // Default: no special handling
+        Ok(())
+    }
+    
+    /// Called when the host needs memory back
+    /// 
+    /// Plugins should drop reconstructible data (typically through
+    /// `PluginCacheManager::shed`) before the host starts evicting blindly. The
+    /// host calls this on every registered hooks instance until enough memory
+    /// has been freed.
+    /// 
+    /// # Arguments
+    /// * `plugin_handle` - Handle to the plugin instance
+    /// * `cache` - The host cache the plugin's entries live in
+    /// * `level` - How urgently memory is needed
+    /// * `bytes_requested` - How much memory the host would like back
+    /// 
+    /// # Returns
+    /// * Estimated number of bytes freed
+    fn on_memory_pressure(
+        &mut self,
+        _plugin_handle: &PluginHandle,
+        _cache: &mut dyn crate::cache::PluginCache,
+        _level: crate::cache::MemoryPressureLevel,
+        _bytes_requested: usize
+    ) -> usize {
+        // Default: nothing to free
+        0
+    }
+    
+    /// Called after the host evicted some of this plugin's cache entries
+    /// 
+    /// Lets plugins release derived resources (GPU buffers, textures) built from
+    /// the evicted CPU-side data, and untrack the keys with
+    /// `PluginCacheManager::handle_evictions`. The host batches evictions per
+    /// plugin and calls this once the cache is no longer borrowed.
+    /// 
+    /// # Arguments
+    /// * `plugin_handle` - Handle to the plugin instance
+    /// * `evictions` - The evicted entries (all belonging to this plugin)
+    fn on_cache_evicted(
+        &mut self,
+        _plugin_handle: &PluginHandle,
+        _evictions: &[crate::cache::CacheEviction]
+    ) {
+        // Default: no derived resources to release
+    }
+    
     /// Clone the hooks for registration
     /// 
     /// This is required for the plugin system to manage hook instances.
@@ -167,6 +783,128 @@ pub trait NodeExecutionHooks: Send + Sync {
     fn clone_box(&self) -> Box<dyn NodeExecutionHooks>;
 }
 
+/// Provenance of a single node execution
+#[derive(Debug, Clone, Default)]
+pub struct ExecutionProvenance {
+    /// Evaluated time in seconds
+    pub time: f32,
+    /// Frame range that was cooked (inclusive), if the evaluation covered frames
+    pub frame_range: Option<(i32, i32)>,
+    /// Content hash of each input by port name
+    pub input_hashes: HashMap<String, u64>,
+    /// Content hash of each output by port name
+    pub output_hashes: HashMap<String, u64>,
+}
+
+impl ExecutionProvenance {
+    /// Build provenance by hashing the inputs and outputs of an execution
+    pub fn compute(
+        time: f32,
+        frame_range: Option<(i32, i32)>,
+        inputs: &HashMap<String, NodeData>,
+        outputs: &HashMap<String, NodeData>
+    ) -> Self {
+        Self {
+            time,
+            frame_range,
+            input_hashes: crate::hashing::hash_port_map(inputs),
+            output_hashes: crate::hashing::hash_port_map(outputs),
+        }
+    }
+    
+    /// Combined hash of all inputs (independent of port iteration order)
+    pub fn inputs_hash(&self) -> u64 {
+        let mut hashes: Vec<(&String, &u64)> = self.input_hashes.iter().collect();
+        hashes.sort();
+        crate::hashing::combine_hashes(hashes.into_iter().map(|(name, hash)| {
+            crate::hashing::combine_hashes([crate::hashing::content_hash(name.as_str()), *hash])
+        }))
+    }
+}
+
+/// What the host does after a node failed to cook
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum FailureAction {
+    /// Cook the node again after waiting `delay`
+    Retry { delay: Duration },
+    /// Give up on the node: its outputs become `NodeData::Error` and the rest
+    /// of the graph is still evaluated
+    Skip,
+    /// Stop evaluating the whole graph
+    Abort,
+}
+
+/// Retry schedule for `NodeExecutionHooks::on_execution_failed`
+/// 
+/// ```rust,ignore
+/// fn on_execution_failed(&mut self, _: &PluginHandle, _: NodeId, _: &PluginError, attempt: u32) -> FailureAction {
+///     RetryPolicy::new(3, Duration::from_millis(500)).with_backoff(2.0).action(attempt)
+/// }
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RetryPolicy {
+    /// Total number of attempts, including the first cook
+    pub max_attempts: u32,
+    /// Delay before the first retry
+    pub delay: Duration,
+    /// Factor the delay is multiplied by after each retry (1.0 = constant)
+    pub backoff: f64,
+    /// Longest delay backoff can grow to
+    pub max_delay: Duration,
+    /// What to do once all attempts failed
+    pub exhausted: FailureAction,
+}
+
+impl RetryPolicy {
+    /// Default for `max_delay`
+    pub const DEFAULT_MAX_DELAY: Duration = Duration::from_secs(300);
+    
+    /// Retry with a constant delay, then skip the node
+    pub fn new(max_attempts: u32, delay: Duration) -> Self {
+        Self {
+            max_attempts,
+            delay,
+            backoff: 1.0,
+            max_delay: Self::DEFAULT_MAX_DELAY.max(delay),
+            exhausted: FailureAction::Skip,
+        }
+    }
+    
+    /// Multiply the delay by `factor` after each retry
+    /// 
+    /// Non-finite factors count as 1.0 (constant delay).
+    pub fn with_backoff(mut self, factor: f64) -> Self {
+        self.backoff = if factor.is_finite() { factor.max(1.0) } else { 1.0 };
+        self
+    }
+    
+    /// Cap the delay backoff can grow to
+    pub fn with_max_delay(mut self, max_delay: Duration) -> Self {
+        self.max_delay = max_delay;
+        self
+    }
+    
+    /// Abort the evaluation instead of skipping once all attempts failed
+    pub fn abort_when_exhausted(mut self) -> Self {
+        self.exhausted = FailureAction::Abort;
+        self
+    }
+    
+    /// Action for a failed attempt (numbered from 1)
+    pub fn action(&self, attempt: u32) -> FailureAction {
+        if attempt >= self.max_attempts {
+            return self.exhausted;
+        }
+        let retries = attempt.saturating_sub(1).min(i32::MAX as u32) as i32;
+        // Growing past Duration::MAX (or a NaN factor) falls back to the cap
+        let delay = Duration::try_from_secs_f64(self.delay.as_secs_f64() * self.backoff.powi(retries))
+            .unwrap_or(self.max_delay);
+        FailureAction::Retry {
+            delay: delay.min(self.max_delay),
+        }
+    }
+}
+
 /// Default implementation for nodes that don't need special handling
 /// 
 /// Plugins that don't need lifecycle hooks can use this default implementation
@@ -180,26 +918,128 @@ impl NodeExecutionHooks for DefaultHooks {
     }
 }
 
+/// Family of nodes a hook registration applies to
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum HookFilter {
+    /// Nodes of one type (`NodeMetadata::node_type`)
+    NodeType(String),
+    /// Nodes in a category or any of its subcategories
+    Category(crate::NodeCategory),
+    /// Nodes carrying a tag (`NodeMetadata::tags`)
+    Tag(String),
+    /// Every node type registered by the plugin providing the hooks
+    AllPluginNodes,
+}
+
+impl HookFilter {
+    /// Check if a node matches
+    /// 
+    /// `from_plugin` tells whether the node's type was registered by the
+    /// plugin that provided the hooks.
+    pub fn matches(&self, metadata: &crate::NodeMetadata, from_plugin: bool) -> bool {
+        match self {
+            HookFilter::NodeType(node_type) => &metadata.node_type == node_type,
+            HookFilter::Category(category) => {
+                &metadata.category == category || metadata.category.is_child_of(category)
+            }
+            HookFilter::Tag(tag) => metadata.tags.contains(tag),
+            HookFilter::AllPluginNodes => from_plugin,
+        }
+    }
+}
+
+/// Predicate deciding per node instance whether hooks apply
+pub type NodePredicate = Arc<dyn Fn(NodeId) -> bool + Send + Sync>;
+
 /// Hook registration information
 /// 
 /// This struct is used to register hooks with the main application's execution engine.
 /// Plugins provide this when they want to participate in lifecycle management.
-#[derive(Debug, Clone)]
+/// 
+/// One registration can serve a whole family of nodes:
+/// 
+/// ```rust,ignore
+/// HookRegistration::for_filter(HookFilter::Tag("usd".into()), "USD stage cache")
+///     .with_filter(HookFilter::Category(NodeCategory::new(&["3D", "USD"])))
+///     .with_node_predicate(move |id| !excluded.contains(&id));
+/// ```
+/// 
+/// A node predicate is code from the plugin's library, so the host must drop
+/// every registration (and clone of it) before unloading the plugin.
+#[derive(Clone)]
 pub struct HookRegistration {
-    /// The node type ID that these hooks apply to
-    pub node_type_id: String,
+    /// The node type ID that these hooks apply to (None if only `filters` are used)
+    pub node_type_id: Option<String>,
     /// Description of what these hooks do (for debugging)
     pub description: String,
+    /// Further node families the hooks apply to (any match is enough)
+    pub filters: Vec<HookFilter>,
+    /// Optional check on individual nodes, applied after the type filters
+    pub node_predicate: Option<NodePredicate>,
 }
 
 impl HookRegistration {
     /// Create a new hook registration
     pub fn new(node_type_id: impl Into<String>, description: impl Into<String>) -> Self {
         Self {
-            node_type_id: node_type_id.into(),
+            node_type_id: Some(node_type_id.into()),
+            description: description.into(),
+            filters: Vec::new(),
+            node_predicate: None,
+        }
+    }
+    
+    /// Create a registration for a family of nodes
+    pub fn for_filter(filter: HookFilter, description: impl Into<String>) -> Self {
+        Self {
+            node_type_id: None,
             description: description.into(),
+            filters: vec![filter],
+            node_predicate: None,
         }
     }
+    
+    /// Create a registration for every node type of the plugin
+    pub fn for_plugin(description: impl Into<String>) -> Self {
+        Self::for_filter(HookFilter::AllPluginNodes, description)
+    }
+    
+    /// Also apply to nodes matching a filter
+    pub fn with_filter(mut self, filter: HookFilter) -> Self {
+        self.filters.push(filter);
+        self
+    }
+    
+    /// Only apply to nodes for which `predicate` returns true
+    pub fn with_node_predicate(mut self, predicate: impl Fn(NodeId) -> bool + Send + Sync + 'static) -> Self {
+        self.node_predicate = Some(Arc::new(predicate));
+        self
+    }
+    
+    /// Check if the hooks apply to a node
+    /// 
+    /// `from_plugin` tells whether the node's type was registered by the
+    /// plugin that provided the hooks. A node predicate that panics counts as
+    /// not matching.
+    pub fn matches(&self, node_id: NodeId, metadata: &crate::NodeMetadata, from_plugin: bool) -> bool {
+        let type_matches = self.node_type_id.as_deref() == Some(metadata.node_type.as_str())
+            || self.filters.iter().any(|filter| filter.matches(metadata, from_plugin));
+        type_matches
+            && self.node_predicate.as_ref().map_or(true, |predicate| {
+                crate::guard::catch_plugin_panic("node_predicate", || predicate(node_id)).unwrap_or(false)
+            })
+    }
+}
+
+impl std::fmt::Debug for HookRegistration {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("HookRegistration")
+            .field("node_type_id", &self.node_type_id)
+            .field("description", &self.description)
+            .field("filters", &self.filters)
+            .field("node_predicate", &self.node_predicate.as_ref().map(|_| ".."))
+            .finish()
+    }
 }
 
 /// Cache management utilities for plugins
@@ -207,7 +1047,7 @@ impl HookRegistration {
 /// These utilities help plugins work with the main application's cache system
 /// without exposing the full complexity of the internal cache implementation.
 pub mod cache_utils {
-    use super::*;
+    use crate::NodeId;
     
     /// Cache key for plugin data
     /// 
@@ -218,7 +1058,7 @@ pub mod cache_utils {
         /// Plugin identifier
         pub plugin_id: String,
         /// Node ID
-        pub node_id: u32,
+        pub node_id: NodeId,
         /// Optional stage identifier for multi-stage operations
         pub stage_id: Option<String>,
         /// Port or data identifier
@@ -227,7 +1067,7 @@ pub mod cache_utils {
     
     impl PluginCacheKey {
         /// Create a simple cache key for single-stage operations
-        pub fn simple(plugin_id: impl Into<String>, node_id: u32, data_id: impl Into<String>) -> Self {
+        pub fn simple(plugin_id: impl Into<String>, node_id: NodeId, data_id: impl Into<String>) -> Self {
             Self {
                 plugin_id: plugin_id.into(),
                 node_id,
@@ -239,7 +1079,7 @@ pub mod cache_utils {
         /// Create a cache key for multi-stage operations (like USD File Reader)
         pub fn with_stage(
             plugin_id: impl Into<String>, 
-            node_id: u32, 
+            node_id: NodeId, 
             stage_id: impl Into<String>,
             data_id: impl Into<String>
         ) -> Self {
@@ -267,9 +1107,9 @@ pub mod cache_utils {
     #[derive(Debug, Clone)]
     pub enum CacheInvalidationPattern {
         /// Invalidate all cache entries for a specific node
-        AllForNode(u32),
+        AllForNode(NodeId),
         /// Invalidate all cache entries for a specific stage of a node
-        StageForNode(u32, String),
+        StageForNode(NodeId, String),
         /// Invalidate a specific cache entry
         Specific(PluginCacheKey),
         /// Invalidate all cache entries for this plugin
@@ -285,8 +1125,6 @@ pub mod cache_utils {
 pub struct ExampleAdvancedHooks {
     /// Plugin identifier
     plugin_id: String,
-    /// Cache keys currently managed by this plugin
-    managed_keys: Vec<cache_utils::PluginCacheKey>,
 }
 
 impl ExampleAdvancedHooks {
@@ -294,16 +1132,20 @@ impl ExampleAdvancedHooks {
     pub fn new(plugin_id: impl Into<String>) -> Self {
         Self {
             plugin_id: plugin_id.into(),
-            managed_keys: Vec::new(),
         }
     }
 }
 
 impl NodeExecutionHooks for ExampleAdvancedHooks {
+    fn priority(&self) -> i32 {
+        // Example: Clears caches, so run before validation hooks
+        HOOK_PRIORITY_CACHE
+    }
+    
     fn before_execution(
         &mut self, 
         _plugin_handle: &PluginHandle,
-        node_id: u32,
+        node_id: NodeId,
         _connections: &HashMap<String, NodeData>
     ) -> Result<(), String> {
         // Example: Clear any temporary caches before execution
@@ -321,7 +1163,7 @@ impl NodeExecutionHooks for ExampleAdvancedHooks {
     fn after_execution(
         &mut self, 
         _plugin_handle: &PluginHandle,
-        node_id: u32,
+        node_id: NodeId,
         outputs: &HashMap<String, NodeData>
     ) -> Result<(), String> {
         // Example: Cache results after successful execution
@@ -337,12 +1179,45 @@ impl NodeExecutionHooks for ExampleAdvancedHooks {
         Ok(())
     }
     
+    fn on_execution_failed(
+        &mut self,
+        _plugin_handle: &PluginHandle,
+        node_id: NodeId,
+        error: &PluginError,
+        attempt: u32
+    ) -> FailureAction {
+        // Example: Retry transient failures a few times with backoff
+        println!("🔧 Plugin {}: Node {} failed (attempt {}): {}", 
+                 self.plugin_id, node_id, attempt, error);
+        
+        // In a real implementation, you might:
+        // - Discard partially written cache entries
+        // - Only retry errors known to be transient (timeouts, busy files)
+        
+        RetryPolicy::new(3, Duration::from_millis(250)).with_backoff(2.0).action(attempt)
+    }
+    
+    fn on_node_duplicated(
+        &mut self,
+        _plugin_handle: &PluginHandle,
+        cache: &mut dyn crate::cache::PluginCache,
+        source_node_id: NodeId,
+        new_node_id: NodeId
+    ) -> Result<(), String> {
+        // Example: Share the source node's cached data with the duplicate
+        let copied = cache.copy_node_entries(&self.plugin_id, source_node_id, new_node_id);
+        println!("📋 Plugin {}: Copied {} cache entries from node {} to node {}", 
+                 self.plugin_id, copied, source_node_id, new_node_id);
+        
+        Ok(())
+    }
+    
     fn on_input_connection_added(
         &mut self, 
         _plugin_handle: &PluginHandle,
-        node_id: u32,
+        node_id: NodeId,
         input_port: &str,
-        source_node_id: u32
+        source_node_id: NodeId
     ) -> Result<(), String> {
         // Example: Invalidate caches when inputs change
         println!("🔗 Plugin {}: New connection to node {} port {} from node {}", 
@@ -359,7 +1234,7 @@ impl NodeExecutionHooks for ExampleAdvancedHooks {
     fn on_parameter_changed(
         &mut self,
         _plugin_handle: &PluginHandle,
-        node_id: u32,
+        node_id: NodeId,
         parameter_name: &str,
         _old_value: &NodeData,
         _new_value: &NodeData
@@ -376,7 +1251,161 @@ impl NodeExecutionHooks for ExampleAdvancedHooks {
         Ok(())
     }
     
+    fn on_tick(
+        &mut self,
+        _plugin_handle: &PluginHandle,
+        node_id: NodeId,
+        delta_seconds: f64
+    ) -> Result<bool, String> {
+        // Example: Advance a simulation by the elapsed time
+        println!("🔧 Plugin {}: Tick node {} by {:.3}s", 
+                 self.plugin_id, node_id, delta_seconds);
+        
+        // In a real implementation, you might:
+        // - Step a simulation with a fixed substep size
+        // - Advance a playback position and report whether the frame changed
+        
+        Ok(delta_seconds > 0.0)
+    }
+    
+    fn on_panel_closed(
+        &mut self,
+        _plugin_handle: &PluginHandle,
+        node_id: NodeId,
+        panel_type: crate::ui::PanelType
+    ) -> Result<(), String> {
+        // Example: Free resources only needed while a panel is visible
+        println!("🔧 Plugin {}: {:?} panel closed for node {}", 
+                 self.plugin_id, panel_type, node_id);
+        
+        // In a real implementation, you might:
+        // - Drop the GPU-side scene of a viewport panel
+        // - Stop a preview render loop
+        
+        Ok(())
+    }
+    
+    fn on_undo(
+        &mut self,
+        _plugin_handle: &PluginHandle,
+        node_id: NodeId,
+        change_description: &str
+    ) -> Result<(), String> {
+        // Example: Resync internal state after an undo
+        println!("🔧 Plugin {}: Undo '{}' on node {}", 
+                 self.plugin_id, change_description, node_id);
+        
+        // In a real implementation, you might:
+        // - Invalidate caches built from the restored parameters
+        // - Rebuild internal state derived from them
+        
+        Ok(())
+    }
+    
+    fn on_frame_changed(
+        &mut self,
+        _plugin_handle: &PluginHandle,
+        node_id: NodeId,
+        old_frame: f64,
+        new_frame: f64
+    ) -> Result<(), String> {
+        // Example: React to timeline changes without polling
+        println!("🔧 Plugin {}: Frame changed from {} to {} for node {}", 
+                 self.plugin_id, old_frame, new_frame, node_id);
+        
+        // In a real implementation, you might:
+        // - Slide a FrameCache window to the new frame
+        // - Drop per-frame state that is no longer needed
+        
+        Ok(())
+    }
+    
+    fn on_selection_changed(
+        &mut self,
+        _plugin_handle: &PluginHandle,
+        selected_node_ids: &[NodeId]
+    ) -> Result<(), String> {
+        // Example: Follow the host selection in a plugin panel
+        println!("🔧 Plugin {}: Selection changed ({} nodes selected)", 
+                 self.plugin_id, selected_node_ids.len());
+        
+        // In a real implementation, you might:
+        // - Show the most recently selected node in an inspector panel
+        // - Highlight the selected prims in a scene tree
+        
+        Ok(())
+    }
+    
+    fn on_workspace_changed(
+        &mut self,
+        _plugin_handle: &PluginHandle,
+        node_id: NodeId,
+        workspace: &str
+    ) -> Result<(), String> {
+        // Example: Adapt a node to the active workspace
+        println!("🔧 Plugin {}: Workspace changed to '{}' for node {}", 
+                 self.plugin_id, workspace, node_id);
+        
+        // In a real implementation, you might:
+        // - Switch default color spaces or units for the workspace
+        // - Hide parameters that only make sense in other workspaces
+        
+        Ok(())
+    }
+    
+    fn on_node_bypassed(
+        &mut self,
+        _plugin_handle: &PluginHandle,
+        node_id: NodeId,
+        bypassed: bool
+    ) -> Result<(), String> {
+        // Example: Free resources while a node is bypassed
+        println!("🔧 Plugin {}: Node {} bypass {}", 
+                 self.plugin_id, node_id, if bypassed { "on" } else { "off" });
+        
+        // In a real implementation, you might:
+        // - Release GPU buffers or open files held for the node
+        // - Rebuild them lazily on the next cook after bypass is turned off
+        
+        Ok(())
+    }
+    
     fn clone_box(&self) -> Box<dyn NodeExecutionHooks> {
         Box::new(self.clone())
     }
-}
\ No newline at end of file
+}
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn retry_delay_grows_up_to_the_cap() {
+        let policy = RetryPolicy::new(u32::MAX, Duration::from_secs(1)).with_backoff(2.0);
+        assert_eq!(policy.action(1), FailureAction::Retry { delay: Duration::from_secs(1) });
+        assert_eq!(policy.action(3), FailureAction::Retry { delay: Duration::from_secs(4) });
+        // 2^2000 seconds overflows Duration
+        assert_eq!(policy.action(2000), FailureAction::Retry { delay: RetryPolicy::DEFAULT_MAX_DELAY });
+        let capped = policy.with_max_delay(Duration::from_secs(3));
+        assert_eq!(capped.action(3), FailureAction::Retry { delay: Duration::from_secs(3) });
+        assert_eq!(RetryPolicy::new(2, Duration::ZERO).action(2), FailureAction::Skip);
+    }
+
+    #[test]
+    fn registration_matching() {
+        let blur = crate::NodeMetadata::new("blur", "Blur", crate::NodeCategory::new(&["Image"]), "");
+        let unnamed = crate::NodeMetadata::new("", "Unnamed", crate::NodeCategory::new(&["Image"]), "");
+        let by_type = HookRegistration::new("blur", "blur hooks");
+        assert!(by_type.matches(NodeId(1), &blur, false));
+        assert!(!by_type.matches(NodeId(1), &unnamed, false));
+
+        // Filter-only registrations have no type, so an empty node type does not match
+        let by_plugin = HookRegistration::for_plugin("plugin hooks");
+        assert_eq!(by_plugin.node_type_id, None);
+        assert!(!by_plugin.matches(NodeId(1), &unnamed, false));
+        assert!(by_plugin.matches(NodeId(1), &unnamed, true));
+
+        let panicking = by_type.with_node_predicate(|id| if id == NodeId(2) { panic!("boom") } else { true });
+        assert!(panicking.matches(NodeId(1), &blur, false));
+        assert!(!panicking.matches(NodeId(2), &blur, false));
+    }
+}
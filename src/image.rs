@@ -0,0 +1,379 @@
+//! Mipmapped and tiled image access
+//!
+//! Large textures (8K and up) are delivered progressively: texture nodes fill in
+//! `ImageData::mip_levels`, and the viewport walks `progressive_tiles()` to upload
+//! coarse levels first and refine incrementally.
+//!
+//! `thumbnail` produces the small previews shown on node bodies (see
+//! `PluginNode::thumbnail`).
+
+use crate::data_types::half_to_f32;
+use crate::{ImageData, ImageFormat, MipLevel, PixelBuffer};
+
+/// A rectangular block of pixels from one image level
+#[derive(Debug, Clone)]
+pub struct ImageTile {
+    /// Mip level (0 = full resolution)
+    pub level: usize,
+    /// Left edge in pixels within the level
+    pub x: u32,
+    /// Top edge in pixels within the level
+    pub y: u32,
+    pub width: u32,
+    pub height: u32,
+    /// Tightly packed pixel data in the image's format
+    pub data: Vec<u8>,
+}
+
+/// Iterator over the tiles of one image level, row by row
+pub struct ImageTileIter<'a> {
+    pixels: &'a PixelBuffer,
+    bytes_per_pixel: usize,
+    level: usize,
+    level_width: u32,
+    level_height: u32,
+    tile_size: u32,
+    x: u32,
+    y: u32,
+}
+
+impl Iterator for ImageTileIter<'_> {
+    type Item = ImageTile;
+
+    fn next(&mut self) -> Option<ImageTile> {
+        if self.y >= self.level_height || self.level_width == 0 {
+            return None;
+        }
+
+        let width = self.tile_size.min(self.level_width - self.x);
+        let height = self.tile_size.min(self.level_height - self.y);
+        let row_bytes = width as usize * self.bytes_per_pixel;
+        let mut data = Vec::with_capacity(row_bytes * height as usize);
+        for row in self.y..self.y + height {
+            let start = row as usize * self.pixels.row_stride + self.x as usize * self.bytes_per_pixel;
+            data.extend_from_slice(self.pixels.data.get(start..start + row_bytes)?);
+        }
+
+        let tile = ImageTile {
+            level: self.level,
+            x: self.x,
+            y: self.y,
+            width,
+            height,
+            data,
+        };
+
+        self.x += self.tile_size;
+        if self.x >= self.level_width {
+            self.x = 0;
+            self.y += self.tile_size;
+        }
+        Some(tile)
+    }
+}
+
+impl ImageData {
+    /// Number of available levels including the base image (0 without pixel data)
+    pub fn level_count(&self) -> usize {
+        if self.pixels.is_some() {
+            1 + self.mip_levels.len()
+        } else {
+            0
+        }
+    }
+
+    /// Get the dimensions and pixels of a level (0 = base image)
+    pub fn level(&self, level: usize) -> Option<(u32, u32, &PixelBuffer)> {
+        if level == 0 {
+            return self.pixels.as_ref().map(|p| (self.width, self.height, p));
+        }
+        self.mip_levels
+            .get(level - 1)
+            .map(|mip| (mip.width, mip.height, &mip.pixels))
+    }
+
+    /// Iterate over the tiles of a level
+    pub fn tiles(&self, level: usize, tile_size: u32) -> Option<ImageTileIter<'_>> {
+        let (level_width, level_height, pixels) = self.level(level)?;
+        Some(ImageTileIter {
+            pixels,
+            bytes_per_pixel: self.format.bytes_per_pixel(),
+            level,
+            level_width,
+            level_height,
+            tile_size: tile_size.max(1),
+            x: 0,
+            y: 0,
+        })
+    }
+
+    /// Iterate over all tiles from the coarsest level to full resolution
+    pub fn progressive_tiles(&self, tile_size: u32) -> impl Iterator<Item = ImageTile> + '_ {
+        (0..self.level_count())
+            .rev()
+            .filter_map(move |level| self.tiles(level, tile_size))
+            .flatten()
+    }
+
+    /// Build the mip chain down to 1x1 by halving with a box filter, replacing existing levels
+    ///
+    /// Each level averages 2x2 blocks of the one above; on odd sizes the blocks
+    /// at the far edge widen to take in the last row or column.
+    pub fn generate_mipmaps(&mut self) {
+        self.mip_levels.clear();
+        let Some(base) = self.pixels.clone() else {
+            return;
+        };
+
+        let (mut width, mut height, mut pixels) = (self.width, self.height, base);
+        while width > 1 || height > 1 {
+            let next_width = (width / 2).max(1);
+            let next_height = (height / 2).max(1);
+            let next = resample_box(self.format, &pixels, width, height, next_width, next_height);
+            self.mip_levels.push(MipLevel {
+                width: next_width,
+                height: next_height,
+                pixels: next.clone(),
+            });
+            (width, height, pixels) = (next_width, next_height, next);
+        }
+    }
+
+    /// Downscale to fit within `max_width` x `max_height`, keeping the aspect ratio
+    ///
+    /// Starts from the smallest mip level that is still large enough, then box
+    /// filters. Images already small enough are copied. Returns None without
+    /// pixel data. The result keeps the image's format and color space.
+    pub fn thumbnail(&self, max_width: u32, max_height: u32) -> Option<ImageData> {
+        let (max_width, max_height) = (max_width.max(1), max_height.max(1));
+        let scale = (max_width as f32 / self.width.max(1) as f32)
+            .min(max_height as f32 / self.height.max(1) as f32)
+            .min(1.0);
+        let width = ((self.width as f32 * scale).round() as u32).max(1);
+        let height = ((self.height as f32 * scale).round() as u32).max(1);
+
+        let (src_width, src_height, src) = (0..self.level_count())
+            .rev()
+            .filter_map(|level| self.level(level))
+            .find(|(w, h, _)| *w >= width && *h >= height)?;
+        let pixels = resample_box(self.format, src, src_width, src_height, width, height);
+
+        Some(ImageData {
+            id: format!("{}_thumbnail", self.id),
+            file_path: self.file_path.clone(),
+            width,
+            height,
+            format: self.format,
+            pixels: Some(pixels),
+            color_space: self.color_space,
+            mip_levels: Vec::new(),
+            udim: None,
+        })
+    }
+}
+
+/// Box-filter resample to a smaller size
+///
+/// Every source pixel contributes to the output pixels it overlaps, so the
+/// half-size case used for mipmaps averages 2x2 blocks.
+fn resample_box(
+    format: ImageFormat,
+    src: &PixelBuffer,
+    width: u32,
+    height: u32,
+    new_width: u32,
+    new_height: u32,
+) -> PixelBuffer {
+    if (width, height) == (new_width, new_height) {
+        return src.clone();
+    }
+    let bpp = format.bytes_per_pixel();
+    let channels = format.channels();
+    let row_stride = new_width as usize * bpp;
+    let mut data = Vec::with_capacity(row_stride * new_height as usize);
+    let span = |i: u32, size: u32, new_size: u32| {
+        let start = (i as u64 * size as u64 / new_size as u64) as u32;
+        let end = (((i + 1) as u64 * size as u64).div_ceil(new_size as u64) as u32).max(start + 1);
+        start..end.min(size)
+    };
+
+    for y in 0..new_height {
+        for x in 0..new_width {
+            let mut sum = vec![0.0f32; channels];
+            let mut count = 0.0;
+            for sy in span(y, height, new_height) {
+                for sx in span(x, width, new_width) {
+                    let start = sy as usize * src.row_stride + sx as usize * bpp;
+                    let Some(pixel) = src.data.get(start..start + bpp) else {
+                        continue;
+                    };
+                    for (c, value) in sum.iter_mut().enumerate() {
+                        *value += read_channel(format, pixel, c);
+                    }
+                    count += 1.0;
+                }
+            }
+            for value in sum {
+                write_channel(format, &mut data, if count > 0.0 { value / count } else { 0.0 });
+            }
+        }
+    }
+
+    PixelBuffer { data, row_stride }
+}
+
+fn read_channel(format: ImageFormat, pixel: &[u8], channel: usize) -> f32 {
+    match format {
+        ImageFormat::RGB8 | ImageFormat::RGBA8 => pixel[channel] as f32,
+        ImageFormat::HDR | ImageFormat::RGBA32F => {
+            let i = channel * 4;
+            f32::from_le_bytes([pixel[i], pixel[i + 1], pixel[i + 2], pixel[i + 3]])
+        }
+        ImageFormat::R16F => half_to_f32(u16::from_le_bytes([pixel[0], pixel[1]])),
+    }
+}
+
+fn write_channel(format: ImageFormat, data: &mut Vec<u8>, value: f32) {
+    match format {
+        ImageFormat::RGB8 | ImageFormat::RGBA8 => data.push(value.round().clamp(0.0, 255.0) as u8),
+        ImageFormat::HDR | ImageFormat::RGBA32F => data.extend_from_slice(&value.to_le_bytes()),
+        ImageFormat::R16F => data.extend_from_slice(&f32_to_half(value).to_le_bytes()),
+    }
+}
+
+/// Convert f32 to IEEE 754 half-precision bits (round toward zero)
+pub fn f32_to_half(value: f32) -> u16 {
+    let bits = value.to_bits();
+    let sign = ((bits >> 16) & 0x8000) as u16;
+    let exponent = ((bits >> 23) & 0xff) as i32;
+    let mantissa = bits & 0x7f_ffff;
+
+    if exponent == 0xff {
+        // Infinity or NaN
+        return sign | 0x7c00 | if mantissa != 0 { 0x200 } else { 0 };
+    }
+    let half_exponent = exponent - 127 + 15;
+    if half_exponent >= 0x1f {
+        sign | 0x7c00
+    } else if half_exponent <= 0 {
+        if half_exponent < -10 {
+            return sign;
+        }
+        let mantissa = mantissa | 0x80_0000;
+        sign | (mantissa >> (14 - half_exponent)) as u16
+    } else {
+        sign | ((half_exponent as u16) << 10) | (mantissa >> 13) as u16
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rgb8(width: u32, height: u32, data: Vec<u8>) -> ImageData {
+        ImageData::from_pixels("test", width, height, ImageFormat::RGB8, data).unwrap()
+    }
+
+    fn gray(width: u32, height: u32, values: &[u8]) -> ImageData {
+        rgb8(width, height, values.iter().flat_map(|v| [*v; 3]).collect())
+    }
+
+    fn first_channel(image: &ImageData, level: usize) -> Vec<u8> {
+        let (_, _, pixels) = image.level(level).unwrap();
+        pixels.data.iter().step_by(3).copied().collect()
+    }
+
+    #[test]
+    fn thumbnails_keep_the_aspect_ratio() {
+        let wide = gray(100, 50, &[7; 5000]);
+        let thumbnail = wide.thumbnail(20, 20).unwrap();
+        assert_eq!((thumbnail.width, thumbnail.height), (20, 10));
+        assert_eq!(thumbnail.pixels.as_ref().unwrap().data.len(), 20 * 10 * 3);
+        assert_eq!(first_channel(&thumbnail, 0), vec![7; 200]);
+
+        let column = gray(1, 200, &[0; 200]);
+        let thumbnail = column.thumbnail(16, 16).unwrap();
+        assert_eq!((thumbnail.width, thumbnail.height), (1, 16));
+
+        let small = gray(2, 2, &[0, 100, 200, 40]);
+        let copy = small.thumbnail(64, 64).unwrap();
+        assert_eq!((copy.width, copy.height), (2, 2));
+        assert_eq!(first_channel(&copy, 0), vec![0, 100, 200, 40]);
+        assert_eq!(first_channel(&small.thumbnail(1, 1).unwrap(), 0), vec![85]);
+
+        let reference = ImageData { pixels: None, ..small };
+        assert!(reference.thumbnail(8, 8).is_none());
+    }
+
+    #[test]
+    fn mipmaps_halve_down_to_one_pixel() {
+        let mut image = gray(4, 2, &[0, 40, 80, 120, 40, 80, 120, 160]);
+        image.generate_mipmaps();
+        let sizes: Vec<(u32, u32)> = image.mip_levels.iter().map(|m| (m.width, m.height)).collect();
+        assert_eq!(sizes, vec![(2, 1), (1, 1)]);
+        assert_eq!(first_channel(&image, 1), vec![40, 120]);
+        assert_eq!(first_channel(&image, 2), vec![80]);
+
+        let mut column = gray(1, 4, &[0, 20, 40, 60]);
+        column.generate_mipmaps();
+        let sizes: Vec<(u32, u32)> = column.mip_levels.iter().map(|m| (m.width, m.height)).collect();
+        assert_eq!(sizes, vec![(1, 2), (1, 1)]);
+        assert_eq!(first_channel(&column, 1), vec![10, 50]);
+
+        // The last column of an odd-sized level is not dropped
+        let mut odd = gray(3, 1, &[0, 30, 90]);
+        odd.generate_mipmaps();
+        assert_eq!(first_channel(&odd, 1), vec![40]);
+
+        let mut half = ImageData::from_pixels(
+            "half",
+            2,
+            1,
+            ImageFormat::R16F,
+            [1.0f32, 2.0].iter().flat_map(|v| f32_to_half(*v).to_le_bytes()).collect(),
+        )
+        .unwrap();
+        half.generate_mipmaps();
+        let data = &half.mip_levels[0].pixels.data;
+        assert_eq!(half_to_f32(u16::from_le_bytes([data[0], data[1]])), 1.5);
+    }
+
+    #[test]
+    fn progressive_tiles_run_from_coarse_to_fine() {
+        let mut image = gray(3, 3, &[0, 1, 2, 3, 4, 5, 6, 7, 8]);
+        image.generate_mipmaps();
+        let tiles: Vec<ImageTile> = image.progressive_tiles(2).collect();
+        let layout: Vec<(usize, u32, u32, u32, u32)> =
+            tiles.iter().map(|t| (t.level, t.x, t.y, t.width, t.height)).collect();
+        assert_eq!(
+            layout,
+            vec![(1, 0, 0, 1, 1), (0, 0, 0, 2, 2), (0, 2, 0, 1, 2), (0, 0, 2, 2, 1), (0, 2, 2, 1, 1)]
+        );
+        let corner: Vec<u8> = tiles[2].data.iter().step_by(3).copied().collect();
+        assert_eq!(corner, vec![2, 5]);
+        assert_eq!(tiles[4].data, vec![8; 3]);
+        assert_eq!(ImageData { pixels: None, ..image }.progressive_tiles(2).count(), 0);
+    }
+
+    #[test]
+    fn half_floats_round_trip() {
+        let exact = [0.0, 1.0, -2.5, 0.333_251_95, 65504.0, 2f32.powi(-14), 2f32.powi(-24), -(2f32.powi(-20))];
+        for value in exact {
+            assert_eq!(half_to_f32(f32_to_half(value)), value, "{value}");
+        }
+        assert_eq!(f32_to_half(1.0), 0x3c00);
+        assert_eq!(f32_to_half(-0.0), 0x8000);
+        // Smallest subnormal and values below it
+        assert_eq!(f32_to_half(2f32.powi(-24)), 0x0001);
+        assert_eq!(f32_to_half(2f32.powi(-25)), 0);
+        assert_eq!(f32_to_half(1e-10), 0);
+        assert_eq!(f32_to_half(f32::INFINITY), 0x7c00);
+        assert_eq!(f32_to_half(f32::NEG_INFINITY), 0xfc00);
+        assert_eq!(f32_to_half(1e6), 0x7c00);
+        assert_eq!(half_to_f32(0x7c00), f32::INFINITY);
+        assert_eq!(half_to_f32(0xfc00), f32::NEG_INFINITY);
+        assert!(half_to_f32(f32_to_half(f32::NAN)).is_nan());
+        // Rounds toward zero
+        assert_eq!(half_to_f32(f32_to_half(1.0 + 1.0 / 2048.0 + 1.0 / 4096.0)), 1.0);
+    }
+}
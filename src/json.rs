@@ -0,0 +1,875 @@
+//! JSON values for loosely structured interchange
+//!
+//! `JsonValue` mirrors the JSON data model so REST responses and config files
+//! can flow through the graph as `NodeData::Json`. It serializes with serde as
+//! plain JSON (untagged), and includes a small parser/printer so plugins don't
+//! need an extra JSON dependency to produce or consume text. Other serializable
+//! types convert with `JsonValue::from_serialize`.
+//!
+//! Numbers are stored as `f64`; integers beyond 2^53 lose precision.
+
+use serde::{ser, Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fmt;
+
+/// A JSON value
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum JsonValue {
+    #[default]
+    Null,
+    Bool(bool),
+    Number(f64),
+    String(String),
+    Array(Vec<JsonValue>),
+    Object(BTreeMap<String, JsonValue>),
+}
+
+impl JsonValue {
+    /// Parse JSON text
+    pub fn parse(text: &str) -> Result<Self, String> {
+        let mut parser = Parser { bytes: text.as_bytes(), pos: 0 };
+        let value = parser.value(0)?;
+        parser.skip_whitespace();
+        if parser.pos != parser.bytes.len() {
+            return Err(parser.error("Trailing characters"));
+        }
+        Ok(value)
+    }
+
+    /// Convert any serializable value, following serde_json's data layout
+    ///
+    /// Enum variants with data become single-key objects, map keys must be
+    /// strings or numbers, and non-finite floats become `null`.
+    pub fn from_serialize<T: Serialize + ?Sized>(value: &T) -> Result<Self, String> {
+        value.serialize(ValueSerializer).map_err(|e| e.0)
+    }
+
+    /// Serialize to compact JSON text
+    pub fn to_json_string(&self) -> String {
+        let mut out = String::new();
+        self.write_json(&mut out);
+        out
+    }
+
+    /// Look up a value by JSON Pointer (RFC 6901), e.g. `"/a/b/0"`
+    ///
+    /// The empty pointer `""` returns the value itself.
+    pub fn get_pointer(&self, pointer: &str) -> Option<&JsonValue> {
+        if pointer.is_empty() {
+            return Some(self);
+        }
+        let rest = pointer.strip_prefix('/')?;
+        rest.split('/')
+            .map(|token| token.replace("~1", "/").replace("~0", "~"))
+            .try_fold(self, |value, token| match value {
+                JsonValue::Object(map) => map.get(&token),
+                JsonValue::Array(items) => parse_index(&token).and_then(|i| items.get(i)),
+                _ => None,
+            })
+    }
+
+    /// Mutable lookup by JSON Pointer (RFC 6901)
+    pub fn get_pointer_mut(&mut self, pointer: &str) -> Option<&mut JsonValue> {
+        if pointer.is_empty() {
+            return Some(self);
+        }
+        let rest = pointer.strip_prefix('/')?;
+        rest.split('/')
+            .map(|token| token.replace("~1", "/").replace("~0", "~"))
+            .try_fold(self, |value, token| match value {
+                JsonValue::Object(map) => map.get_mut(&token),
+                JsonValue::Array(items) => parse_index(&token).and_then(|i| items.get_mut(i)),
+                _ => None,
+            })
+    }
+
+    /// Get an object member by key
+    pub fn get(&self, key: &str) -> Option<&JsonValue> {
+        match self {
+            JsonValue::Object(map) => map.get(key),
+            _ => None,
+        }
+    }
+
+    /// Check if this is null
+    pub fn is_null(&self) -> bool {
+        matches!(self, JsonValue::Null)
+    }
+
+    /// Try to extract as boolean
+    pub fn as_bool(&self) -> Option<bool> {
+        match self {
+            JsonValue::Bool(b) => Some(*b),
+            _ => None,
+        }
+    }
+
+    /// Try to extract as number
+    pub fn as_f64(&self) -> Option<f64> {
+        match self {
+            JsonValue::Number(n) => Some(*n),
+            _ => None,
+        }
+    }
+
+    /// Try to extract as an integer (only if the number has no fractional part)
+    pub fn as_i64(&self) -> Option<i64> {
+        match self {
+            JsonValue::Number(n) if n.fract() == 0.0 && n.abs() < 9.0e15 => Some(*n as i64),
+            _ => None,
+        }
+    }
+
+    /// Try to extract as string
+    pub fn as_str(&self) -> Option<&str> {
+        match self {
+            JsonValue::String(s) => Some(s),
+            _ => None,
+        }
+    }
+
+    /// Try to extract as array
+    pub fn as_array(&self) -> Option<&Vec<JsonValue>> {
+        match self {
+            JsonValue::Array(items) => Some(items),
+            _ => None,
+        }
+    }
+
+    /// Try to extract as object
+    pub fn as_object(&self) -> Option<&BTreeMap<String, JsonValue>> {
+        match self {
+            JsonValue::Object(map) => Some(map),
+            _ => None,
+        }
+    }
+
+    fn write_json(&self, out: &mut String) {
+        match self {
+            JsonValue::Null => out.push_str("null"),
+            JsonValue::Bool(b) => out.push_str(if *b { "true" } else { "false" }),
+            // JSON has no NaN/Infinity
+            JsonValue::Number(n) if !n.is_finite() => out.push_str("null"),
+            JsonValue::Number(n) => out.push_str(&n.to_string()),
+            JsonValue::String(s) => write_string(s, out),
+            JsonValue::Array(items) => {
+                out.push('[');
+                for (i, item) in items.iter().enumerate() {
+                    if i > 0 {
+                        out.push(',');
+                    }
+                    item.write_json(out);
+                }
+                out.push(']');
+            }
+            JsonValue::Object(map) => {
+                out.push('{');
+                for (i, (key, value)) in map.iter().enumerate() {
+                    if i > 0 {
+                        out.push(',');
+                    }
+                    write_string(key, out);
+                    out.push(':');
+                    value.write_json(out);
+                }
+                out.push('}');
+            }
+        }
+    }
+}
+
+impl fmt::Display for JsonValue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.to_json_string())
+    }
+}
+
+impl From<bool> for JsonValue {
+    fn from(value: bool) -> Self {
+        JsonValue::Bool(value)
+    }
+}
+
+impl From<f64> for JsonValue {
+    fn from(value: f64) -> Self {
+        JsonValue::Number(value)
+    }
+}
+
+impl From<i64> for JsonValue {
+    fn from(value: i64) -> Self {
+        JsonValue::Number(value as f64)
+    }
+}
+
+impl From<&str> for JsonValue {
+    fn from(value: &str) -> Self {
+        JsonValue::String(value.to_string())
+    }
+}
+
+impl From<String> for JsonValue {
+    fn from(value: String) -> Self {
+        JsonValue::String(value)
+    }
+}
+
+/// Error raised while converting a value with `JsonValue::from_serialize`
+#[derive(Debug)]
+struct SerializeError(String);
+
+impl fmt::Display for SerializeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl std::error::Error for SerializeError {}
+
+impl ser::Error for SerializeError {
+    fn custom<T: fmt::Display>(msg: T) -> Self {
+        SerializeError(msg.to_string())
+    }
+}
+
+/// Serde serializer producing a `JsonValue`
+struct ValueSerializer;
+
+/// Wrap a variant's content in a single-key object
+fn variant(name: &str, content: JsonValue) -> JsonValue {
+    JsonValue::Object(BTreeMap::from([(name.to_string(), content)]))
+}
+
+impl ser::Serializer for ValueSerializer {
+    type Ok = JsonValue;
+    type Error = SerializeError;
+    type SerializeSeq = SeqSerializer;
+    type SerializeTuple = SeqSerializer;
+    type SerializeTupleStruct = SeqSerializer;
+    type SerializeTupleVariant = SeqSerializer;
+    type SerializeMap = MapSerializer;
+    type SerializeStruct = MapSerializer;
+    type SerializeStructVariant = MapSerializer;
+
+    fn serialize_bool(self, v: bool) -> Result<JsonValue, SerializeError> {
+        Ok(JsonValue::Bool(v))
+    }
+
+    fn serialize_i8(self, v: i8) -> Result<JsonValue, SerializeError> {
+        self.serialize_f64(v.into())
+    }
+
+    fn serialize_i16(self, v: i16) -> Result<JsonValue, SerializeError> {
+        self.serialize_f64(v.into())
+    }
+
+    fn serialize_i32(self, v: i32) -> Result<JsonValue, SerializeError> {
+        self.serialize_f64(v.into())
+    }
+
+    fn serialize_i64(self, v: i64) -> Result<JsonValue, SerializeError> {
+        Ok(JsonValue::from(v))
+    }
+
+    fn serialize_u8(self, v: u8) -> Result<JsonValue, SerializeError> {
+        self.serialize_f64(v.into())
+    }
+
+    fn serialize_u16(self, v: u16) -> Result<JsonValue, SerializeError> {
+        self.serialize_f64(v.into())
+    }
+
+    fn serialize_u32(self, v: u32) -> Result<JsonValue, SerializeError> {
+        self.serialize_f64(v.into())
+    }
+
+    fn serialize_u64(self, v: u64) -> Result<JsonValue, SerializeError> {
+        Ok(JsonValue::Number(v as f64))
+    }
+
+    fn serialize_f32(self, v: f32) -> Result<JsonValue, SerializeError> {
+        self.serialize_f64(v.into())
+    }
+
+    fn serialize_f64(self, v: f64) -> Result<JsonValue, SerializeError> {
+        Ok(if v.is_finite() { JsonValue::Number(v) } else { JsonValue::Null })
+    }
+
+    fn serialize_char(self, v: char) -> Result<JsonValue, SerializeError> {
+        Ok(JsonValue::String(v.to_string()))
+    }
+
+    fn serialize_str(self, v: &str) -> Result<JsonValue, SerializeError> {
+        Ok(JsonValue::from(v))
+    }
+
+    fn serialize_bytes(self, v: &[u8]) -> Result<JsonValue, SerializeError> {
+        Ok(JsonValue::Array(v.iter().map(|b| JsonValue::Number((*b).into())).collect()))
+    }
+
+    fn serialize_none(self) -> Result<JsonValue, SerializeError> {
+        Ok(JsonValue::Null)
+    }
+
+    fn serialize_some<T: Serialize + ?Sized>(self, value: &T) -> Result<JsonValue, SerializeError> {
+        value.serialize(self)
+    }
+
+    fn serialize_unit(self) -> Result<JsonValue, SerializeError> {
+        Ok(JsonValue::Null)
+    }
+
+    fn serialize_unit_struct(self, _name: &'static str) -> Result<JsonValue, SerializeError> {
+        Ok(JsonValue::Null)
+    }
+
+    fn serialize_unit_variant(
+        self,
+        _name: &'static str,
+        _index: u32,
+        variant: &'static str,
+    ) -> Result<JsonValue, SerializeError> {
+        Ok(JsonValue::from(variant))
+    }
+
+    fn serialize_newtype_struct<T: Serialize + ?Sized>(
+        self,
+        _name: &'static str,
+        value: &T,
+    ) -> Result<JsonValue, SerializeError> {
+        value.serialize(self)
+    }
+
+    fn serialize_newtype_variant<T: Serialize + ?Sized>(
+        self,
+        _name: &'static str,
+        _index: u32,
+        name: &'static str,
+        value: &T,
+    ) -> Result<JsonValue, SerializeError> {
+        Ok(variant(name, value.serialize(self)?))
+    }
+
+    fn serialize_seq(self, len: Option<usize>) -> Result<SeqSerializer, SerializeError> {
+        Ok(SeqSerializer { variant: None, items: Vec::with_capacity(len.unwrap_or(0)) })
+    }
+
+    fn serialize_tuple(self, len: usize) -> Result<SeqSerializer, SerializeError> {
+        self.serialize_seq(Some(len))
+    }
+
+    fn serialize_tuple_struct(self, _name: &'static str, len: usize) -> Result<SeqSerializer, SerializeError> {
+        self.serialize_seq(Some(len))
+    }
+
+    fn serialize_tuple_variant(
+        self,
+        _name: &'static str,
+        _index: u32,
+        variant: &'static str,
+        len: usize,
+    ) -> Result<SeqSerializer, SerializeError> {
+        Ok(SeqSerializer { variant: Some(variant), items: Vec::with_capacity(len) })
+    }
+
+    fn serialize_map(self, _len: Option<usize>) -> Result<MapSerializer, SerializeError> {
+        Ok(MapSerializer { variant: None, map: BTreeMap::new(), key: None })
+    }
+
+    fn serialize_struct(self, _name: &'static str, _len: usize) -> Result<MapSerializer, SerializeError> {
+        self.serialize_map(None)
+    }
+
+    fn serialize_struct_variant(
+        self,
+        _name: &'static str,
+        _index: u32,
+        variant: &'static str,
+        _len: usize,
+    ) -> Result<MapSerializer, SerializeError> {
+        Ok(MapSerializer { variant: Some(variant), map: BTreeMap::new(), key: None })
+    }
+}
+
+/// Collects sequences, tuples and tuple variants
+struct SeqSerializer {
+    variant: Option<&'static str>,
+    items: Vec<JsonValue>,
+}
+
+impl SeqSerializer {
+    fn push<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), SerializeError> {
+        self.items.push(value.serialize(ValueSerializer)?);
+        Ok(())
+    }
+
+    fn finish(self) -> Result<JsonValue, SerializeError> {
+        let array = JsonValue::Array(self.items);
+        Ok(match self.variant {
+            Some(name) => variant(name, array),
+            None => array,
+        })
+    }
+}
+
+impl ser::SerializeSeq for SeqSerializer {
+    type Ok = JsonValue;
+    type Error = SerializeError;
+
+    fn serialize_element<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), SerializeError> {
+        self.push(value)
+    }
+
+    fn end(self) -> Result<JsonValue, SerializeError> {
+        self.finish()
+    }
+}
+
+impl ser::SerializeTuple for SeqSerializer {
+    type Ok = JsonValue;
+    type Error = SerializeError;
+
+    fn serialize_element<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), SerializeError> {
+        self.push(value)
+    }
+
+    fn end(self) -> Result<JsonValue, SerializeError> {
+        self.finish()
+    }
+}
+
+impl ser::SerializeTupleStruct for SeqSerializer {
+    type Ok = JsonValue;
+    type Error = SerializeError;
+
+    fn serialize_field<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), SerializeError> {
+        self.push(value)
+    }
+
+    fn end(self) -> Result<JsonValue, SerializeError> {
+        self.finish()
+    }
+}
+
+impl ser::SerializeTupleVariant for SeqSerializer {
+    type Ok = JsonValue;
+    type Error = SerializeError;
+
+    fn serialize_field<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), SerializeError> {
+        self.push(value)
+    }
+
+    fn end(self) -> Result<JsonValue, SerializeError> {
+        self.finish()
+    }
+}
+
+/// Collects maps, structs and struct variants
+struct MapSerializer {
+    variant: Option<&'static str>,
+    map: BTreeMap<String, JsonValue>,
+    /// Key waiting for its value
+    key: Option<String>,
+}
+
+impl MapSerializer {
+    fn insert<T: Serialize + ?Sized>(&mut self, key: String, value: &T) -> Result<(), SerializeError> {
+        self.map.insert(key, value.serialize(ValueSerializer)?);
+        Ok(())
+    }
+
+    fn finish(self) -> Result<JsonValue, SerializeError> {
+        let object = JsonValue::Object(self.map);
+        Ok(match self.variant {
+            Some(name) => variant(name, object),
+            None => object,
+        })
+    }
+}
+
+impl ser::SerializeMap for MapSerializer {
+    type Ok = JsonValue;
+    type Error = SerializeError;
+
+    fn serialize_key<T: Serialize + ?Sized>(&mut self, key: &T) -> Result<(), SerializeError> {
+        let key = match key.serialize(ValueSerializer)? {
+            JsonValue::String(key) => key,
+            JsonValue::Number(n) => n.to_string(),
+            JsonValue::Bool(b) => b.to_string(),
+            _ => return Err(SerializeError("JSON object keys must be strings or numbers".to_string())),
+        };
+        self.key = Some(key);
+        Ok(())
+    }
+
+    fn serialize_value<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), SerializeError> {
+        let key = self
+            .key
+            .take()
+            .ok_or_else(|| SerializeError("Map value serialized before its key".to_string()))?;
+        self.insert(key, value)
+    }
+
+    fn end(self) -> Result<JsonValue, SerializeError> {
+        self.finish()
+    }
+}
+
+impl ser::SerializeStruct for MapSerializer {
+    type Ok = JsonValue;
+    type Error = SerializeError;
+
+    fn serialize_field<T: Serialize + ?Sized>(&mut self, key: &'static str, value: &T) -> Result<(), SerializeError> {
+        self.insert(key.to_string(), value)
+    }
+
+    fn end(self) -> Result<JsonValue, SerializeError> {
+        self.finish()
+    }
+}
+
+impl ser::SerializeStructVariant for MapSerializer {
+    type Ok = JsonValue;
+    type Error = SerializeError;
+
+    fn serialize_field<T: Serialize + ?Sized>(&mut self, key: &'static str, value: &T) -> Result<(), SerializeError> {
+        self.insert(key.to_string(), value)
+    }
+
+    fn end(self) -> Result<JsonValue, SerializeError> {
+        self.finish()
+    }
+}
+
+/// Array indices must be plain decimal without leading zeros (RFC 6901)
+fn parse_index(token: &str) -> Option<usize> {
+    if token.is_empty() || (token.len() > 1 && token.starts_with('0')) || !token.bytes().all(|b| b.is_ascii_digit()) {
+        return None;
+    }
+    token.parse().ok()
+}
+
+fn write_string(s: &str, out: &mut String) {
+    out.push('"');
+    for c in s.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            c if (c as u32) < 0x20 => out.push_str(&format!("\\u{:04x}", c as u32)),
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+}
+
+/// Maximum nesting depth accepted by the parser
+const MAX_DEPTH: usize = 256;
+
+struct Parser<'a> {
+    bytes: &'a [u8],
+    pos: usize,
+}
+
+impl Parser<'_> {
+    fn error(&self, message: &str) -> String {
+        format!("{} at byte {}", message, self.pos)
+    }
+
+    fn skip_whitespace(&mut self) {
+        while matches!(self.bytes.get(self.pos), Some(b' ' | b'\t' | b'\n' | b'\r')) {
+            self.pos += 1;
+        }
+    }
+
+    fn expect_literal(&mut self, literal: &str, value: JsonValue) -> Result<JsonValue, String> {
+        if self.bytes[self.pos..].starts_with(literal.as_bytes()) {
+            self.pos += literal.len();
+            Ok(value)
+        } else {
+            Err(self.error("Invalid literal"))
+        }
+    }
+
+    fn value(&mut self, depth: usize) -> Result<JsonValue, String> {
+        if depth > MAX_DEPTH {
+            return Err(self.error("Nesting too deep"));
+        }
+        self.skip_whitespace();
+        match self.bytes.get(self.pos) {
+            Some(b'n') => self.expect_literal("null", JsonValue::Null),
+            Some(b't') => self.expect_literal("true", JsonValue::Bool(true)),
+            Some(b'f') => self.expect_literal("false", JsonValue::Bool(false)),
+            Some(b'"') => self.string().map(JsonValue::String),
+            Some(b'[') => {
+                self.pos += 1;
+                let mut items = Vec::new();
+                self.skip_whitespace();
+                if self.bytes.get(self.pos) == Some(&b']') {
+                    self.pos += 1;
+                    return Ok(JsonValue::Array(items));
+                }
+                loop {
+                    items.push(self.value(depth + 1)?);
+                    self.skip_whitespace();
+                    match self.bytes.get(self.pos) {
+                        Some(b',') => self.pos += 1,
+                        Some(b']') => {
+                            self.pos += 1;
+                            return Ok(JsonValue::Array(items));
+                        }
+                        _ => return Err(self.error("Expected ',' or ']'")),
+                    }
+                }
+            }
+            Some(b'{') => {
+                self.pos += 1;
+                let mut map = BTreeMap::new();
+                self.skip_whitespace();
+                if self.bytes.get(self.pos) == Some(&b'}') {
+                    self.pos += 1;
+                    return Ok(JsonValue::Object(map));
+                }
+                loop {
+                    self.skip_whitespace();
+                    if self.bytes.get(self.pos) != Some(&b'"') {
+                        return Err(self.error("Expected object key"));
+                    }
+                    let key = self.string()?;
+                    self.skip_whitespace();
+                    if self.bytes.get(self.pos) != Some(&b':') {
+                        return Err(self.error("Expected ':'"));
+                    }
+                    self.pos += 1;
+                    map.insert(key, self.value(depth + 1)?);
+                    self.skip_whitespace();
+                    match self.bytes.get(self.pos) {
+                        Some(b',') => self.pos += 1,
+                        Some(b'}') => {
+                            self.pos += 1;
+                            return Ok(JsonValue::Object(map));
+                        }
+                        _ => return Err(self.error("Expected ',' or '}'")),
+                    }
+                }
+            }
+            Some(b'-' | b'0'..=b'9') => self.number(),
+            Some(_) => Err(self.error("Unexpected character")),
+            None => Err(self.error("Unexpected end of input")),
+        }
+    }
+
+    fn digits(&mut self) -> usize {
+        let start = self.pos;
+        while matches!(self.bytes.get(self.pos), Some(b'0'..=b'9')) {
+            self.pos += 1;
+        }
+        self.pos - start
+    }
+
+    /// `-? (0 | [1-9][0-9]*) (. [0-9]+)? ([eE] [+-]? [0-9]+)?`
+    fn number(&mut self) -> Result<JsonValue, String> {
+        let start = self.pos;
+        if self.bytes.get(self.pos) == Some(&b'-') {
+            self.pos += 1;
+        }
+        let int_start = self.pos;
+        let int_digits = self.digits();
+        if int_digits == 0 || (int_digits > 1 && self.bytes[int_start] == b'0') {
+            return Err(self.error("Invalid number"));
+        }
+        if self.bytes.get(self.pos) == Some(&b'.') {
+            self.pos += 1;
+            if self.digits() == 0 {
+                return Err(self.error("Invalid number"));
+            }
+        }
+        if matches!(self.bytes.get(self.pos), Some(b'e' | b'E')) {
+            self.pos += 1;
+            if matches!(self.bytes.get(self.pos), Some(b'+' | b'-')) {
+                self.pos += 1;
+            }
+            if self.digits() == 0 {
+                return Err(self.error("Invalid number"));
+            }
+        }
+        std::str::from_utf8(&self.bytes[start..self.pos])
+            .ok()
+            .and_then(|s| s.parse::<f64>().ok())
+            .filter(|n| n.is_finite())
+            .map(JsonValue::Number)
+            .ok_or_else(|| self.error("Number out of range"))
+    }
+
+    fn hex4(&mut self) -> Result<u32, String> {
+        let digits = self
+            .bytes
+            .get(self.pos..self.pos + 4)
+            .filter(|d| d.iter().all(u8::is_ascii_hexdigit))
+            .and_then(|d| std::str::from_utf8(d).ok())
+            .and_then(|d| u32::from_str_radix(d, 16).ok())
+            .ok_or_else(|| self.error("Invalid unicode escape"))?;
+        self.pos += 4;
+        Ok(digits)
+    }
+
+    fn string(&mut self) -> Result<String, String> {
+        // Skip opening quote
+        self.pos += 1;
+        let mut out = String::new();
+        loop {
+            let start = self.pos;
+            while !matches!(self.bytes.get(self.pos), None | Some(b'"' | b'\\')) {
+                self.pos += 1;
+            }
+            out.push_str(std::str::from_utf8(&self.bytes[start..self.pos]).map_err(|_| self.error("Invalid UTF-8"))?);
+            match self.bytes.get(self.pos) {
+                Some(b'"') => {
+                    self.pos += 1;
+                    return Ok(out);
+                }
+                Some(b'\\') => {
+                    self.pos += 1;
+                    let escape = *self.bytes.get(self.pos).ok_or_else(|| self.error("Unterminated string"))?;
+                    self.pos += 1;
+                    match escape {
+                        b'"' => out.push('"'),
+                        b'\\' => out.push('\\'),
+                        b'/' => out.push('/'),
+                        b'b' => out.push('\u{8}'),
+                        b'f' => out.push('\u{c}'),
+                        b'n' => out.push('\n'),
+                        b'r' => out.push('\r'),
+                        b't' => out.push('\t'),
+                        b'u' => {
+                            let mut code = self.hex4()?;
+                            // Combine UTF-16 surrogate pairs; a lone surrogate becomes U+FFFD
+                            if (0xd800..0xdc00).contains(&code) && self.bytes[self.pos..].starts_with(b"\\u") {
+                                let high_end = self.pos;
+                                self.pos += 2;
+                                let low = self.hex4()?;
+                                if (0xdc00..0xe000).contains(&low) {
+                                    code = 0x10000 + ((code - 0xd800) << 10) + (low - 0xdc00);
+                                } else {
+                                    // Not a low surrogate: parse it as its own escape
+                                    self.pos = high_end;
+                                }
+                            }
+                            out.push(char::from_u32(code).unwrap_or('\u{fffd}'));
+                        }
+                        _ => return Err(self.error("Invalid escape")),
+                    }
+                }
+                _ => return Err(self.error("Unterminated string")),
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(text: &str) -> JsonValue {
+        JsonValue::parse(text).unwrap_or_else(|e| panic!("{}: {}", text, e))
+    }
+
+    #[test]
+    fn parses_documents() {
+        let value = parse(r#" { "a": [1, true, null, {"b": "c"}], "d": -0.5e1 } "#);
+        assert_eq!(value.get_pointer("/a/0").and_then(JsonValue::as_i64), Some(1));
+        assert_eq!(value.get_pointer("/a/1").and_then(JsonValue::as_bool), Some(true));
+        assert!(value.get_pointer("/a/2").unwrap().is_null());
+        assert_eq!(value.get_pointer("/a/3/b").and_then(JsonValue::as_str), Some("c"));
+        assert_eq!(value.get("d").and_then(JsonValue::as_f64), Some(-5.0));
+        assert_eq!(value.get_pointer("/a/01"), None);
+    }
+
+    #[test]
+    fn string_escapes() {
+        assert_eq!(parse(r#""\"\\\/\b\f\n\r\t""#), JsonValue::from("\"\\/\u{8}\u{c}\n\r\t"));
+        assert_eq!(parse(r#""é中""#), JsonValue::from("é中"));
+        assert!(JsonValue::parse(r#""\x""#).is_err());
+        assert!(JsonValue::parse(r#""\u12""#).is_err());
+        assert!(JsonValue::parse(r#""\u+123""#).is_err());
+        assert!(JsonValue::parse(r#""unterminated"#).is_err());
+    }
+
+    #[test]
+    fn surrogate_pairs() {
+        assert_eq!(parse(r#""\ud83d\ude00""#), JsonValue::from("😀"));
+        assert_eq!(parse(r#""\uD83D\uDE00!""#), JsonValue::from("😀!"));
+        assert_eq!(parse(r#""\ud83d\u0041""#), JsonValue::from("\u{fffd}A"));
+        // Lone surrogates are replaced, and a following escape is kept
+        assert_eq!(parse(r#""\ud83d""#), JsonValue::from("\u{fffd}"));
+        assert_eq!(parse(r#""\ude00x""#), JsonValue::from("\u{fffd}x"));
+        assert_eq!(parse(r#""\ud83dA""#), JsonValue::from("\u{fffd}A"));
+    }
+
+    #[test]
+    fn number_edge_cases() {
+        assert_eq!(parse("0").as_f64(), Some(0.0));
+        assert_eq!(parse("-0").as_f64(), Some(0.0));
+        assert_eq!(parse("1E+2").as_f64(), Some(100.0));
+        assert_eq!(parse("2.5e-3").as_f64(), Some(0.0025));
+        assert_eq!(parse("9007199254740993").as_f64(), Some(9007199254740992.0));
+        for invalid in ["01", "1.", ".5", "-", "+1", "1e", "1e+", "--1", "1.2.3", "0x10", "NaN", "Infinity", "1e400"] {
+            assert!(JsonValue::parse(invalid).is_err(), "{} should be rejected", invalid);
+        }
+    }
+
+    #[test]
+    fn deep_nesting_is_rejected_without_overflowing_the_stack() {
+        let depth = 100_000;
+        let text = "[".repeat(depth) + &"]".repeat(depth);
+        assert!(JsonValue::parse(&text).unwrap_err().starts_with("Nesting too deep"));
+
+        let text = "[".repeat(MAX_DEPTH) + &"]".repeat(MAX_DEPTH);
+        assert!(JsonValue::parse(&text).is_ok());
+    }
+
+    #[test]
+    fn round_trips_through_text() {
+        let value = parse(r#"{"list":[1.5,"tab\t\u0001",false],"nested":{"empty":[],"obj":{}}}"#);
+        let text = value.to_json_string();
+        assert_eq!(text, r#"{"list":[1.5,"tab\t\u0001",false],"nested":{"empty":[],"obj":{}}}"#);
+        assert_eq!(parse(&text), value);
+        assert_eq!(JsonValue::Number(f64::NAN).to_json_string(), "null");
+    }
+
+    #[test]
+    fn rejects_malformed_documents() {
+        for invalid in ["", "[1,]", "{\"a\" 1}", "{1: 2}", "[1 2]", "nul", "true false", "{\"a\":1,}"] {
+            assert!(JsonValue::parse(invalid).is_err(), "{:?} should be rejected", invalid);
+        }
+    }
+
+    #[test]
+    fn converts_serializable_values() {
+        #[derive(Serialize)]
+        enum Shape {
+            Point,
+            Circle(f32),
+            Line(i32, i32),
+            Rect { w: u8, h: u8 },
+        }
+        let mut scores = BTreeMap::new();
+        scores.insert(7u32, f64::NAN);
+        let value = JsonValue::from_serialize(&(
+            vec![Shape::Point, Shape::Circle(0.5), Shape::Line(1, -2), Shape::Rect { w: 3, h: 4 }],
+            scores,
+            Some('x'),
+            None::<bool>,
+        ))
+        .unwrap();
+        assert_eq!(
+            value.to_json_string(),
+            r#"[["Point",{"Circle":0.5},{"Line":[1,-2]},{"Rect":{"h":4,"w":3}}],{"7":null},"x",null]"#
+        );
+        let unsupported: BTreeMap<Vec<u8>, u8> = BTreeMap::from([(vec![1], 1)]);
+        assert!(JsonValue::from_serialize(&unsupported).is_err());
+    }
+}
@@ -24,12 +24,26 @@
 //!     }
 //!     
 //!     fn register_nodes(&self, registry: &mut dyn NodeRegistryTrait) {
-//!         registry.register_node_factory(Box::new(MyNodeFactory));
+//!         registry.register_node_factory(Box::new(MyNodeFactory)).ok();
+//!     }
+//! }
+//! 
+//! // Describe and create your node
+//! pub struct MyNodeFactory;
+//! 
+//! impl NodeFactory for MyNodeFactory {
+//!     fn metadata(&self) -> NodeMetadata {
+//!         NodeMetadata::new("my_multiply", "Multiply", NodeCategory::math(), "Multiplies its input")
+//!     }
+//!     
+//!     fn create_node(&self, position: egui::Pos2) -> PluginNodeHandle {
+//!         PluginNodeHandle::new(Box::new(MyNode { position, multiplier: 2.0 }))
 //!     }
 //! }
 //! 
 //! // Define your node
 //! pub struct MyNode {
+//!     position: egui::Pos2,
 //!     multiplier: f32,
 //! }
 //! 
@@ -44,7 +58,13 @@
 //!         outputs
 //!     }
 //!     
-//!     // ... other required methods
+//!     fn id(&self) -> String { "my_multiply".to_string() }
+//!     fn position(&self) -> egui::Pos2 { self.position }
+//!     fn set_position(&mut self, position: egui::Pos2) { self.position = position; }
+//!     fn get_parameter_ui(&self) -> ParameterUI { ParameterUI::new() }
+//!     fn handle_ui_action(&mut self, _action: UIAction) -> Vec<ParameterChange> { Vec::new() }
+//!     fn get_parameter(&self, _name: &str) -> Option<NodeData> { None }
+//!     fn set_parameter(&mut self, _name: &str, _value: NodeData) {}
 //! }
 //! ```
 //! 
@@ -55,25 +75,37 @@
 //! ```rust
 //! use nodle_plugin_sdk::*;
 //! use nodle_plugin_sdk::cache::strategies::MultiStageCache;
+//! use std::collections::HashMap;
 //! 
 //! pub struct USDProcessorNode {
 //!     cache: MultiStageCache,
 //!     file_path: String,
+//! #   position: egui::Pos2,
+//! }
+//! 
+//! impl USDProcessorNode {
+//!     fn load_usd_file(&self, path: &str) -> NodeData {
+//!         NodeData::String(path.to_string())
+//!     }
+//!     
+//!     fn process_usd_data(&self, data: &NodeData) -> NodeData {
+//!         data.clone()
+//!     }
 //! }
 //! 
 //! impl PluginNode for USDProcessorNode {
 //!     fn process_with_cache(
 //!         &mut self, 
-//!         inputs: &HashMap<String, NodeData>,
+//!         _inputs: &HashMap<String, NodeData>,
 //!         cache: &mut dyn PluginCache,
-//!         node_id: u32
+//!         node_id: NodeId
 //!     ) -> HashMap<String, NodeData> {
 //!         // Stage 1: Load USD file (cached by file path)
 //!         let usd_data = if let Some(cached) = self.cache.get_stage_cached(cache, node_id, "load", 0) {
 //!             cached.clone()
 //!         } else {
-//!             let loaded = self.load_usd_file(&self.file_path).unwrap();
-//!             self.cache.store_stage_result(cache, node_id, "load", 0, loaded.clone()).unwrap();
+//!             let loaded = self.load_usd_file(&self.file_path);
+//!             self.cache.store_stage_result(cache, node_id, "load", 0, loaded.clone()).ok();
 //!             loaded
 //!         };
 //!         
@@ -81,8 +113,8 @@
 //!         let processed = if let Some(cached) = self.cache.get_stage_cached(cache, node_id, "process", 0) {
 //!             cached.clone()
 //!         } else {
-//!             let processed = self.process_usd_data(&usd_data).unwrap();
-//!             self.cache.store_stage_result(cache, node_id, "process", 0, processed.clone()).unwrap();
+//!             let processed = self.process_usd_data(&usd_data);
+//!             self.cache.store_stage_result(cache, node_id, "process", 0, processed.clone()).ok();
 //!             processed
 //!         };
 //!         
@@ -90,6 +122,16 @@
 //!         outputs.insert("output".to_string(), processed);
 //!         outputs
 //!     }
+//!     
+//!     // ... other required methods
+//! #   fn process(&mut self, _inputs: &HashMap<String, NodeData>) -> HashMap<String, NodeData> { HashMap::new() }
+//! #   fn id(&self) -> String { "usd_processor".to_string() }
+//! #   fn position(&self) -> egui::Pos2 { self.position }
+//! #   fn set_position(&mut self, position: egui::Pos2) { self.position = position; }
+//! #   fn get_parameter_ui(&self) -> ParameterUI { ParameterUI::new() }
+//! #   fn handle_ui_action(&mut self, _action: UIAction) -> Vec<ParameterChange> { Vec::new() }
+//! #   fn get_parameter(&self, _name: &str) -> Option<NodeData> { None }
+//! #   fn set_parameter(&mut self, _name: &str, _value: NodeData) {}
 //! }
 //! ```
 //! 
@@ -97,18 +139,19 @@
 //! 
 //! ```rust
 //! use nodle_plugin_sdk::*;
+//! use std::collections::HashMap;
 //! 
 //! #[derive(Clone)]
 //! pub struct MyNodeHooks;
 //! 
 //! impl NodeExecutionHooks for MyNodeHooks {
-//!     fn before_execution(&mut self, _handle: &PluginHandle, node_id: u32, _inputs: &HashMap<String, NodeData>) -> Result<(), String> {
+//!     fn before_execution(&mut self, _handle: &PluginHandle, node_id: NodeId, _inputs: &HashMap<String, NodeData>) -> Result<(), String> {
 //!         println!("Preparing node {} for execution", node_id);
 //!         // Clear temporary caches, validate inputs, etc.
 //!         Ok(())
 //!     }
 //!     
-//!     fn on_parameter_changed(&mut self, _handle: &PluginHandle, node_id: u32, param: &str, _old: &NodeData, _new: &NodeData) -> Result<(), String> {
+//!     fn on_parameter_changed(&mut self, _handle: &PluginHandle, node_id: NodeId, param: &str, _old: &NodeData, _new: &NodeData) -> Result<(), String> {
 //!         println!("Parameter '{}' changed on node {}", param, node_id);
 //!         // Invalidate specific caches based on which parameter changed
 //!         Ok(())
@@ -119,13 +162,28 @@
 //!     }
 //! }
 //! 
+//! # pub struct MyNode { position: egui::Pos2 }
 //! impl PluginNode for MyNode {
 //!     fn get_execution_hooks(&self) -> Option<Box<dyn NodeExecutionHooks>> {
 //!         Some(Box::new(MyNodeHooks))
 //!     }
+//!     
+//!     // ... other required methods
+//! #   fn process(&mut self, _inputs: &HashMap<String, NodeData>) -> HashMap<String, NodeData> { HashMap::new() }
+//! #   fn id(&self) -> String { "my_node".to_string() }
+//! #   fn position(&self) -> egui::Pos2 { self.position }
+//! #   fn set_position(&mut self, position: egui::Pos2) { self.position = position; }
+//! #   fn get_parameter_ui(&self) -> ParameterUI { ParameterUI::new() }
+//! #   fn handle_ui_action(&mut self, _action: UIAction) -> Vec<ParameterChange> { Vec::new() }
+//! #   fn get_parameter(&self, _name: &str) -> Option<NodeData> { None }
+//! #   fn set_parameter(&mut self, _name: &str, _value: NodeData) {}
 //! }
 //! ```
 
+// The SDK builds with egui's minimum Rust version (1.81); these lints suggest
+// std APIs stabilized after it
+#![allow(clippy::manual_is_multiple_of, clippy::unnecessary_map_or, clippy::manual_repeat_n)]
+
 pub mod data_types;
 pub mod metadata;
 pub mod plugin;
@@ -135,6 +193,53 @@ pub mod viewport;
 pub mod hooks;
 pub mod cache;
 pub mod ui;
+pub mod state;
+pub mod snapshot;
+pub mod usd;
+pub mod drivers;
+pub mod assets;
+pub mod scene;
+pub mod process;
+pub mod geometry;
+pub mod context;
+pub mod image;
+pub mod color;
+pub mod guard;
+pub mod theme;
+pub mod units;
+pub mod hashing;
+pub mod datetime;
+pub mod node_id;
+pub mod json;
+pub mod render;
+pub mod convert;
+pub mod framebuffer;
+pub mod user_data;
+pub mod table;
+pub mod table_io;
+pub mod transform;
+pub mod uv;
+pub mod subdiv;
+pub mod document;
+pub mod plan;
+pub mod params;
+pub mod disk_cache;
+pub mod curve;
+pub mod progress;
+pub mod file_io;
+pub mod probe;
+pub mod dedup;
+pub mod channels;
+pub mod lifecycle;
+pub mod clock;
+pub mod sidecar;
+pub mod resources;
+pub mod variation;
+pub mod ui_snapshot;
+pub mod frames;
+pub mod health;
+pub mod preparation;
+pub mod graph;
 
 // Re-export commonly used types
 pub use data_types::*;
@@ -144,14 +249,50 @@ pub use registry::*;
 pub use errors::*;
 pub use hooks::*;
 pub use cache::*;
+pub use state::*;
+pub use snapshot::*;
+pub use usd::*;
+pub use drivers::*;
+pub use assets::*;
+pub use process::*;
+pub use context::*;
+pub use color::*;
+pub use guard::*;
+pub use units::*;
+pub use datetime::*;
+pub use node_id::*;
+pub use json::*;
+pub use table::*;
+pub use uv::*;
+pub use subdiv::*;
+pub use plan::*;
+pub use params::*;
+pub use disk_cache::*;
+pub use progress::*;
+pub use file_io::*;
+pub use probe::*;
+pub use dedup::*;
+pub use lifecycle::*;
+pub use clock::*;
+pub use sidecar::*;
+pub use resources::*;
+pub use variation::*;
+pub use ui_snapshot::*;
+pub use frames::*;
+pub use health::*;
+pub use preparation::*;
+pub use graph::*;
+pub use user_data::NodeUserData;
+pub use curve::{CurveBasis, CurvePoint, Easing, GradientData, Ramp, RampInterpolation, RampKey};
 
 // Specific re-exports from ui to avoid conflicts
 pub use ui::{PanelType, InterfaceParameter, UIElement, ParameterChange, UIAction, ParameterUI};
 
 // Specific re-exports from viewport to avoid conflicts  
-pub use viewport::{CameraData, CameraManipulation, ViewportData, ViewportSettings, MeshData, ShadingMode};
+pub use viewport::{CameraData, CameraManipulation, ColorManagement, ViewportData, ViewportSettings, MeshData, ShadingMode, ViewTransform};
 
 // Data types are the authoritative source for SceneData, MaterialData, LightData, LightType
+// (viewport shares materials/lights; see scene.rs for SceneData/MeshData conversions)
 
 // Re-export egui for convenience
 pub use egui::{Color32, Pos2, Vec2, Ui};
\ No newline at end of file
@@ -0,0 +1,124 @@
+//! Node lifecycle context
+//!
+//! `NodeFactory::create_node` only receives a position, so nodes have no way
+//! to learn where they may store files or how to log until their first cook.
+//! The host passes a `NodeContext` to `PluginNode::on_created` right after
+//! creating (or loading) a node, and calls `PluginNode::on_destroyed` before
+//! dropping it.
+
+use crate::NodeId;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::path::PathBuf;
+use std::sync::Arc;
+
+/// Severity of a log message
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub enum LogLevel {
+    Debug,
+    Info,
+    Warning,
+    Error,
+}
+
+/// Receives log messages from a node (implemented by the host)
+pub trait NodeLogger: Send + Sync {
+    fn log(&self, level: LogLevel, message: &str);
+}
+
+impl<F> NodeLogger for F
+where
+    F: Fn(LogLevel, &str) + Send + Sync,
+{
+    fn log(&self, level: LogLevel, message: &str) {
+        self(level, message)
+    }
+}
+
+/// Cloneable logger handle (discards messages by default)
+#[derive(Clone, Default)]
+pub struct LogHandle(Option<Arc<dyn NodeLogger>>);
+
+impl LogHandle {
+    /// Wrap a logger
+    pub fn new(logger: impl NodeLogger + 'static) -> Self {
+        Self(Some(Arc::new(logger)))
+    }
+
+    /// Log a message
+    pub fn log(&self, level: LogLevel, message: &str) {
+        if let Some(logger) = &self.0 {
+            logger.log(level, message);
+        }
+    }
+
+    /// Log at debug level
+    pub fn debug(&self, message: &str) {
+        self.log(LogLevel::Debug, message);
+    }
+
+    /// Log at info level
+    pub fn info(&self, message: &str) {
+        self.log(LogLevel::Info, message);
+    }
+
+    /// Log a warning
+    pub fn warn(&self, message: &str) {
+        self.log(LogLevel::Warning, message);
+    }
+
+    /// Log an error
+    pub fn error(&self, message: &str) {
+        self.log(LogLevel::Error, message);
+    }
+}
+
+impl fmt::Debug for LogHandle {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("LogHandle")
+    }
+}
+
+/// Host context given to a node when it is created
+#[derive(Debug, Clone)]
+pub struct NodeContext {
+    /// Stable node ID (same as passed to `set_node_id`)
+    pub node_id: NodeId,
+    /// Node type identifier from the factory metadata
+    pub node_type: String,
+    /// Directory reserved for this node's files (None for unsaved projects)
+    ///
+    /// The host creates it on demand and deletes it with the node.
+    pub storage_dir: Option<PathBuf>,
+    /// Where to send log messages (shown in the host's log panel)
+    pub logger: LogHandle,
+}
+
+impl NodeContext {
+    /// Create a context without storage or logging
+    pub fn new(node_id: NodeId, node_type: impl Into<String>) -> Self {
+        Self {
+            node_id,
+            node_type: node_type.into(),
+            storage_dir: None,
+            logger: LogHandle::default(),
+        }
+    }
+
+    /// Set the node's storage directory
+    pub fn with_storage_dir(mut self, dir: impl Into<PathBuf>) -> Self {
+        self.storage_dir = Some(dir.into());
+        self
+    }
+
+    /// Set the logger
+    pub fn with_logger(mut self, logger: LogHandle) -> Self {
+        self.logger = logger;
+        self
+    }
+
+    /// Path of a file in the node's storage directory
+    pub fn storage_path(&self, name: &str) -> Option<PathBuf> {
+        self.storage_dir.as_ref().map(|dir| dir.join(name))
+    }
+}
@@ -1,6 +1,6 @@
 //! Node metadata and categorization system
 
-use crate::{DataType, PanelType};
+use crate::{ClockSource, DataType, EvaluationQuality, ExecutionLimits, PanelType, Unit};
 use egui::{Color32, Vec2};
 use serde::{Deserialize, Serialize};
 
@@ -88,6 +88,43 @@ pub struct PortDefinition {
     pub data_type: DataType,
     pub optional: bool,
     pub description: Option<String>,
+    /// How this port is promoted to the boundary of a group/subnet
+    #[serde(default)]
+    pub promotion: Option<PortPromotion>,
+    /// Unit of scalar values on this port
+    #[serde(default)]
+    pub unit: Option<Unit>,
+}
+
+/// Group boundary promotion settings for a port
+/// 
+/// When a plugin node is placed inside a group/subnet, the host uses these
+/// settings to decide which ports appear on the group boundary and how.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PortPromotion {
+    /// Label shown on the group boundary (defaults to the port name)
+    pub label: Option<String>,
+    /// Sort order on the group boundary (lower first)
+    pub order: i32,
+    /// Whether the host promotes this port automatically when grouping
+    pub auto_promote: bool,
+}
+
+impl PortPromotion {
+    /// Promote automatically with the given boundary order
+    pub fn auto(order: i32) -> Self {
+        Self {
+            label: None,
+            order,
+            auto_promote: true,
+        }
+    }
+    
+    /// Set the boundary label
+    pub fn with_label(mut self, label: &str) -> Self {
+        self.label = Some(label.to_string());
+        self
+    }
 }
 
 impl PortDefinition {
@@ -98,6 +135,8 @@ impl PortDefinition {
             data_type,
             optional: false,
             description: None,
+            promotion: None,
+            unit: None,
         }
     }
     
@@ -108,6 +147,8 @@ impl PortDefinition {
             data_type,
             optional: true,
             description: None,
+            promotion: None,
+            unit: None,
         }
     }
     
@@ -116,6 +157,38 @@ impl PortDefinition {
         self.description = Some(description.to_string());
         self
     }
+    
+    /// Set the unit of scalar values on this port
+    pub fn with_unit(mut self, unit: Unit) -> Self {
+        self.unit = Some(unit);
+        self
+    }
+    
+    /// Check if this port can connect to another, including unit compatibility
+    /// 
+    /// Ports without units connect to anything their data type allows; ports
+    /// with units additionally require the same kind of quantity.
+    pub fn can_connect_to(&self, other: &PortDefinition) -> bool {
+        let units_compatible = match (self.unit, other.unit) {
+            (Some(a), Some(b)) => a.kind() == b.kind(),
+            _ => true,
+        };
+        units_compatible && self.data_type.can_connect_to(&other.data_type)
+    }
+    
+    /// Set group boundary promotion for this port
+    pub fn with_promotion(mut self, promotion: PortPromotion) -> Self {
+        self.promotion = Some(promotion);
+        self
+    }
+    
+    /// Get the label to show on a group boundary
+    pub fn promoted_label(&self) -> &str {
+        self.promotion
+            .as_ref()
+            .and_then(|p| p.label.as_deref())
+            .unwrap_or(&self.name)
+    }
 }
 
 /// Panel positioning preferences
@@ -192,6 +265,10 @@ pub struct NodeMetadata {
     pub execution_mode: ExecutionMode,
     pub processing_cost: ProcessingCost,
     pub requires_gpu: bool,
+    pub supported_qualities: Vec<EvaluationQuality>,
+    pub limits: ExecutionLimits,
+    /// Clock that drives evaluation of realtime nodes (None = host timeline)
+    pub clock: Option<ClockSource>,
     
     // Advanced properties
     pub is_workspace_node: bool,
@@ -238,6 +315,9 @@ impl NodeMetadata {
             execution_mode: ExecutionMode::OnDemand,
             processing_cost: ProcessingCost::Low,
             requires_gpu: false,
+            supported_qualities: vec![EvaluationQuality::Final],
+            limits: ExecutionLimits::default(),
+            clock: None,
             
             // Advanced properties
             is_workspace_node: false,
@@ -280,4 +360,49 @@ impl NodeMetadata {
         self.outputs = outputs;
         self
     }
+    
+    /// Set the evaluation qualities this node honors
+    pub fn with_supported_qualities(mut self, qualities: Vec<EvaluationQuality>) -> Self {
+        self.supported_qualities = qualities;
+        self
+    }
+    
+    /// Set soft cook time and memory limits
+    pub fn with_limits(mut self, limits: ExecutionLimits) -> Self {
+        self.limits = limits;
+        self
+    }
+    
+    /// Drive evaluation from a clock source
+    /// 
+    /// Only honored for `ExecutionMode::Realtime` nodes; the host reports the
+    /// clock actually used in `ExecutionContext::clock`.
+    pub fn with_clock(mut self, clock: ClockSource) -> Self {
+        self.clock = Some(clock);
+        self
+    }
+    
+    /// Get input ports that should be auto-promoted to a group boundary, in boundary order
+    pub fn promoted_inputs(&self) -> Vec<&PortDefinition> {
+        promoted(&self.inputs)
+    }
+    
+    /// Get output ports that should be auto-promoted to a group boundary, in boundary order
+    pub fn promoted_outputs(&self) -> Vec<&PortDefinition> {
+        promoted(&self.outputs)
+    }
+    
+    /// Resolve a requested evaluation quality against the supported ones
+    pub fn resolve_quality(&self, requested: EvaluationQuality) -> EvaluationQuality {
+        requested.resolve(&self.supported_qualities)
+    }
+}
+
+fn promoted(ports: &[PortDefinition]) -> Vec<&PortDefinition> {
+    let mut promoted: Vec<&PortDefinition> = ports
+        .iter()
+        .filter(|p| p.promotion.as_ref().is_some_and(|p| p.auto_promote))
+        .collect();
+    promoted.sort_by_key(|p| p.promotion.as_ref().map(|p| p.order).unwrap_or(0));
+    promoted
 }
\ No newline at end of file
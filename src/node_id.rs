@@ -0,0 +1,50 @@
+//! Stable node identifiers
+//!
+//! The host assigns every node instance a `NodeId` when it is created and
+//! persists it in the project file, so the same node keeps the same ID across
+//! save/load. Hooks, cache keys, snapshots and the node API all use this type,
+//! so caches and external references (e.g. render-farm jobs) survive reloads.
+
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+/// Host-assigned, project-persistent identifier of a node instance
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct NodeId(pub u64);
+
+impl NodeId {
+    /// Create a node ID from its raw value
+    pub const fn new(raw: u64) -> Self {
+        Self(raw)
+    }
+
+    /// Get the raw value
+    pub const fn raw(&self) -> u64 {
+        self.0
+    }
+}
+
+impl fmt::Display for NodeId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl From<u64> for NodeId {
+    fn from(raw: u64) -> Self {
+        Self(raw)
+    }
+}
+
+impl From<u32> for NodeId {
+    fn from(raw: u32) -> Self {
+        Self(raw as u64)
+    }
+}
+
+impl From<NodeId> for u64 {
+    fn from(id: NodeId) -> Self {
+        id.0
+    }
+}
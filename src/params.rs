@@ -0,0 +1,427 @@
+//! Typed parameter descriptors
+//!
+//! `get_parameter`/`set_parameter` take any `NodeData`, so a node has no way to
+//! reject a string written to a float parameter. Factories can declare their
+//! parameters up front instead:
+//!
+//! ```
+//! # use nodle_plugin_sdk::{ParamDescriptor, ParamSet};
+//! # struct BlurFactory;
+//! # impl BlurFactory {
+//! fn parameters(&self) -> ParamSet {
+//!     ParamSet::new()
+//!         .param(ParamDescriptor::float("radius").range(0.0, 10.0).default(1.0))
+//!         .param(ParamDescriptor::choice("mode", &["Fast", "Accurate"]))
+//! }
+//! # }
+//! # assert_eq!(BlurFactory.parameters().names(), ["radius", "mode"]);
+//! ```
+//!
+//! The host reads the declarations through `safe_parameters`, which rejects
+//! invalid defaults, validates every `set_parameter` call against the set (see
+//! `PluginNodeHandle::safe_set_parameter`), seeds new nodes with the defaults,
+//! and can build the parameter panel with `ParamSet::parameter_ui`.
+
+use crate::{FrameRange, FrameRate, GradientData, NodeData, ParameterUI, PathData, Timecode, UIElement, ValidationError};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// Value type of a declared parameter
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum ParamKind {
+    Float,
+    Integer,
+    Boolean,
+    String,
+    Vector3,
+    Color,
+    Path,
+    /// One of a fixed list of strings
+    Choice,
+    FrameRange,
+    /// Timecode at the frame rate of the default value
+    Timecode,
+    /// Color ramp
+    Gradient,
+}
+
+/// Declaration of one node parameter
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ParamDescriptor {
+    /// Name passed to `get_parameter`/`set_parameter`
+    pub name: String,
+    /// Label shown in the parameter panel
+    pub label: String,
+    pub kind: ParamKind,
+    pub default: NodeData,
+    /// Inclusive range for `Float`/`Integer` parameters
+    #[serde(default)]
+    pub min: Option<f32>,
+    #[serde(default)]
+    pub max: Option<f32>,
+    /// Allowed values for `Choice` parameters
+    #[serde(default)]
+    pub options: Vec<String>,
+    /// File dialog filter for `Path` parameters (e.g. "*.usd")
+    #[serde(default)]
+    pub filter: String,
+    #[serde(default)]
+    pub description: String,
+}
+
+impl ParamDescriptor {
+    fn new(name: &str, kind: ParamKind, default: NodeData) -> Self {
+        Self {
+            name: name.to_string(),
+            label: name.to_string(),
+            kind,
+            default,
+            min: None,
+            max: None,
+            options: Vec::new(),
+            filter: String::new(),
+            description: String::new(),
+        }
+    }
+
+    /// Declare a float parameter (default 0.0)
+    pub fn float(name: &str) -> Self {
+        Self::new(name, ParamKind::Float, NodeData::Float(0.0))
+    }
+
+    /// Declare an integer parameter (default 0)
+    pub fn integer(name: &str) -> Self {
+        Self::new(name, ParamKind::Integer, NodeData::Integer(0))
+    }
+
+    /// Declare a boolean parameter (default false)
+    pub fn boolean(name: &str) -> Self {
+        Self::new(name, ParamKind::Boolean, NodeData::Boolean(false))
+    }
+
+    /// Declare a string parameter (default empty)
+    pub fn string(name: &str) -> Self {
+        Self::new(name, ParamKind::String, NodeData::String(String::new()))
+    }
+
+    /// Declare a vector parameter (default zero)
+    pub fn vector3(name: &str) -> Self {
+        Self::new(name, ParamKind::Vector3, NodeData::Vector3([0.0; 3]))
+    }
+
+    /// Declare an RGBA color parameter (default opaque white)
+    pub fn color(name: &str) -> Self {
+        Self::new(name, ParamKind::Color, NodeData::Color([1.0; 4]))
+    }
+
+    /// Declare a file path parameter (default empty)
+    pub fn path(name: &str) -> Self {
+        Self::new(name, ParamKind::Path, NodeData::Path(PathData::new("")))
+    }
+
+    /// Declare a choice parameter (default is the first option)
+    pub fn choice(name: &str, options: &[&str]) -> Self {
+        let default = NodeData::String(options.first().map(|o| o.to_string()).unwrap_or_default());
+        let mut descriptor = Self::new(name, ParamKind::Choice, default);
+        descriptor.options = options.iter().map(|o| o.to_string()).collect();
+        descriptor
+    }
+
+    /// Declare a frame range parameter (default 1001-1100)
+    pub fn frame_range(name: &str) -> Self {
+        Self::new(name, ParamKind::FrameRange, FrameRange::new(1001, 1100).into())
+    }
+
+    /// Declare a timecode parameter at a frame rate (default 00:00:00:00)
+    pub fn timecode(name: &str, rate: FrameRate) -> Self {
+        let value = Timecode {
+            drop_frame: rate.is_drop_frame_rate(),
+            ..Timecode::default()
+        };
+        Self::new(name, ParamKind::Timecode, NodeData::Timecode { value, rate })
+    }
+
+    /// Declare a color ramp parameter (default black to white)
+    pub fn gradient(name: &str) -> Self {
+        let default = GradientData::two_color([0.0, 0.0, 0.0, 1.0], [1.0; 4]);
+        Self::new(name, ParamKind::Gradient, NodeData::Gradient(default))
+    }
+
+    /// Set the panel label
+    pub fn label(mut self, label: impl Into<String>) -> Self {
+        self.label = label.into();
+        self
+    }
+
+    /// Set the allowed numeric range
+    pub fn range(mut self, min: f32, max: f32) -> Self {
+        self.min = Some(min);
+        self.max = Some(max);
+        self
+    }
+
+    /// Set the default value
+    ///
+    /// The value is converted like a `set_parameter` call. A value that does
+    /// not fit the declaration is kept as given and reported by `check`, so
+    /// the host rejects the declarations (see `safe_parameters`).
+    pub fn default(mut self, value: impl Into<NodeData>) -> Self {
+        let value = value.into();
+        self.default = self.coerce(value.clone()).unwrap_or(value);
+        self
+    }
+
+    /// Check that the default value fits the declaration
+    pub fn check(&self) -> Result<(), ValidationError> {
+        self.coerce(self.default.clone()).map(|_| ()).map_err(|e| {
+            ValidationError::InvalidValue(format!("Invalid default for parameter '{}': {}", self.name, e))
+        })
+    }
+
+    /// Set the file dialog filter
+    pub fn filter(mut self, filter: impl Into<String>) -> Self {
+        self.filter = filter.into();
+        self
+    }
+
+    /// Set the tooltip description
+    pub fn description(mut self, description: impl Into<String>) -> Self {
+        self.description = description.into();
+        self
+    }
+
+    /// Convert a value to this parameter's type and check it
+    ///
+    /// Lossless widenings are accepted alongside exact matches, plus the
+    /// shapes the built-in widgets emit: sliders send floats for integer
+    /// parameters, and file pickers send strings for path parameters.
+    pub fn coerce(&self, value: NodeData) -> Result<NodeData, ValidationError> {
+        let value = match (self.kind, value) {
+            (ParamKind::Float, NodeData::Float(v)) => NodeData::Float(v),
+            (ParamKind::Float, NodeData::Integer(v)) => NodeData::Float(v as f32),
+            (ParamKind::Integer, NodeData::Integer(v)) => NodeData::Integer(v),
+            (ParamKind::Integer, NodeData::Float(v)) => NodeData::Integer(v.round() as i32),
+            (ParamKind::Boolean, NodeData::Boolean(v)) => NodeData::Boolean(v),
+            (ParamKind::String, NodeData::String(v)) => NodeData::String(v),
+            (ParamKind::Vector3, NodeData::Vector3(v)) => NodeData::Vector3(v),
+            (ParamKind::Color, NodeData::Color(v)) => NodeData::Color(v),
+            (ParamKind::Path, NodeData::Path(v)) => NodeData::Path(v),
+            (ParamKind::Path, NodeData::String(v)) => NodeData::Path(PathData::new(v)),
+            (ParamKind::Choice, NodeData::String(v)) => NodeData::String(v),
+            (ParamKind::FrameRange, range @ NodeData::FrameRange { .. }) => range,
+            (ParamKind::FrameRange, NodeData::String(v)) => FrameRange::parse(&v)
+                .map_err(|e| ValidationError::InvalidValue(format!("{}: {}", self.name, e)))?
+                .into(),
+            (ParamKind::Timecode, timecode @ NodeData::Timecode { .. }) => timecode,
+            (ParamKind::Gradient, NodeData::Gradient(v)) => NodeData::Gradient(v),
+            (ParamKind::Timecode, NodeData::String(v)) => NodeData::Timecode {
+                value: Timecode::parse(&v).map_err(|e| ValidationError::InvalidValue(format!("{}: {}", self.name, e)))?,
+                rate: self.default.as_timecode().map(|(_, rate)| rate).unwrap_or_default(),
+            },
+            (ParamKind::Choice, NodeData::Integer(i)) => match self.options.get(i.max(0) as usize) {
+                Some(option) if i >= 0 => NodeData::String(option.clone()),
+                _ => {
+                    return Err(ValidationError::InvalidValue(format!(
+                        "{}: option index {} is out of range",
+                        self.name, i
+                    )))
+                }
+            },
+            (kind, other) => {
+                return Err(ValidationError::InvalidValue(format!(
+                    "{}: expected {:?}, found {}",
+                    self.name,
+                    kind,
+                    other.type_name()
+                )))
+            }
+        };
+
+        if let Some(number) = value.as_float().or(value.as_integer().map(|v| v as f32)) {
+            if self.min.is_some_and(|min| number < min) || self.max.is_some_and(|max| number > max) {
+                let bound = |b: Option<f32>| b.map(|b| b.to_string()).unwrap_or_default();
+                return Err(ValidationError::InvalidValue(format!(
+                    "{}: {} is outside {}..={}",
+                    self.name,
+                    number,
+                    bound(self.min),
+                    bound(self.max)
+                )));
+            }
+        }
+        if self.kind == ParamKind::Choice {
+            let text = value.as_string().unwrap_or_default();
+            if !self.options.iter().any(|o| o == text) {
+                return Err(ValidationError::InvalidValue(format!(
+                    "{}: '{}' is not one of {:?}",
+                    self.name, text, self.options
+                )));
+            }
+        }
+        Ok(value)
+    }
+
+    /// Build the panel widget for this parameter
+    ///
+    /// `current` is the node's value; the default is shown if it is missing or
+    /// of the wrong type. Numbers without a declared range get a 0..=1 slider.
+    pub fn ui_element(&self, current: Option<&NodeData>) -> UIElement {
+        let value = current
+            .and_then(|v| self.coerce(v.clone()).ok())
+            .unwrap_or_else(|| self.default.clone());
+        let label = self.label.clone();
+        let parameter_name = self.name.clone();
+        match self.kind {
+            ParamKind::Float | ParamKind::Integer => UIElement::Slider {
+                label,
+                value: value.as_float().or(value.as_integer().map(|v| v as f32)).unwrap_or_default(),
+                min: self.min.unwrap_or(0.0),
+                max: self.max.unwrap_or(1.0),
+                parameter_name,
+            },
+            ParamKind::Boolean => UIElement::Checkbox {
+                label,
+                value: value.as_boolean().unwrap_or_default(),
+                parameter_name,
+            },
+            ParamKind::String => UIElement::TextEdit {
+                label,
+                value: value.as_string().unwrap_or_default().to_string(),
+                parameter_name,
+            },
+            ParamKind::Vector3 => UIElement::Vector3Input {
+                label,
+                value: value.as_vector3().unwrap_or_default(),
+                parameter_name,
+            },
+            ParamKind::Color => UIElement::ColorPicker {
+                label,
+                value: value.as_color().unwrap_or([1.0; 4]),
+                parameter_name,
+            },
+            ParamKind::Path => UIElement::FilePicker {
+                label,
+                value: value.as_path().map(|p| p.raw.clone()).unwrap_or_default(),
+                filter: self.filter.clone(),
+                parameter_name,
+            },
+            ParamKind::Choice => {
+                let text = value.as_string().unwrap_or_default();
+                UIElement::ComboBox {
+                    label,
+                    selected: self.options.iter().position(|o| o == text).unwrap_or(0),
+                    options: self.options.clone(),
+                    parameter_name,
+                }
+            }
+            ParamKind::FrameRange => UIElement::FrameRangeInput {
+                label,
+                value: value.as_frame_range().unwrap_or(FrameRange::single(1)),
+                parameter_name,
+            },
+            ParamKind::Timecode => {
+                let (value, rate) = value.as_timecode().unwrap_or_default();
+                UIElement::TimecodeInput {
+                    label,
+                    value,
+                    rate,
+                    parameter_name,
+                }
+            }
+            ParamKind::Gradient => UIElement::GradientEditor {
+                label,
+                value: value.as_gradient().cloned().unwrap_or_default(),
+                parameter_name,
+            },
+        }
+    }
+}
+
+/// The declared parameters of a node type
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ParamSet {
+    pub params: Vec<ParamDescriptor>,
+}
+
+impl ParamSet {
+    /// Create an empty set
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a parameter declaration
+    pub fn param(mut self, descriptor: ParamDescriptor) -> Self {
+        self.params.push(descriptor);
+        self
+    }
+
+    /// Find a declaration by name
+    pub fn get(&self, name: &str) -> Option<&ParamDescriptor> {
+        self.params.iter().find(|p| p.name == name)
+    }
+
+    /// Check if no parameters are declared
+    pub fn is_empty(&self) -> bool {
+        self.params.is_empty()
+    }
+
+    /// Check every declaration, returning the first invalid default
+    pub fn check(&self) -> Result<(), ValidationError> {
+        self.params.iter().try_for_each(ParamDescriptor::check)
+    }
+
+    /// Validate a `set_parameter` call, returning the value to store
+    ///
+    /// Names that are not declared are rejected.
+    pub fn validate(&self, name: &str, value: NodeData) -> Result<NodeData, ValidationError> {
+        self.get(name)
+            .ok_or_else(|| ValidationError::InvalidValue(format!("Unknown parameter: {}", name)))?
+            .coerce(value)
+    }
+
+    /// Names of the declared parameters, in declaration order
+    pub fn names(&self) -> Vec<String> {
+        self.params.iter().map(|p| p.name.clone()).collect()
+    }
+
+    /// Names of the declared `Path` parameters (rewritten by asset relinking)
+    pub fn path_parameters(&self) -> Vec<String> {
+        self.params
+            .iter()
+            .filter(|p| p.kind == ParamKind::Path)
+            .map(|p| p.name.clone())
+            .collect()
+    }
+
+    /// Default value of every declared parameter
+    pub fn defaults(&self) -> HashMap<String, NodeData> {
+        self.params
+            .iter()
+            .map(|p| (p.name.clone(), p.default.clone()))
+            .collect()
+    }
+
+    /// Build a parameter panel from the declarations, in declaration order
+    pub fn parameter_ui(&self, current: impl Fn(&str) -> Option<NodeData>) -> ParameterUI {
+        let mut ui = ParameterUI::new();
+        for param in &self.params {
+            ui.add_element(param.ui_element(current(&param.name).as_ref()));
+        }
+        ui
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn invalid_default_is_reported_not_panicked() {
+        let set = ParamSet::new()
+            .param(ParamDescriptor::float("radius").default(1.0))
+            .param(ParamDescriptor::float("scale").default("big"));
+        assert!(set.get("radius").unwrap().check().is_ok());
+        let err = set.check().unwrap_err();
+        assert!(err.to_string().contains("'scale'"));
+    }
+}
@@ -0,0 +1,143 @@
+//! Dry-run execution plans
+//!
+//! In dry-run mode the host calls `PluginNode::plan` instead of processing, so
+//! exporter and farm-submission nodes can report what a cook would do (files
+//! written, frames, output sizes) without doing it. The host aggregates the
+//! plans of all nodes into a cook preview.
+
+use crate::{DataType, PathData};
+use serde::{Deserialize, Serialize};
+
+/// A file a node would write
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PlannedWrite {
+    pub path: PathData,
+    /// Estimated file size (None if unknown)
+    #[serde(default)]
+    pub estimated_bytes: Option<u64>,
+    /// Whether an existing file would be replaced
+    #[serde(default)]
+    pub overwrites: bool,
+}
+
+/// An output a node would produce
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PlannedOutput {
+    pub port: String,
+    pub data_type: DataType,
+    /// Estimated in-memory size (None if unknown)
+    #[serde(default)]
+    pub estimated_bytes: Option<u64>,
+}
+
+/// What a node would do if cooked
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ExecutionPlan {
+    /// One-line description (e.g. "Export 240 frames to shot010.usd")
+    #[serde(default)]
+    pub summary: String,
+    /// Files that would be read
+    #[serde(default)]
+    pub reads: Vec<PathData>,
+    /// Files that would be written
+    #[serde(default)]
+    pub writes: Vec<PlannedWrite>,
+    /// Outputs that would be produced
+    #[serde(default)]
+    pub outputs: Vec<PlannedOutput>,
+    /// Frames that would be cooked (empty = current frame only)
+    #[serde(default)]
+    pub frames: Vec<i32>,
+    /// Estimated wall-clock time in seconds (None if unknown)
+    #[serde(default)]
+    pub estimated_seconds: Option<f64>,
+    /// Problems the cook would run into (missing inputs, unwritable paths)
+    #[serde(default)]
+    pub warnings: Vec<String>,
+}
+
+impl ExecutionPlan {
+    /// Create a plan with a summary
+    pub fn new(summary: impl Into<String>) -> Self {
+        Self {
+            summary: summary.into(),
+            ..Default::default()
+        }
+    }
+
+    /// Add a file that would be read
+    pub fn reads(mut self, path: PathData) -> Self {
+        self.reads.push(path);
+        self
+    }
+
+    /// Add a file that would be written
+    pub fn writes(mut self, path: PathData, estimated_bytes: Option<u64>) -> Self {
+        self.writes.push(PlannedWrite {
+            path,
+            estimated_bytes,
+            overwrites: false,
+        });
+        self
+    }
+
+    /// Add an output that would be produced
+    pub fn output(mut self, port: impl Into<String>, data_type: DataType, estimated_bytes: Option<u64>) -> Self {
+        self.outputs.push(PlannedOutput {
+            port: port.into(),
+            data_type,
+            estimated_bytes,
+        });
+        self
+    }
+
+    /// Set the frames to cook from an inclusive range and step
+    pub fn frame_range(mut self, start: i32, end: i32, step: i32) -> Self {
+        self.frames = (start..=end).step_by(step.max(1) as usize).collect();
+        self
+    }
+
+    /// Set the estimated time in seconds
+    pub fn estimated_seconds(mut self, seconds: f64) -> Self {
+        self.estimated_seconds = Some(seconds);
+        self
+    }
+
+    /// Add a warning
+    pub fn warning(mut self, warning: impl Into<String>) -> Self {
+        self.warnings.push(warning.into());
+        self
+    }
+
+    /// Check if the plan has side effects (writes files)
+    pub fn has_side_effects(&self) -> bool {
+        !self.writes.is_empty()
+    }
+
+    /// Total estimated size of written files (unknown sizes count as zero)
+    pub fn total_write_bytes(&self) -> u64 {
+        self.writes.iter().filter_map(|w| w.estimated_bytes).sum()
+    }
+
+    /// Check if every written file has a size estimate
+    pub fn write_sizes_known(&self) -> bool {
+        self.writes.iter().all(|w| w.estimated_bytes.is_some())
+    }
+
+    /// Fold another node's plan into this one (for graph-wide previews)
+    ///
+    /// Frames are unioned and known times are summed.
+    pub fn merge(&mut self, other: ExecutionPlan) {
+        self.reads.extend(other.reads);
+        self.writes.extend(other.writes);
+        self.outputs.extend(other.outputs);
+        self.frames.extend(other.frames);
+        self.frames.sort_unstable();
+        self.frames.dedup();
+        self.estimated_seconds = match (self.estimated_seconds, other.estimated_seconds) {
+            (Some(a), Some(b)) => Some(a + b),
+            (a, b) => a.or(b),
+        };
+        self.warnings.extend(other.warnings);
+    }
+}
@@ -1,6 +1,6 @@
 //! Plugin interface and metadata
 
-use crate::{NodeMetadata, PluginError, NodeRegistryTrait, NodeCategory, NodeData, ParameterUI, UIAction, ParameterChange};
+use crate::{NodeMetadata, PluginError, NodeRegistryTrait, NodeData, ParameterUI, UIAction, ParameterChange};
 
 // Viewport rendering is now handled by the core using viewport data
 // See viewport.rs for the new data-driven approach
@@ -17,6 +17,7 @@ pub struct PluginInfo {
 
 /// Menu structure for organizing nodes in the UI
 #[derive(Debug, Clone)]
+#[allow(clippy::large_enum_variant)]
 pub enum MenuStructure {
     Category {
         name: String,
@@ -49,6 +50,8 @@ pub trait NodePlugin: Send + Sync {
     }
     
     /// Called when plugin is unloaded (optional)
+    ///
+    /// Plugins holding a [`crate::PluginState`] should tear it down here.
     fn on_unload(&self) -> Result<(), PluginError> {
         Ok(())
     }
@@ -59,6 +62,7 @@ pub trait NodePlugin: Send + Sync {
 #[repr(C)]
 pub struct PluginHandle {
     plugin: *mut dyn NodePlugin,
+    theme: Option<crate::theme::ThemeHandle>,
 }
 
 impl PluginHandle {
@@ -66,23 +70,84 @@ impl PluginHandle {
     pub fn new(plugin: Box<dyn NodePlugin>) -> Self {
         Self {
             plugin: Box::into_raw(plugin),
+            theme: None,
         }
     }
     
+    /// Set the active UI theme (called by the host after loading and on theme changes)
+    pub fn set_theme(&mut self, theme: crate::theme::ThemeHandle) {
+        self.theme = Some(theme);
+    }
+    
+    /// Get the host's active UI theme (None until the host sets one)
+    pub fn theme(&self) -> Option<&crate::theme::ThemeHandle> {
+        self.theme.as_ref()
+    }
+    
     /// Convert back to a boxed plugin (takes ownership)
+    ///
+    /// # Safety
+    /// The handle must have been created by [`PluginHandle::new`] and not already consumed.
     pub unsafe fn into_plugin(self) -> Box<dyn NodePlugin> {
         Box::from_raw(self.plugin)
     }
     
     /// Get a reference to the plugin
+    ///
+    /// # Safety
+    /// The handle must still own a live plugin (not yet passed to `into_plugin`).
     pub unsafe fn as_plugin(&self) -> &dyn NodePlugin {
         &*self.plugin
     }
     
     /// Get a mutable reference to the plugin
+    ///
+    /// # Safety
+    /// The handle must still own a live plugin and no other reference to it may be active.
     pub unsafe fn as_plugin_mut(&mut self) -> &mut dyn NodePlugin {
         &mut *self.plugin
     }
+    
+    /// Run a call against the plugin, converting panics into `PluginError`
+    ///
+    /// # Safety
+    /// Same requirements as [`PluginHandle::as_plugin`].
+    pub unsafe fn call<R>(&self, context: &str, f: impl FnOnce(&dyn NodePlugin) -> R) -> Result<R, PluginError> {
+        let plugin = self.as_plugin();
+        crate::guard::catch_plugin_panic(context, || f(plugin))
+    }
+    
+    /// Get plugin metadata without letting a panic escape
+    ///
+    /// # Safety
+    /// Same requirements as [`PluginHandle::as_plugin`].
+    pub unsafe fn safe_plugin_info(&self) -> Result<PluginInfo, PluginError> {
+        self.call("plugin_info", |p| p.plugin_info())
+    }
+    
+    /// Register the plugin's nodes without letting a panic escape
+    ///
+    /// # Safety
+    /// Same requirements as [`PluginHandle::as_plugin`].
+    pub unsafe fn safe_register_nodes(&self, registry: &mut dyn NodeRegistryTrait) -> Result<(), PluginError> {
+        self.call("register_nodes", |p| p.register_nodes(registry))
+    }
+    
+    /// Call `on_load` without letting a panic escape
+    ///
+    /// # Safety
+    /// Same requirements as [`PluginHandle::as_plugin`].
+    pub unsafe fn safe_on_load(&self) -> Result<(), PluginError> {
+        self.call("on_load", |p| p.on_load())?
+    }
+    
+    /// Call `on_unload` without letting a panic escape
+    ///
+    /// # Safety
+    /// Same requirements as [`PluginHandle::as_plugin`].
+    pub unsafe fn safe_on_unload(&self) -> Result<(), PluginError> {
+        self.call("on_unload", |p| p.on_unload())?
+    }
 }
 
 /// Concrete wrapper for safe plugin node transfer
@@ -101,19 +166,471 @@ impl PluginNodeHandle {
     }
     
     /// Convert back to a boxed plugin node (takes ownership)
+    ///
+    /// # Safety
+    /// The handle must have been created by [`PluginNodeHandle::new`] and not already consumed.
     pub unsafe fn into_node(self) -> Box<dyn PluginNode> {
         Box::from_raw(self.node)
     }
     
     /// Get a reference to the plugin node
+    ///
+    /// # Safety
+    /// The handle must still own a live node (not yet passed to `into_node`).
     pub unsafe fn as_node(&self) -> &dyn PluginNode {
         &*self.node
     }
     
     /// Get a mutable reference to the plugin node
+    ///
+    /// # Safety
+    /// The handle must still own a live node and no other reference to it may be active.
     pub unsafe fn as_node_mut(&mut self) -> &mut dyn PluginNode {
         &mut *self.node
     }
+    
+    /// Run a read-only call against the node, converting panics into `PluginError`
+    ///
+    /// # Safety
+    /// Same requirements as [`PluginNodeHandle::as_node`].
+    pub unsafe fn call<R>(&self, context: &str, f: impl FnOnce(&dyn PluginNode) -> R) -> Result<R, PluginError> {
+        let node = self.as_node();
+        crate::guard::catch_plugin_panic(context, || f(node))
+    }
+    
+    /// Run a mutating call against the node, converting panics into `PluginError`
+    ///
+    /// # Safety
+    /// Same requirements as [`PluginNodeHandle::as_node_mut`].
+    pub unsafe fn call_mut<R>(&mut self, context: &str, f: impl FnOnce(&mut dyn PluginNode) -> R) -> Result<R, PluginError> {
+        let node = self.as_node_mut();
+        crate::guard::catch_plugin_panic(context, || f(node))
+    }
+    
+    /// Process the node without letting a panic escape
+    ///
+    /// # Safety
+    /// Same requirements as [`PluginNodeHandle::as_node_mut`].
+    pub unsafe fn safe_process(
+        &mut self,
+        inputs: &std::collections::HashMap<String, NodeData>
+    ) -> Result<std::collections::HashMap<String, NodeData>, PluginError> {
+        self.call_mut("process", |n| n.process(inputs))
+    }
+    
+    /// Run `try_process` without letting a panic escape
+    ///
+    /// # Safety
+    /// Same requirements as [`PluginNodeHandle::as_node_mut`].
+    pub unsafe fn safe_try_process(
+        &mut self,
+        inputs: &std::collections::HashMap<String, NodeData>
+    ) -> Result<crate::process::ProcessResult, PluginError> {
+        self.call_mut("try_process", |n| n.try_process(inputs))?
+            .map_err(PluginError::Other)
+    }
+    
+    /// Run `process_with_context` without letting a panic escape
+    ///
+    /// # Safety
+    /// Same requirements as [`PluginNodeHandle::as_node_mut`].
+    pub unsafe fn safe_process_with_context(
+        &mut self,
+        inputs: &std::collections::HashMap<String, NodeData>,
+        ctx: &crate::context::ExecutionContext
+    ) -> Result<crate::process::ProcessResult, PluginError> {
+        self.call_mut("process_with_context", |n| n.process_with_context(inputs, ctx))?
+            .map_err(PluginError::Other)
+    }
+    
+    /// Run `process_with_cache` without letting a panic escape
+    ///
+    /// # Safety
+    /// Same requirements as [`PluginNodeHandle::as_node_mut`].
+    pub unsafe fn safe_process_with_cache(
+        &mut self,
+        inputs: &std::collections::HashMap<String, NodeData>,
+        cache: &mut dyn crate::cache::PluginCache,
+        node_id: crate::NodeId
+    ) -> Result<std::collections::HashMap<String, NodeData>, PluginError> {
+        self.call_mut("process_with_cache", |n| n.process_with_cache(inputs, cache, node_id))
+    }
+    
+    /// Get the node's execution plan without letting a panic escape
+    ///
+    /// # Safety
+    /// Same requirements as [`PluginNodeHandle::as_node`].
+    pub unsafe fn safe_plan(
+        &self,
+        ctx: &crate::context::ExecutionContext
+    ) -> Result<crate::plan::ExecutionPlan, PluginError> {
+        self.call("plan", |n| n.plan(ctx))
+    }
+    
+    /// Queue the node's preload keys without letting a panic escape
+    ///
+    /// # Safety
+    /// Same requirements as [`PluginNodeHandle::as_node`].
+    pub unsafe fn safe_queue_preloads(
+        &self,
+        cache: &mut dyn crate::cache::PluginCache,
+        node_id: crate::NodeId
+    ) -> Result<usize, PluginError> {
+        self.call("queue_preloads", |n| n.queue_preloads(cache, node_id))
+    }
+    
+    /// Compute a queued preload key without letting a panic escape
+    ///
+    /// # Safety
+    /// Same requirements as [`PluginNodeHandle::as_node_mut`].
+    pub unsafe fn safe_preload(
+        &mut self,
+        key: &crate::cache::PluginCacheKey,
+        cache: &mut dyn crate::cache::PluginCache
+    ) -> Result<bool, PluginError> {
+        self.call_mut("preload", |n| n.preload(key, cache))?
+            .map_err(PluginError::Other)
+    }
+    
+    /// Render the node's thumbnail without letting a panic escape
+    ///
+    /// # Safety
+    /// Same requirements as [`PluginNodeHandle::as_node`].
+    pub unsafe fn safe_thumbnail(&self, size: (u32, u32)) -> Result<Option<crate::ImageData>, PluginError> {
+        self.call("thumbnail", |n| n.thumbnail(size))
+    }
+    
+    /// Get the node's asset dependencies without letting a panic escape
+    ///
+    /// # Safety
+    /// Same requirements as [`PluginNodeHandle::as_node`].
+    pub unsafe fn safe_asset_dependencies(&self) -> Result<Vec<crate::assets::AssetRef>, PluginError> {
+        self.call("asset_dependencies", |n| n.asset_dependencies())
+    }
+    
+    /// Get the node's render delegate without letting a panic escape
+    ///
+    /// # Safety
+    /// Same requirements as [`PluginNodeHandle::as_node_mut`].
+    pub unsafe fn safe_get_render_delegate(
+        &mut self
+    ) -> Result<Option<Box<dyn crate::render::RenderDelegate>>, PluginError> {
+        self.call_mut("get_render_delegate", |n| n.get_render_delegate())
+    }
+    
+    /// Get viewport data for rendering without letting a panic escape
+    ///
+    /// # Safety
+    /// Same requirements as [`PluginNodeHandle::as_node`].
+    pub unsafe fn safe_get_viewport_data(&self) -> Result<Option<crate::viewport::ViewportData>, PluginError> {
+        self.call("get_viewport_data", |n| n.get_viewport_data())
+    }
+    
+    /// Forward a viewport camera manipulation without letting a panic escape
+    ///
+    /// # Safety
+    /// Same requirements as [`PluginNodeHandle::as_node_mut`].
+    pub unsafe fn safe_handle_viewport_camera(
+        &mut self,
+        manipulation: crate::viewport::CameraManipulation
+    ) -> Result<(), PluginError> {
+        self.call_mut("handle_viewport_camera", |n| n.handle_viewport_camera(manipulation))
+    }
+    
+    /// Forward changed viewport settings without letting a panic escape
+    ///
+    /// # Safety
+    /// Same requirements as [`PluginNodeHandle::as_node_mut`].
+    pub unsafe fn safe_handle_viewport_settings(
+        &mut self,
+        settings: crate::viewport::ViewportSettings
+    ) -> Result<(), PluginError> {
+        self.call_mut("handle_viewport_settings", |n| n.handle_viewport_settings(settings))
+    }
+    
+    /// Get the parameter UI without letting a panic escape
+    ///
+    /// # Safety
+    /// Same requirements as [`PluginNodeHandle::as_node`].
+    pub unsafe fn safe_get_parameter_ui(&self) -> Result<ParameterUI, PluginError> {
+        self.call("get_parameter_ui", |n| n.get_parameter_ui())
+    }
+    
+    /// Handle a UI action without letting a panic escape
+    ///
+    /// # Safety
+    /// Same requirements as [`PluginNodeHandle::as_node_mut`].
+    pub unsafe fn safe_handle_ui_action(&mut self, action: UIAction) -> Result<Vec<ParameterChange>, PluginError> {
+        self.call_mut("handle_ui_action", |n| n.handle_ui_action(action))
+    }
+    
+    /// Handle a UI action with document access without letting a panic escape
+    ///
+    /// # Safety
+    /// Same requirements as [`PluginNodeHandle::as_node_mut`].
+    pub unsafe fn safe_handle_ui_action_with_document(
+        &mut self,
+        action: UIAction,
+        document: &mut dyn crate::document::DocumentEditApi
+    ) -> Result<Vec<ParameterChange>, PluginError> {
+        self.call_mut("handle_ui_action_with_document", |n| n.handle_ui_action_with_document(action, document))
+    }
+    
+    /// Set a parameter after checking it against the node type's declarations
+    /// 
+    /// Values are converted as described in `ParamDescriptor::coerce`. With an
+    /// empty `ParamSet` the value is passed through unchecked.
+    ///
+    /// # Safety
+    /// Same requirements as [`PluginNodeHandle::as_node_mut`].
+    pub unsafe fn safe_set_parameter(
+        &mut self,
+        params: &crate::params::ParamSet,
+        name: &str,
+        value: NodeData
+    ) -> Result<(), PluginError> {
+        let value = if params.is_empty() {
+            value
+        } else {
+            params.validate(name, value).map_err(|e| PluginError::Other(e.to_string()))?
+        };
+        self.call_mut("set_parameter", |n| n.set_parameter(name, value))
+    }
+    
+    /// Call `on_created` without letting a panic escape
+    ///
+    /// # Safety
+    /// Same requirements as [`PluginNodeHandle::as_node_mut`].
+    pub unsafe fn safe_on_created(&mut self, ctx: &crate::lifecycle::NodeContext) -> Result<(), PluginError> {
+        self.call_mut("on_created", |n| n.on_created(ctx))
+    }
+    
+    /// Call `on_destroyed` without letting a panic escape
+    ///
+    /// # Safety
+    /// Same requirements as [`PluginNodeHandle::as_node_mut`].
+    pub unsafe fn safe_on_destroyed(&mut self) -> Result<(), PluginError> {
+        self.call_mut("on_destroyed", |n| n.on_destroyed())
+    }
+    
+    /// Assign the node's graph ID without letting a panic escape
+    ///
+    /// # Safety
+    /// Same requirements as [`PluginNodeHandle::as_node_mut`].
+    pub unsafe fn safe_set_node_id(&mut self, id: crate::NodeId) -> Result<(), PluginError> {
+        self.call_mut("set_node_id", |n| n.set_node_id(id))
+    }
+    
+    /// Duplicate the node without letting a panic escape
+    ///
+    /// # Safety
+    /// Same requirements as [`PluginNodeHandle::as_node`].
+    pub unsafe fn safe_duplicate(&self) -> Result<Option<PluginNodeHandle>, PluginError> {
+        self.call("duplicate", |n| n.duplicate())
+    }
+    
+    /// Apply a "relink assets" request without letting a panic escape
+    ///
+    /// # Safety
+    /// Same requirements as [`PluginNodeHandle::as_node_mut`].
+    pub unsafe fn safe_relink_paths(
+        &mut self,
+        request: &crate::assets::RelinkRequest
+    ) -> Result<Vec<crate::assets::PathRewrite>, PluginError> {
+        self.call_mut("relink_paths", |n| n.relink_paths(request))
+    }
+    
+    /// Run the node's self-check without letting a panic escape
+    ///
+    /// # Safety
+    /// Same requirements as [`PluginNodeHandle::as_node`].
+    pub unsafe fn safe_self_check(&self) -> Result<Vec<crate::health::HealthIssue>, PluginError> {
+        self.call("self_check", |n| n.self_check())
+    }
+    
+    /// Get the node's bypass routes without letting a panic escape
+    ///
+    /// # Safety
+    /// Same requirements as [`PluginNodeHandle::as_node`].
+    pub unsafe fn safe_bypass_mapping(&self) -> Result<Option<Vec<crate::process::BypassRoute>>, PluginError> {
+        self.call("bypass_mapping", |n| n.bypass_mapping())
+    }
+    
+    /// Let the node register parameter drivers without letting a panic escape
+    ///
+    /// # Safety
+    /// Same requirements as [`PluginNodeHandle::as_node`].
+    pub unsafe fn safe_register_drivers(&self, drivers: &mut crate::drivers::DriverRegistry) -> Result<(), PluginError> {
+        self.call("register_drivers", |n| n.register_drivers(drivers))
+    }
+    
+    /// Get the node's execution hooks without letting a panic escape
+    ///
+    /// # Safety
+    /// Same requirements as [`PluginNodeHandle::as_node`].
+    pub unsafe fn safe_get_execution_hooks(
+        &self
+    ) -> Result<Option<Box<dyn crate::hooks::NodeExecutionHooks>>, PluginError> {
+        self.call("get_execution_hooks", |n| n.get_execution_hooks())
+    }
+    
+    /// Get the node's USD payload resolver without letting a panic escape
+    ///
+    /// # Safety
+    /// Same requirements as [`PluginNodeHandle::as_node`].
+    pub unsafe fn safe_get_payload_resolver(
+        &self
+    ) -> Result<Option<Box<dyn crate::usd::USDPayloadResolver>>, PluginError> {
+        self.call("get_payload_resolver", |n| n.get_payload_resolver())
+    }
+    
+    /// Take a UI snapshot of the node without letting a panic escape
+    /// 
+    /// Call between cooks; see `ui_snapshot` for the protocol.
+    ///
+    /// # Safety
+    /// Same requirements as [`PluginNodeHandle::as_node`].
+    pub unsafe fn safe_snapshot_parameters(
+        &self,
+        params: &crate::params::ParamSet,
+        version: u64
+    ) -> Result<crate::ui_snapshot::ParameterSnapshot, PluginError> {
+        self.call("snapshot_parameters", |n| {
+            let values = n.snapshot_parameters().unwrap_or_else(|| {
+                params
+                    .names()
+                    .into_iter()
+                    .filter_map(|name| n.get_parameter(&name).map(|value| (name, value)))
+                    .collect()
+            });
+            crate::ui_snapshot::ParameterSnapshot::new(values, version)
+        })
+    }
+    
+    /// Apply edits queued by a snapshot-based panel without letting a panic escape
+    /// 
+    /// Call between cooks. Parameter changes, including those returned by
+    /// `handle_ui_action` for queued actions, are validated against `params`;
+    /// rejected changes are skipped and reported with the reason. An edit the
+    /// node fails to apply is reported too, and the remaining edits still run.
+    ///
+    /// # Safety
+    /// Same requirements as [`PluginNodeHandle::as_node_mut`].
+    pub unsafe fn safe_apply_edits(
+        &mut self,
+        params: &crate::params::ParamSet,
+        edits: Vec<crate::ui_snapshot::QueuedEdit>
+    ) -> crate::ui_snapshot::AppliedEdits {
+        let mut applied = crate::ui_snapshot::AppliedEdits::default();
+        for edit in edits {
+            let changes = match edit {
+                crate::ui_snapshot::QueuedEdit::Parameter(change) => vec![change],
+                crate::ui_snapshot::QueuedEdit::Action(action) => match self.safe_handle_ui_action(action) {
+                    Ok(changes) => changes,
+                    Err(e) => {
+                        applied.failed.push(e);
+                        continue;
+                    }
+                },
+            };
+            for change in changes {
+                let value = if params.is_empty() {
+                    change.value
+                } else {
+                    match params.validate(&change.parameter, change.value) {
+                        Ok(value) => value,
+                        Err(e) => {
+                            applied.rejected.push((change.parameter, e));
+                            continue;
+                        }
+                    }
+                };
+                if let Err(e) = self.call_mut("set_parameter", |n| n.set_parameter(&change.parameter, value)) {
+                    applied.failed.push(e);
+                }
+            }
+        }
+        applied
+    }
+    
+    /// Read the node's parameters for a sidecar export without letting a panic escape
+    /// 
+    /// Exports `PluginNode::sidecar_parameters`, or the declared parameters
+    /// of `params` if the node does not override it. Values that cannot be
+    /// stored in a sidecar are skipped.
+    ///
+    /// # Safety
+    /// Same requirements as [`PluginNodeHandle::as_node`].
+    pub unsafe fn safe_export_parameters(
+        &self,
+        node_type: &str,
+        params: &crate::params::ParamSet
+    ) -> Result<crate::sidecar::SidecarNode, PluginError> {
+        self.call("export_parameters", |n| {
+            let mut sidecar = crate::sidecar::SidecarNode::new(node_type);
+            for name in n.sidecar_parameters().unwrap_or_else(|| params.names()) {
+                if let Some(value) = n.get_parameter(&name) {
+                    sidecar.insert(name, value);
+                }
+            }
+            sidecar
+        })
+    }
+    
+    /// Apply sidecar parameters without letting a panic escape
+    /// 
+    /// Values are converted to the type of the node's current value and
+    /// validated against `params`. Parameters the node does not export (see
+    /// `safe_export_parameters`) or that fail validation are skipped and
+    /// reported with the reason. A parameter the node fails to read or set is
+    /// reported too, and the remaining parameters still apply.
+    ///
+    /// # Safety
+    /// Same requirements as [`PluginNodeHandle::as_node_mut`].
+    pub unsafe fn safe_import_parameters(
+        &mut self,
+        sidecar: &crate::sidecar::SidecarNode,
+        params: &crate::params::ParamSet
+    ) -> crate::ui_snapshot::AppliedEdits {
+        let mut applied = crate::ui_snapshot::AppliedEdits::default();
+        let names = match self.call("sidecar_parameters", |n| n.sidecar_parameters()) {
+            Ok(names) => names.unwrap_or_else(|| params.names()),
+            Err(e) => {
+                applied.failed.push(e);
+                return applied;
+            }
+        };
+        for (name, value) in &sidecar.parameters {
+            if !names.contains(name) {
+                let reason = crate::ValidationError::InvalidValue(format!("Unknown parameter: {}", name));
+                applied.rejected.push((name.clone(), reason));
+                continue;
+            }
+            let current = match self.call("get_parameter", |n| n.get_parameter(name)) {
+                Ok(current) => current,
+                Err(e) => {
+                    applied.failed.push(e);
+                    continue;
+                }
+            };
+            let value = crate::sidecar::conform_sidecar_value(value.clone(), current.as_ref());
+            let value = if params.is_empty() {
+                value
+            } else {
+                match params.validate(name, value) {
+                    Ok(value) => value,
+                    Err(e) => {
+                        applied.rejected.push((name.clone(), e));
+                        continue;
+                    }
+                }
+            };
+            if let Err(e) = self.call_mut("set_parameter", |n| n.set_parameter(name, value)) {
+                applied.failed.push(e);
+            }
+        }
+        applied
+    }
 }
 
 /// Node factory trait for creating nodes
@@ -123,25 +640,159 @@ pub trait NodeFactory: Send + Sync {
     
     /// Create a new node instance at the given position
     fn create_node(&self, position: egui::Pos2) -> PluginNodeHandle;
+    
+    /// Declare the typed parameters of this node type
+    /// 
+    /// The host validates `set_parameter` calls against these and seeds new
+    /// nodes with their defaults. An empty set (the default) leaves parameters
+    /// unchecked.
+    fn parameters(&self) -> crate::params::ParamSet {
+        crate::params::ParamSet::new()
+    }
+    
+    /// Build the parameter panel from a snapshot, without the node (optional override)
+    /// 
+    /// Lets the host draw the panel while the node is cooking (see
+    /// `ui_snapshot`). The default lays out the declared `parameters`; None
+    /// (the default without declarations) makes the host fall back to
+    /// `PluginNode::get_parameter_ui`.
+    fn snapshot_ui(&self, snapshot: &crate::ui_snapshot::ParameterSnapshot) -> Option<ParameterUI> {
+        let params = self.parameters();
+        if params.is_empty() {
+            return None;
+        }
+        Some(params.parameter_ui(|name| snapshot.get(name).cloned()))
+    }
+}
+
+/// Create a node through a factory without letting a panic escape
+pub fn safe_create_node(factory: &dyn NodeFactory, position: egui::Pos2) -> Result<PluginNodeHandle, PluginError> {
+    crate::guard::catch_plugin_panic("create_node", || factory.create_node(position))
+}
+
+/// Get a factory's parameter declarations without letting a panic escape
+/// 
+/// Declarations with an invalid default value are rejected.
+pub fn safe_parameters(factory: &dyn NodeFactory) -> Result<crate::params::ParamSet, PluginError> {
+    let params = crate::guard::catch_plugin_panic("parameters", || factory.parameters())?;
+    params.check().map_err(|e| PluginError::RegistrationError(e.to_string()))?;
+    Ok(params)
+}
+
+/// Run an execution hook without letting a panic escape
+/// 
+/// Hook errors are returned as `PluginError::Other` alongside panics.
+pub fn safe_hook_call(
+    hooks: &mut dyn crate::hooks::NodeExecutionHooks,
+    context: &str,
+    f: impl FnOnce(&mut dyn crate::hooks::NodeExecutionHooks) -> Result<(), String>
+) -> Result<(), PluginError> {
+    crate::guard::catch_plugin_panic(context, || f(hooks))?.map_err(PluginError::Other)
 }
 
 /// Simplified node interface for plugins
 pub trait PluginNode: Send + Sync {
     /// Get the node's unique identifier
+    /// 
+    /// Not guaranteed to be stable across sessions; use `node_id()` for
+    /// references that must survive save/load.
     fn id(&self) -> String;
     
+    /// Get the host-assigned stable node ID (optional override)
+    /// 
+    /// Nodes that store the ID received in `set_node_id` should return it here.
+    fn node_id(&self) -> Option<crate::NodeId> {
+        None
+    }
+    
+    /// Receive the host-assigned stable node ID (optional override)
+    /// 
+    /// Called after the node is created and again after it is loaded from a
+    /// project. The same ID is passed to hooks and used in cache keys.
+    fn set_node_id(&mut self, _id: crate::NodeId) {
+        // Default: the node doesn't need its ID
+    }
+    
+    /// Create a copy of this node for copy/paste and duplicate (optional override)
+    /// 
+    /// None (the default) makes the host create a fresh node through the
+    /// factory and copy the parameters, losing internal state. Nodes that
+    /// derive `Clone` can return `Some(PluginNodeHandle::new(Box::new(self.clone())))`.
+    /// The host then assigns the copy a new ID with `set_node_id` and calls
+    /// `NodeExecutionHooks::on_node_duplicated`.
+    fn duplicate(&self) -> Option<PluginNodeHandle> {
+        None
+    }
+    
+    /// Called once after the node is created or loaded, with host context (optional)
+    /// 
+    /// Runs after `set_node_id` and before the first cook. Use it to set up
+    /// per-node resources that need the node ID, a storage directory or logging.
+    fn on_created(&mut self, _ctx: &crate::lifecycle::NodeContext) {
+        // Default: nothing to set up
+    }
+    
+    /// Called right before the host drops the node (optional)
+    /// 
+    /// Unlike `NodeExecutionHooks::on_node_removed` this also runs when the
+    /// project is closed, so it is the place to release per-node resources.
+    fn on_destroyed(&mut self) {
+        // Default: nothing to release
+    }
+    
     /// Get the node's position
     fn position(&self) -> egui::Pos2;
     
     /// Set the node's position
     fn set_position(&mut self, position: egui::Pos2);
     
+    /// Get the node's persistent user notes and metadata (optional override)
+    /// 
+    /// Nodes that embed a `NodeUserData` return it here; the host saves it with
+    /// the project and shows the notes in the parameter panel.
+    fn user_data(&self) -> Option<&crate::user_data::NodeUserData> {
+        None
+    }
+    
+    /// Get mutable user data (optional override, see `user_data`)
+    fn user_data_mut(&mut self) -> Option<&mut crate::user_data::NodeUserData> {
+        None
+    }
+    
+    /// Get a user data value by key
+    fn get_user_data(&self, key: &str) -> Option<NodeData> {
+        self.user_data()?.get(key).cloned()
+    }
+    
+    /// Set a user data value (returns false if the node doesn't store user data)
+    fn set_user_data(&mut self, key: &str, value: NodeData) -> bool {
+        match self.user_data_mut() {
+            Some(data) => {
+                data.set(key, value);
+                true
+            }
+            None => false,
+        }
+    }
+    
     /// Get the parameter UI description
     fn get_parameter_ui(&self) -> ParameterUI;
     
     /// Handle UI actions
     fn handle_ui_action(&mut self, action: UIAction) -> Vec<ParameterChange>;
     
+    /// Handle UI actions with access to the node graph document (optional override)
+    /// 
+    /// Use `document` for undoable edits beyond this node (see `crate::document`).
+    /// If not overridden, falls back to handle_ui_action().
+    fn handle_ui_action_with_document(
+        &mut self,
+        action: UIAction,
+        _document: &mut dyn crate::document::DocumentEditApi
+    ) -> Vec<ParameterChange> {
+        self.handle_ui_action(action)
+    }
+    
     /// Get a parameter value
     fn get_parameter(&self, name: &str) -> Option<NodeData>;
     
@@ -151,20 +802,204 @@ pub trait PluginNode: Send + Sync {
     /// Process the node (execute its functionality)
     fn process(&mut self, inputs: &std::collections::HashMap<String, NodeData>) -> std::collections::HashMap<String, NodeData>;
     
+    /// Process the node, reporting failure and per-output warnings (optional override)
+    /// 
+    /// The host displays returned warnings on the outgoing wires and in downstream
+    /// nodes' input tooltips. If not overridden, wraps the basic process() method.
+    fn try_process(
+        &mut self,
+        inputs: &std::collections::HashMap<String, NodeData>
+    ) -> Result<crate::process::ProcessResult, String> {
+        Ok(self.process(inputs).into())
+    }
+    
+    /// Process the node with the host's execution context (optional override)
+    /// 
+    /// Nodes that honor evaluation qualities (see `NodeMetadata::supported_qualities`)
+    /// should read `ctx.quality` here. Long-running nodes with `NodeMetadata::limits`
+    /// should call `ctx.check_limits()?` at safe points to abort gracefully.
+    /// Optional outputs that are expensive to build can be skipped when
+    /// `ctx.is_output_used(name)` is false. `ctx.dirty_reasons` tells why the
+    /// node is cooking, e.g. to rebuild only what a parameter change affects.
+    /// If not overridden, falls back to try_process().
+    fn process_with_context(
+        &mut self,
+        inputs: &std::collections::HashMap<String, NodeData>,
+        _ctx: &crate::context::ExecutionContext
+    ) -> Result<crate::process::ProcessResult, String> {
+        self.try_process(inputs)
+    }
+    
+    /// Describe what processing would do, without doing it (optional override)
+    /// 
+    /// Called by the host in dry-run mode. Nodes with side effects (exporters,
+    /// farm submission) should report files, frames and sizes; nodes that only
+    /// compute outputs can keep the default empty plan.
+    fn plan(&self, _ctx: &crate::context::ExecutionContext) -> crate::plan::ExecutionPlan {
+        crate::plan::ExecutionPlan::default()
+    }
+    
     /// Process the node with access to the cache system (optional override)
     /// 
     /// Plugins can override this method to use the advanced caching system.
+    /// Background tasks that populate entries should clone `cache.shared_handle()`
+    /// instead of holding on to `cache`.
     /// If not overridden, falls back to the basic process() method.
     fn process_with_cache(
         &mut self, 
         inputs: &std::collections::HashMap<String, NodeData>,
-        cache: &mut dyn crate::cache::PluginCache,
-        node_id: u32
+        _cache: &mut dyn crate::cache::PluginCache,
+        _node_id: crate::NodeId
     ) -> std::collections::HashMap<String, NodeData> {
         // Default implementation falls back to basic process
         self.process(inputs)
     }
     
+    /// Queue cache keys worth warming when a project opens (optional)
+    /// 
+    /// File-reader nodes queue the keys of their expensive load stages through
+    /// their plugin's `PluginCacheManager::request_preload`, which only accepts
+    /// the plugin's own keys, and return the number queued. Nodes can queue more
+    /// the same way later, e.g. when a file path parameter changes.
+    fn queue_preloads(
+        &self,
+        _cache: &mut dyn crate::cache::PluginCache,
+        _node_id: crate::NodeId
+    ) -> usize {
+        0
+    }
+    
+    /// Compute and store a queued preload key (optional)
+    /// 
+    /// Called by the host at idle time, off the interactive cook. Return whether
+    /// data was stored; errors are logged and the key is dropped from the queue.
+    fn preload(
+        &mut self,
+        _key: &crate::cache::PluginCacheKey,
+        _cache: &mut dyn crate::cache::PluginCache
+    ) -> Result<bool, String> {
+        Ok(false)
+    }
+    
+    /// Render a small preview of the node's output for the node body (optional)
+    /// 
+    /// The host schedules this off the UI thread after cooks, with `size` as the
+    /// maximum thumbnail size in pixels, and caches the result per node until
+    /// `thumbnail_key` changes. Texture nodes can return `ImageData::thumbnail`;
+    /// return None to show no preview.
+    fn thumbnail(&self, _size: (u32, u32)) -> Option<crate::ImageData> {
+        None
+    }
+    
+    /// Fingerprint of what the thumbnail shows (optional)
+    /// 
+    /// The host regenerates the cached thumbnail only when this changes. None
+    /// means the thumbnail is regenerated after every cook.
+    fn thumbnail_key(&self) -> Option<u64> {
+        None
+    }
+    
+    /// List external files this node depends on (optional)
+    /// 
+    /// Used by the host for project collect/archive and missing-file reporting.
+    fn asset_dependencies(&self) -> Vec<crate::assets::AssetRef> {
+        Vec::new()
+    }
+    
+    /// Names of the parameters that hold file paths (optional)
+    /// 
+    /// Defaults to the parameters recorded in `asset_dependencies`. Nodes whose
+    /// factory declares `ParamKind::Path` parameters can return
+    /// `ParamSet::path_parameters` instead.
+    fn path_parameters(&self) -> Vec<String> {
+        let mut parameters: Vec<String> = self
+            .asset_dependencies()
+            .into_iter()
+            .filter_map(|asset| asset.parameter)
+            .collect();
+        parameters.sort();
+        parameters.dedup();
+        parameters
+    }
+    
+    /// Apply a host "relink assets" request (optional override)
+    /// 
+    /// The default rewrites every `path_parameters` entry holding a string or
+    /// path through `set_parameter`. Override it if paths also live in
+    /// internal state (e.g. USD layer stacks).
+    fn relink_paths(&mut self, request: &crate::assets::RelinkRequest) -> Vec<crate::assets::PathRewrite> {
+        let mut rewrites = Vec::new();
+        for parameter in self.path_parameters() {
+            let (old_path, value) = match self.get_parameter(&parameter) {
+                Some(NodeData::String(old)) => match request.rewrite(&old) {
+                    Some(new) => (old, NodeData::String(new)),
+                    None => continue,
+                },
+                Some(NodeData::Path(old)) => match old.relinked(request) {
+                    Some(new) => (old.raw, NodeData::Path(new)),
+                    None => continue,
+                },
+                _ => continue,
+            };
+            let new_path = match &value {
+                NodeData::Path(path) => path.raw.clone(),
+                other => other.as_string().unwrap_or_default().to_string(),
+            };
+            self.set_parameter(&parameter, value);
+            rewrites.push(crate::assets::PathRewrite {
+                parameter: Some(parameter),
+                old_path,
+                new_path,
+            });
+        }
+        rewrites
+    }
+    
+    /// Report problems that would make the node fail (optional)
+    /// 
+    /// Run by the host's "validate scene" command without cooking, so keep it
+    /// cheap: check that files exist, licenses are valid, cached data is
+    /// compatible and required hardware is present. `health::check_assets`
+    /// covers the files listed in `asset_dependencies`.
+    fn self_check(&self) -> Vec<crate::health::HealthIssue> {
+        Vec::new()
+    }
+    
+    /// Input to output pass-through while the node is bypassed (optional override)
+    /// 
+    /// None (the default) lets the host infer routes from port types with
+    /// `BypassRoute::infer`. Return an empty list to output nothing when
+    /// bypassed (e.g. generators with no meaningful pass-through).
+    fn bypass_mapping(&self) -> Option<Vec<crate::process::BypassRoute>> {
+        None
+    }
+    
+    /// Parameter values for a UI snapshot (optional override)
+    /// 
+    /// None (the default) snapshots the parameters declared by the factory's
+    /// `ParamSet` through `get_parameter`. Override it to include values that
+    /// `NodeFactory::snapshot_ui` needs but are not declared.
+    fn snapshot_parameters(&self) -> Option<std::collections::HashMap<String, NodeData>> {
+        None
+    }
+    
+    /// Parameters written by "Export Parameters…" (optional override)
+    /// 
+    /// None (the default) exports the parameters declared by the factory's
+    /// `ParamSet`. Nodes without declarations can list their parameters here;
+    /// an empty list opts out of sidecars.
+    fn sidecar_parameters(&self) -> Option<Vec<String>> {
+        None
+    }
+    
+    /// Register parameter drivers for this node (optional)
+    /// 
+    /// The host calls this once after creating the node and evaluates the
+    /// registered drivers before each cook.
+    fn register_drivers(&self, _drivers: &mut crate::drivers::DriverRegistry) {
+        // Default: no driven parameters
+    }
+    
     /// Get execution hooks for this node (optional)
     /// 
     /// Plugins can return hooks to participate in the execution lifecycle.
@@ -173,6 +1008,22 @@ pub trait PluginNode: Send + Sync {
         None
     }
     
+    /// Get a resolver for lazily loaded USD payloads (for USD reader nodes)
+    /// 
+    /// Nodes that emit `USDPayloadRef` handles must return a resolver so the host
+    /// can load payload geometry on demand.
+    fn get_payload_resolver(&self) -> Option<Box<dyn crate::usd::USDPayloadResolver>> {
+        None
+    }
+    
+    /// Get a render delegate (for external renderer nodes)
+    /// 
+    /// The host drives the delegate and displays its progressive tiles in the
+    /// viewport and viewer panels.
+    fn get_render_delegate(&mut self) -> Option<Box<dyn crate::render::RenderDelegate>> {
+        None
+    }
+    
     /// Get viewport data for rendering (for viewport-type nodes)
     fn get_viewport_data(&self) -> Option<crate::viewport::ViewportData> {
         // Default implementation for non-viewport nodes
@@ -180,13 +1031,13 @@ pub trait PluginNode: Send + Sync {
     }
     
     /// Handle viewport camera manipulation (for viewport-type nodes)
-    fn handle_viewport_camera(&mut self, manipulation: crate::viewport::CameraManipulation) {
+    fn handle_viewport_camera(&mut self, _manipulation: crate::viewport::CameraManipulation) {
         // Default implementation for non-viewport nodes
         // Does nothing
     }
     
     /// Handle viewport settings changes (for viewport-type nodes)
-    fn handle_viewport_settings(&mut self, settings: crate::viewport::ViewportSettings) {
+    fn handle_viewport_settings(&mut self, _settings: crate::viewport::ViewportSettings) {
         // Default implementation for non-viewport nodes
         // Does nothing
     }
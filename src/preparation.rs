@@ -0,0 +1,168 @@
+//! Two-phase node preparation
+//!
+//! `NodeExecutionHooks::before_execution` runs on the host's UI thread, so slow
+//! preparation there (mounting network volumes, warming texture caches)
+//! freezes the editor. Hooks can instead start the work in
+//! `begin_preparation` and hand back a `PreparationToken`; the host polls it
+//! through `poll_preparation`, showing progress, and cooks the node once it is
+//! ready:
+//!
+//! ```rust,ignore
+//! fn begin_preparation(
+//!     &mut self,
+//!     _plugin_handle: &PluginHandle,
+//!     _node_id: NodeId,
+//!     _connections: &HashMap<String, NodeData>,
+//! ) -> Result<Option<PreparationToken>, String> {
+//!     let volume = self.volume.clone();
+//!     Ok(Some(PreparationToken::spawn(move |token| {
+//!         token.report(0.0, "Mounting volume");
+//!         mount(&volume, token.cancellation())?;
+//!         token.report(0.5, "Warming textures");
+//!         warm_textures(&volume)
+//!     })))
+//! }
+//! ```
+//!
+//! Host side:
+//!
+//! 1. Call `begin_preparation`. `None` means nothing to wait for.
+//! 2. Call `poll_preparation` each UI frame while it returns
+//!    `PreparationStatus::Pending`, displaying the progress.
+//! 3. On `Ready`, call `before_execution` and cook as usual. On `Failed`,
+//!    report the error and skip the cook.
+//!
+//! If the user cancels, the host calls `PreparationToken::cancel` and stops
+//! polling.
+
+use crate::{CancellationToken, ProgressReporter};
+use std::sync::{Arc, Mutex};
+
+/// State of a preparation
+#[derive(Debug, Clone, PartialEq)]
+pub enum PreparationStatus {
+    /// Still running
+    Pending {
+        /// Completion (0.0-1.0)
+        fraction: f32,
+        /// Short status message (e.g. "Mounting volume")
+        message: String,
+    },
+    /// Done; the node can be cooked
+    Ready,
+    /// Failed or was cancelled; the node should not be cooked
+    Failed(String),
+}
+
+impl PreparationStatus {
+    /// Check if the preparation is done, successfully or not
+    pub fn is_finished(&self) -> bool {
+        !matches!(self, PreparationStatus::Pending { .. })
+    }
+}
+
+/// Handle to preparation running in the background
+///
+/// Clones share the same state: the worker reports progress and finishes the
+/// token while the host polls another clone.
+#[derive(Debug, Clone)]
+pub struct PreparationToken {
+    status: Arc<Mutex<PreparationStatus>>,
+    cancel: CancellationToken,
+}
+
+impl Default for PreparationToken {
+    fn default() -> Self {
+        Self {
+            status: Arc::new(Mutex::new(PreparationStatus::Pending {
+                fraction: 0.0,
+                message: String::new(),
+            })),
+            cancel: CancellationToken::new(),
+        }
+    }
+}
+
+impl PreparationToken {
+    /// Create a pending token (finish it with `finish`)
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Token that is already finished, for preparation that completed immediately
+    pub fn finished(result: Result<(), String>) -> Self {
+        let token = Self::new();
+        token.finish(result);
+        token
+    }
+
+    /// Run `work` on a new thread and finish the token with its result
+    ///
+    /// A panic in `work` fails the preparation instead of leaving it pending.
+    pub fn spawn(work: impl FnOnce(&PreparationToken) -> Result<(), String> + Send + 'static) -> Self {
+        let token = Self::new();
+        let worker = token.clone();
+        std::thread::spawn(move || {
+            let result = crate::guard::catch_plugin_panic("preparation", || work(&worker))
+                .map_err(|e| e.to_string())
+                .and_then(|result| result);
+            worker.finish(result);
+        });
+        token
+    }
+
+    /// Update the progress of a pending preparation
+    pub fn report(&self, fraction: f32, message: &str) {
+        let mut status = self.lock();
+        if !status.is_finished() {
+            *status = PreparationStatus::Pending {
+                fraction: fraction.clamp(0.0, 1.0),
+                message: message.to_string(),
+            };
+        }
+    }
+
+    /// Mark the preparation as done
+    ///
+    /// Ignored if the token already finished or was cancelled.
+    pub fn finish(&self, result: Result<(), String>) {
+        let mut status = self.lock();
+        if !status.is_finished() {
+            *status = match result {
+                Ok(()) => PreparationStatus::Ready,
+                Err(message) => PreparationStatus::Failed(message),
+            };
+        }
+    }
+
+    /// Current state
+    pub fn status(&self) -> PreparationStatus {
+        self.lock().clone()
+    }
+
+    /// Ask the worker to stop and fail the preparation
+    pub fn cancel(&self) {
+        self.cancel.cancel();
+        self.finish(Err("Preparation cancelled".to_string()));
+    }
+
+    /// Check if the host cancelled the preparation
+    pub fn is_cancelled(&self) -> bool {
+        self.cancel.is_cancelled()
+    }
+
+    /// Cancellation flag to pass to long-running calls in the worker
+    pub fn cancellation(&self) -> &CancellationToken {
+        &self.cancel
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, PreparationStatus> {
+        self.status.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+impl ProgressReporter for PreparationToken {
+    fn report(&self, fraction: f32, message: &str) {
+        PreparationToken::report(self, fraction, message)
+    }
+}
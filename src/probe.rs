@@ -0,0 +1,236 @@
+//! Lightweight plugin metadata probe
+//!
+//! Loading a plugin runs its constructor and `on_load`, which may initialize
+//! GPU contexts or allocate large resources. The probe lets the host's plugin
+//! manager list and vet plugins (SDK version conflicts, required hardware)
+//! without constructing them. Plugins export it next to `create_plugin`:
+//!
+//! ```rust,ignore
+//! nodle_plugin_sdk::export_plugin_probe!(
+//!     PluginManifest::new(PluginInfo { /* ... */ })
+//!         .with_capability(PluginCapability::Gpu)
+//!         .with_capability(PluginCapability::Viewport)
+//! );
+//! ```
+//!
+//! The manifest crosses the library boundary as NUL-terminated JSON, so it can
+//! be read even when the plugin was built against an incompatible SDK.
+
+use crate::{JsonValue, PluginError, PluginInfo, SDK_VERSION};
+use std::collections::BTreeMap;
+use std::ffi::{c_char, CStr, CString};
+
+/// Name of the exported probe function
+pub const PROBE_SYMBOL: &str = "nodle_plugin_probe";
+
+/// Signature of the exported probe function
+///
+/// Returns a pointer to NUL-terminated manifest JSON owned by the plugin and
+/// valid until it is unloaded, or null if building the manifest failed.
+pub type ProbeFn = unsafe extern "C" fn() -> *const c_char;
+
+/// Feature or requirement a plugin declares up front
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum PluginCapability {
+    /// Needs a GPU to function
+    Gpu,
+    /// Provides viewport data
+    Viewport,
+    /// Provides a render delegate
+    RenderDelegate,
+    /// Reads or writes USD
+    Usd,
+    /// Installs execution hooks
+    ExecutionHooks,
+    /// Accesses the network
+    Network,
+    /// Capability unknown to this SDK version
+    Other(String),
+}
+
+impl PluginCapability {
+    /// Name used in the manifest
+    pub fn as_str(&self) -> &str {
+        match self {
+            PluginCapability::Gpu => "gpu",
+            PluginCapability::Viewport => "viewport",
+            PluginCapability::RenderDelegate => "render_delegate",
+            PluginCapability::Usd => "usd",
+            PluginCapability::ExecutionHooks => "execution_hooks",
+            PluginCapability::Network => "network",
+            PluginCapability::Other(name) => name,
+        }
+    }
+
+    /// Parse a manifest name (unknown names become `Other`)
+    pub fn from_name(name: &str) -> Self {
+        match name {
+            "gpu" => PluginCapability::Gpu,
+            "viewport" => PluginCapability::Viewport,
+            "render_delegate" => PluginCapability::RenderDelegate,
+            "usd" => PluginCapability::Usd,
+            "execution_hooks" => PluginCapability::ExecutionHooks,
+            "network" => PluginCapability::Network,
+            other => PluginCapability::Other(other.to_string()),
+        }
+    }
+}
+
+/// What the probe reports about a plugin
+#[derive(Debug, Clone)]
+pub struct PluginManifest {
+    pub info: PluginInfo,
+    /// SDK version the plugin was built against
+    pub sdk_version: String,
+    pub capabilities: Vec<PluginCapability>,
+}
+
+impl PluginManifest {
+    /// Create a manifest for the current SDK version
+    pub fn new(info: PluginInfo) -> Self {
+        Self {
+            info,
+            sdk_version: SDK_VERSION.to_string(),
+            capabilities: Vec::new(),
+        }
+    }
+
+    /// Declare a capability
+    pub fn with_capability(mut self, capability: PluginCapability) -> Self {
+        if !self.capabilities.contains(&capability) {
+            self.capabilities.push(capability);
+        }
+        self
+    }
+
+    /// Check if a capability is declared
+    pub fn has_capability(&self, capability: &PluginCapability) -> bool {
+        self.capabilities.contains(capability)
+    }
+
+    /// Check the plugin was built against an SDK compatible with `host_sdk_version`
+    ///
+    /// Versions must share the major version, and the minor version too while
+    /// the major version is 0.
+    pub fn check_sdk(&self, host_sdk_version: &str) -> Result<(), PluginError> {
+        let release = |version: &str| -> Option<(u64, u64)> {
+            let mut parts = version.split('.');
+            let major = parts.next()?.trim().parse().ok()?;
+            let minor = parts.next().unwrap_or("0").trim().parse().ok()?;
+            Some((major, minor))
+        };
+        let compatible = match (release(&self.sdk_version), release(host_sdk_version)) {
+            (Some((0, plugin_minor)), Some((0, host_minor))) => plugin_minor == host_minor,
+            (Some((plugin_major, _)), Some((host_major, _))) => plugin_major == host_major,
+            _ => false,
+        };
+        if compatible {
+            Ok(())
+        } else {
+            Err(PluginError::CompatibilityError(format!(
+                "{} was built against SDK {}, host uses {}",
+                self.info.name, self.sdk_version, host_sdk_version
+            )))
+        }
+    }
+
+    /// Serialize to manifest JSON
+    pub fn to_json_string(&self) -> String {
+        let mut info = BTreeMap::new();
+        info.insert("name".to_string(), JsonValue::from(self.info.name.as_str()));
+        info.insert("version".to_string(), JsonValue::from(self.info.version.as_str()));
+        info.insert("author".to_string(), JsonValue::from(self.info.author.as_str()));
+        info.insert("description".to_string(), JsonValue::from(self.info.description.as_str()));
+        info.insert(
+            "compatible_version".to_string(),
+            JsonValue::from(self.info.compatible_version.as_str()),
+        );
+        let mut object = BTreeMap::new();
+        object.insert("info".to_string(), JsonValue::Object(info));
+        object.insert("sdk_version".to_string(), JsonValue::from(self.sdk_version.as_str()));
+        object.insert(
+            "capabilities".to_string(),
+            JsonValue::Array(self.capabilities.iter().map(|c| JsonValue::from(c.as_str())).collect()),
+        );
+        JsonValue::Object(object).to_json_string()
+    }
+
+    /// Parse manifest JSON
+    pub fn parse(text: &str) -> Result<Self, PluginError> {
+        let invalid = |what: &str| PluginError::LoadError(format!("Invalid plugin manifest: {}", what));
+        let json = JsonValue::parse(text).map_err(|e| invalid(&e))?;
+        let string = |pointer: &str| -> Result<String, PluginError> {
+            json.get_pointer(pointer)
+                .and_then(JsonValue::as_str)
+                .map(str::to_string)
+                .ok_or_else(|| invalid(&format!("missing {}", pointer)))
+        };
+        Ok(Self {
+            info: PluginInfo {
+                name: string("/info/name")?,
+                version: string("/info/version")?,
+                author: string("/info/author")?,
+                description: string("/info/description")?,
+                compatible_version: string("/info/compatible_version")?,
+            },
+            sdk_version: string("/sdk_version")?,
+            capabilities: json
+                .get("capabilities")
+                .and_then(JsonValue::as_array)
+                .map(|names| {
+                    names
+                        .iter()
+                        .filter_map(JsonValue::as_str)
+                        .map(PluginCapability::from_name)
+                        .collect()
+                })
+                .unwrap_or_default(),
+        })
+    }
+}
+
+/// Build the NUL-terminated manifest returned by an exported probe
+///
+/// Used by `export_plugin_probe!`; panics while building the manifest are
+/// caught and reported as a null pointer.
+pub fn manifest_to_c_string(build: impl FnOnce() -> PluginManifest) -> Option<CString> {
+    crate::guard::catch_plugin_panic("probe", || {
+        let json = build().to_json_string();
+        // JSON strings escape control characters, so interior NULs cannot occur
+        CString::new(json).ok()
+    })
+    .ok()
+    .flatten()
+}
+
+/// Read the manifest returned by a plugin's probe function
+///
+/// # Safety
+/// `ptr` must be null or point to a NUL-terminated string that stays valid
+/// for the duration of the call (as returned by a `ProbeFn`).
+pub unsafe fn read_probe(ptr: *const c_char) -> Result<PluginManifest, PluginError> {
+    if ptr.is_null() {
+        return Err(PluginError::LoadError("Plugin probe returned no manifest".to_string()));
+    }
+    let text = CStr::from_ptr(ptr)
+        .to_str()
+        .map_err(|e| PluginError::LoadError(format!("Invalid plugin manifest: {}", e)))?;
+    PluginManifest::parse(text)
+}
+
+/// Export the plugin probe (`nodle_plugin_probe`) for a manifest expression
+///
+/// The expression is evaluated once, on the first probe call.
+#[macro_export]
+macro_rules! export_plugin_probe {
+    ($manifest:expr) => {
+        #[no_mangle]
+        pub extern "C" fn nodle_plugin_probe() -> *const ::std::ffi::c_char {
+            static MANIFEST: ::std::sync::OnceLock<Option<::std::ffi::CString>> = ::std::sync::OnceLock::new();
+            MANIFEST
+                .get_or_init(|| $crate::probe::manifest_to_c_string(|| $manifest))
+                .as_ref()
+                .map_or(::std::ptr::null(), |manifest| manifest.as_ptr())
+        }
+    };
+}
@@ -0,0 +1,378 @@
+//! Node execution results
+//!
+//! `PluginNode::try_process` returns a `ProcessResult` carrying the output data
+//! plus structured warnings attached to specific outputs. The host displays
+//! output warnings on the outgoing wires and in downstream nodes' input tooltips.
+//!
+//! Output data is held in `Outputs`, which keeps ports in the order the node
+//! produced them and can carry labelled auxiliary outputs (debug views, masks)
+//! and per-output metadata for downstream caching.
+//!
+//! A bypassed node is not cooked; the host passes inputs straight through to
+//! outputs along the node's `BypassRoute`s instead.
+
+use crate::{DateTime, NodeData, NodeMetadata};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// Warning attached to a node output
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct OutputWarning {
+    /// Output port the warning applies to (None = whole node)
+    pub output: Option<String>,
+    /// Human-readable message (e.g. "normals missing, generated flat")
+    pub message: String,
+    /// Optional machine-readable code for filtering
+    pub code: Option<String>,
+}
+
+impl OutputWarning {
+    /// Create a warning for a specific output port
+    pub fn on_output(output: impl Into<String>, message: impl Into<String>) -> Self {
+        Self {
+            output: Some(output.into()),
+            message: message.into(),
+            code: None,
+        }
+    }
+
+    /// Create a warning for the whole node
+    pub fn on_node(message: impl Into<String>) -> Self {
+        Self {
+            output: None,
+            message: message.into(),
+            code: None,
+        }
+    }
+
+    /// Add a machine-readable code
+    pub fn with_code(mut self, code: impl Into<String>) -> Self {
+        self.code = Some(code.into());
+        self
+    }
+}
+
+/// Optional facts about an output value
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct OutputMetadata {
+    /// Content hash of the data (lets downstream caches skip re-hashing)
+    #[serde(default)]
+    pub hash: Option<u64>,
+    /// When the underlying data was produced or last modified
+    #[serde(default)]
+    pub timestamp: Option<DateTime>,
+}
+
+/// One produced output
+#[derive(Debug, Clone)]
+pub struct OutputEntry {
+    /// Port name
+    pub name: String,
+    pub data: NodeData,
+    /// Display label for auxiliary outputs (None = use the port's label)
+    pub label: Option<String>,
+    /// Extra output not declared in the node's metadata (shown for inspection only)
+    pub auxiliary: bool,
+    pub metadata: OutputMetadata,
+}
+
+/// Ordered node outputs
+/// 
+/// ```rust,ignore
+/// let outputs = Outputs::new()
+///     .with("Geometry", geometry.into())
+///     .with_auxiliary("debug_normals", "Normals (debug)", normals.into())
+///     .with_hash("Geometry", hash);
+/// ```
+/// 
+/// Setting a port that already exists replaces its data in place, keeping its
+/// position and label.
+#[derive(Debug, Clone, Default)]
+pub struct Outputs {
+    entries: Vec<OutputEntry>,
+}
+
+impl Outputs {
+    /// Create an empty output set
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set an output value
+    pub fn with(mut self, name: impl Into<String>, data: NodeData) -> Self {
+        self.insert(name, data);
+        self
+    }
+
+    /// Set a labelled auxiliary output
+    pub fn with_auxiliary(mut self, name: impl Into<String>, label: impl Into<String>, data: NodeData) -> Self {
+        let name = name.into();
+        self.insert(name.clone(), data);
+        if let Some(output) = self.output_mut(&name) {
+            output.label = Some(label.into());
+            output.auxiliary = true;
+        }
+        self
+    }
+
+    /// Record the content hash of an existing output
+    pub fn with_hash(mut self, name: &str, hash: u64) -> Self {
+        if let Some(output) = self.output_mut(name) {
+            output.metadata.hash = Some(hash);
+        }
+        self
+    }
+
+    /// Record the timestamp of an existing output
+    pub fn with_timestamp(mut self, name: &str, timestamp: DateTime) -> Self {
+        if let Some(output) = self.output_mut(name) {
+            output.metadata.timestamp = Some(timestamp);
+        }
+        self
+    }
+
+    /// Set an output value, returning the previous value
+    pub fn insert(&mut self, name: impl Into<String>, data: NodeData) -> Option<NodeData> {
+        let name = name.into();
+        match self.output_mut(&name) {
+            Some(output) => Some(std::mem::replace(&mut output.data, data)),
+            None => {
+                self.entries.push(OutputEntry {
+                    name,
+                    data,
+                    label: None,
+                    auxiliary: false,
+                    metadata: OutputMetadata::default(),
+                });
+                None
+            }
+        }
+    }
+
+    /// Get an output value
+    pub fn get(&self, name: &str) -> Option<&NodeData> {
+        self.output(name).map(|output| &output.data)
+    }
+
+    /// Get an output with its label and metadata
+    pub fn output(&self, name: &str) -> Option<&OutputEntry> {
+        self.entries.iter().find(|output| output.name == name)
+    }
+
+    /// Get an output mutably
+    pub fn output_mut(&mut self, name: &str) -> Option<&mut OutputEntry> {
+        self.entries.iter_mut().find(|output| output.name == name)
+    }
+
+    /// Get the metadata of an output
+    pub fn metadata(&self, name: &str) -> Option<&OutputMetadata> {
+        self.output(name).map(|output| &output.metadata)
+    }
+
+    /// Remove an output
+    pub fn remove(&mut self, name: &str) -> Option<NodeData> {
+        let index = self.entries.iter().position(|output| output.name == name)?;
+        Some(self.entries.remove(index).data)
+    }
+
+    /// Check if an output is set
+    pub fn contains(&self, name: &str) -> bool {
+        self.output(name).is_some()
+    }
+
+    /// Number of outputs
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Check if no outputs are set
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Iterate over outputs in order
+    pub fn iter(&self) -> impl Iterator<Item = &OutputEntry> {
+        self.entries.iter()
+    }
+
+    /// Port names in order
+    pub fn names(&self) -> impl Iterator<Item = &str> {
+        self.entries.iter().map(|output| output.name.as_str())
+    }
+
+    /// Declared (non-auxiliary) outputs in order
+    pub fn primary(&self) -> impl Iterator<Item = &OutputEntry> {
+        self.entries.iter().filter(|output| !output.auxiliary)
+    }
+
+    /// Auxiliary outputs in order
+    pub fn auxiliary(&self) -> impl Iterator<Item = &OutputEntry> {
+        self.entries.iter().filter(|output| output.auxiliary)
+    }
+
+    /// Convert to a plain map, dropping order, labels and metadata
+    pub fn into_map(self) -> HashMap<String, NodeData> {
+        self.entries
+            .into_iter()
+            .map(|output| (output.name, output.data))
+            .collect()
+    }
+}
+
+impl From<HashMap<String, NodeData>> for Outputs {
+    /// Maps have no order, so ports are sorted by name
+    fn from(map: HashMap<String, NodeData>) -> Self {
+        let mut pairs: Vec<(String, NodeData)> = map.into_iter().collect();
+        pairs.sort_by(|a, b| a.0.cmp(&b.0));
+        pairs.into_iter().collect()
+    }
+}
+
+impl From<Outputs> for HashMap<String, NodeData> {
+    fn from(outputs: Outputs) -> Self {
+        outputs.into_map()
+    }
+}
+
+impl FromIterator<(String, NodeData)> for Outputs {
+    fn from_iter<I: IntoIterator<Item = (String, NodeData)>>(iter: I) -> Self {
+        let mut outputs = Outputs::new();
+        for (name, data) in iter {
+            outputs.insert(name, data);
+        }
+        outputs
+    }
+}
+
+impl IntoIterator for Outputs {
+    type Item = OutputEntry;
+    type IntoIter = std::vec::IntoIter<OutputEntry>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.entries.into_iter()
+    }
+}
+
+/// Result of a successful node execution
+#[derive(Debug, Clone, Default)]
+pub struct ProcessResult {
+    /// Output data in port order
+    pub outputs: Outputs,
+    /// Warnings produced during execution
+    pub warnings: Vec<OutputWarning>,
+}
+
+impl ProcessResult {
+    /// Create an empty result
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set an output value
+    pub fn with_output(mut self, name: impl Into<String>, data: NodeData) -> Self {
+        self.outputs.insert(name.into(), data);
+        self
+    }
+
+    /// Attach a warning to an output port
+    pub fn warn(&mut self, output: impl Into<String>, message: impl Into<String>) {
+        self.warnings.push(OutputWarning::on_output(output, message));
+    }
+
+    /// Attach a warning to the whole node
+    pub fn warn_node(&mut self, message: impl Into<String>) {
+        self.warnings.push(OutputWarning::on_node(message));
+    }
+
+    /// Get the warnings for an output port (including node-level warnings)
+    pub fn warnings_for(&self, output: &str) -> Vec<&OutputWarning> {
+        self.warnings
+            .iter()
+            .filter(|w| w.output.as_deref().map_or(true, |o| o == output))
+            .collect()
+    }
+
+    /// Check if any warnings were produced
+    pub fn has_warnings(&self) -> bool {
+        !self.warnings.is_empty()
+    }
+}
+
+impl From<HashMap<String, NodeData>> for ProcessResult {
+    fn from(outputs: HashMap<String, NodeData>) -> Self {
+        Outputs::from(outputs).into()
+    }
+}
+
+impl From<Outputs> for ProcessResult {
+    fn from(outputs: Outputs) -> Self {
+        Self {
+            outputs,
+            warnings: Vec::new(),
+        }
+    }
+}
+
+/// Input passed straight through to an output while the node is bypassed
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct BypassRoute {
+    /// Input port the data comes from
+    pub input: String,
+    /// Output port that receives it
+    pub output: String,
+}
+
+impl BypassRoute {
+    /// Create a route from an input to an output
+    pub fn new(input: impl Into<String>, output: impl Into<String>) -> Self {
+        Self {
+            input: input.into(),
+            output: output.into(),
+        }
+    }
+
+    /// Routes inferred from port types
+    ///
+    /// Each output is fed by the first input of the same data type that no
+    /// earlier output uses. Outputs without a matching input get no route.
+    pub fn infer(metadata: &NodeMetadata) -> Vec<BypassRoute> {
+        let mut used = vec![false; metadata.inputs.len()];
+        metadata
+            .outputs
+            .iter()
+            .filter_map(|output| {
+                let index = metadata
+                    .inputs
+                    .iter()
+                    .enumerate()
+                    .position(|(i, input)| !used[i] && input.data_type == output.data_type)?;
+                used[index] = true;
+                Some(BypassRoute::new(metadata.inputs[index].name.as_str(), output.name.as_str()))
+            })
+            .collect()
+    }
+}
+
+/// Outputs of a bypassed node
+///
+/// Each routed output gets its input's value; unrouted outputs and outputs
+/// whose input is unconnected get `NodeData::None`. Outputs keep the order of
+/// `outputs`.
+pub fn bypass_outputs(
+    routes: &[BypassRoute],
+    outputs: &[String],
+    inputs: &HashMap<String, NodeData>,
+) -> Outputs {
+    outputs
+        .iter()
+        .map(|output| {
+            let data = routes
+                .iter()
+                .find(|route| &route.output == output)
+                .and_then(|route| inputs.get(&route.input))
+                .cloned()
+                .unwrap_or(NodeData::None);
+            (output.clone(), data)
+        })
+        .collect()
+}
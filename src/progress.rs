@@ -0,0 +1,84 @@
+//! Progress reporting and cooperative cancellation
+//!
+//! The host puts a `ProgressHandle` and a `CancellationToken` in every
+//! `ExecutionContext`. Long-running nodes report how far along they are and
+//! check the token at safe points, returning early once the user cancels.
+
+use std::fmt;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+
+/// Receives progress updates from a running operation
+pub trait ProgressReporter: Send + Sync {
+    /// Report completion (0.0-1.0) with a short status message
+    fn report(&self, fraction: f32, message: &str);
+}
+
+impl<F> ProgressReporter for F
+where
+    F: Fn(f32, &str) + Send + Sync,
+{
+    fn report(&self, fraction: f32, message: &str) {
+        self(fraction, message)
+    }
+}
+
+/// Reporter that discards updates
+#[derive(Debug, Clone, Copy, Default)]
+pub struct NoProgress;
+
+impl ProgressReporter for NoProgress {
+    fn report(&self, _fraction: f32, _message: &str) {}
+}
+
+/// Cloneable, shareable progress reporter (discards updates by default)
+#[derive(Clone)]
+pub struct ProgressHandle(Arc<dyn ProgressReporter>);
+
+impl ProgressHandle {
+    /// Wrap a reporter
+    pub fn new(reporter: impl ProgressReporter + 'static) -> Self {
+        Self(Arc::new(reporter))
+    }
+}
+
+impl Default for ProgressHandle {
+    fn default() -> Self {
+        Self::new(NoProgress)
+    }
+}
+
+impl fmt::Debug for ProgressHandle {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("ProgressHandle")
+    }
+}
+
+impl ProgressReporter for ProgressHandle {
+    fn report(&self, fraction: f32, message: &str) {
+        self.0.report(fraction.clamp(0.0, 1.0), message)
+    }
+}
+
+/// Shared flag the host sets to ask a running operation to stop
+///
+/// Clones share the same flag.
+#[derive(Debug, Clone, Default)]
+pub struct CancellationToken(Arc<AtomicBool>);
+
+impl CancellationToken {
+    /// Create a token that is not cancelled
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Request cancellation
+    pub fn cancel(&self) {
+        self.0.store(true, Ordering::Relaxed);
+    }
+
+    /// Check if cancellation was requested
+    pub fn is_cancelled(&self) -> bool {
+        self.0.load(Ordering::Relaxed)
+    }
+}
@@ -0,0 +1,576 @@
+//! Render delegate bridge
+//!
+//! External renderer plugins (Cycles and others) implement `RenderDelegate` and
+//! hand it to the host through `PluginNode::get_render_delegate`. The host sends
+//! the scene, then incremental `SceneDelta`s as the graph changes; the delegate
+//! streams progressive `TileData` back through the host's `TileSink`, which
+//! feeds the viewport and viewer panels.
+//!
+//! `RenderSettings` also flows through the graph (`NodeData::RenderSettings`).
+//! Beyond the core fields it carries namespaced key-value settings
+//! (`"cycles:samples"`, `"common:motion_blur"`), so settings nodes and renderers
+//! from different vendors can share one type. Each renderer describes its keys
+//! with a `RenderSettingsSchema` for validation and defaults.
+
+use crate::framebuffer::TileData;
+use crate::viewport::{CameraData, LightData, MaterialData, MeshData, SceneData};
+use crate::{PluginError, ValidationError};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::sync::Arc;
+
+/// Standard AOV (arbitrary output variable) names
+pub mod aov {
+    /// Final beauty pass (RGBA)
+    pub const BEAUTY: &str = "beauty";
+    /// Camera-space depth (1 channel)
+    pub const DEPTH: &str = "depth";
+    /// World-space shading normal (3 channels)
+    pub const NORMAL: &str = "normal";
+    /// Surface albedo (3 channels)
+    pub const ALBEDO: &str = "albedo";
+    /// Object/prim ID (1 channel)
+    pub const OBJECT_ID: &str = "object_id";
+    /// World-space position (3 channels)
+    pub const POSITION: &str = "position";
+    /// Cryptomatte object matte (RGBA per rank layer, see `channels::cryptomatte_layer`)
+    pub const CRYPTO_OBJECT: &str = "crypto_object";
+    /// Cryptomatte material matte
+    pub const CRYPTO_MATERIAL: &str = "crypto_material";
+    /// Cryptomatte asset matte
+    pub const CRYPTO_ASSET: &str = "crypto_asset";
+}
+
+/// Request for an output pass from the renderer
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct AovRequest {
+    /// AOV name (see `aov` for standard names)
+    pub name: String,
+    /// Number of channels expected per pixel
+    pub channels: u32,
+}
+
+impl AovRequest {
+    /// Create an AOV request
+    pub fn new(name: impl Into<String>, channels: u32) -> Self {
+        Self {
+            name: name.into(),
+            channels,
+        }
+    }
+
+    /// Request the RGBA beauty pass
+    pub fn beauty() -> Self {
+        Self::new(aov::BEAUTY, 4)
+    }
+
+    /// Request a standard AOV with its standard channel count (see `channels`)
+    pub fn standard(name: &str) -> Option<Self> {
+        crate::channels::standard_aov(name)
+            .map(|spec| Self::new(crate::channels::canonical_aov_name(name), spec.channel_count() as u32))
+    }
+}
+
+/// Settings for a render session
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RenderSettings {
+    /// Output resolution in pixels
+    pub width: u32,
+    pub height: u32,
+    /// Target samples per pixel (None = render until stopped)
+    pub max_samples: Option<u32>,
+    /// Preferred tile size in pixels
+    pub tile_size: u32,
+    /// Passes to produce (the beauty pass is always rendered)
+    pub aovs: Vec<AovRequest>,
+    /// Namespaced renderer settings (`"namespace:name"`)
+    #[serde(default)]
+    pub values: BTreeMap<String, SettingValue>,
+}
+
+impl Default for RenderSettings {
+    fn default() -> Self {
+        Self {
+            width: 800,
+            height: 600,
+            max_samples: Some(128),
+            tile_size: 64,
+            aovs: vec![AovRequest::beauty()],
+            values: BTreeMap::new(),
+        }
+    }
+}
+
+impl RenderSettings {
+    /// Set a keyed value
+    pub fn set(&mut self, key: impl Into<String>, value: impl Into<SettingValue>) {
+        self.values.insert(key.into(), value.into());
+    }
+
+    /// Set a keyed value (builder form)
+    pub fn with_value(mut self, key: impl Into<String>, value: impl Into<SettingValue>) -> Self {
+        self.set(key, value);
+        self
+    }
+
+    /// Get a keyed value
+    pub fn get(&self, key: &str) -> Option<&SettingValue> {
+        self.values.get(key)
+    }
+
+    /// Remove a keyed value
+    pub fn remove(&mut self, key: &str) -> Option<SettingValue> {
+        self.values.remove(key)
+    }
+
+    /// Iterate over the values in a namespace as `(name, value)`
+    pub fn namespace<'a>(&'a self, namespace: &'a str) -> impl Iterator<Item = (&'a str, &'a SettingValue)> + 'a {
+        self.values
+            .iter()
+            .filter_map(move |(key, value)| match split_setting_key(key) {
+                (Some(ns), name) if ns == namespace => Some((name, value)),
+                _ => None,
+            })
+    }
+
+    /// Layer `overrides` on top of these settings
+    ///
+    /// Keyed values from `overrides` replace ours key by key; keys only present
+    /// here are kept. The core fields (resolution, samples, tiles, AOVs) are not
+    /// touched, since a partial settings node cannot leave them unset.
+    pub fn merge(&mut self, overrides: &RenderSettings) {
+        for (key, value) in &overrides.values {
+            self.values.insert(key.clone(), value.clone());
+        }
+    }
+
+    /// Return a copy with `overrides` merged on top
+    pub fn merged(&self, overrides: &RenderSettings) -> RenderSettings {
+        let mut merged = self.clone();
+        merged.merge(overrides);
+        merged
+    }
+}
+
+/// Split `"namespace:name"` into its parts (no namespace if there is no `:`)
+pub fn split_setting_key(key: &str) -> (Option<&str>, &str) {
+    match key.split_once(':') {
+        Some((namespace, name)) => (Some(namespace), name),
+        None => (None, key),
+    }
+}
+
+/// Type of a keyed render setting
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum SettingKind {
+    Bool,
+    Int,
+    Float,
+    String,
+    Vector3,
+    Color,
+}
+
+/// Value of a keyed render setting
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum SettingValue {
+    Bool(bool),
+    Int(i64),
+    Float(f64),
+    String(String),
+    Vector3([f32; 3]),
+    Color([f32; 4]),
+}
+
+impl SettingValue {
+    /// Get the kind of this value
+    pub fn kind(&self) -> SettingKind {
+        match self {
+            SettingValue::Bool(_) => SettingKind::Bool,
+            SettingValue::Int(_) => SettingKind::Int,
+            SettingValue::Float(_) => SettingKind::Float,
+            SettingValue::String(_) => SettingKind::String,
+            SettingValue::Vector3(_) => SettingKind::Vector3,
+            SettingValue::Color(_) => SettingKind::Color,
+        }
+    }
+
+    /// Try to read as a boolean
+    pub fn as_bool(&self) -> Option<bool> {
+        match self {
+            SettingValue::Bool(b) => Some(*b),
+            _ => None,
+        }
+    }
+
+    /// Try to read as an integer
+    pub fn as_int(&self) -> Option<i64> {
+        match self {
+            SettingValue::Int(i) => Some(*i),
+            _ => None,
+        }
+    }
+
+    /// Try to read as a float (integers are widened)
+    pub fn as_float(&self) -> Option<f64> {
+        match self {
+            SettingValue::Float(f) => Some(*f),
+            SettingValue::Int(i) => Some(*i as f64),
+            _ => None,
+        }
+    }
+
+    /// Try to read as a string
+    pub fn as_str(&self) -> Option<&str> {
+        match self {
+            SettingValue::String(s) => Some(s),
+            _ => None,
+        }
+    }
+
+    /// Check if this value is acceptable for a kind (integers are accepted as floats)
+    pub fn matches_kind(&self, kind: SettingKind) -> bool {
+        self.kind() == kind || (kind == SettingKind::Float && self.kind() == SettingKind::Int)
+    }
+}
+
+impl From<bool> for SettingValue {
+    fn from(value: bool) -> Self {
+        SettingValue::Bool(value)
+    }
+}
+
+impl From<i64> for SettingValue {
+    fn from(value: i64) -> Self {
+        SettingValue::Int(value)
+    }
+}
+
+impl From<i32> for SettingValue {
+    fn from(value: i32) -> Self {
+        SettingValue::Int(value as i64)
+    }
+}
+
+impl From<f64> for SettingValue {
+    fn from(value: f64) -> Self {
+        SettingValue::Float(value)
+    }
+}
+
+impl From<f32> for SettingValue {
+    fn from(value: f32) -> Self {
+        SettingValue::Float(value as f64)
+    }
+}
+
+impl From<&str> for SettingValue {
+    fn from(value: &str) -> Self {
+        SettingValue::String(value.to_string())
+    }
+}
+
+impl From<String> for SettingValue {
+    fn from(value: String) -> Self {
+        SettingValue::String(value)
+    }
+}
+
+impl From<[f32; 3]> for SettingValue {
+    fn from(value: [f32; 3]) -> Self {
+        SettingValue::Vector3(value)
+    }
+}
+
+impl From<[f32; 4]> for SettingValue {
+    fn from(value: [f32; 4]) -> Self {
+        SettingValue::Color(value)
+    }
+}
+
+/// Description of one keyed setting in a renderer's schema
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SettingSpec {
+    /// Name within the schema's namespace
+    pub name: String,
+    pub kind: SettingKind,
+    #[serde(default)]
+    pub default: Option<SettingValue>,
+    /// Inclusive numeric range for `Int`/`Float` settings
+    #[serde(default)]
+    pub min: Option<f64>,
+    #[serde(default)]
+    pub max: Option<f64>,
+    /// Allowed values for `String` settings (empty = any)
+    #[serde(default)]
+    pub choices: Vec<String>,
+    #[serde(default)]
+    pub description: String,
+}
+
+impl SettingSpec {
+    /// Create a setting description
+    pub fn new(name: impl Into<String>, kind: SettingKind) -> Self {
+        Self {
+            name: name.into(),
+            kind,
+            default: None,
+            min: None,
+            max: None,
+            choices: Vec::new(),
+            description: String::new(),
+        }
+    }
+
+    /// Set the default value
+    pub fn with_default(mut self, default: impl Into<SettingValue>) -> Self {
+        self.default = Some(default.into());
+        self
+    }
+
+    /// Set the allowed numeric range
+    pub fn with_range(mut self, min: f64, max: f64) -> Self {
+        self.min = Some(min);
+        self.max = Some(max);
+        self
+    }
+
+    /// Set the allowed string values
+    pub fn with_choices(mut self, choices: &[&str]) -> Self {
+        self.choices = choices.iter().map(|c| c.to_string()).collect();
+        self
+    }
+
+    /// Set the tooltip description
+    pub fn with_description(mut self, description: impl Into<String>) -> Self {
+        self.description = description.into();
+        self
+    }
+
+    /// Check a value against this description
+    pub fn check(&self, key: &str, value: &SettingValue) -> Result<(), ValidationError> {
+        if !value.matches_kind(self.kind) {
+            return Err(ValidationError::InvalidValue(format!(
+                "{}: expected {:?}, found {:?}",
+                key,
+                self.kind,
+                value.kind()
+            )));
+        }
+        if let Some(number) = value.as_float() {
+            if self.min.is_some_and(|min| number < min) || self.max.is_some_and(|max| number > max) {
+                let bound = |b: Option<f64>| b.map(|b| b.to_string()).unwrap_or_default();
+                return Err(ValidationError::InvalidValue(format!(
+                    "{}: {} is outside {}..={}",
+                    key,
+                    number,
+                    bound(self.min),
+                    bound(self.max)
+                )));
+            }
+        }
+        if let Some(text) = value.as_str() {
+            if !self.choices.is_empty() && !self.choices.iter().any(|c| c == text) {
+                return Err(ValidationError::InvalidValue(format!(
+                    "{}: '{}' is not one of {:?}",
+                    key, text, self.choices
+                )));
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Keyed settings a renderer understands, all within one namespace
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct RenderSettingsSchema {
+    pub namespace: String,
+    pub settings: Vec<SettingSpec>,
+}
+
+impl RenderSettingsSchema {
+    /// Create an empty schema for a namespace
+    pub fn new(namespace: impl Into<String>) -> Self {
+        Self {
+            namespace: namespace.into(),
+            settings: Vec::new(),
+        }
+    }
+
+    /// Add a setting description
+    pub fn setting(mut self, spec: SettingSpec) -> Self {
+        self.settings.push(spec);
+        self
+    }
+
+    /// Full key for a setting name (`"namespace:name"`)
+    pub fn key(&self, name: &str) -> String {
+        format!("{}:{}", self.namespace, name)
+    }
+
+    /// Find a setting description by name
+    pub fn get(&self, name: &str) -> Option<&SettingSpec> {
+        self.settings.iter().find(|spec| spec.name == name)
+    }
+
+    /// Validate the keys in this schema's namespace
+    ///
+    /// Unknown keys, wrong types and out-of-range values are all reported. Keys
+    /// from other namespaces belong to other renderers and are ignored.
+    pub fn validate(&self, settings: &RenderSettings) -> Result<(), Vec<ValidationError>> {
+        let errors: Vec<ValidationError> = settings
+            .namespace(&self.namespace)
+            .filter_map(|(name, value)| {
+                let key = self.key(name);
+                match self.get(name) {
+                    Some(spec) => spec.check(&key, value).err(),
+                    None => Some(ValidationError::InvalidValue(format!("{}: unknown setting", key))),
+                }
+            })
+            .collect();
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(errors)
+        }
+    }
+
+    /// Fill in defaults for settings that are not set
+    pub fn apply_defaults(&self, settings: &mut RenderSettings) {
+        for spec in &self.settings {
+            if let Some(default) = &spec.default {
+                settings.values.entry(self.key(&spec.name)).or_insert_with(|| default.clone());
+            }
+        }
+    }
+}
+
+/// Incremental scene change sent to a running render
+///
+/// Meshes, materials and lights are matched by `id`.
+#[derive(Debug, Clone, Default)]
+pub struct SceneDelta {
+    /// New or changed meshes
+    pub upserted_meshes: Vec<MeshData>,
+    /// IDs of removed meshes
+    pub removed_meshes: Vec<String>,
+    /// New or changed materials
+    pub upserted_materials: Vec<MaterialData>,
+    /// IDs of removed materials
+    pub removed_materials: Vec<String>,
+    /// New or changed lights
+    pub upserted_lights: Vec<LightData>,
+    /// IDs of removed lights
+    pub removed_lights: Vec<String>,
+    /// New camera, if it moved
+    pub camera: Option<CameraData>,
+}
+
+impl SceneDelta {
+    /// Check if the delta contains no changes
+    pub fn is_empty(&self) -> bool {
+        self.upserted_meshes.is_empty()
+            && self.removed_meshes.is_empty()
+            && self.upserted_materials.is_empty()
+            && self.removed_materials.is_empty()
+            && self.upserted_lights.is_empty()
+            && self.removed_lights.is_empty()
+            && self.camera.is_none()
+    }
+
+    /// Check if only the camera changed (renderers can often skip BVH rebuilds)
+    pub fn is_camera_only(&self) -> bool {
+        self.camera.is_some() && SceneDelta { camera: None, ..self.clone() }.is_empty()
+    }
+
+    /// Apply the delta to a scene
+    pub fn apply(&self, scene: &mut SceneData) {
+        upsert(&mut scene.meshes, &self.upserted_meshes, &self.removed_meshes, |m| &m.id);
+        upsert(&mut scene.materials, &self.upserted_materials, &self.removed_materials, |m| &m.id);
+        upsert(&mut scene.lights, &self.upserted_lights, &self.removed_lights, |l| &l.id);
+        if let Some(camera) = &self.camera {
+            scene.camera = camera.clone();
+        }
+    }
+}
+
+fn upsert<T: Clone>(items: &mut Vec<T>, upserted: &[T], removed: &[String], id: impl Fn(&T) -> &String) {
+    items.retain(|item| !removed.contains(id(item)));
+    for new in upserted {
+        match items.iter_mut().find(|item| id(item) == id(new)) {
+            Some(existing) => *existing = new.clone(),
+            None => items.push(new.clone()),
+        }
+    }
+}
+
+/// Host-side receiver for progressive render output
+///
+/// The host implements this trait; delegates may call it from their own render
+/// threads.
+pub trait TileSink: Send + Sync {
+    /// Receive a progressive tile
+    ///
+    /// Tiles carry their own sample counts; hosts typically feed them into a
+    /// `FrameBuffer` per AOV.
+    fn on_tile(&self, tile: TileData);
+
+    /// Report overall progress in 0.0..=1.0
+    fn on_progress(&self, _progress: f32) {
+        // Default: progress is not displayed
+    }
+
+    /// Called once the render has converged or been stopped
+    fn on_complete(&self) {
+        // Default: nothing to do
+    }
+
+    /// Report a render failure
+    fn on_error(&self, _error: PluginError) {
+        // Default: errors are dropped
+    }
+}
+
+/// Renderer bridge implemented by external renderer plugins
+pub trait RenderDelegate: Send {
+    /// Renderer name for display (e.g. "Cycles")
+    fn name(&self) -> &str;
+
+    /// AOV names this renderer can produce
+    fn supported_aovs(&self) -> Vec<String> {
+        vec![aov::BEAUTY.to_string()]
+    }
+
+    /// Keyed settings this renderer reads from `RenderSettings::values`
+    fn settings_schema(&self) -> RenderSettingsSchema {
+        RenderSettingsSchema::default()
+    }
+
+    /// Start rendering a scene, streaming results into `sink`
+    ///
+    /// Should return quickly; rendering is expected to run in the background.
+    fn begin_render(
+        &mut self,
+        scene: &SceneData,
+        settings: &RenderSettings,
+        sink: Arc<dyn TileSink>,
+    ) -> Result<(), PluginError>;
+
+    /// Apply incremental scene changes to a running render (restarts sampling)
+    fn update_scene(&mut self, delta: &SceneDelta) -> Result<(), PluginError>;
+
+    /// Request an additional AOV from a running render
+    fn request_aov(&mut self, request: &AovRequest) -> Result<(), PluginError> {
+        if self.supported_aovs().contains(&request.name) {
+            Ok(())
+        } else {
+            Err(PluginError::Other(format!("AOV '{}' is not supported by {}", request.name, self.name())))
+        }
+    }
+
+    /// Stop rendering and release render threads
+    fn stop_render(&mut self);
+
+    /// Check if a render is in progress
+    fn is_rendering(&self) -> bool;
+}
@@ -0,0 +1,384 @@
+//! Shared resources borrowed across nodes
+//!
+//! Some resources are expensive to create and safe to share, such as an opened
+//! USD stage or a compiled shader, but are not `NodeData` and so cannot live
+//! in the plugin cache. The host owns a `ResourcePool` and hands it to nodes
+//! through `ExecutionContext::resources`. The first node to need a resource
+//! opens and parks it under a content hash, and other nodes of the same plugin
+//! borrow it, so five readers of the same file open it once:
+//!
+//! ```rust,ignore
+//! let hash = content_hash(&(path, load_mask));
+//! let stage = pool.get_or_open("usd", hash, || {
+//!     let stage = open_stage(path)?;
+//!     let size = stage.estimated_size();
+//!     Ok((stage, size))
+//! })?;
+//! ```
+//!
+//! Resources stay in the pool while leases are held. Unborrowed resources are
+//! evicted once they have been idle for the pool's idle timeout, or sooner,
+//! least recently used first, when the pool is over its memory budget.
+
+use std::any::Any;
+use std::collections::HashMap;
+use std::fmt;
+use std::ops::Deref;
+use std::sync::{Arc, Mutex, Weak};
+use std::time::{Duration, Instant};
+
+type PoolKey = (String, u64);
+
+/// How long unborrowed resources are kept by default
+pub const DEFAULT_RESOURCE_IDLE_TIMEOUT: Duration = Duration::from_secs(60);
+
+struct PoolEntry {
+    resource: Arc<dyn Any + Send + Sync>,
+    size_bytes: usize,
+    borrows: usize,
+    last_used: Instant,
+}
+
+struct PoolState {
+    entries: HashMap<PoolKey, PoolEntry>,
+    budget: Option<usize>,
+    idle_timeout: Option<Duration>,
+}
+
+impl PoolState {
+    fn total_size(&self) -> usize {
+        self.entries.values().map(|entry| entry.size_bytes).sum()
+    }
+
+    /// Remove idle resources past the timeout, then LRU idle resources until
+    /// `target` bytes remain
+    fn evict(&mut self, target: Option<usize>) -> Vec<Arc<dyn Any + Send + Sync>> {
+        let now = Instant::now();
+        let mut evicted = Vec::new();
+        if let Some(timeout) = self.idle_timeout {
+            let expired: Vec<PoolKey> = self
+                .entries
+                .iter()
+                .filter(|(_, entry)| entry.borrows == 0 && now.duration_since(entry.last_used) >= timeout)
+                .map(|(key, _)| key.clone())
+                .collect();
+            evicted.extend(expired.iter().filter_map(|key| self.entries.remove(key)).map(|e| e.resource));
+        }
+        if let Some(target) = target {
+            let mut idle: Vec<(Instant, PoolKey)> = self
+                .entries
+                .iter()
+                .filter(|(_, entry)| entry.borrows == 0)
+                .map(|(key, entry)| (entry.last_used, key.clone()))
+                .collect();
+            idle.sort();
+            let mut total = self.total_size();
+            for (_, key) in idle {
+                if total <= target {
+                    break;
+                }
+                if let Some(entry) = self.entries.remove(&key) {
+                    total -= entry.size_bytes;
+                    evicted.push(entry.resource);
+                }
+            }
+        }
+        evicted
+    }
+}
+
+/// Host-owned pool of shared resources keyed by plugin and content hash
+///
+/// Clones share the same pool. Resources are dropped outside the pool's lock,
+/// so expensive destructors do not block other nodes.
+#[derive(Clone)]
+pub struct ResourcePool {
+    state: Arc<Mutex<PoolState>>,
+}
+
+impl Default for ResourcePool {
+    fn default() -> Self {
+        Self {
+            state: Arc::new(Mutex::new(PoolState {
+                entries: HashMap::new(),
+                budget: None,
+                idle_timeout: Some(DEFAULT_RESOURCE_IDLE_TIMEOUT),
+            })),
+        }
+    }
+}
+
+impl ResourcePool {
+    /// Create a pool without a memory budget and the default idle timeout
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Evict idle resources when the pool holds more than `bytes`
+    pub fn with_budget(self, bytes: usize) -> Self {
+        self.lock().budget = Some(bytes);
+        self
+    }
+
+    /// Keep unborrowed resources for `timeout` (None = until over budget or removed)
+    pub fn with_idle_timeout(self, timeout: Option<Duration>) -> Self {
+        self.lock().idle_timeout = timeout;
+        self
+    }
+
+    /// Borrow a parked resource
+    ///
+    /// Returns None if nothing is parked under the key or it has a different type.
+    pub fn borrow<T: Any + Send + Sync>(&self, plugin_id: &str, hash: u64) -> Option<ResourceLease<T>> {
+        let mut state = self.lock();
+        let entry = state.entries.get_mut(&(plugin_id.to_string(), hash))?;
+        let resource = entry.resource.clone().downcast::<T>().ok()?;
+        entry.borrows += 1;
+        entry.last_used = Instant::now();
+        Some(self.lease(plugin_id, hash, resource))
+    }
+
+    /// Park a resource and borrow it
+    ///
+    /// If a resource of the same type is already parked under the key (another
+    /// node opened it concurrently), that one is borrowed and `resource` is
+    /// dropped. A resource of a different type is replaced.
+    pub fn insert<T: Any + Send + Sync>(
+        &self,
+        plugin_id: &str,
+        hash: u64,
+        resource: T,
+        size_bytes: usize,
+    ) -> ResourceLease<T> {
+        let key = (plugin_id.to_string(), hash);
+        let (resource, replaced, evicted) = {
+            let mut state = self.lock();
+            let existing = state
+                .entries
+                .get(&key)
+                .and_then(|entry| entry.resource.clone().downcast::<T>().ok());
+            let mut replaced = None;
+            let resource = match existing {
+                Some(existing) => existing,
+                None => {
+                    let resource = Arc::new(resource);
+                    replaced = state.entries.insert(
+                        key.clone(),
+                        PoolEntry {
+                            resource: resource.clone(),
+                            size_bytes,
+                            borrows: 0,
+                            last_used: Instant::now(),
+                        },
+                    );
+                    resource
+                }
+            };
+            if let Some(entry) = state.entries.get_mut(&key) {
+                entry.borrows += 1;
+                entry.last_used = Instant::now();
+            }
+            let budget = state.budget;
+            let evicted = state.evict(budget);
+            (resource, replaced, evicted)
+        };
+        drop((replaced, evicted));
+        self.lease(plugin_id, hash, resource)
+    }
+
+    /// Borrow a parked resource, or open and park it
+    ///
+    /// `open` returns the resource and its estimated size in bytes. It runs
+    /// without the pool locked; if two nodes open the same resource at once,
+    /// the first one parked wins and the other is dropped.
+    pub fn get_or_open<T, F>(&self, plugin_id: &str, hash: u64, open: F) -> Result<ResourceLease<T>, String>
+    where
+        T: Any + Send + Sync,
+        F: FnOnce() -> Result<(T, usize), String>,
+    {
+        if let Some(lease) = self.borrow(plugin_id, hash) {
+            return Ok(lease);
+        }
+        let (resource, size_bytes) = open()?;
+        Ok(self.insert(plugin_id, hash, resource, size_bytes))
+    }
+
+    /// Check if a resource is parked under the key
+    pub fn contains(&self, plugin_id: &str, hash: u64) -> bool {
+        self.lock().entries.contains_key(&(plugin_id.to_string(), hash))
+    }
+
+    /// Number of leases held on a resource (None if not parked)
+    pub fn borrow_count(&self, plugin_id: &str, hash: u64) -> Option<usize> {
+        self.lock()
+            .entries
+            .get(&(plugin_id.to_string(), hash))
+            .map(|entry| entry.borrows)
+    }
+
+    /// Remove a resource from the pool
+    ///
+    /// Outstanding leases keep it alive until they are dropped.
+    pub fn remove(&self, plugin_id: &str, hash: u64) -> bool {
+        let removed = self.lock().entries.remove(&(plugin_id.to_string(), hash));
+        removed.is_some()
+    }
+
+    /// Remove all of a plugin's resources, returning how many were removed
+    ///
+    /// Hosts must call this before unloading a plugin, since the resources'
+    /// destructors live in the plugin library.
+    pub fn clear_plugin(&self, plugin_id: &str) -> usize {
+        let removed: Vec<PoolEntry> = {
+            let mut state = self.lock();
+            let keys: Vec<PoolKey> = state.entries.keys().filter(|(id, _)| id == plugin_id).cloned().collect();
+            keys.iter().filter_map(|key| state.entries.remove(key)).collect()
+        };
+        removed.len()
+    }
+
+    /// Evict expired resources and enforce the budget, returning bytes freed
+    ///
+    /// Hosts call this periodically; it also runs whenever a resource is
+    /// parked or a lease is dropped.
+    pub fn evict_idle(&self) -> usize {
+        self.evict_to(None)
+    }
+
+    /// Evict unborrowed resources, least recently used first, until `bytes` are freed
+    ///
+    /// For memory pressure; returns the bytes actually freed.
+    pub fn shed(&self, bytes: usize) -> usize {
+        let total = self.total_size();
+        self.evict_to(Some(total.saturating_sub(bytes)))
+    }
+
+    /// Number of parked resources
+    pub fn len(&self) -> usize {
+        self.lock().entries.len()
+    }
+
+    /// Check if nothing is parked
+    pub fn is_empty(&self) -> bool {
+        self.lock().entries.is_empty()
+    }
+
+    /// Estimated size of all parked resources in bytes
+    pub fn total_size(&self) -> usize {
+        self.lock().total_size()
+    }
+
+    fn evict_to(&self, target: Option<usize>) -> usize {
+        let (before, after, evicted) = {
+            let mut state = self.lock();
+            let before = state.total_size();
+            let target = match (target, state.budget) {
+                (Some(target), Some(budget)) => Some(target.min(budget)),
+                (target, budget) => target.or(budget),
+            };
+            let evicted = state.evict(target);
+            (before, state.total_size(), evicted)
+        };
+        drop(evicted);
+        before - after
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, PoolState> {
+        self.state.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    fn lease<T>(&self, plugin_id: &str, hash: u64, resource: Arc<T>) -> ResourceLease<T> {
+        ResourceLease {
+            resource,
+            key: (plugin_id.to_string(), hash),
+            pool: Arc::downgrade(&self.state),
+        }
+    }
+}
+
+impl fmt::Debug for ResourcePool {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let state = self.lock();
+        f.debug_struct("ResourcePool")
+            .field("resources", &state.entries.len())
+            .field("total_size", &state.total_size())
+            .field("budget", &state.budget)
+            .finish()
+    }
+}
+
+/// Borrowed resource from a `ResourcePool`
+///
+/// The resource stays parked at least until the last lease is dropped.
+pub struct ResourceLease<T> {
+    resource: Arc<T>,
+    key: PoolKey,
+    pool: Weak<Mutex<PoolState>>,
+}
+
+impl<T> ResourceLease<T> {
+    /// Content hash the resource is parked under
+    pub fn hash(&self) -> u64 {
+        self.key.1
+    }
+
+    /// The pool entry for this lease, unless it was removed or replaced since
+    fn entry<'a>(&self, state: &'a mut PoolState) -> Option<&'a mut PoolEntry> {
+        state
+            .entries
+            .get_mut(&self.key)
+            .filter(|entry| Arc::as_ptr(&entry.resource) as *const () == Arc::as_ptr(&self.resource) as *const ())
+    }
+}
+
+impl<T> Deref for ResourceLease<T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        &self.resource
+    }
+}
+
+impl<T> Clone for ResourceLease<T> {
+    fn clone(&self) -> Self {
+        if let Some(pool) = self.pool.upgrade() {
+            let mut state = pool.lock().unwrap_or_else(|e| e.into_inner());
+            if let Some(entry) = self.entry(&mut state) {
+                entry.borrows += 1;
+            }
+        }
+        Self {
+            resource: self.resource.clone(),
+            key: self.key.clone(),
+            pool: self.pool.clone(),
+        }
+    }
+}
+
+impl<T> Drop for ResourceLease<T> {
+    fn drop(&mut self) {
+        let Some(pool) = self.pool.upgrade() else {
+            return;
+        };
+        let evicted = {
+            let mut state = pool.lock().unwrap_or_else(|e| e.into_inner());
+            if let Some(entry) = self.entry(&mut state) {
+                entry.borrows = entry.borrows.saturating_sub(1);
+                entry.last_used = Instant::now();
+            }
+            let budget = state.budget;
+            state.evict(budget)
+        };
+        drop(evicted);
+    }
+}
+
+impl<T: fmt::Debug> fmt::Debug for ResourceLease<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ResourceLease")
+            .field("plugin_id", &self.key.0)
+            .field("hash", &self.key.1)
+            .field("resource", &self.resource)
+            .finish()
+    }
+}
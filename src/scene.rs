@@ -0,0 +1,149 @@
+//! Conversions between node scene data and viewport scene data
+//!
+//! Materials and lights are shared between `data_types` and `viewport`. Geometry
+//! and scenes have a node-side form (`GeometryData`/`SceneData`) and a flat,
+//! render-ready form (`MeshData`/`viewport::SceneData`); the conversions here
+//! translate between them without losing geometry, material or light data, so
+//! viewport nodes don't have to hand-copy scenes.
+
+use crate::data_types::{self, GeometryData};
+use crate::viewport::{self, MeshData};
+use crate::usd::IDENTITY_TRANSFORM;
+
+impl MeshData {
+    /// Convert node geometry into a render mesh with the given transform
+    /// 
+    /// N-gon geometry is triangulated; face-varying attributes are not carried
+    /// over since render meshes only store per-vertex attributes.
+    pub fn from_geometry(geometry: &GeometryData, transform: [[f32; 4]; 4]) -> Self {
+        let triangulated;
+        let geometry = if geometry.is_triangulated() {
+            geometry
+        } else {
+            triangulated = geometry.triangulate();
+            &triangulated
+        };
+        Self {
+            id: geometry.id.clone(),
+            vertices: geometry.vertices.iter().flatten().copied().collect(),
+            normals: geometry.normals.iter().flatten().copied().collect(),
+            uvs: geometry.uvs.iter().flatten().copied().collect(),
+            indices: geometry.indices.clone(),
+            vertex_colors: if geometry.colors.is_empty() {
+                None
+            } else {
+                Some(geometry.colors.iter().flatten().copied().collect())
+            },
+            material_id: geometry.material_id.clone(),
+            transform,
+            motion_samples: Vec::new(),
+        }
+    }
+}
+
+impl From<&GeometryData> for MeshData {
+    fn from(geometry: &GeometryData) -> Self {
+        MeshData::from_geometry(geometry, IDENTITY_TRANSFORM)
+    }
+}
+
+impl From<&MeshData> for GeometryData {
+    fn from(mesh: &MeshData) -> Self {
+        GeometryData {
+            id: mesh.id.clone(),
+            vertices: chunks3(&mesh.vertices),
+            indices: mesh.indices.clone(),
+            normals: chunks3(&mesh.normals),
+            uvs: mesh.uvs.chunks_exact(2).map(|c| [c[0], c[1]]).collect(),
+            colors: mesh.vertex_colors.as_deref().map(chunks3).unwrap_or_default(),
+            material_id: mesh.material_id.clone(),
+            ..Default::default()
+        }
+    }
+}
+
+fn chunks3(values: &[f32]) -> Vec<[f32; 3]> {
+    values.chunks_exact(3).map(|c| [c[0], c[1], c[2]]).collect()
+}
+
+impl From<&data_types::SceneData> for viewport::SceneData {
+    fn from(scene: &data_types::SceneData) -> Self {
+        let meshes: Vec<MeshData> = scene
+            .geometry
+            .iter()
+            .map(|geometry| {
+                let transform = scene
+                    .transforms
+                    .get(&geometry.id)
+                    .copied()
+                    .unwrap_or(IDENTITY_TRANSFORM);
+                MeshData::from_geometry(geometry, transform)
+            })
+            .collect();
+        let bounding_box = mesh_bounds(&meshes);
+
+        viewport::SceneData {
+            meshes,
+            materials: scene.materials.clone(),
+            lights: scene.lights.clone(),
+            bounding_box,
+            ..Default::default()
+        }
+    }
+}
+
+impl From<&viewport::SceneData> for data_types::SceneData {
+    fn from(scene: &viewport::SceneData) -> Self {
+        data_types::SceneData {
+            geometry: scene.meshes.iter().map(GeometryData::from).collect(),
+            materials: scene.materials.clone(),
+            lights: scene.lights.clone(),
+            transforms: scene
+                .meshes
+                .iter()
+                .map(|mesh| (mesh.id.clone(), mesh.transform))
+                .collect(),
+        }
+    }
+}
+
+/// Compute scene-space bounds over all mesh vertices, with each mesh's transform applied
+fn mesh_bounds(meshes: &[MeshData]) -> Option<([f32; 3], [f32; 3])> {
+    let mut points = meshes.iter().flat_map(|m| {
+        m.vertices
+            .chunks_exact(3)
+            .map(|p| crate::transform::transform_point(&m.transform, [p[0], p[1], p[2]]))
+    });
+    let first = points.next()?;
+    Some(points.fold((first, first), |(mut min, mut max), p| {
+        for i in 0..3 {
+            min[i] = min[i].min(p[i]);
+            max[i] = max[i].max(p[i]);
+        }
+        (min, max)
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn scene_bounds_include_mesh_transforms() {
+        let geometry = GeometryData {
+            id: "tri".to_string(),
+            vertices: vec![[0.0, 0.0, 0.0], [1.0, 0.0, 0.0], [0.0, 2.0, 0.0]],
+            indices: vec![0, 1, 2],
+            ..Default::default()
+        };
+        let mut translated = IDENTITY_TRANSFORM;
+        translated[3] = [5.0, 0.0, -1.0, 1.0];
+        let scene = data_types::SceneData {
+            geometry: vec![geometry],
+            transforms: [("tri".to_string(), translated)].into_iter().collect(),
+            ..Default::default()
+        };
+        let viewport = viewport::SceneData::from(&scene);
+        assert_eq!(viewport.bounding_box, Some(([5.0, 0.0, -1.0], [6.0, 2.0, -1.0])));
+    }
+}
@@ -0,0 +1,710 @@
+//! Parameter sidecar files
+//!
+//! Artists share node setups between projects by exporting a node's (or a
+//! selection's) parameters to a small JSON or TOML file and importing them onto
+//! nodes of the same type elsewhere, without copying whole graphs. Hosts offer
+//! this in the node context menu (`SidecarAction`) and move values in and out
+//! of nodes with `PluginNodeHandle::safe_export_parameters` and
+//! `safe_import_parameters`. In TOML a sidecar looks like:
+//!
+//! ```toml
+//! sdk_version = "0.1.0"
+//!
+//! [[nodes]]
+//! node_type = "Blur"
+//!
+//! [nodes.parameters]
+//! radius = 1.5
+//! mode = "Accurate"
+//! tint = [1.0, 0.5, 0.5, 1.0]
+//! ```
+//!
+//! Only plain values are written: numbers, booleans, strings, paths, vectors,
+//! colors and gradients. Parameters holding other data are skipped.
+//!
+//! A gradient is an array of stops `[position, r, g, b, a]`, followed by the
+//! interpolation toward the next stop unless it is linear (`"constant"`,
+//! `"smooth"`, or an easing such as `"ease:quad_in"`; a cubic-bezier easing
+//! adds its four control values).
+
+use crate::{
+    Easing, FrameRange, GradientData, JsonValue, NodeData, PathData, RampInterpolation, RampKey, Timecode, SDK_VERSION,
+};
+use std::collections::BTreeMap;
+use std::fmt::Write as _;
+use std::path::Path;
+
+/// File format of a sidecar
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum SidecarFormat {
+    Json,
+    Toml,
+}
+
+impl SidecarFormat {
+    /// Format for a file extension (`.json` or `.toml`)
+    pub fn from_path(path: impl AsRef<Path>) -> Option<Self> {
+        let extension = path.as_ref().extension()?.to_str()?.to_ascii_lowercase();
+        match extension.as_str() {
+            "json" => Some(SidecarFormat::Json),
+            "toml" => Some(SidecarFormat::Toml),
+            _ => None,
+        }
+    }
+
+    /// File extension without the dot
+    pub fn extension(self) -> &'static str {
+        match self {
+            SidecarFormat::Json => "json",
+            SidecarFormat::Toml => "toml",
+        }
+    }
+}
+
+/// Standard node context-menu actions for sidecars
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum SidecarAction {
+    /// Write the selected nodes' parameters to a file
+    Export,
+    /// Apply a file's parameters to the selected nodes
+    Import,
+}
+
+impl SidecarAction {
+    pub const ALL: [SidecarAction; 2] = [SidecarAction::Export, SidecarAction::Import];
+
+    /// Stable action identifier
+    pub fn id(self) -> &'static str {
+        match self {
+            SidecarAction::Export => "nodle.parameters.export",
+            SidecarAction::Import => "nodle.parameters.import",
+        }
+    }
+
+    /// Menu label
+    pub fn label(self) -> &'static str {
+        match self {
+            SidecarAction::Export => "Export Parameters…",
+            SidecarAction::Import => "Import Parameters…",
+        }
+    }
+
+    /// File dialog filter
+    pub fn file_filter(self) -> &'static str {
+        "*.json;*.toml"
+    }
+
+    /// Look up an action by identifier
+    pub fn from_id(id: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|action| action.id() == id)
+    }
+}
+
+/// Parameters of one node in a sidecar
+#[derive(Debug, Clone)]
+pub struct SidecarNode {
+    /// Node type the parameters came from
+    pub node_type: String,
+    /// Parameter values by name
+    ///
+    /// Values read from a file carry the shape stored in it (whole numbers are
+    /// `Integer`, other numbers `Float`, arrays are `Vector3` or `Color`); they
+    /// are converted to each parameter's type on import.
+    pub parameters: BTreeMap<String, NodeData>,
+}
+
+impl SidecarNode {
+    /// Create an entry without parameters
+    pub fn new(node_type: impl Into<String>) -> Self {
+        Self {
+            node_type: node_type.into(),
+            parameters: BTreeMap::new(),
+        }
+    }
+
+    /// Add a parameter value, returning false if it cannot be stored in a sidecar
+    pub fn insert(&mut self, name: impl Into<String>, value: NodeData) -> bool {
+        if value_to_json(&value).is_none() {
+            return false;
+        }
+        self.parameters.insert(name.into(), value);
+        true
+    }
+
+    fn to_json(&self) -> JsonValue {
+        let parameters = self
+            .parameters
+            .iter()
+            .filter_map(|(name, value)| Some((name.clone(), value_to_json(value)?)))
+            .collect();
+        let mut object = BTreeMap::new();
+        object.insert("node_type".to_string(), JsonValue::from(self.node_type.as_str()));
+        object.insert("parameters".to_string(), JsonValue::Object(parameters));
+        JsonValue::Object(object)
+    }
+
+    fn from_json(json: &JsonValue) -> Result<Self, String> {
+        let node_type = json
+            .get("node_type")
+            .and_then(JsonValue::as_str)
+            .ok_or("Sidecar node is missing node_type")?;
+        let mut node = SidecarNode::new(node_type);
+        if let Some(parameters) = json.get("parameters").and_then(JsonValue::as_object) {
+            for (name, value) in parameters {
+                // JSON writes non-finite numbers as null
+                if value.is_null() {
+                    continue;
+                }
+                let value = value_from_json(value)
+                    .ok_or_else(|| format!("Unsupported value for parameter '{}'", name))?;
+                node.parameters.insert(name.clone(), value);
+            }
+        }
+        Ok(node)
+    }
+}
+
+/// Exported parameters of one or more nodes
+#[derive(Debug, Clone)]
+pub struct ParameterSidecar {
+    /// SDK version that wrote the file
+    pub sdk_version: String,
+    /// One entry per exported node, in selection order
+    pub nodes: Vec<SidecarNode>,
+}
+
+impl Default for ParameterSidecar {
+    fn default() -> Self {
+        Self {
+            sdk_version: SDK_VERSION.to_string(),
+            nodes: Vec::new(),
+        }
+    }
+}
+
+impl ParameterSidecar {
+    /// Create an empty sidecar for the current SDK version
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a node's parameters
+    pub fn with_node(mut self, node: SidecarNode) -> Self {
+        self.nodes.push(node);
+        self
+    }
+
+    /// Entry to apply to the `occurrence`-th selected node of a type
+    ///
+    /// Entries of the type are used in order; when the selection has more
+    /// nodes of the type than the file, the first entry is reused.
+    pub fn node_for(&self, node_type: &str, occurrence: usize) -> Option<&SidecarNode> {
+        let mut matching = self.nodes.iter().filter(|node| node.node_type == node_type);
+        let first = matching.clone().next();
+        matching.nth(occurrence).or(first)
+    }
+
+    /// Serialize to JSON
+    pub fn to_json_string(&self) -> String {
+        let mut object = BTreeMap::new();
+        object.insert("sdk_version".to_string(), JsonValue::from(self.sdk_version.as_str()));
+        object.insert(
+            "nodes".to_string(),
+            JsonValue::Array(self.nodes.iter().map(SidecarNode::to_json).collect()),
+        );
+        JsonValue::Object(object).to_json_string()
+    }
+
+    /// Serialize to TOML
+    pub fn to_toml_string(&self) -> String {
+        let mut out = String::new();
+        let _ = writeln!(out, "sdk_version = {}", toml_string(&self.sdk_version));
+        for node in &self.nodes {
+            let _ = writeln!(out, "\n[[nodes]]\nnode_type = {}", toml_string(&node.node_type));
+            out.push_str("\n[nodes.parameters]\n");
+            for (name, value) in &node.parameters {
+                let text = match value {
+                    NodeData::Integer(v) => v.to_string(),
+                    other => match value_to_json(other) {
+                        Some(json) => toml_value(&json),
+                        None => continue,
+                    },
+                };
+                let _ = writeln!(out, "{} = {}", toml_key(name), text);
+            }
+        }
+        out
+    }
+
+    /// Serialize in a format
+    pub fn to_format_string(&self, format: SidecarFormat) -> String {
+        match format {
+            SidecarFormat::Json => self.to_json_string(),
+            SidecarFormat::Toml => self.to_toml_string(),
+        }
+    }
+
+    /// Parse sidecar text
+    pub fn parse(text: &str, format: SidecarFormat) -> Result<Self, String> {
+        let json = match format {
+            SidecarFormat::Json => JsonValue::parse(text)?,
+            SidecarFormat::Toml => parse_toml(text)?,
+        };
+        let nodes = match json.get("nodes") {
+            Some(JsonValue::Array(nodes)) => nodes.iter().map(SidecarNode::from_json).collect::<Result<_, _>>()?,
+            Some(_) => return Err("Sidecar 'nodes' must be an array".to_string()),
+            None => Vec::new(),
+        };
+        Ok(Self {
+            sdk_version: json
+                .get("sdk_version")
+                .and_then(JsonValue::as_str)
+                .unwrap_or_default()
+                .to_string(),
+            nodes,
+        })
+    }
+
+    /// Write to a `.json` or `.toml` file
+    pub fn write(&self, path: impl AsRef<Path>) -> Result<(), String> {
+        let path = path.as_ref();
+        let format = SidecarFormat::from_path(path)
+            .ok_or_else(|| format!("Unknown sidecar format: {}", path.display()))?;
+        std::fs::write(path, self.to_format_string(format))
+            .map_err(|e| format!("Failed to write {}: {}", path.display(), e))
+    }
+
+    /// Read a `.json` or `.toml` file
+    pub fn read(path: impl AsRef<Path>) -> Result<Self, String> {
+        let path = path.as_ref();
+        let format = SidecarFormat::from_path(path)
+            .ok_or_else(|| format!("Unknown sidecar format: {}", path.display()))?;
+        let text = std::fs::read_to_string(path).map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+        Self::parse(&text, format).map_err(|e| format!("{}: {}", path.display(), e))
+    }
+}
+
+/// Convert a value read from a sidecar to the type of the node's current value
+///
+/// Numbers become integers or floats, strings become paths, frame ranges or
+/// timecodes and arrays become colors or vectors to match `current`. Other values, and
+/// strings that fail to parse, are returned unchanged.
+pub fn conform_sidecar_value(value: NodeData, current: Option<&NodeData>) -> NodeData {
+    match (value, current) {
+        (NodeData::Float(v), Some(NodeData::Integer(_))) => NodeData::Integer(v.round() as i32),
+        (NodeData::Integer(v), Some(NodeData::Float(_))) => NodeData::Float(v as f32),
+        (NodeData::String(v), Some(NodeData::Path(_))) => NodeData::Path(PathData::new(v)),
+        (NodeData::String(v), Some(NodeData::FrameRange { .. })) => match FrameRange::parse(&v) {
+            Ok(range) => range.into(),
+            Err(_) => NodeData::String(v),
+        },
+        (NodeData::String(v), Some(NodeData::Timecode { rate, .. })) => match Timecode::parse(&v) {
+            Ok(value) => NodeData::Timecode { value, rate: *rate },
+            Err(_) => NodeData::String(v),
+        },
+        (NodeData::Color([r, g, b, _]), Some(NodeData::Vector3(_))) => NodeData::Vector3([r, g, b]),
+        (NodeData::Vector3([r, g, b]), Some(NodeData::Color(_))) => NodeData::Color([r, g, b, 1.0]),
+        (NodeData::Color(value), Some(NodeData::ColorIn { space, .. })) => NodeData::ColorIn { value, space: *space },
+        (value, _) => value,
+    }
+}
+
+fn float_to_json(v: f32) -> JsonValue {
+    // Via the shortest decimal form, so 0.1f32 is written as 0.1
+    JsonValue::Number(v.to_string().parse().unwrap_or(v as f64))
+}
+
+fn value_to_json(value: &NodeData) -> Option<JsonValue> {
+    let floats = |values: &[f32]| JsonValue::Array(values.iter().copied().map(float_to_json).collect());
+    Some(match value {
+        NodeData::Float(v) => float_to_json(*v),
+        NodeData::Integer(v) => JsonValue::from(*v as i64),
+        NodeData::Boolean(v) => JsonValue::from(*v),
+        NodeData::String(v) => JsonValue::from(v.as_str()),
+        NodeData::Path(path) => JsonValue::from(path.raw.as_str()),
+        NodeData::Vector3(v) => floats(v),
+        NodeData::Color(v) | NodeData::ColorIn { value: v, .. } => floats(v),
+        NodeData::FrameRange { start, end, step } => {
+            JsonValue::from(FrameRange::new(*start, *end).with_step(*step).to_string().as_str())
+        }
+        NodeData::Timecode { value, .. } => JsonValue::from(value.to_string().as_str()),
+        NodeData::Gradient(gradient) => JsonValue::Array(gradient.keys().iter().map(stop_to_json).collect()),
+        _ => return None,
+    })
+}
+
+/// Easing names used in gradient stops (`CubicBezier` is written separately)
+const EASING_NAMES: [(Easing, &str); 14] = [
+    (Easing::Linear, "linear"),
+    (Easing::Step, "step"),
+    (Easing::SmoothStep, "smooth_step"),
+    (Easing::QuadIn, "quad_in"),
+    (Easing::QuadOut, "quad_out"),
+    (Easing::QuadInOut, "quad_in_out"),
+    (Easing::CubicIn, "cubic_in"),
+    (Easing::CubicOut, "cubic_out"),
+    (Easing::CubicInOut, "cubic_in_out"),
+    (Easing::SineIn, "sine_in"),
+    (Easing::SineOut, "sine_out"),
+    (Easing::SineInOut, "sine_in_out"),
+    (Easing::ExpoIn, "expo_in"),
+    (Easing::ExpoOut, "expo_out"),
+];
+
+fn stop_to_json(key: &RampKey<[f32; 4]>) -> JsonValue {
+    let mut stop: Vec<JsonValue> = std::iter::once(key.position)
+        .chain(key.value)
+        .map(float_to_json)
+        .collect();
+    match key.interpolation {
+        RampInterpolation::Linear => {}
+        RampInterpolation::Constant => stop.push(JsonValue::from("constant")),
+        RampInterpolation::Smooth => stop.push(JsonValue::from("smooth")),
+        RampInterpolation::Eased(Easing::CubicBezier(points)) => {
+            stop.push(JsonValue::from("ease:cubic_bezier"));
+            stop.extend(points.into_iter().map(float_to_json));
+        }
+        RampInterpolation::Eased(easing) => {
+            let name = EASING_NAMES.iter().find(|(e, _)| *e == easing).map_or("linear", |(_, name)| name);
+            stop.push(JsonValue::from(format!("ease:{}", name)));
+        }
+    }
+    JsonValue::Array(stop)
+}
+
+fn stop_from_json(json: &JsonValue) -> Option<RampKey<[f32; 4]>> {
+    let items = json.as_array()?;
+    let number = |index: usize| items.get(index)?.as_f64().map(|v| v as f32);
+    let value = [number(1)?, number(2)?, number(3)?, number(4)?];
+    let interpolation = match items.get(5) {
+        None if items.len() == 5 => RampInterpolation::Linear,
+        Some(JsonValue::String(name)) => match (name.as_str(), items.len()) {
+            ("constant", 6) => RampInterpolation::Constant,
+            ("smooth", 6) => RampInterpolation::Smooth,
+            ("ease:cubic_bezier", 10) => {
+                RampInterpolation::Eased(Easing::CubicBezier([number(6)?, number(7)?, number(8)?, number(9)?]))
+            }
+            (name, 6) => {
+                let name = name.strip_prefix("ease:")?;
+                RampInterpolation::Eased(EASING_NAMES.iter().find(|(_, n)| *n == name)?.0)
+            }
+            _ => return None,
+        },
+        _ => return None,
+    };
+    Some(RampKey {
+        position: number(0)?,
+        value,
+        interpolation,
+    })
+}
+
+fn value_from_json(json: &JsonValue) -> Option<NodeData> {
+    Some(match json {
+        JsonValue::Bool(v) => NodeData::Boolean(*v),
+        JsonValue::Number(v) if v.fract() == 0.0 && (i32::MIN as f64..=i32::MAX as f64).contains(v) => {
+            NodeData::Integer(*v as i32)
+        }
+        JsonValue::Number(v) => NodeData::Float(*v as f32),
+        JsonValue::String(v) => NodeData::String(v.clone()),
+        // Arrays of arrays (or an empty array) are gradient stops
+        JsonValue::Array(items) if items.iter().all(|item| item.as_array().is_some()) => {
+            NodeData::Gradient(GradientData::from_keys(items.iter().map(stop_from_json).collect::<Option<_>>()?))
+        }
+        JsonValue::Array(items) => {
+            let values = items.iter().map(JsonValue::as_f64).collect::<Option<Vec<_>>>()?;
+            match values[..] {
+                [x, y, z] => NodeData::Vector3([x as f32, y as f32, z as f32]),
+                [r, g, b, a] => NodeData::Color([r as f32, g as f32, b as f32, a as f32]),
+                _ => return None,
+            }
+        }
+        _ => return None,
+    })
+}
+
+fn toml_string(text: &str) -> String {
+    let mut out = String::from("\"");
+    for c in text.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            c if c.is_control() => {
+                let _ = write!(out, "\\u{:04X}", c as u32);
+            }
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+    out
+}
+
+fn toml_key(key: &str) -> String {
+    let bare = !key.is_empty() && key.bytes().all(|b| b.is_ascii_alphanumeric() || b == b'_' || b == b'-');
+    if bare {
+        key.to_string()
+    } else {
+        toml_string(key)
+    }
+}
+
+fn toml_value(value: &JsonValue) -> String {
+    match value {
+        JsonValue::Bool(v) => v.to_string(),
+        // Keep a fraction so TOML readers see a float
+        JsonValue::Number(v) if v.is_nan() => "nan".to_string(),
+        JsonValue::Number(v) if v.fract() == 0.0 && v.abs() < 1e15 => format!("{:.1}", v),
+        JsonValue::Number(v) => v.to_string(),
+        JsonValue::String(v) => toml_string(v),
+        JsonValue::Array(items) => {
+            let items: Vec<String> = items.iter().map(toml_value).collect();
+            format!("[{}]", items.join(", "))
+        }
+        JsonValue::Null | JsonValue::Object(_) => String::new(),
+    }
+}
+
+/// Parse the subset of TOML written by `to_toml_string` into the JSON layout
+///
+/// Supports comments, top-level keys, `[[nodes]]`, `[nodes.parameters]`, and
+/// single-line strings, numbers, booleans and arrays.
+fn parse_toml(text: &str) -> Result<JsonValue, String> {
+    let mut root = BTreeMap::new();
+    let mut nodes: Vec<BTreeMap<String, JsonValue>> = Vec::new();
+    let mut section = "";
+    for (index, line) in text.lines().enumerate() {
+        let error = |message: &str| format!("line {}: {}", index + 1, message);
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        if line.starts_with('[') {
+            let header = line.split('#').next().unwrap_or_default().trim();
+            section = match header {
+                "[[nodes]]" => {
+                    nodes.push(BTreeMap::new());
+                    "nodes"
+                }
+                "[nodes.parameters]" if !nodes.is_empty() => "parameters",
+                _ => return Err(error(&format!("unsupported table {}", header))),
+            };
+            continue;
+        }
+
+        let mut cursor = TomlCursor { text: line, pos: 0 };
+        let key = cursor.key().ok_or_else(|| error("expected key"))?;
+        cursor.skip_whitespace();
+        if !cursor.eat('=') {
+            return Err(error("expected '='"));
+        }
+        let value = cursor.value().ok_or_else(|| error("invalid value"))?;
+        cursor.skip_whitespace();
+        if !cursor.rest().is_empty() && !cursor.rest().starts_with('#') {
+            return Err(error("unexpected text after value"));
+        }
+
+        let table = match (section, nodes.last_mut()) {
+            ("nodes", Some(node)) => node,
+            ("parameters", Some(node)) => {
+                match node
+                    .entry("parameters".to_string())
+                    .or_insert_with(|| JsonValue::Object(BTreeMap::new()))
+                {
+                    JsonValue::Object(parameters) => parameters,
+                    _ => return Err(error("'parameters' is not a table")),
+                }
+            }
+            _ => &mut root,
+        };
+        table.insert(key, value);
+    }
+    root.insert(
+        "nodes".to_string(),
+        JsonValue::Array(nodes.into_iter().map(JsonValue::Object).collect()),
+    );
+    Ok(JsonValue::Object(root))
+}
+
+struct TomlCursor<'a> {
+    text: &'a str,
+    pos: usize,
+}
+
+impl<'a> TomlCursor<'a> {
+    fn rest(&self) -> &'a str {
+        &self.text[self.pos..]
+    }
+
+    fn skip_whitespace(&mut self) {
+        let rest = self.rest();
+        self.pos += rest.len() - rest.trim_start().len();
+    }
+
+    fn eat(&mut self, c: char) -> bool {
+        if self.rest().starts_with(c) {
+            self.pos += c.len_utf8();
+            true
+        } else {
+            false
+        }
+    }
+
+    fn key(&mut self) -> Option<String> {
+        self.skip_whitespace();
+        if self.rest().starts_with('"') || self.rest().starts_with('\'') {
+            return self.string();
+        }
+        let len = self
+            .rest()
+            .find(|c: char| !(c.is_ascii_alphanumeric() || c == '_' || c == '-'))
+            .unwrap_or(self.rest().len());
+        let key = self.rest()[..len].to_string();
+        self.pos += len;
+        (!key.is_empty()).then_some(key)
+    }
+
+    fn value(&mut self) -> Option<JsonValue> {
+        self.skip_whitespace();
+        let rest = self.rest();
+        if rest.starts_with('"') || rest.starts_with('\'') {
+            return self.string().map(JsonValue::String);
+        }
+        if self.eat('[') {
+            let mut items = Vec::new();
+            loop {
+                self.skip_whitespace();
+                if self.eat(']') {
+                    return Some(JsonValue::Array(items));
+                }
+                items.push(self.value()?);
+                self.skip_whitespace();
+                if !self.eat(',') {
+                    self.skip_whitespace();
+                    return self.eat(']').then_some(JsonValue::Array(items));
+                }
+            }
+        }
+        let len = rest
+            .find(|c: char| c.is_whitespace() || c == ',' || c == ']' || c == '#')
+            .unwrap_or(rest.len());
+        let token = &rest[..len];
+        self.pos += len;
+        match token {
+            "true" => Some(JsonValue::Bool(true)),
+            "false" => Some(JsonValue::Bool(false)),
+            "inf" | "+inf" => Some(JsonValue::Number(f64::INFINITY)),
+            "-inf" => Some(JsonValue::Number(f64::NEG_INFINITY)),
+            "nan" | "+nan" | "-nan" => Some(JsonValue::Number(f64::NAN)),
+            _ => token.replace('_', "").parse().ok().map(JsonValue::Number),
+        }
+    }
+
+    fn string(&mut self) -> Option<String> {
+        let quote = self.rest().chars().next()?;
+        self.pos += 1;
+        let mut out = String::new();
+        let mut chars = self.rest().char_indices();
+        while let Some((offset, c)) = chars.next() {
+            match c {
+                c if c == quote => {
+                    self.pos += offset + 1;
+                    return Some(out);
+                }
+                // Literal ('...') strings have no escapes
+                '\\' if quote == '"' => {
+                    let escaped = match chars.next()?.1 {
+                        'n' => '\n',
+                        'r' => '\r',
+                        't' => '\t',
+                        'b' => '\u{8}',
+                        'f' => '\u{c}',
+                        '"' => '"',
+                        '\\' => '\\',
+                        marker @ ('u' | 'U') => {
+                            let digits = if marker == 'u' { 4 } else { 8 };
+                            let hex: String = (0..digits).filter_map(|_| chars.next().map(|(_, c)| c)).collect();
+                            char::from_u32(u32::from_str_radix(&hex, 16).ok()?)?
+                        }
+                        _ => return None,
+                    };
+                    out.push(escaped);
+                }
+                c => out.push(c),
+            }
+        }
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample() -> ParameterSidecar {
+        let mut node = SidecarNode::new("Blur");
+        node.insert("radius", NodeData::Float(1.5));
+        node.insert("scale", NodeData::Float(2.0));
+        node.insert("iterations", NodeData::Integer(3));
+        node.insert("enabled", NodeData::Boolean(true));
+        node.insert("mode", NodeData::String("Accurate \"fast\"\n".to_string()));
+        node.insert("tint", NodeData::Color([1.0, 0.5, 0.25, 1.0]));
+        node.insert("offset", NodeData::Vector3([0.0, -1.0, 0.1]));
+        let gradient = GradientData::two_color([0.0, 0.0, 0.0, 1.0], [1.0, 0.5, 0.25, 1.0])
+            .with_key(0.25, [0.1, 0.2, 0.3, 1.0], RampInterpolation::Constant)
+            .with_key(0.5, [0.4, 0.5, 0.6, 1.0], RampInterpolation::Eased(Easing::QuadIn))
+            .with_key(
+                0.75,
+                [0.7, 0.8, 0.9, 0.5],
+                RampInterpolation::Eased(Easing::CubicBezier([0.25, 0.1, 0.25, 1.0])),
+            );
+        node.insert("ramp", NodeData::Gradient(gradient));
+        node.insert("empty_ramp", NodeData::Gradient(GradientData::new()));
+        ParameterSidecar::new().with_node(node)
+    }
+
+    fn assert_round_trip(format: SidecarFormat) {
+        let sidecar = sample();
+        let parsed = ParameterSidecar::parse(&sidecar.to_format_string(format), format).unwrap();
+        assert_eq!(parsed.sdk_version, sidecar.sdk_version);
+        let (original, parsed) = (&sidecar.nodes[0], &parsed.nodes[0]);
+        assert_eq!(parsed.node_type, "Blur");
+        for (name, value) in &original.parameters {
+            let read = conform_sidecar_value(parsed.parameters[name].clone(), Some(value));
+            assert_eq!(format!("{:?}", read), format!("{:?}", value), "{} in {:?}", name, format);
+        }
+    }
+
+    #[test]
+    fn toml_round_trip() {
+        assert_round_trip(SidecarFormat::Toml);
+    }
+
+    #[test]
+    fn json_round_trip() {
+        assert_round_trip(SidecarFormat::Json);
+    }
+
+    #[test]
+    fn whole_numbers_read_as_integers() {
+        let text = "[[nodes]]\nnode_type = \"Blur\"\n\n[nodes.parameters]\ncount = 4 # comment\nradius = 0.5\n";
+        let sidecar = ParameterSidecar::parse(text, SidecarFormat::Toml).unwrap();
+        let parameters = &sidecar.nodes[0].parameters;
+        assert!(matches!(parameters["count"], NodeData::Integer(4)));
+        assert!(matches!(parameters["radius"], NodeData::Float(v) if v == 0.5));
+        assert!(matches!(
+            conform_sidecar_value(NodeData::Integer(4), Some(&NodeData::Float(0.0))),
+            NodeData::Float(v) if v == 4.0
+        ));
+    }
+
+    #[test]
+    fn toml_reader_rejects_malformed_lines() {
+        assert!(ParameterSidecar::parse("[other]\n", SidecarFormat::Toml).is_err());
+        assert!(ParameterSidecar::parse("key value\n", SidecarFormat::Toml).is_err());
+        assert!(ParameterSidecar::parse("key = [1, 2\n", SidecarFormat::Toml).is_err());
+        assert!(ParameterSidecar::parse("key = 1 2\n", SidecarFormat::Toml).is_err());
+    }
+}
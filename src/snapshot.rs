@@ -0,0 +1,162 @@
+//! Graph snapshot export for bug reports
+//!
+//! Plugins can ask the host for an anonymized snapshot of the sub-graph around one
+//! of their nodes (node types, parameters, connection topology and versions) so that
+//! users can attach it to support requests and vendors can reproduce issues.
+//!
+//! Snapshots are captured through the same `GraphHandle` the host hands to
+//! execution hooks and cooks (`GraphQuery::capture_snapshot`).
+
+use crate::{NodeData, NodeId};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// Version of this SDK, recorded in every snapshot
+pub const SDK_VERSION: &str = env!("CARGO_PKG_VERSION");
+
+/// Placeholder written in place of redacted values
+pub const REDACTED: &str = "<redacted>";
+
+/// Options controlling what a snapshot contains
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SnapshotOptions {
+    /// Include nodes feeding into the requested node
+    pub include_upstream: bool,
+    /// Include nodes consuming the requested node's outputs
+    pub include_downstream: bool,
+    /// Maximum number of connections to follow from the requested node (None = unlimited)
+    pub max_depth: Option<usize>,
+    /// Replace file paths with a placeholder
+    pub redact_file_paths: bool,
+    /// Replace all string values with a placeholder
+    pub redact_strings: bool,
+}
+
+impl Default for SnapshotOptions {
+    fn default() -> Self {
+        Self {
+            include_upstream: true,
+            include_downstream: false,
+            max_depth: None,
+            redact_file_paths: false,
+            redact_strings: false,
+        }
+    }
+}
+
+impl SnapshotOptions {
+    /// Options with all redaction enabled
+    pub fn redacted() -> Self {
+        Self {
+            redact_file_paths: true,
+            redact_strings: true,
+            ..Self::default()
+        }
+    }
+}
+
+/// A node captured in a snapshot
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SnapshotNode {
+    /// Node ID within the snapshot
+    pub node_id: NodeId,
+    /// Registered node type
+    pub node_type: String,
+    /// Parameter values at the time of the snapshot
+    pub parameters: HashMap<String, NodeData>,
+}
+
+/// A connection captured in a snapshot
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SnapshotConnection {
+    pub from_node: NodeId,
+    pub from_port: String,
+    pub to_node: NodeId,
+    pub to_port: String,
+}
+
+/// Snapshot of a sub-graph suitable for bug reports
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GraphSnapshot {
+    /// SDK version the plugin was built against
+    pub sdk_version: String,
+    /// Host application version
+    pub host_version: String,
+    /// Node the snapshot was requested for
+    pub root_node: NodeId,
+    /// Captured nodes
+    pub nodes: Vec<SnapshotNode>,
+    /// Connections between captured nodes
+    pub connections: Vec<SnapshotConnection>,
+}
+
+impl GraphSnapshot {
+    /// Create an empty snapshot for a node
+    pub fn new(host_version: impl Into<String>, root_node: NodeId) -> Self {
+        Self {
+            sdk_version: SDK_VERSION.to_string(),
+            host_version: host_version.into(),
+            root_node,
+            nodes: Vec::new(),
+            connections: Vec::new(),
+        }
+    }
+
+    /// Apply the redaction settings from `options` to all parameter values
+    pub fn redact(&mut self, options: &SnapshotOptions) {
+        if !options.redact_file_paths && !options.redact_strings {
+            return;
+        }
+        for node in &mut self.nodes {
+            for value in node.parameters.values_mut() {
+                redact_value(value, options);
+            }
+        }
+    }
+}
+
+/// Redact a single value according to `options`
+pub fn redact_value(value: &mut NodeData, options: &SnapshotOptions) {
+    match value {
+        NodeData::String(s) | NodeData::Any(s) | NodeData::USDScene(s)
+            if options.redact_strings || (options.redact_file_paths && looks_like_path(s)) =>
+        {
+            *s = REDACTED.to_string();
+        }
+        NodeData::Path(path) if options.redact_file_paths || options.redact_strings => {
+            path.raw = REDACTED.to_string();
+        }
+        NodeData::Error(error) if options.redact_strings || (options.redact_file_paths && looks_like_path(&error.message)) => {
+            error.message = REDACTED.to_string();
+        }
+        NodeData::Json(json) if options.redact_strings || options.redact_file_paths => {
+            redact_json(json, options);
+        }
+        NodeData::Stage(stage) if options.redact_file_paths && stage.file_path.is_some() => {
+            stage.file_path = Some(REDACTED.to_string());
+        }
+        NodeData::Image(image) if options.redact_file_paths && image.file_path.is_some() => {
+            image.file_path = Some(REDACTED.to_string());
+        }
+        _ => {}
+    }
+}
+
+fn redact_json(value: &mut crate::JsonValue, options: &SnapshotOptions) {
+    match value {
+        crate::JsonValue::String(s) if options.redact_strings || looks_like_path(s) => {
+            *s = REDACTED.to_string();
+        }
+        crate::JsonValue::Array(items) => items.iter_mut().for_each(|item| redact_json(item, options)),
+        crate::JsonValue::Object(map) => map.values_mut().for_each(|item| redact_json(item, options)),
+        _ => {}
+    }
+}
+
+/// Heuristic check for strings that look like file system paths
+pub fn looks_like_path(s: &str) -> bool {
+    s.contains('/')
+        || s.contains('\\')
+        || s.starts_with('~')
+        || (s.len() > 2 && s.as_bytes()[1] == b':' && s.as_bytes()[0].is_ascii_alphabetic())
+}
@@ -0,0 +1,88 @@
+//! Per-plugin shared state
+//!
+//! This module provides a replacement for `lazy_static`/`static` globals in plugins.
+//! State lives inside the plugin instance rather than in the dynamic library's
+//! statics, so it is torn down on unload, does not survive hot-reloads, and is not
+//! shared between two host instances that happen to load the same library.
+
+use std::sync::{Arc, RwLock};
+
+/// Lazily initialized, concurrency-safe state owned by a plugin instance
+///
+/// Cloning a `PluginState` produces another handle to the same state, so it can be
+/// handed to node factories, nodes and execution hooks (including hook clones made
+/// through `clone_box`) while still being owned by the plugin.
+///
+/// The plugin should call [`PluginState::teardown`] from `NodePlugin::on_unload` so
+/// that the state is dropped while the library is still loaded.
+pub struct PluginState<T: Send + Sync + 'static> {
+    inner: Arc<RwLock<Option<Arc<T>>>>,
+}
+
+impl<T: Send + Sync + 'static> PluginState<T> {
+    /// Create a new, uninitialized state
+    pub fn new() -> Self {
+        Self {
+            inner: Arc::new(RwLock::new(None)),
+        }
+    }
+
+    /// Get the state, initializing it with `init` on first access
+    ///
+    /// If the state was torn down it is initialized again.
+    pub fn get_or_init(&self, init: impl FnOnce() -> T) -> Arc<T> {
+        if let Some(value) = self.get() {
+            return value;
+        }
+
+        let mut guard = self.inner.write().unwrap_or_else(|e| e.into_inner());
+        // Another thread may have initialized the state while we waited for the lock
+        guard.get_or_insert_with(|| Arc::new(init())).clone()
+    }
+
+    /// Get the state if it has been initialized
+    pub fn get(&self) -> Option<Arc<T>> {
+        self.inner.read().unwrap_or_else(|e| e.into_inner()).clone()
+    }
+
+    /// Check if the state has been initialized
+    pub fn is_initialized(&self) -> bool {
+        self.inner.read().unwrap_or_else(|e| e.into_inner()).is_some()
+    }
+
+    /// Drop the state (call from `NodePlugin::on_unload`)
+    ///
+    /// Returns the previous value so the caller can perform explicit cleanup.
+    /// Outstanding `Arc`s obtained through `get`/`get_or_init` keep the value alive
+    /// until they are dropped.
+    pub fn teardown(&self) -> Option<Arc<T>> {
+        self.inner.write().unwrap_or_else(|e| e.into_inner()).take()
+    }
+
+    /// Number of handles sharing this state (for debugging leaks across hook clones)
+    pub fn handle_count(&self) -> usize {
+        Arc::strong_count(&self.inner)
+    }
+}
+
+impl<T: Send + Sync + 'static> Clone for PluginState<T> {
+    fn clone(&self) -> Self {
+        Self {
+            inner: Arc::clone(&self.inner),
+        }
+    }
+}
+
+impl<T: Send + Sync + 'static> Default for PluginState<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: Send + Sync + std::fmt::Debug + 'static> std::fmt::Debug for PluginState<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("PluginState")
+            .field("value", &self.get())
+            .finish()
+    }
+}
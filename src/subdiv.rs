@@ -0,0 +1,570 @@
+//! Subdivision surfaces
+//!
+//! `SubdivisionSurface` carries the USD subdivision metadata (scheme, boundary
+//! rule, creases and corners) alongside a mesh so subdiv assets keep their
+//! control cage as they flow through the graph. `GeometryData::subdivide` is a
+//! reference CPU refinement for previews and exports; renderers with native
+//! subdivision should consume the cage and tags directly.
+//!
+//! Crease and corner sharpness follow USD/OpenSubdiv semantics: 0 is smooth,
+//! values of `INFINITE_SHARPNESS` or more are infinitely sharp, and in between
+//! the sharpness drops by one per refinement level.
+
+use crate::{GeometryData, GeometryError, UVInterpolation, UVSet, USDMeshGeometry};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// Sharpness at or above which a crease or corner never softens
+pub const INFINITE_SHARPNESS: f32 = 10.0;
+
+/// Subdivision scheme (USD `subdivisionScheme`)
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum SubdivisionScheme {
+    /// Plain polygon mesh
+    #[default]
+    None,
+    CatmullClark,
+    /// Triangle-based scheme (non-triangle faces are triangulated first)
+    Loop,
+    /// Splits faces without smoothing
+    Bilinear,
+}
+
+/// Boundary interpolation rule (USD `interpolateBoundary`)
+///
+/// `None` is refined like `EdgeOnly`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum BoundaryInterpolation {
+    None,
+    /// Boundary edges are sharp
+    EdgeOnly,
+    /// Boundary edges are sharp and vertices on a single face are corners
+    #[default]
+    EdgeAndCorner,
+}
+
+/// A chain of sharpened edges
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Crease {
+    /// Vertex indices along the crease (consecutive pairs are edges)
+    pub vertices: Vec<u32>,
+    pub sharpness: f32,
+}
+
+/// A sharpened vertex
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct Corner {
+    pub vertex: u32,
+    pub sharpness: f32,
+}
+
+/// Subdivision metadata for a mesh
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct SubdivisionSurface {
+    pub scheme: SubdivisionScheme,
+    #[serde(default)]
+    pub boundary: BoundaryInterpolation,
+    #[serde(default)]
+    pub creases: Vec<Crease>,
+    #[serde(default)]
+    pub corners: Vec<Corner>,
+    /// Control cage face sizes (empty = the owning mesh's faces are the cage)
+    ///
+    /// `USDMeshGeometry` stores triangles, so readers keep the original polygons
+    /// here to preserve the level-0 topology.
+    #[serde(default)]
+    pub face_vertex_counts: Vec<u32>,
+    /// Control cage face-vertex indices into the mesh vertices
+    #[serde(default)]
+    pub face_vertex_indices: Vec<u32>,
+    /// Preferred display refinement level
+    #[serde(default)]
+    pub refinement_level: u32,
+}
+
+impl SubdivisionSurface {
+    /// Catmull-Clark tags with default boundary interpolation
+    pub fn catmull_clark() -> Self {
+        Self::new(SubdivisionScheme::CatmullClark)
+    }
+
+    /// Create tags for a scheme
+    pub fn new(scheme: SubdivisionScheme) -> Self {
+        Self {
+            scheme,
+            ..Default::default()
+        }
+    }
+
+    /// Set the control cage topology
+    pub fn with_cage(mut self, face_vertex_counts: Vec<u32>, face_vertex_indices: Vec<u32>) -> Self {
+        self.face_vertex_counts = face_vertex_counts;
+        self.face_vertex_indices = face_vertex_indices;
+        self
+    }
+
+    /// Add a crease along a vertex chain
+    pub fn with_crease(mut self, vertices: Vec<u32>, sharpness: f32) -> Self {
+        self.creases.push(Crease { vertices, sharpness });
+        self
+    }
+
+    /// Add a sharp corner
+    pub fn with_corner(mut self, vertex: u32, sharpness: f32) -> Self {
+        self.corners.push(Corner { vertex, sharpness });
+        self
+    }
+
+    /// Check if a control cage is stored
+    pub fn has_cage(&self) -> bool {
+        !self.face_vertex_counts.is_empty()
+    }
+
+    /// Validate indices against a vertex count
+    pub fn validate(&self, vertex_count: usize) -> Result<(), GeometryError> {
+        let indices = self
+            .face_vertex_indices
+            .iter()
+            .chain(self.creases.iter().flat_map(|c| &c.vertices))
+            .chain(self.corners.iter().map(|c| &c.vertex));
+        for &index in indices {
+            if index as usize >= vertex_count {
+                return Err(GeometryError::IndexOutOfBounds { index, vertex_count });
+            }
+        }
+        if let Some(face) = self.face_vertex_counts.iter().position(|&c| c < 3) {
+            return Err(GeometryError::DegenerateFace(face));
+        }
+        let expected: usize = self.face_vertex_counts.iter().map(|&c| c as usize).sum();
+        if expected != self.face_vertex_indices.len() {
+            return Err(GeometryError::FaceCountMismatch {
+                expected,
+                actual: self.face_vertex_indices.len(),
+            });
+        }
+        Ok(())
+    }
+}
+
+impl USDMeshGeometry {
+    /// Level-0 geometry: the control cage polygons if stored, else the triangles
+    ///
+    /// Per-vertex attributes are kept; the subdivision tags travel along so the
+    /// result can be refined with `GeometryData::subdivide`.
+    pub fn control_cage(&self) -> GeometryData {
+        let vertex_count = self.vertices.len();
+        let per_vertex = |len: usize| len == vertex_count;
+        let (indices, face_vertex_counts) = match &self.subdivision {
+            Some(subdiv) if subdiv.has_cage() => {
+                (subdiv.face_vertex_indices.clone(), subdiv.face_vertex_counts.clone())
+            }
+            _ => (self.indices.clone(), Vec::new()),
+        };
+        GeometryData {
+            id: self.prim_path.clone(),
+            vertices: self.vertices.clone(),
+            indices,
+            normals: if per_vertex(self.normals.len()) { self.normals.clone() } else { Vec::new() },
+            uvs: if per_vertex(self.uvs.len()) { self.uvs.clone() } else { Vec::new() },
+            colors: if per_vertex(self.vertex_colors.len()) { self.vertex_colors.clone() } else { Vec::new() },
+            material_id: self.material_path.clone(),
+            face_vertex_counts,
+            uv_sets: self
+                .uv_sets
+                .iter()
+                .filter(|set| set.interpolation == UVInterpolation::Vertex)
+                .cloned()
+                .collect(),
+            subdivision: self.subdivision.clone().map(|subdiv| SubdivisionSurface {
+                face_vertex_counts: Vec::new(),
+                face_vertex_indices: Vec::new(),
+                ..subdiv
+            }),
+            ..Default::default()
+        }
+    }
+}
+
+impl GeometryData {
+    /// Refine the mesh `levels` times using its subdivision tags
+    ///
+    /// Meshes without tags are refined as Catmull-Clark; `SubdivisionScheme::None`
+    /// returns an unchanged copy. Vertex and face-varying attributes are
+    /// interpolated linearly and normals are recomputed if present. The result
+    /// is a plain polygon mesh (`subdivision` is None).
+    pub fn subdivide(&self, levels: u32) -> Result<GeometryData, GeometryError> {
+        self.validate()?;
+        let tags = self.subdivision.clone().unwrap_or_else(SubdivisionSurface::catmull_clark);
+        tags.validate(self.vertices.len())?;
+        if tags.scheme == SubdivisionScheme::None || levels == 0 {
+            return Ok(self.clone());
+        }
+
+        let mut mesh = if tags.has_cage() {
+            GeometryData {
+                indices: tags.face_vertex_indices.clone(),
+                face_vertex_counts: tags.face_vertex_counts.clone(),
+                face_varying_normals: Vec::new(),
+                face_varying_uvs: Vec::new(),
+                uv_sets: Vec::new(),
+                ..self.clone()
+            }
+        } else {
+            self.clone()
+        };
+        if tags.scheme == SubdivisionScheme::Loop && !mesh.is_triangulated() {
+            mesh = mesh.triangulate();
+        }
+
+        let mut creases = tags.creases.clone();
+        let mut corners = tags.corners.clone();
+        for _ in 0..levels {
+            mesh = refine(&mesh, &tags, &mut creases, &mut corners);
+        }
+
+        if !self.normals.is_empty() || !self.face_varying_normals.is_empty() {
+            mesh.compute_smooth_normals();
+        }
+        mesh.subdivision = None;
+        Ok(mesh)
+    }
+}
+
+struct Edge {
+    a: u32,
+    b: u32,
+    faces: Vec<usize>,
+    sharpness: f32,
+}
+
+struct Topology {
+    faces: Vec<Vec<u32>>,
+    edges: Vec<Edge>,
+    edge_map: HashMap<(u32, u32), usize>,
+    vertex_edges: Vec<Vec<usize>>,
+    vertex_faces: Vec<Vec<usize>>,
+    corner_sharpness: Vec<f32>,
+}
+
+impl Topology {
+    fn build(mesh: &GeometryData, tags: &SubdivisionSurface, creases: &[Crease], corners: &[Corner]) -> Self {
+        let vertex_count = mesh.vertices.len();
+        let faces: Vec<Vec<u32>> = mesh.face_ranges().into_iter().map(|r| mesh.indices[r].to_vec()).collect();
+        let mut edges: Vec<Edge> = Vec::new();
+        let mut edge_map = HashMap::new();
+        let mut vertex_edges = vec![Vec::new(); vertex_count];
+        let mut vertex_faces = vec![Vec::new(); vertex_count];
+
+        for (f, face) in faces.iter().enumerate() {
+            for (i, &a) in face.iter().enumerate() {
+                let b = face[(i + 1) % face.len()];
+                let index = *edge_map.entry(edge_key(a, b)).or_insert_with(|| {
+                    edges.push(Edge { a, b, faces: Vec::new(), sharpness: 0.0 });
+                    vertex_edges[a as usize].push(edges.len() - 1);
+                    vertex_edges[b as usize].push(edges.len() - 1);
+                    edges.len() - 1
+                });
+                edges[index].faces.push(f);
+                vertex_faces[a as usize].push(f);
+            }
+        }
+
+        for crease in creases {
+            for pair in crease.vertices.windows(2) {
+                if let Some(&e) = edge_map.get(&edge_key(pair[0], pair[1])) {
+                    edges[e].sharpness = edges[e].sharpness.max(crease.sharpness);
+                }
+            }
+        }
+        // Boundary and non-manifold edges never smooth
+        for edge in &mut edges {
+            if edge.faces.len() != 2 {
+                edge.sharpness = INFINITE_SHARPNESS;
+            }
+        }
+
+        let mut corner_sharpness = vec![0.0f32; vertex_count];
+        for corner in corners {
+            let s = &mut corner_sharpness[corner.vertex as usize];
+            *s = s.max(corner.sharpness);
+        }
+        if tags.boundary == BoundaryInterpolation::EdgeAndCorner {
+            for (v, faces) in vertex_faces.iter().enumerate() {
+                if faces.len() == 1 {
+                    corner_sharpness[v] = INFINITE_SHARPNESS;
+                }
+            }
+        }
+
+        Self {
+            faces,
+            edges,
+            edge_map,
+            vertex_edges,
+            vertex_faces,
+            corner_sharpness,
+        }
+    }
+
+    fn edge(&self, a: u32, b: u32) -> usize {
+        self.edge_map[&edge_key(a, b)]
+    }
+
+    /// Apply the crease/corner rules to a smooth vertex position
+    fn vertex_rule(&self, positions: &[[f32; 3]], v: usize, smooth: [f32; 3]) -> [f32; 3] {
+        let p = positions[v];
+        let sharp: Vec<&Edge> = self.vertex_edges[v]
+            .iter()
+            .map(|&e| &self.edges[e])
+            .filter(|e| e.sharpness > 0.0)
+            .collect();
+        let base = match sharp.len() {
+            0 | 1 => smooth,
+            2 => {
+                let other = |e: &Edge| positions[if e.a as usize == v { e.b } else { e.a } as usize];
+                let crease = mix(&[(other(sharp[0]), 1.0), (p, 6.0), (other(sharp[1]), 1.0)]);
+                let weight = (sharp[0].sharpness.min(1.0) + sharp[1].sharpness.min(1.0)) * 0.5;
+                lerp(smooth, crease, weight)
+            }
+            _ => p,
+        };
+        lerp(base, p, self.corner_sharpness[v].min(1.0))
+    }
+}
+
+fn edge_key(a: u32, b: u32) -> (u32, u32) {
+    (a.min(b), a.max(b))
+}
+
+/// One refinement step
+fn refine(
+    mesh: &GeometryData,
+    tags: &SubdivisionSurface,
+    creases: &mut Vec<Crease>,
+    corners: &mut Vec<Corner>,
+) -> GeometryData {
+    let topo = Topology::build(mesh, tags, creases, corners);
+    let loop_scheme = tags.scheme == SubdivisionScheme::Loop;
+    let (vertices, new_faces) = if loop_scheme {
+        refine_loop(&mesh.vertices, &topo)
+    } else {
+        refine_quads(&mesh.vertices, &topo, tags.scheme == SubdivisionScheme::Bilinear)
+    };
+
+    let vertex_count = mesh.vertices.len() as u32;
+    for crease in creases.iter_mut() {
+        crease.vertices = crease
+            .vertices
+            .windows(2)
+            .filter_map(|pair| topo.edge_map.get(&edge_key(pair[0], pair[1])).map(|&e| (pair, e)))
+            .flat_map(|(pair, e)| [pair[0], vertex_count + e as u32, pair[1]])
+            .collect();
+        crease.vertices.dedup();
+        crease.sharpness = decay(crease.sharpness);
+    }
+    creases.retain(|c| c.sharpness > 0.0 && c.vertices.len() > 1);
+    for corner in corners.iter_mut() {
+        corner.sharpness = decay(corner.sharpness);
+    }
+    corners.retain(|c| c.sharpness > 0.0);
+
+    let refine_vertex = |values: &[[f32; 2]]| refine_linear(values, mesh.vertices.len(), &topo, loop_scheme);
+    GeometryData {
+        id: mesh.id.clone(),
+        indices: new_faces.concat(),
+        face_vertex_counts: if loop_scheme { Vec::new() } else { vec![4; new_faces.len()] },
+        normals: Vec::new(),
+        uvs: refine_vertex(&mesh.uvs),
+        colors: refine_linear(&mesh.colors, mesh.vertices.len(), &topo, loop_scheme),
+        material_id: mesh.material_id.clone(),
+        face_varying_normals: Vec::new(),
+        face_varying_uvs: refine_corners(&mesh.face_varying_uvs, &topo, loop_scheme),
+        uv_sets: mesh
+            .uv_sets
+            .iter()
+            .map(|set| UVSet {
+                uvs: match set.interpolation {
+                    UVInterpolation::Vertex => refine_vertex(&set.uvs),
+                    UVInterpolation::FaceVarying => refine_corners(&set.uvs, &topo, loop_scheme),
+                },
+                ..set.clone()
+            })
+            .collect(),
+        subdivision: None,
+        vertices,
+    }
+}
+
+/// Catmull-Clark (or bilinear) step: vertex points, then edge points, then face points
+fn refine_quads(positions: &[[f32; 3]], topo: &Topology, bilinear: bool) -> (Vec<[f32; 3]>, Vec<Vec<u32>>) {
+    let face_points: Vec<[f32; 3]> = topo
+        .faces
+        .iter()
+        .map(|face| average(face.iter().map(|&v| positions[v as usize])))
+        .collect();
+    let edge_points = topo.edges.iter().map(|edge| {
+        let mid = average([positions[edge.a as usize], positions[edge.b as usize]].into_iter());
+        if bilinear || edge.faces.len() != 2 {
+            return mid;
+        }
+        let smooth = average(
+            [positions[edge.a as usize], positions[edge.b as usize]]
+                .into_iter()
+                .chain(edge.faces.iter().map(|&f| face_points[f])),
+        );
+        lerp(smooth, mid, edge.sharpness.min(1.0))
+    });
+    let vertex_points = (0..positions.len()).map(|v| {
+        let n = topo.vertex_edges[v].len();
+        if bilinear || n < 3 {
+            return positions[v];
+        }
+        let q = average(topo.vertex_faces[v].iter().map(|&f| face_points[f]));
+        let r = average(topo.vertex_edges[v].iter().map(|&e| {
+            let edge = &topo.edges[e];
+            average([positions[edge.a as usize], positions[edge.b as usize]].into_iter())
+        }));
+        let n = n as f32;
+        let smooth = mix(&[(q, 1.0 / n), (r, 2.0 / n), (positions[v], (n - 3.0) / n)]);
+        topo.vertex_rule(positions, v, smooth)
+    });
+
+    let mut vertices: Vec<[f32; 3]> = vertex_points.collect();
+    vertices.extend(edge_points);
+    vertices.extend(face_points.iter().copied());
+
+    let edge_base = positions.len() as u32;
+    let face_base = edge_base + topo.edges.len() as u32;
+    let mut faces = Vec::new();
+    for (f, face) in topo.faces.iter().enumerate() {
+        let n = face.len();
+        for i in 0..n {
+            let (prev, current, next) = (face[(i + n - 1) % n], face[i], face[(i + 1) % n]);
+            faces.push(vec![
+                current,
+                edge_base + topo.edge(current, next) as u32,
+                face_base + f as u32,
+                edge_base + topo.edge(prev, current) as u32,
+            ]);
+        }
+    }
+    (vertices, faces)
+}
+
+/// Loop step on triangles: vertex points, then edge points
+fn refine_loop(positions: &[[f32; 3]], topo: &Topology) -> (Vec<[f32; 3]>, Vec<Vec<u32>>) {
+    let edge_points = topo.edges.iter().map(|edge| {
+        let (a, b) = (positions[edge.a as usize], positions[edge.b as usize]);
+        let mid = average([a, b].into_iter());
+        if edge.faces.len() != 2 {
+            return mid;
+        }
+        let opposite = |f: usize| {
+            let v = topo.faces[f].iter().find(|&&v| v != edge.a && v != edge.b).copied().unwrap_or(edge.a);
+            positions[v as usize]
+        };
+        let smooth = mix(&[(a, 0.375), (b, 0.375), (opposite(edge.faces[0]), 0.125), (opposite(edge.faces[1]), 0.125)]);
+        lerp(smooth, mid, edge.sharpness.min(1.0))
+    });
+    let vertex_points = (0..positions.len()).map(|v| {
+        let n = topo.vertex_edges[v].len();
+        if n < 3 {
+            return positions[v];
+        }
+        let neighbours = average(topo.vertex_edges[v].iter().map(|&e| {
+            let edge = &topo.edges[e];
+            positions[if edge.a as usize == v { edge.b } else { edge.a } as usize]
+        }));
+        let beta = if n == 3 { 3.0 / 16.0 } else { 3.0 / (8.0 * n as f32) };
+        let weight = n as f32 * beta;
+        let smooth = mix(&[(positions[v], 1.0 - weight), (neighbours, weight)]);
+        topo.vertex_rule(positions, v, smooth)
+    });
+
+    let mut vertices: Vec<[f32; 3]> = vertex_points.collect();
+    vertices.extend(edge_points);
+
+    let base = positions.len() as u32;
+    let mut faces = Vec::new();
+    for face in &topo.faces {
+        let (a, b, c) = (face[0], face[1], face[2]);
+        let ab = base + topo.edge(a, b) as u32;
+        let bc = base + topo.edge(b, c) as u32;
+        let ca = base + topo.edge(c, a) as u32;
+        faces.extend([vec![a, ab, ca], vec![b, bc, ab], vec![c, ca, bc], vec![ab, bc, ca]]);
+    }
+    (vertices, faces)
+}
+
+/// Linearly refine a per-vertex attribute (empty or mismatched input stays empty)
+fn refine_linear<const N: usize>(values: &[[f32; N]], vertex_count: usize, topo: &Topology, loop_scheme: bool) -> Vec<[f32; N]> {
+    if values.len() != vertex_count {
+        return Vec::new();
+    }
+    let mut out = values.to_vec();
+    out.extend(
+        topo.edges
+            .iter()
+            .map(|edge| average([values[edge.a as usize], values[edge.b as usize]].into_iter())),
+    );
+    if !loop_scheme {
+        out.extend(topo.faces.iter().map(|face| average(face.iter().map(|&v| values[v as usize]))));
+    }
+    out
+}
+
+/// Linearly refine a face-varying attribute, matching the new face order
+fn refine_corners<const N: usize>(values: &[[f32; N]], topo: &Topology, loop_scheme: bool) -> Vec<[f32; N]> {
+    let corner_count: usize = topo.faces.iter().map(Vec::len).sum();
+    if values.len() != corner_count {
+        return Vec::new();
+    }
+    let mut out = Vec::with_capacity(corner_count * 4);
+    let mut start = 0;
+    for face in &topo.faces {
+        let corners = &values[start..start + face.len()];
+        start += face.len();
+        let mid = |i: usize, j: usize| average([corners[i], corners[j]].into_iter());
+        if loop_scheme {
+            let (ab, bc, ca) = (mid(0, 1), mid(1, 2), mid(2, 0));
+            out.extend([corners[0], ab, ca, corners[1], bc, ab, corners[2], ca, bc, ab, bc, ca]);
+        } else {
+            let n = corners.len();
+            let center = average(corners.iter().copied());
+            for (i, &corner) in corners.iter().enumerate() {
+                out.extend([corner, mid(i, (i + 1) % n), center, mid((i + n - 1) % n, i)]);
+            }
+        }
+    }
+    out
+}
+
+fn decay(sharpness: f32) -> f32 {
+    if sharpness >= INFINITE_SHARPNESS {
+        sharpness
+    } else {
+        (sharpness - 1.0).max(0.0)
+    }
+}
+
+fn add<const N: usize>(a: [f32; N], b: [f32; N]) -> [f32; N] {
+    std::array::from_fn(|i| a[i] + b[i])
+}
+
+fn lerp<const N: usize>(a: [f32; N], b: [f32; N], t: f32) -> [f32; N] {
+    std::array::from_fn(|i| a[i] + (b[i] - a[i]) * t)
+}
+
+fn mix<const N: usize>(terms: &[([f32; N], f32)]) -> [f32; N] {
+    let total: f32 = terms.iter().map(|(_, w)| w).sum();
+    let total = if total.abs() > f32::EPSILON { total } else { 1.0 };
+    std::array::from_fn(|i| terms.iter().map(|(v, w)| v[i] * w).sum::<f32>() / total)
+}
+
+fn average<const N: usize>(values: impl Iterator<Item = [f32; N]>) -> [f32; N] {
+    let (sum, count) = values.fold(([0.0; N], 0usize), |(sum, count), v| (add(sum, v), count + 1));
+    if count == 0 {
+        return sum;
+    }
+    sum.map(|v| v / count as f32)
+}
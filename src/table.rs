@@ -0,0 +1,211 @@
+//! Tabular data
+//!
+//! `TableData` carries rows of typed values through the graph (`NodeData::Table`),
+//! for data-import nodes, spreadsheets and pipeline tooling. Columns have a
+//! single inferred type; individual cells may still be `Null`. See `table_io`
+//! for CSV/TSV/JSON-lines readers.
+
+use crate::{DateTime, JsonValue};
+use serde::{Deserialize, Serialize};
+
+/// Type of a table column
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum ColumnType {
+    Boolean,
+    Integer,
+    Float,
+    DateTime,
+    #[default]
+    String,
+}
+
+impl ColumnType {
+    /// Get a human-readable name for this column type
+    pub fn name(&self) -> &'static str {
+        match self {
+            ColumnType::Boolean => "Boolean",
+            ColumnType::Integer => "Integer",
+            ColumnType::Float => "Float",
+            ColumnType::DateTime => "DateTime",
+            ColumnType::String => "String",
+        }
+    }
+}
+
+/// A single table cell
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub enum TableValue {
+    #[default]
+    Null,
+    Boolean(bool),
+    Integer(i64),
+    Float(f64),
+    DateTime(DateTime),
+    String(String),
+}
+
+impl TableValue {
+    /// Check if this cell is empty
+    pub fn is_null(&self) -> bool {
+        matches!(self, TableValue::Null)
+    }
+
+    /// Try to read as a float (integers are widened)
+    pub fn as_f64(&self) -> Option<f64> {
+        match self {
+            TableValue::Float(f) => Some(*f),
+            TableValue::Integer(i) => Some(*i as f64),
+            _ => None,
+        }
+    }
+
+    /// Try to read as an integer
+    pub fn as_i64(&self) -> Option<i64> {
+        match self {
+            TableValue::Integer(i) => Some(*i),
+            _ => None,
+        }
+    }
+
+    /// Try to read as a string
+    pub fn as_str(&self) -> Option<&str> {
+        match self {
+            TableValue::String(s) => Some(s),
+            _ => None,
+        }
+    }
+
+    /// Format the cell for display (empty string for `Null`)
+    pub fn to_display_string(&self) -> String {
+        match self {
+            TableValue::Null => String::new(),
+            TableValue::Boolean(b) => b.to_string(),
+            TableValue::Integer(i) => i.to_string(),
+            TableValue::Float(f) => f.to_string(),
+            TableValue::DateTime(t) => t.to_rfc3339(),
+            TableValue::String(s) => s.clone(),
+        }
+    }
+
+    /// Convert to a JSON value
+    pub fn to_json(&self) -> JsonValue {
+        match self {
+            TableValue::Null => JsonValue::Null,
+            TableValue::Boolean(b) => JsonValue::Bool(*b),
+            TableValue::Integer(i) => JsonValue::Number(*i as f64),
+            TableValue::Float(f) => JsonValue::Number(*f),
+            TableValue::DateTime(t) => JsonValue::String(t.to_rfc3339()),
+            TableValue::String(s) => JsonValue::String(s.clone()),
+        }
+    }
+}
+
+/// Column name and type
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TableColumn {
+    pub name: String,
+    pub column_type: ColumnType,
+}
+
+impl TableColumn {
+    /// Create a column definition
+    pub fn new(name: impl Into<String>, column_type: ColumnType) -> Self {
+        Self {
+            name: name.into(),
+            column_type,
+        }
+    }
+}
+
+/// Row-oriented table with typed columns
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct TableData {
+    /// Column definitions
+    pub columns: Vec<TableColumn>,
+    /// Rows, each with one value per column
+    pub rows: Vec<Vec<TableValue>>,
+}
+
+impl TableData {
+    /// Create an empty table with the given columns
+    pub fn new(columns: Vec<TableColumn>) -> Self {
+        Self {
+            columns,
+            rows: Vec::new(),
+        }
+    }
+
+    /// Number of rows
+    pub fn row_count(&self) -> usize {
+        self.rows.len()
+    }
+
+    /// Number of columns
+    pub fn column_count(&self) -> usize {
+        self.columns.len()
+    }
+
+    /// Find a column index by name
+    pub fn column_index(&self, name: &str) -> Option<usize> {
+        self.columns.iter().position(|c| c.name == name)
+    }
+
+    /// Append a row, padding or truncating it to the column count
+    pub fn push_row(&mut self, mut row: Vec<TableValue>) {
+        row.resize(self.columns.len(), TableValue::Null);
+        self.rows.push(row);
+    }
+
+    /// Get a cell by row index and column name
+    pub fn get(&self, row: usize, column: &str) -> Option<&TableValue> {
+        let index = self.column_index(column)?;
+        self.rows.get(row)?.get(index)
+    }
+
+    /// Iterate over the values of one column
+    ///
+    /// Rows too short to have the column yield `TableValue::Null`.
+    pub fn column_values(&self, column: &str) -> Option<impl Iterator<Item = &TableValue>> {
+        const NULL: &TableValue = &TableValue::Null;
+        let index = self.column_index(column)?;
+        Some(self.rows.iter().map(move |row| row.get(index).unwrap_or(NULL)))
+    }
+
+    /// Append the rows of another table with the same columns (e.g. chunked reads)
+    pub fn append(&mut self, other: TableData) -> Result<(), String> {
+        if other.columns != self.columns {
+            return Err("Cannot append table with different columns".to_string());
+        }
+        self.rows.extend(other.rows);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn column_values_tolerate_short_rows() {
+        let mut table = TableData::new(vec![
+            TableColumn::new("a", ColumnType::Integer),
+            TableColumn::new("b", ColumnType::Integer),
+        ]);
+        table.push_row(vec![TableValue::Integer(1), TableValue::Integer(2)]);
+        // Rows are public and may bypass push_row's padding
+        table.rows.push(vec![TableValue::Integer(3)]);
+
+        let b: Vec<&TableValue> = table.column_values("b").unwrap().collect();
+        assert_eq!(b, vec![&TableValue::Integer(2), &TableValue::Null]);
+        assert_eq!(table.get(1, "b"), None);
+        assert!(table.column_values("missing").is_none());
+    }
+
+    #[test]
+    fn push_row_pads_and_truncates() {
+        let mut table = TableData::new(vec![TableColumn::new("a", ColumnType::String)]);
+        table.push_row(Vec::new());
+        table.push_row(vec![TableValue::Integer(1), TableValue::Integer(2)]);
+        assert_eq!(table.rows, vec![vec![TableValue::Null], vec![TableValue::Integer(1)]]);
+    }
+}
@@ -0,0 +1,657 @@
+//! CSV/TSV/JSON-lines readers producing `TableData`
+//!
+//! Shared parsing so data-import nodes behave the same on messy real-world
+//! files: byte-order marks and legacy encodings are detected, delimiters are
+//! sniffed, quoted fields may contain delimiters and newlines, ragged rows are
+//! padded, and column types are inferred. Large files can be read in chunks with
+//! `CsvChunkReader`.
+
+use crate::{ColumnType, DateTime, JsonValue, TableColumn, TableData, TableValue};
+use std::fs::File;
+use std::io::{BufRead, BufReader, Read};
+use std::path::Path;
+
+/// Detected text encoding
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TextEncoding {
+    Utf8,
+    Utf16Le,
+    Utf16Be,
+    /// Fallback for invalid UTF-8 (ISO-8859-1)
+    Latin1,
+}
+
+/// Decode file bytes, detecting the encoding from the BOM or content
+pub fn decode_text(bytes: &[u8]) -> (String, TextEncoding) {
+    if let Some(rest) = bytes.strip_prefix(&[0xef, 0xbb, 0xbf]) {
+        return (String::from_utf8_lossy(rest).into_owned(), TextEncoding::Utf8);
+    }
+    if let Some(rest) = bytes.strip_prefix(&[0xff, 0xfe]) {
+        return (decode_utf16(rest, u16::from_le_bytes), TextEncoding::Utf16Le);
+    }
+    if let Some(rest) = bytes.strip_prefix(&[0xfe, 0xff]) {
+        return (decode_utf16(rest, u16::from_be_bytes), TextEncoding::Utf16Be);
+    }
+    match std::str::from_utf8(bytes) {
+        Ok(text) => (text.to_string(), TextEncoding::Utf8),
+        Err(_) => (bytes.iter().map(|&b| b as char).collect(), TextEncoding::Latin1),
+    }
+}
+
+fn decode_utf16(bytes: &[u8], from_bytes: fn([u8; 2]) -> u16) -> String {
+    let units = bytes.chunks_exact(2).map(|pair| from_bytes([pair[0], pair[1]]));
+    char::decode_utf16(units)
+        .map(|c| c.unwrap_or(char::REPLACEMENT_CHARACTER))
+        .collect()
+}
+
+/// Options for delimited text parsing
+#[derive(Debug, Clone)]
+pub struct CsvOptions {
+    /// Field delimiter (None = detect from the first lines)
+    pub delimiter: Option<char>,
+    /// Whether the first record holds column names
+    pub has_header: bool,
+    /// Infer column types (otherwise every column is `String`)
+    pub infer_types: bool,
+    /// Trim whitespace around unquoted fields
+    pub trim: bool,
+    /// Rows per chunk for `CsvChunkReader`
+    pub chunk_rows: usize,
+}
+
+impl Default for CsvOptions {
+    fn default() -> Self {
+        Self {
+            delimiter: None,
+            has_header: true,
+            infer_types: true,
+            trim: true,
+            chunk_rows: 10_000,
+        }
+    }
+}
+
+impl CsvOptions {
+    /// Options for tab-separated files
+    pub fn tsv() -> Self {
+        Self {
+            delimiter: Some('\t'),
+            ..Self::default()
+        }
+    }
+}
+
+/// Guess the delimiter from sample text (`,`, `;`, tab or `|`)
+///
+/// Picks the candidate that appears a consistent, non-zero number of times per
+/// line (outside quotes), defaulting to a comma.
+pub fn detect_delimiter(sample: &str) -> char {
+    let lines: Vec<&str> = sample.lines().filter(|l| !l.trim().is_empty()).take(10).collect();
+    let mut best = (',', 0usize, false);
+    for candidate in [',', ';', '\t', '|'] {
+        let counts: Vec<usize> = lines.iter().map(|line| count_unquoted(line, candidate)).collect();
+        let first = counts.first().copied().unwrap_or(0);
+        let consistent = counts.iter().all(|&c| c == first);
+        // Prefer consistent counts, then higher counts
+        if first > 0 && ((consistent && !best.2) || (consistent == best.2 && first > best.1)) {
+            best = (candidate, first, consistent);
+        }
+    }
+    best.0
+}
+
+fn count_unquoted(line: &str, delimiter: char) -> usize {
+    let mut in_quotes = false;
+    line.chars()
+        .filter(|&c| {
+            if c == '"' {
+                in_quotes = !in_quotes;
+            }
+            c == delimiter && !in_quotes
+        })
+        .count()
+}
+
+/// Parse delimited text into a table
+pub fn parse_csv(text: &str, options: &CsvOptions) -> Result<TableData, String> {
+    let delimiter = options.delimiter.unwrap_or_else(|| detect_delimiter(text));
+    let records = parse_records(text, delimiter, options.trim)?;
+    Ok(build_table(records, options, None))
+}
+
+/// Read a CSV/TSV file, detecting encoding and delimiter
+pub fn read_csv(path: impl AsRef<Path>, options: &CsvOptions) -> Result<TableData, String> {
+    let path = path.as_ref();
+    let bytes = std::fs::read(path).map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+    let (text, _) = decode_text(&bytes);
+    let mut options = options.clone();
+    if options.delimiter.is_none() && has_extension(path, &["tsv", "tab"]) {
+        options.delimiter = Some('\t');
+    }
+    parse_csv(&text, &options)
+}
+
+/// Parse JSON lines (one object per line) into a table
+///
+/// Columns are the union of object keys in first-seen order. Blank lines are
+/// skipped; nested arrays/objects are stored as JSON strings.
+pub fn parse_json_lines(text: &str, infer_types: bool) -> Result<TableData, String> {
+    let mut names: Vec<String> = Vec::new();
+    let mut objects = Vec::new();
+    for (line_number, line) in text.lines().enumerate() {
+        if line.trim().is_empty() {
+            continue;
+        }
+        let value = JsonValue::parse(line).map_err(|e| format!("Line {}: {}", line_number + 1, e))?;
+        let JsonValue::Object(object) = value else {
+            return Err(format!("Line {}: expected a JSON object", line_number + 1));
+        };
+        for key in object.keys() {
+            if !names.contains(key) {
+                names.push(key.clone());
+            }
+        }
+        objects.push(object);
+    }
+
+    let records: Vec<Vec<Option<String>>> = objects
+        .iter()
+        .map(|object| {
+            names
+                .iter()
+                .map(|name| match object.get(name) {
+                    None | Some(JsonValue::Null) => None,
+                    Some(JsonValue::String(s)) => Some(s.clone()),
+                    Some(other) => Some(other.to_json_string()),
+                })
+                .collect()
+        })
+        .collect();
+    let options = CsvOptions {
+        has_header: false,
+        infer_types,
+        ..CsvOptions::default()
+    };
+    Ok(build_table_from_cells(names, records, &options, None))
+}
+
+/// Read a JSON-lines file
+pub fn read_json_lines(path: impl AsRef<Path>, infer_types: bool) -> Result<TableData, String> {
+    let path = path.as_ref();
+    let bytes = std::fs::read(path).map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+    parse_json_lines(&decode_text(&bytes).0, infer_types)
+}
+
+/// Streaming reader that yields a large delimited file in chunks of rows
+///
+/// The header and column types are taken from the first chunk; later values
+/// that don't fit the inferred type are kept as `TableValue::String`. UTF-16
+/// input is not supported in chunked mode; use `read_csv` instead.
+pub struct CsvChunkReader<R: BufRead> {
+    reader: R,
+    options: CsvOptions,
+    delimiter: Option<char>,
+    encoding: Option<TextEncoding>,
+    columns: Option<Vec<TableColumn>>,
+    finished: bool,
+}
+
+impl CsvChunkReader<BufReader<File>> {
+    /// Open a file for chunked reading
+    pub fn open(path: impl AsRef<Path>, options: CsvOptions) -> Result<Self, String> {
+        let path = path.as_ref();
+        let file = File::open(path).map_err(|e| format!("Failed to open {}: {}", path.display(), e))?;
+        let mut options = options;
+        if options.delimiter.is_none() && has_extension(path, &["tsv", "tab"]) {
+            options.delimiter = Some('\t');
+        }
+        Ok(Self::new(BufReader::new(file), options))
+    }
+}
+
+impl<R: BufRead> CsvChunkReader<R> {
+    /// Wrap a buffered reader
+    pub fn new(reader: R, options: CsvOptions) -> Self {
+        Self {
+            delimiter: options.delimiter,
+            reader,
+            options,
+            encoding: None,
+            columns: None,
+            finished: false,
+        }
+    }
+
+    /// Column definitions (available after the first chunk)
+    pub fn columns(&self) -> Option<&[TableColumn]> {
+        self.columns.as_deref()
+    }
+
+    /// Read the next chunk (None at end of input)
+    pub fn next_chunk(&mut self) -> Option<Result<TableData, String>> {
+        if self.finished {
+            return None;
+        }
+        let mut text = String::new();
+        let mut records = 0;
+        let header = usize::from(self.columns.is_none() && self.options.has_header);
+        // Read whole records (quoted fields may span lines)
+        while records < self.options.chunk_rows.max(1) + header {
+            match self.read_record_text() {
+                Ok(Some(record)) => {
+                    text.push_str(&record);
+                    records += 1;
+                }
+                Ok(None) => {
+                    self.finished = true;
+                    break;
+                }
+                Err(e) => {
+                    self.finished = true;
+                    return Some(Err(e));
+                }
+            }
+        }
+        if records == 0 {
+            return None;
+        }
+
+        let delimiter = *self.delimiter.get_or_insert_with(|| detect_delimiter(&text));
+        let records = match parse_records(&text, delimiter, self.options.trim) {
+            Ok(records) => records,
+            Err(e) => return Some(Err(e)),
+        };
+        let table = match &self.columns {
+            None => {
+                let table = build_table(records, &self.options, None);
+                self.columns = Some(table.columns.clone());
+                table
+            }
+            Some(columns) => {
+                let options = CsvOptions {
+                    has_header: false,
+                    ..self.options.clone()
+                };
+                build_table(records, &options, Some(columns))
+            }
+        };
+        Some(Ok(table))
+    }
+
+    fn read_record_text(&mut self) -> Result<Option<String>, String> {
+        let mut record = String::new();
+        let mut quotes: Option<QuoteState> = None;
+        loop {
+            let mut bytes = Vec::new();
+            let read = self.reader.read_until(b'\n', &mut bytes).map_err(|e| e.to_string())?;
+            if read == 0 {
+                return Ok(if record.is_empty() { None } else { Some(record) });
+            }
+            let line = self.decode_line(&bytes)?;
+            let delimiter = *self.delimiter.get_or_insert_with(|| detect_delimiter(&line));
+            let state = quotes.get_or_insert_with(|| QuoteState::new(delimiter));
+            state.scan(&line);
+            record.push_str(&line);
+            // A quoted field still open at the end of the line continues on the next one
+            if !state.in_quotes {
+                return Ok(Some(record));
+            }
+        }
+    }
+
+    fn decode_line(&mut self, bytes: &[u8]) -> Result<String, String> {
+        let encoding = match self.encoding {
+            Some(encoding) => encoding,
+            None => {
+                let (_, encoding) = decode_text(bytes);
+                if matches!(encoding, TextEncoding::Utf16Le | TextEncoding::Utf16Be) {
+                    return Err("UTF-16 input is not supported for chunked reads".to_string());
+                }
+                self.encoding = Some(encoding);
+                let bytes = bytes.strip_prefix(&[0xef, 0xbb, 0xbf]).unwrap_or(bytes);
+                return Ok(decode_text(bytes).0);
+            }
+        };
+        Ok(match encoding {
+            TextEncoding::Latin1 => bytes.iter().map(|&b| b as char).collect(),
+            _ => match std::str::from_utf8(bytes) {
+                Ok(text) => text.to_string(),
+                Err(_) => bytes.iter().map(|&b| b as char).collect(),
+            },
+        })
+    }
+}
+
+impl<R: BufRead> Iterator for CsvChunkReader<R> {
+    type Item = Result<TableData, String>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.next_chunk()
+    }
+}
+
+/// Read a whole stream as CSV (convenience over `CsvChunkReader`)
+pub fn read_csv_from(reader: impl Read, options: &CsvOptions) -> Result<TableData, String> {
+    let mut bytes = Vec::new();
+    BufReader::new(reader).read_to_end(&mut bytes).map_err(|e| e.to_string())?;
+    parse_csv(&decode_text(&bytes).0, options)
+}
+
+fn has_extension(path: &Path, extensions: &[&str]) -> bool {
+    path.extension()
+        .map(|e| extensions.iter().any(|x| e.eq_ignore_ascii_case(x)))
+        .unwrap_or(false)
+}
+
+/// Quoting state across the lines of one record, following the rules of `parse_records`
+struct QuoteState {
+    delimiter: char,
+    in_quotes: bool,
+    /// The current field holds only whitespace so far (a quote may still open it)
+    field_blank: bool,
+}
+
+impl QuoteState {
+    fn new(delimiter: char) -> Self {
+        Self {
+            delimiter,
+            in_quotes: false,
+            field_blank: true,
+        }
+    }
+
+    fn scan(&mut self, line: &str) {
+        let mut chars = line.chars().peekable();
+        while let Some(c) = chars.next() {
+            if self.in_quotes {
+                match c {
+                    // Escaped quote
+                    '"' if chars.peek() == Some(&'"') => {
+                        chars.next();
+                        self.field_blank = false;
+                    }
+                    '"' => self.in_quotes = false,
+                    c if !c.is_whitespace() => self.field_blank = false,
+                    _ => {}
+                }
+                continue;
+            }
+            match c {
+                '"' if self.field_blank => self.in_quotes = true,
+                c if c == self.delimiter || c == '\n' || c == '\r' => self.field_blank = true,
+                c if c.is_whitespace() => {}
+                _ => self.field_blank = false,
+            }
+        }
+    }
+}
+
+/// Split text into records of fields (RFC 4180 quoting); `None` marks empty fields
+fn parse_records(text: &str, delimiter: char, trim: bool) -> Result<Vec<Vec<Option<String>>>, String> {
+    let mut records = Vec::new();
+    let mut record: Vec<Option<String>> = Vec::new();
+    let mut field = String::new();
+    let mut quoted = false;
+    let mut in_quotes = false;
+    let mut chars = text.chars().peekable();
+    let mut line = 1;
+
+    let finish_field = |field: &mut String, quoted: &mut bool, record: &mut Vec<Option<String>>| {
+        let value = if *quoted || !trim { field.clone() } else { field.trim().to_string() };
+        record.push(if value.is_empty() && !*quoted { None } else { Some(value) });
+        field.clear();
+        *quoted = false;
+    };
+
+    while let Some(c) = chars.next() {
+        if in_quotes {
+            match c {
+                '"' if chars.peek() == Some(&'"') => {
+                    chars.next();
+                    field.push('"');
+                }
+                '"' => in_quotes = false,
+                '\n' => {
+                    line += 1;
+                    field.push(c);
+                }
+                _ => field.push(c),
+            }
+            continue;
+        }
+        match c {
+            '"' if field.trim().is_empty() => {
+                field.clear();
+                in_quotes = true;
+                quoted = true;
+            }
+            c if c == delimiter => finish_field(&mut field, &mut quoted, &mut record),
+            '\r' if chars.peek() == Some(&'\n') => {}
+            '\n' | '\r' => {
+                line += 1;
+                finish_field(&mut field, &mut quoted, &mut record);
+                // Skip blank lines
+                if !(record.len() == 1 && record[0].is_none()) {
+                    records.push(std::mem::take(&mut record));
+                }
+                record.clear();
+            }
+            _ => field.push(c),
+        }
+    }
+    if in_quotes {
+        return Err(format!("Unterminated quoted field at line {}", line));
+    }
+    if !field.is_empty() || quoted || !record.is_empty() {
+        finish_field(&mut field, &mut quoted, &mut record);
+        if !(record.len() == 1 && record[0].is_none()) {
+            records.push(record);
+        }
+    }
+    Ok(records)
+}
+
+fn build_table(
+    mut records: Vec<Vec<Option<String>>>,
+    options: &CsvOptions,
+    columns: Option<&[TableColumn]>,
+) -> TableData {
+    let width = records.iter().map(Vec::len).max().unwrap_or(0);
+    let names: Vec<String> = if options.has_header && !records.is_empty() {
+        let header = records.remove(0);
+        (0..width.max(header.len()))
+            .map(|i| match header.get(i) {
+                Some(Some(name)) => name.clone(),
+                _ => format!("column_{}", i + 1),
+            })
+            .collect()
+    } else if let Some(columns) = columns {
+        columns.iter().map(|c| c.name.clone()).collect()
+    } else {
+        (0..width).map(|i| format!("column_{}", i + 1)).collect()
+    };
+    build_table_from_cells(names, records, options, columns)
+}
+
+fn build_table_from_cells(
+    names: Vec<String>,
+    records: Vec<Vec<Option<String>>>,
+    options: &CsvOptions,
+    columns: Option<&[TableColumn]>,
+) -> TableData {
+    let columns: Vec<TableColumn> = match columns {
+        Some(columns) => columns.to_vec(),
+        None => names
+            .into_iter()
+            .enumerate()
+            .map(|(i, name)| {
+                let column_type = if options.infer_types {
+                    infer_type(records.iter().filter_map(|r| r.get(i).and_then(|v| v.as_deref())))
+                } else {
+                    ColumnType::String
+                };
+                TableColumn::new(name, column_type)
+            })
+            .collect(),
+    };
+
+    let mut table = TableData::new(columns);
+    for record in records {
+        let row = table
+            .columns
+            .iter()
+            .enumerate()
+            .map(|(i, column)| match record.get(i) {
+                Some(Some(text)) => parse_value(text, column.column_type),
+                _ => TableValue::Null,
+            })
+            .collect();
+        table.push_row(row);
+    }
+    table
+}
+
+/// Infer the narrowest type that fits every non-empty value
+fn infer_type<'a>(values: impl Iterator<Item = &'a str>) -> ColumnType {
+    let mut candidates = [ColumnType::Boolean, ColumnType::Integer, ColumnType::Float, ColumnType::DateTime];
+    let mut any = false;
+    for value in values {
+        any = true;
+        for candidate in candidates.iter_mut() {
+            if *candidate != ColumnType::String && parse_typed(value, *candidate).is_none() {
+                *candidate = ColumnType::String;
+            }
+        }
+        if candidates.iter().all(|c| *c == ColumnType::String) {
+            return ColumnType::String;
+        }
+    }
+    if !any {
+        return ColumnType::String;
+    }
+    candidates
+        .into_iter()
+        .find(|c| *c != ColumnType::String)
+        .unwrap_or(ColumnType::String)
+}
+
+fn parse_value(text: &str, column_type: ColumnType) -> TableValue {
+    parse_typed(text, column_type).unwrap_or_else(|| TableValue::String(text.to_string()))
+}
+
+/// Numbers written with leading zeros ("007", "-00.5") are identifiers such as
+/// ZIP codes or shot numbers; keep them as text so the zeros are not lost
+fn has_leading_zero(text: &str) -> bool {
+    let digits = text.strip_prefix(['-', '+']).unwrap_or(text).as_bytes();
+    digits.len() > 1 && digits[0] == b'0' && digits[1].is_ascii_digit()
+}
+
+fn parse_typed(text: &str, column_type: ColumnType) -> Option<TableValue> {
+    let text = text.trim();
+    if matches!(column_type, ColumnType::Integer | ColumnType::Float) && has_leading_zero(text) {
+        return None;
+    }
+    match column_type {
+        ColumnType::Boolean => match text.to_ascii_lowercase().as_str() {
+            "true" => Some(TableValue::Boolean(true)),
+            "false" => Some(TableValue::Boolean(false)),
+            _ => None,
+        },
+        ColumnType::Integer => text.parse().ok().map(TableValue::Integer),
+        ColumnType::Float => {
+            // Reject words Rust accepts as floats ("inf", "NaN") unless written as numbers
+            let numeric = text.bytes().any(|b| b.is_ascii_digit());
+            text.parse().ok().filter(|_| numeric).map(TableValue::Float)
+        }
+        ColumnType::DateTime => DateTime::parse_rfc3339(text).ok().map(TableValue::DateTime),
+        ColumnType::String => Some(TableValue::String(text.to_string())),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn column(table: &TableData, name: &str) -> Vec<TableValue> {
+        table.column_values(name).unwrap().cloned().collect()
+    }
+
+    fn string(s: &str) -> TableValue {
+        TableValue::String(s.to_string())
+    }
+
+    #[test]
+    fn parses_quoted_fields() {
+        let text = "name,notes\n\"Smith, J\",\"said \"\"hi\"\"\"\n\"multi\nline\",\n";
+        let table = parse_csv(text, &CsvOptions::default()).unwrap();
+        assert_eq!(column(&table, "name"), vec![string("Smith, J"), string("multi\nline")]);
+        assert_eq!(column(&table, "notes"), vec![string("said \"hi\""), TableValue::Null]);
+    }
+
+    #[test]
+    fn infers_column_types() {
+        let text = "i,f,b,s\n1,1.5,true,x\n-2,2,FALSE,\n";
+        let table = parse_csv(text, &CsvOptions::default()).unwrap();
+        let types: Vec<ColumnType> = table.columns.iter().map(|c| c.column_type).collect();
+        assert_eq!(types, vec![ColumnType::Integer, ColumnType::Float, ColumnType::Boolean, ColumnType::String]);
+        assert_eq!(column(&table, "f"), vec![TableValue::Float(1.5), TableValue::Float(2.0)]);
+        assert_eq!(column(&table, "s"), vec![string("x"), TableValue::Null]);
+    }
+
+    #[test]
+    fn keeps_leading_zeros_as_text() {
+        let table = parse_csv("zip,n\n007,0\n10,0.5\n", &CsvOptions::default()).unwrap();
+        assert_eq!(table.columns[0].column_type, ColumnType::String);
+        assert_eq!(column(&table, "zip"), vec![string("007"), string("10")]);
+        assert_eq!(column(&table, "n"), vec![TableValue::Float(0.0), TableValue::Float(0.5)]);
+        assert!(has_leading_zero("-00.5"));
+        assert!(!has_leading_zero("0.5"));
+        assert!(!has_leading_zero("0"));
+    }
+
+    #[test]
+    fn detects_delimiters_and_pads_ragged_rows() {
+        assert_eq!(detect_delimiter("a;b;c\n1;2;3\n"), ';');
+        assert_eq!(detect_delimiter("a\tb\n\"x\ty\"\tz\n"), '\t');
+        let table = parse_csv("a;b;c\n1;2\n", &CsvOptions::default()).unwrap();
+        assert_eq!(table.rows, vec![vec![TableValue::Integer(1), TableValue::Integer(2), TableValue::Null]]);
+    }
+
+    #[test]
+    fn rejects_unterminated_quotes() {
+        assert!(parse_csv("a\n\"open\n", &CsvOptions::default()).is_err());
+    }
+
+    #[test]
+    fn decodes_byte_order_marks() {
+        assert_eq!(decode_text(b"\xef\xbb\xbfa,b"), ("a,b".to_string(), TextEncoding::Utf8));
+        assert_eq!(decode_text(b"\xff\xfea\x00"), ("a".to_string(), TextEncoding::Utf16Le));
+        assert_eq!(decode_text(b"caf\xe9"), ("café".to_string(), TextEncoding::Latin1));
+    }
+
+    #[test]
+    fn chunked_reads_keep_records_with_escaped_and_inner_quotes() {
+        // Escaped quotes, a quoted newline, and a bare inch mark inside an unquoted field
+        let text = "id,label\n1,\"a \"\"b\"\"\nc\"\n2,5\" screen\n3,\"x\"\n";
+        let options = CsvOptions {
+            chunk_rows: 1,
+            ..CsvOptions::default()
+        };
+        let chunks: Vec<TableData> = CsvChunkReader::new(text.as_bytes(), options)
+            .collect::<Result<_, _>>()
+            .unwrap();
+        let labels: Vec<TableValue> = chunks.iter().flat_map(|chunk| column(chunk, "label")).collect();
+        assert_eq!(labels, vec![string("a \"b\"\nc"), string("5\" screen"), string("x")]);
+        assert_eq!(chunks.len(), 3);
+    }
+
+    #[test]
+    fn parses_json_lines() {
+        let table = parse_json_lines("{\"a\": 1, \"b\": \"x\"}\n\n{\"c\": [1]}\n", true).unwrap();
+        let names: Vec<&str> = table.columns.iter().map(|c| c.name.as_str()).collect();
+        assert_eq!(names, vec!["a", "b", "c"]);
+        assert_eq!(column(&table, "c"), vec![TableValue::Null, string("[1]")]);
+        assert!(parse_json_lines("[1]\n", true).is_err());
+    }
+}
@@ -0,0 +1,137 @@
+//! Theme-aware port colors
+//!
+//! The host supplies a `ThemeProvider` describing the active UI theme so that
+//! port and wire colors (`DataType::themed_color`) stay legible under light,
+//! dark, high-contrast and colorblind-safe themes. Plugins receive it as a
+//! `ThemeHandle` from `PluginHandle::theme` or `ExecutionContext::theme`.
+
+use crate::DataType;
+use egui::Color32;
+use std::fmt;
+use std::sync::Arc;
+
+/// Base UI theme variant
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ThemeVariant {
+    Dark,
+    Light,
+    HighContrast,
+}
+
+/// Host-supplied color palette for data types
+pub trait ThemeProvider: Send + Sync {
+    /// Active theme variant
+    fn variant(&self) -> ThemeVariant;
+
+    /// Color for a data type (None = use the default palette for the variant)
+    fn port_color(&self, _data_type: &DataType) -> Option<Color32> {
+        None
+    }
+}
+
+/// Built-in theme using the default palettes
+#[derive(Debug, Clone, Copy)]
+pub struct BuiltinTheme {
+    pub variant: ThemeVariant,
+    /// Use the Okabe-Ito colorblind-safe palette
+    pub colorblind_safe: bool,
+}
+
+impl ThemeProvider for BuiltinTheme {
+    fn variant(&self) -> ThemeVariant {
+        self.variant
+    }
+
+    fn port_color(&self, data_type: &DataType) -> Option<Color32> {
+        if self.colorblind_safe {
+            Some(colorblind_safe_color(data_type))
+        } else {
+            None
+        }
+    }
+}
+
+/// Cloneable handle to the host's theme
+///
+/// The host sets it on each `PluginHandle` (`PluginHandle::set_theme`), where
+/// execution hooks can read it, and on `ExecutionContext::theme` for cooks. It
+/// is passed in explicitly because a provider stored in a static would live in
+/// the plugin library's own copy of the SDK and never see the host's theme.
+#[derive(Clone)]
+pub struct ThemeHandle(Arc<dyn ThemeProvider>);
+
+impl ThemeHandle {
+    /// Wrap a theme provider
+    pub fn new(provider: impl ThemeProvider + 'static) -> Self {
+        Self(Arc::new(provider))
+    }
+
+    /// Color for a data type under this theme
+    pub fn port_color(&self, data_type: &DataType) -> Color32 {
+        data_type.themed_color(self.0.as_ref())
+    }
+}
+
+impl std::ops::Deref for ThemeHandle {
+    type Target = dyn ThemeProvider;
+
+    fn deref(&self) -> &Self::Target {
+        self.0.as_ref()
+    }
+}
+
+impl fmt::Debug for ThemeHandle {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("ThemeHandle").field(&self.0.variant()).finish()
+    }
+}
+
+/// Adjust a dark-theme palette color for a theme variant
+pub fn adapt_color(color: Color32, variant: ThemeVariant) -> Color32 {
+    match variant {
+        ThemeVariant::Dark => color,
+        // Darken so colors keep contrast against a light background
+        ThemeVariant::Light => Color32::from_rgb(
+            (color.r() as f32 * 0.6) as u8,
+            (color.g() as f32 * 0.6) as u8,
+            (color.b() as f32 * 0.6) as u8,
+        ),
+        // Push each channel to the extremes for maximum separation
+        ThemeVariant::HighContrast => Color32::from_rgb(
+            if color.r() >= 128 { 255 } else { 0 },
+            if color.g() >= 128 { 255 } else { 0 },
+            if color.b() >= 128 { 255 } else { 0 },
+        ),
+    }
+}
+
+/// Okabe-Ito based palette, distinguishable under common color vision deficiencies
+pub fn colorblind_safe_color(data_type: &DataType) -> Color32 {
+    match data_type {
+        DataType::Float | DataType::Integer => Color32::from_rgb(0, 114, 178), // Blue
+        DataType::Vector3 => Color32::from_rgb(213, 94, 0),                   // Vermillion
+        DataType::Color => Color32::from_rgb(230, 159, 0),                    // Orange
+        DataType::String => Color32::from_rgb(0, 158, 115),                   // Bluish green
+        DataType::Boolean => Color32::from_rgb(204, 121, 167),                // Reddish purple
+        DataType::Light => Color32::from_rgb(240, 228, 66),                   // Yellow
+        DataType::Image => Color32::from_rgb(86, 180, 233),                   // Sky blue
+        DataType::Any => Color32::from_rgb(150, 150, 150),                    // Gray
+        _ => Color32::from_rgb(200, 200, 200),                                // Light gray
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn handles_apply_the_host_theme() {
+        let light = ThemeHandle::new(BuiltinTheme { variant: ThemeVariant::Light, colorblind_safe: false });
+        assert_eq!(light.port_color(&DataType::Float), Color32::from_rgb(60, 90, 153));
+
+        let colorblind = ThemeHandle::new(BuiltinTheme { variant: ThemeVariant::Dark, colorblind_safe: true });
+        assert_eq!(colorblind.port_color(&DataType::Float), Color32::from_rgb(0, 114, 178));
+
+        assert_eq!(DataType::Float.color(), DataType::Float.default_color());
+    }
+}
@@ -0,0 +1,243 @@
+//! Transform stacks
+//!
+//! `TransformStack` is the shared translate/rotate/scale/pivot description used
+//! by transform nodes, so every plugin composes rotations in the same order.
+//! Composition follows USD's `xformCommonAPI`:
+//! translate, then pivot, rotate, scale, inverse pivot.
+//!
+//! Matrices are `[[f32; 4]; 4]` in USD layout: row vectors, with the translation
+//! in the last row (`m[3][0..3]`).
+
+use serde::{Deserialize, Serialize};
+
+/// Order in which Euler rotations are applied
+///
+/// `XYZ` rotates about X first, then Y, then Z (USD/Maya convention).
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum RotationOrder {
+    #[default]
+    XYZ,
+    XZY,
+    YXZ,
+    YZX,
+    ZXY,
+    ZYX,
+}
+
+impl RotationOrder {
+    /// Axis indices (0 = X, 1 = Y, 2 = Z) in application order
+    pub fn axes(&self) -> [usize; 3] {
+        match self {
+            RotationOrder::XYZ => [0, 1, 2],
+            RotationOrder::XZY => [0, 2, 1],
+            RotationOrder::YXZ => [1, 0, 2],
+            RotationOrder::YZX => [1, 2, 0],
+            RotationOrder::ZXY => [2, 0, 1],
+            RotationOrder::ZYX => [2, 1, 0],
+        }
+    }
+
+    /// Get the conventional name (e.g. "XYZ")
+    pub fn name(&self) -> &'static str {
+        match self {
+            RotationOrder::XYZ => "XYZ",
+            RotationOrder::XZY => "XZY",
+            RotationOrder::YXZ => "YXZ",
+            RotationOrder::YZX => "YZX",
+            RotationOrder::ZXY => "ZXY",
+            RotationOrder::ZYX => "ZYX",
+        }
+    }
+}
+
+/// Translate/rotate/scale/pivot transform description
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct TransformStack {
+    pub translate: [f32; 3],
+    /// Euler angles in degrees
+    pub rotate: [f32; 3],
+    pub rotation_order: RotationOrder,
+    pub scale: [f32; 3],
+    /// Point that rotation and scale happen around (in local space)
+    pub pivot: [f32; 3],
+}
+
+impl Default for TransformStack {
+    fn default() -> Self {
+        Self {
+            translate: [0.0; 3],
+            rotate: [0.0; 3],
+            rotation_order: RotationOrder::XYZ,
+            scale: [1.0; 3],
+            pivot: [0.0; 3],
+        }
+    }
+}
+
+impl TransformStack {
+    /// Identity transform
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the translation
+    pub fn with_translate(mut self, translate: [f32; 3]) -> Self {
+        self.translate = translate;
+        self
+    }
+
+    /// Set the rotation (degrees) and rotation order
+    pub fn with_rotate(mut self, rotate: [f32; 3], order: RotationOrder) -> Self {
+        self.rotate = rotate;
+        self.rotation_order = order;
+        self
+    }
+
+    /// Set the scale
+    pub fn with_scale(mut self, scale: [f32; 3]) -> Self {
+        self.scale = scale;
+        self
+    }
+
+    /// Set the pivot
+    pub fn with_pivot(mut self, pivot: [f32; 3]) -> Self {
+        self.pivot = pivot;
+        self
+    }
+
+    /// Rotation as a unit quaternion `[x, y, z, w]`
+    pub fn rotation_quaternion(&self) -> [f32; 4] {
+        self.rotation_order
+            .axes()
+            .iter()
+            .fold([0.0, 0.0, 0.0, 1.0], |q, &axis| {
+                let half = self.rotate[axis].to_radians() * 0.5;
+                let mut step = [0.0, 0.0, 0.0, half.cos()];
+                step[axis] = half.sin();
+                // Later rotations apply on top of earlier ones
+                quat_mul(step, q)
+            })
+    }
+
+    /// Compose into a 4x4 matrix (USD layout, see module docs)
+    pub fn matrix(&self) -> [[f32; 4]; 4] {
+        compose(self.translate, self.rotation_quaternion(), self.scale, self.pivot)
+    }
+
+    /// Transform a point by this stack
+    pub fn transform_point(&self, point: [f32; 3]) -> [f32; 3] {
+        transform_point(&self.matrix(), point)
+    }
+
+    /// Interpolate each component linearly (matches keyframed channel animation)
+    pub fn lerp(&self, other: &TransformStack, t: f32) -> TransformStack {
+        TransformStack {
+            translate: lerp3(self.translate, other.translate, t),
+            rotate: lerp3(self.rotate, other.rotate, t),
+            rotation_order: self.rotation_order,
+            scale: lerp3(self.scale, other.scale, t),
+            pivot: lerp3(self.pivot, other.pivot, t),
+        }
+    }
+
+    /// Interpolate to a matrix using the shortest rotation path (slerp)
+    ///
+    /// Unlike `lerp`, this is independent of rotation order and never flips, so
+    /// it suits motion-blur sub-samples and blending.
+    pub fn interpolate_matrix(&self, other: &TransformStack, t: f32) -> [[f32; 4]; 4] {
+        compose(
+            lerp3(self.translate, other.translate, t),
+            slerp(self.rotation_quaternion(), other.rotation_quaternion(), t),
+            lerp3(self.scale, other.scale, t),
+            lerp3(self.pivot, other.pivot, t),
+        )
+    }
+}
+
+/// Multiply two matrices in USD layout (`a` applied first, then `b`)
+pub fn multiply_matrices(a: &[[f32; 4]; 4], b: &[[f32; 4]; 4]) -> [[f32; 4]; 4] {
+    let mut out = [[0.0; 4]; 4];
+    for (row, out_row) in out.iter_mut().enumerate() {
+        for (col, value) in out_row.iter_mut().enumerate() {
+            *value = (0..4).map(|k| a[row][k] * b[k][col]).sum();
+        }
+    }
+    out
+}
+
+/// Transform a point by a matrix in USD layout
+pub fn transform_point(m: &[[f32; 4]; 4], p: [f32; 3]) -> [f32; 3] {
+    let w = p[0] * m[0][3] + p[1] * m[1][3] + p[2] * m[2][3] + m[3][3];
+    let w = if w.abs() > f32::EPSILON { w } else { 1.0 };
+    [
+        (p[0] * m[0][0] + p[1] * m[1][0] + p[2] * m[2][0] + m[3][0]) / w,
+        (p[0] * m[0][1] + p[1] * m[1][1] + p[2] * m[2][1] + m[3][1]) / w,
+        (p[0] * m[0][2] + p[1] * m[1][2] + p[2] * m[2][2] + m[3][2]) / w,
+    ]
+}
+
+/// p' = (R * S * (p - pivot)) + pivot + translate, written in row-vector form
+fn compose(translate: [f32; 3], rotation: [f32; 4], scale: [f32; 3], pivot: [f32; 3]) -> [[f32; 4]; 4] {
+    let r = quat_to_rows(rotation);
+    let mut m = [[0.0; 4]; 4];
+    for row in 0..3 {
+        for col in 0..3 {
+            m[row][col] = r[row][col] * scale[row];
+        }
+    }
+    // Translation row: translate + pivot - pivot * (S R)
+    for col in 0..3 {
+        let rotated_pivot: f32 = (0..3).map(|k| pivot[k] * m[k][col]).sum();
+        m[3][col] = translate[col] + pivot[col] - rotated_pivot;
+    }
+    m[3][3] = 1.0;
+    m
+}
+
+/// Rotation matrix rows for a unit quaternion (row-vector convention)
+fn quat_to_rows(q: [f32; 4]) -> [[f32; 3]; 3] {
+    let [x, y, z, w] = q;
+    [
+        [1.0 - 2.0 * (y * y + z * z), 2.0 * (x * y + z * w), 2.0 * (x * z - y * w)],
+        [2.0 * (x * y - z * w), 1.0 - 2.0 * (x * x + z * z), 2.0 * (y * z + x * w)],
+        [2.0 * (x * z + y * w), 2.0 * (y * z - x * w), 1.0 - 2.0 * (x * x + y * y)],
+    ]
+}
+
+fn quat_mul(a: [f32; 4], b: [f32; 4]) -> [f32; 4] {
+    [
+        a[3] * b[0] + a[0] * b[3] + a[1] * b[2] - a[2] * b[1],
+        a[3] * b[1] - a[0] * b[2] + a[1] * b[3] + a[2] * b[0],
+        a[3] * b[2] + a[0] * b[1] - a[1] * b[0] + a[2] * b[3],
+        a[3] * b[3] - a[0] * b[0] - a[1] * b[1] - a[2] * b[2],
+    ]
+}
+
+fn slerp(a: [f32; 4], b: [f32; 4], t: f32) -> [f32; 4] {
+    let mut dot: f32 = (0..4).map(|i| a[i] * b[i]).sum();
+    // Take the shorter path
+    let b = if dot < 0.0 {
+        dot = -dot;
+        b.map(|v| -v)
+    } else {
+        b
+    };
+    let (wa, wb) = if dot > 0.9995 {
+        (1.0 - t, t)
+    } else {
+        let theta = dot.clamp(-1.0, 1.0).acos();
+        let sin = theta.sin();
+        (((1.0 - t) * theta).sin() / sin, (t * theta).sin() / sin)
+    };
+    let q = [0, 1, 2, 3].map(|i| a[i] * wa + b[i] * wb);
+    let len = q.iter().map(|v| v * v).sum::<f32>().sqrt();
+    if len > f32::EPSILON {
+        q.map(|v| v / len)
+    } else {
+        [0.0, 0.0, 0.0, 1.0]
+    }
+}
+
+fn lerp3(a: [f32; 3], b: [f32; 3], t: f32) -> [f32; 3] {
+    [a[0] + (b[0] - a[0]) * t, a[1] + (b[1] - a[1]) * t, a[2] + (b[2] - a[2]) * t]
+}
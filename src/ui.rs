@@ -3,7 +3,11 @@
 //! This module provides rich UI components that match the main application's
 //! interface system, allowing plugins to create sophisticated parameter panels.
 
-use crate::NodeData;
+use crate::{
+    FrameRange, FrameRate, GradientData, JsonValue, NodeData, NodeId, PluginCache, PluginCacheKey, RampInterpolation,
+    Timecode, Unit,
+};
+use std::collections::BTreeMap;
 use egui::{Color32, DragValue, Ui};
 use serde::{Deserialize, Serialize};
 
@@ -32,7 +36,15 @@ pub enum PanelType {
 /// This matches the main application's InterfaceParameter system exactly
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub enum InterfaceParameter {
-    Float { value: f32, min: f32, max: f32, step: f32 },
+    Float {
+        value: f32,
+        min: f32,
+        max: f32,
+        step: f32,
+        /// Unit of the stored value (displayed with conversion, e.g. radians as degrees)
+        #[serde(default)]
+        unit: Option<Unit>,
+    },
     Integer { value: i32, min: i32, max: i32 },
     Vector3 { value: [f32; 3] },
     Color { value: [f32; 4] },
@@ -46,12 +58,25 @@ impl InterfaceParameter {
     /// Render the parameter in the UI and return if it changed
     pub fn render(&mut self, ui: &mut Ui, label: &str) -> bool {
         match self {
-            InterfaceParameter::Float { value, min, max, step } => {
-                ui.add(DragValue::new(value)
+            InterfaceParameter::Float { value, min, max, step, unit } => {
+                let mut drag = DragValue::new(value)
                     .speed(*step)
                     .range(*min..=*max)
-                    .prefix(format!("{}: ", label)))
-                    .changed()
+                    .prefix(format!("{}: ", label));
+                if let Some(unit) = *unit {
+                    let display = unit.display_unit();
+                    drag = drag
+                        .custom_formatter(move |v, _| {
+                            let shown = unit.convert(v as f32, display).unwrap_or(v as f32);
+                            format!("{:.3}", shown)
+                        })
+                        .custom_parser(move |text| {
+                            let parsed = text.trim().trim_end_matches(display.suffix()).trim().parse::<f32>().ok()?;
+                            display.convert(parsed, unit).map(|v| v as f64)
+                        })
+                        .suffix(display.suffix());
+                }
+                ui.add(drag).changed()
             }
             InterfaceParameter::Integer { value, min, max } => {
                 ui.add(DragValue::new(value)
@@ -145,53 +170,29 @@ impl InterfaceParameter {
     /// Update the parameter from NodeData
     pub fn from_node_data(&mut self, data: &NodeData) -> bool {
         match (self, data) {
-            (InterfaceParameter::Float { value, .. }, NodeData::Float(new_value)) => {
-                if *value != *new_value {
-                    *value = *new_value;
-                    true
-                } else {
-                    false
-                }
+            (InterfaceParameter::Float { value, .. }, NodeData::Float(new_value)) if *value != *new_value => {
+                *value = *new_value;
+                true
             }
-            (InterfaceParameter::Integer { value, .. }, NodeData::Integer(new_value)) => {
-                if *value != *new_value {
-                    *value = *new_value;
-                    true
-                } else {
-                    false
-                }
+            (InterfaceParameter::Integer { value, .. }, NodeData::Integer(new_value)) if *value != *new_value => {
+                *value = *new_value;
+                true
             }
-            (InterfaceParameter::Vector3 { value }, NodeData::Vector3(new_value)) => {
-                if *value != *new_value {
-                    *value = *new_value;
-                    true
-                } else {
-                    false
-                }
+            (InterfaceParameter::Vector3 { value }, NodeData::Vector3(new_value)) if *value != *new_value => {
+                *value = *new_value;
+                true
             }
-            (InterfaceParameter::Color { value }, NodeData::Color(new_value)) => {
-                if *value != *new_value {
-                    *value = *new_value;
-                    true
-                } else {
-                    false
-                }
+            (InterfaceParameter::Color { value }, NodeData::Color(new_value)) if *value != *new_value => {
+                *value = *new_value;
+                true
             }
-            (InterfaceParameter::String { value }, NodeData::String(new_value)) => {
-                if value != new_value {
-                    *value = new_value.clone();
-                    true
-                } else {
-                    false
-                }
+            (InterfaceParameter::String { value }, NodeData::String(new_value)) if value != new_value => {
+                *value = new_value.clone();
+                true
             }
-            (InterfaceParameter::Boolean { value }, NodeData::Boolean(new_value)) => {
-                if *value != *new_value {
-                    *value = *new_value;
-                    true
-                } else {
-                    false
-                }
+            (InterfaceParameter::Boolean { value }, NodeData::Boolean(new_value)) if *value != *new_value => {
+                *value = *new_value;
+                true
             }
             (InterfaceParameter::Enum { value, options }, NodeData::String(new_value)) => {
                 if let Some(index) = options.iter().position(|opt| opt == new_value) {
@@ -205,13 +206,9 @@ impl InterfaceParameter {
                     false
                 }
             }
-            (InterfaceParameter::FilePath { value, .. }, NodeData::String(new_value)) => {
-                if value != new_value {
-                    *value = new_value.clone();
-                    true
-                } else {
-                    false
-                }
+            (InterfaceParameter::FilePath { value, .. }, NodeData::String(new_value)) if value != new_value => {
+                *value = new_value.clone();
+                true
             }
             _ => false, // Type mismatch
         }
@@ -289,6 +286,193 @@ pub enum UIElement {
     },
     Horizontal(Vec<UIElement>),
     Vertical(Vec<UIElement>),
+    /// Multi-line node notes (changes use `NOTES_PARAMETER`)
+    Notes { value: String },
+    /// Start, end and step of a frame range
+    FrameRangeInput {
+        label: String,
+        value: FrameRange,
+        parameter_name: String,
+    },
+    /// Hours, minutes, seconds and frames of a timecode
+    TimecodeInput {
+        label: String,
+        value: Timecode,
+        rate: FrameRate,
+        parameter_name: String,
+    },
+    /// Color ramp preview with editable stops (position, color, interpolation)
+    GradientEditor {
+        label: String,
+        value: GradientData,
+        parameter_name: String,
+    },
+    /// List of cache entries with invalidate buttons (clicks use `CACHE_INSPECTOR_PARAMETER`)
+    CacheInspector {
+        label: String,
+        entries: Vec<CacheInspectorEntry>,
+    },
+}
+
+/// Parameter name reported when the node notes field is edited
+/// 
+/// The host stores the new notes in the node's `NodeUserData` rather than
+/// forwarding the change to the plugin.
+pub const NOTES_PARAMETER: &str = "__notes";
+
+/// Parameter name reported when a cache inspector button is clicked
+/// 
+/// The value is a `NodeData::Json` array of the clicked entries' keys (every
+/// entry for "invalidate all"), so it stays valid if the panel is rebuilt
+/// before the click is handled. The host turns it into
+/// `UIAction::InvalidateCache` with `ParameterUI::cache_action`.
+pub const CACHE_INSPECTOR_PARAMETER: &str = "__cache_invalidate";
+
+/// One row of a `UIElement::CacheInspector`
+#[derive(Debug, Clone)]
+pub struct CacheInspectorEntry {
+    pub key: PluginCacheKey,
+    /// Estimated memory size of the entry
+    pub size_bytes: usize,
+    /// Time since insertion, in the host's cache time units (see `CacheEntryInfo`)
+    pub age: u64,
+    /// Number of reads
+    pub access_count: u64,
+}
+
+impl CacheInspectorEntry {
+    /// Collect a plugin's entries (optionally only one node's), largest first
+    /// 
+    /// Ages are measured from the most recent insertion or access among the
+    /// collected entries.
+    pub fn collect(cache: &dyn PluginCache, plugin_id: &str, node_id: Option<NodeId>) -> Vec<Self> {
+        let infos: Vec<_> = cache
+            .get_plugin_keys(plugin_id)
+            .into_iter()
+            .filter(|key| node_id.map_or(true, |id| key.node_id == id))
+            .filter_map(|key| cache.entry_info(key).map(|info| (key.clone(), info)))
+            .collect();
+        let now = infos
+            .iter()
+            .map(|(_, info)| info.inserted_at.max(info.last_access))
+            .max()
+            .unwrap_or(0);
+        let mut entries: Vec<Self> = infos
+            .into_iter()
+            .map(|(key, info)| Self {
+                key,
+                size_bytes: info.size_bytes,
+                age: now.saturating_sub(info.inserted_at),
+                access_count: info.access_count,
+            })
+            .collect();
+        entries.sort_by_key(|entry| std::cmp::Reverse(entry.size_bytes));
+        entries
+    }
+    
+    /// Short description of the key for display (stage, port, frame)
+    pub fn describe(&self) -> String {
+        let mut text = match &self.key.stage_id {
+            Some(stage) => format!("{}[{}]", stage, self.key.port_index),
+            None => format!("port {}", self.key.port_index),
+        };
+        if let Some(frame) = self.key.frame {
+            text.push_str(&format!(" @{}", frame));
+        }
+        text
+    }
+}
+
+/// Draw a gradient preview strip and one row of controls per stop
+/// 
+/// Returns true if the gradient was edited.
+fn render_gradient_editor(ui: &mut Ui, gradient: &mut GradientData, id_salt: &str) -> bool {
+    const SEGMENTS: usize = 64;
+    let to_color32 = |c: [f32; 4]| {
+        let byte = |v: f32| (v.clamp(0.0, 1.0) * 255.0).round() as u8;
+        Color32::from_rgba_unmultiplied(byte(c[0]), byte(c[1]), byte(c[2]), byte(c[3]))
+    };
+    
+    let (rect, _) = ui.allocate_exact_size(egui::vec2(ui.available_width(), 16.0), egui::Sense::hover());
+    let painter = ui.painter_at(rect);
+    let half_step = 0.5 / SEGMENTS as f32;
+    let width = rect.width() / SEGMENTS as f32;
+    for (i, color) in gradient.sample((half_step, 1.0 - half_step), SEGMENTS).into_iter().enumerate() {
+        let left = rect.left() + i as f32 * width;
+        let segment = egui::Rect::from_min_max(egui::pos2(left, rect.top()), egui::pos2(left + width, rect.bottom()));
+        painter.rect_filled(segment, 0.0, to_color32(color));
+    }
+    for key in gradient.keys() {
+        let x = rect.left() + key.position.clamp(0.0, 1.0) * rect.width();
+        painter.vline(x, rect.y_range(), egui::Stroke::new(1.0, ui.visuals().strong_text_color()));
+    }
+    
+    let mut changed = false;
+    let mut moved = None;
+    let mut removed = None;
+    let can_remove = gradient.keys().len() > 1;
+    for index in 0..gradient.keys().len() {
+        let mut position = gradient.keys()[index].position;
+        let Some((color, interpolation)) = gradient.key_mut(index) else { continue };
+        ui.horizontal(|ui| {
+            if ui.add(DragValue::new(&mut position).range(0.0..=1.0).speed(0.01)).changed() {
+                moved = Some((index, position));
+            }
+            changed |= ui.color_edit_button_rgba_unmultiplied(color).changed();
+            let name = |interpolation: &RampInterpolation| match interpolation {
+                RampInterpolation::Constant => "Constant",
+                RampInterpolation::Linear => "Linear",
+                RampInterpolation::Smooth => "Smooth",
+                RampInterpolation::Eased(_) => "Eased",
+            };
+            egui::ComboBox::from_id_salt((id_salt, index))
+                .selected_text(name(interpolation))
+                .show_ui(ui, |ui| {
+                    for option in [RampInterpolation::Constant, RampInterpolation::Linear, RampInterpolation::Smooth] {
+                        changed |= ui.selectable_value(interpolation, option, name(&option)).changed();
+                    }
+                });
+            if can_remove && ui.small_button("✖").on_hover_text("Remove stop").clicked() {
+                removed = Some(index);
+            }
+        });
+    }
+    
+    if let Some((index, position)) = moved {
+        changed |= gradient.move_key(index, position).is_some();
+    } else if let Some(index) = removed {
+        changed |= gradient.remove_key(index).is_some();
+    }
+    
+    if ui.button("+ Add Stop").clicked() {
+        // Split the widest gap between stops (or the middle of an empty ramp)
+        let positions: Vec<f32> = gradient.keys().iter().map(|k| k.position).collect();
+        let position = positions
+            .windows(2)
+            .max_by(|a, b| (a[1] - a[0]).total_cmp(&(b[1] - b[0])))
+            .map(|gap| (gap[0] + gap[1]) * 0.5)
+            .unwrap_or(0.5);
+        let color = gradient.evaluate(position).unwrap_or([1.0; 4]);
+        gradient.add_key(position, color, RampInterpolation::Linear);
+        changed = true;
+    }
+    changed
+}
+
+/// Format a byte count for display (e.g. "1.5 MiB")
+fn format_bytes(bytes: usize) -> String {
+    const UNITS: [&str; 5] = ["B", "KiB", "MiB", "GiB", "TiB"];
+    let mut value = bytes as f64;
+    let mut unit = 0;
+    while value >= 1024.0 && unit < UNITS.len() - 1 {
+        value /= 1024.0;
+        unit += 1;
+    }
+    if unit == 0 {
+        format!("{} B", bytes)
+    } else {
+        format!("{:.1} {}", value, UNITS[unit])
+    }
 }
 
 impl UIElement {
@@ -446,7 +630,7 @@ impl UIElement {
                             value: NodeData::Vector3(*value),
                         });
                     }
-                }).inner;
+                });
             }
             UIElement::ColorEdit { label, value, parameter_name } => {
                 ui.horizontal(|ui| {
@@ -466,21 +650,108 @@ impl UIElement {
                             value: NodeData::Color([value[0], value[1], value[2], 1.0]),
                         });
                     }
-                }).inner;
+                });
             }
             UIElement::Horizontal(children) => {
                 ui.horizontal(|ui| {
                     for child in children {
                         changes.extend(child.render(ui));
                     }
-                }).inner;
+                });
             }
             UIElement::Vertical(children) => {
                 ui.vertical(|ui| {
                     for child in children {
                         changes.extend(child.render(ui));
                     }
-                }).inner;
+                });
+            }
+            UIElement::Notes { value } => {
+                ui.collapsing("Notes", |ui| {
+                    if ui.text_edit_multiline(value).changed() {
+                        changes.push(ParameterChange {
+                            parameter: NOTES_PARAMETER.to_string(),
+                            value: NodeData::String(value.clone()),
+                        });
+                    }
+                });
+            }
+            UIElement::FrameRangeInput { label, value, parameter_name } => {
+                ui.horizontal(|ui| {
+                    ui.label(label.as_str());
+                    let mut changed = false;
+                    changed |= ui.add(DragValue::new(&mut value.start).prefix("Start:")).changed();
+                    changed |= ui.add(DragValue::new(&mut value.end).prefix("End:")).changed();
+                    changed |= ui.add(DragValue::new(&mut value.step).range(1..=i32::MAX).prefix("Step:")).changed();
+                    if changed {
+                        changes.push(ParameterChange {
+                            parameter: parameter_name.clone(),
+                            value: (*value).into(),
+                        });
+                    }
+                });
+            }
+            UIElement::TimecodeInput { label, value, rate, parameter_name } => {
+                ui.horizontal(|ui| {
+                    ui.label(label.as_str());
+                    let last_frame = rate.timecode_base().saturating_sub(1);
+                    let mut changed = false;
+                    changed |= ui.add(DragValue::new(&mut value.hours).range(0..=23)).changed();
+                    ui.label(":");
+                    changed |= ui.add(DragValue::new(&mut value.minutes).range(0..=59)).changed();
+                    ui.label(":");
+                    changed |= ui.add(DragValue::new(&mut value.seconds).range(0..=59)).changed();
+                    ui.label(if value.drop_frame { ";" } else { ":" });
+                    changed |= ui.add(DragValue::new(&mut value.frames).range(0..=last_frame)).changed();
+                    if changed {
+                        // Snap frame numbers skipped by drop-frame counting to a valid timecode
+                        *value = Timecode::from_frame(value.to_frame(*rate), *rate);
+                        changes.push(ParameterChange {
+                            parameter: parameter_name.clone(),
+                            value: NodeData::Timecode { value: *value, rate: *rate },
+                        });
+                    }
+                });
+            }
+            UIElement::GradientEditor { label, value, parameter_name } => {
+                ui.label(label.as_str());
+                if render_gradient_editor(ui, value, parameter_name) {
+                    changes.push(ParameterChange {
+                        parameter: parameter_name.clone(),
+                        value: NodeData::Gradient(value.clone()),
+                    });
+                }
+            }
+            UIElement::CacheInspector { label, entries } => {
+                let total: usize = entries.iter().map(|entry| entry.size_bytes).sum();
+                ui.collapsing(format!("{} ({}, {})", label, entries.len(), format_bytes(total)), |ui| {
+                    egui::Grid::new(label.as_str()).striped(true).show(ui, |ui| {
+                        ui.strong("Entry");
+                        ui.strong("Size");
+                        ui.strong("Age");
+                        ui.strong("Reads");
+                        ui.end_row();
+                        for entry in entries.iter() {
+                            ui.label(entry.describe());
+                            ui.label(format_bytes(entry.size_bytes));
+                            ui.label(entry.age.to_string());
+                            ui.label(entry.access_count.to_string());
+                            if ui.small_button("✖").on_hover_text("Invalidate").clicked() {
+                                changes.push(ParameterChange {
+                                    parameter: CACHE_INSPECTOR_PARAMETER.to_string(),
+                                    value: cache_keys_value([&entry.key]),
+                                });
+                            }
+                            ui.end_row();
+                        }
+                    });
+                    if !entries.is_empty() && ui.button("Invalidate all").clicked() {
+                        changes.push(ParameterChange {
+                            parameter: CACHE_INSPECTOR_PARAMETER.to_string(),
+                            value: cache_keys_value(entries.iter().map(|entry| &entry.key)),
+                        });
+                    }
+                });
             }
         }
         
@@ -497,10 +768,16 @@ pub struct ParameterChange {
 
 /// UI action types for plugin interaction
 #[derive(Debug, Clone)]
+#[allow(clippy::large_enum_variant)]
 pub enum UIAction {
     ButtonClicked { action: String },
     ParameterChanged { parameter: String, value: NodeData },
     FileSelected { parameter: String, path: String },
+    /// Invalidate cache entries clicked in a `UIElement::CacheInspector`
+    /// 
+    /// The host invalidates the entries, then forwards the action so the node
+    /// can untrack them (e.g. `PluginCacheManager::handle_evictions`).
+    InvalidateCache { keys: Vec<PluginCacheKey> },
 }
 
 /// Parameter UI structure for plugins
@@ -612,6 +889,78 @@ impl ParameterUI {
         });
     }
     
+    /// Add the node notes field
+    pub fn add_notes(&mut self, notes: impl Into<String>) {
+        self.add_element(UIElement::Notes { value: notes.into() });
+    }
+    
+    /// Add a frame range input
+    pub fn add_frame_range_input(&mut self, label: impl Into<String>, value: FrameRange, parameter_name: impl Into<String>) {
+        self.add_element(UIElement::FrameRangeInput {
+            label: label.into(),
+            value,
+            parameter_name: parameter_name.into(),
+        });
+    }
+    
+    /// Add a timecode input
+    pub fn add_timecode_input(
+        &mut self,
+        label: impl Into<String>,
+        value: Timecode,
+        rate: FrameRate,
+        parameter_name: impl Into<String>
+    ) {
+        self.add_element(UIElement::TimecodeInput {
+            label: label.into(),
+            value,
+            rate,
+            parameter_name: parameter_name.into(),
+        });
+    }
+    
+    /// Add a gradient (color ramp) editor
+    pub fn add_gradient_editor(
+        &mut self,
+        label: impl Into<String>,
+        value: GradientData,
+        parameter_name: impl Into<String>
+    ) {
+        self.add_element(UIElement::GradientEditor {
+            label: label.into(),
+            value,
+            parameter_name: parameter_name.into(),
+        });
+    }
+    
+    /// Add a cache inspector listing a plugin's entries (optionally one node's)
+    pub fn add_cache_inspector(
+        &mut self,
+        label: impl Into<String>,
+        cache: &dyn PluginCache,
+        plugin_id: &str,
+        node_id: Option<NodeId>
+    ) {
+        self.add_element(UIElement::CacheInspector {
+            label: label.into(),
+            entries: CacheInspectorEntry::collect(cache, plugin_id, node_id),
+        });
+    }
+    
+    /// Turn a cache inspector click into a `UIAction::InvalidateCache`
+    /// 
+    /// Returns None for changes that did not come from a cache inspector.
+    pub fn cache_action(&self, change: &ParameterChange) -> Option<UIAction> {
+        if change.parameter != CACHE_INSPECTOR_PARAMETER {
+            return None;
+        }
+        let NodeData::Json(JsonValue::Array(values)) = &change.value else {
+            return None;
+        };
+        let keys = values.iter().map(cache_key_from_json).collect::<Option<Vec<_>>>()?;
+        Some(UIAction::InvalidateCache { keys })
+    }
+    
     /// Render all elements and return any parameter changes
     pub fn render(&mut self, ui: &mut Ui) -> Vec<ParameterChange> {
         let mut changes = Vec::new();
@@ -622,8 +971,70 @@ impl ParameterUI {
     }
 }
 
+/// Encode cache keys as the value of a cache inspector click
+fn cache_keys_value<'a>(keys: impl IntoIterator<Item = &'a PluginCacheKey>) -> NodeData {
+    NodeData::Json(JsonValue::Array(keys.into_iter().map(cache_key_to_json).collect()))
+}
+
+// 64-bit IDs and hashes are strings, since JSON numbers lose precision past 2^53
+fn cache_key_to_json(key: &PluginCacheKey) -> JsonValue {
+    let optional = |value: Option<JsonValue>| value.unwrap_or(JsonValue::Null);
+    let mut object = BTreeMap::new();
+    object.insert("plugin_id".to_string(), JsonValue::from(key.plugin_id.as_str()));
+    object.insert("node_id".to_string(), JsonValue::from(key.node_id.0.to_string()));
+    object.insert("stage_id".to_string(), optional(key.stage_id.as_deref().map(JsonValue::from)));
+    object.insert("port_index".to_string(), JsonValue::from(key.port_index as i64));
+    object.insert(
+        "input_hash".to_string(),
+        optional(key.input_hash.map(|hash| JsonValue::from(format!("{:016x}", hash)))),
+    );
+    object.insert("frame".to_string(), optional(key.frame.map(|frame| JsonValue::from(frame as i64))));
+    JsonValue::Object(object)
+}
+
+fn cache_key_from_json(value: &JsonValue) -> Option<PluginCacheKey> {
+    let optional = |name: &str| value.get(name).filter(|v| !v.is_null());
+    Some(PluginCacheKey {
+        plugin_id: value.get("plugin_id")?.as_str()?.to_string(),
+        node_id: NodeId(value.get("node_id")?.as_str()?.parse().ok()?),
+        stage_id: match optional("stage_id") {
+            Some(stage) => Some(stage.as_str()?.to_string()),
+            None => None,
+        },
+        port_index: usize::try_from(value.get("port_index")?.as_i64()?).ok()?,
+        input_hash: match optional("input_hash") {
+            Some(hash) => Some(u64::from_str_radix(hash.as_str()?, 16).ok()?),
+            None => None,
+        },
+        frame: match optional("frame") {
+            Some(frame) => Some(i32::try_from(frame.as_i64()?).ok()?),
+            None => None,
+        },
+    })
+}
+
 impl Default for ParameterUI {
     fn default() -> Self {
         Self::new()
     }
-}
\ No newline at end of file
+}
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn cache_action_carries_the_clicked_keys() {
+        let key = PluginCacheKey::with_stage("usd", NodeId(u64::MAX), "load", 2)
+            .with_input_hash(u64::MAX - 1)
+            .with_frame(-5);
+        let plain = PluginCacheKey::new("usd", NodeId(3), 0);
+        let change = ParameterChange {
+            parameter: CACHE_INSPECTOR_PARAMETER.to_string(),
+            value: cache_keys_value([&key, &plain]),
+        };
+        match ParameterUI::new().cache_action(&change) {
+            Some(UIAction::InvalidateCache { keys }) => assert_eq!(keys, vec![key, plain]),
+            other => panic!("unexpected action: {:?}", other),
+        }
+    }
+}
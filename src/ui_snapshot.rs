@@ -0,0 +1,153 @@
+//! Parameter panels rendered from immutable snapshots
+//!
+//! `PluginNode::get_parameter_ui` borrows the node, so the host cannot draw a
+//! node's panel while the engine is cooking it on another thread. With the
+//! snapshot protocol, UI and execution run concurrently:
+//!
+//! 1. Between cooks the host takes a `ParameterSnapshot` of the node
+//!    (`PluginNodeHandle::safe_snapshot_parameters`).
+//! 2. The UI thread builds the panel from the snapshot alone
+//!    (`NodeFactory::snapshot_ui`), without touching the node.
+//! 3. Edits made in the panel go into a `ParameterEditQueue`.
+//! 4. Between cooks the host drains the queue into the node
+//!    (`PluginNodeHandle::safe_apply_edits`) and takes a new snapshot.
+//!
+//! Node types whose factory returns None from `snapshot_ui` keep using
+//! `get_parameter_ui`, and the host must not cook them while drawing.
+
+use crate::{NodeData, ParameterChange, PluginError, UIAction, ValidationError};
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+
+/// Immutable copy of a node's parameter values
+#[derive(Debug, Clone, Default)]
+pub struct ParameterSnapshot {
+    /// Parameter values by name
+    pub values: HashMap<String, NodeData>,
+    /// Host counter increased on every snapshot of the node, to detect stale panels
+    pub version: u64,
+}
+
+impl ParameterSnapshot {
+    /// Create a snapshot from parameter values
+    pub fn new(values: HashMap<String, NodeData>, version: u64) -> Self {
+        Self { values, version }
+    }
+
+    /// Value of a parameter
+    pub fn get(&self, name: &str) -> Option<&NodeData> {
+        self.values.get(name)
+    }
+
+    /// Snapshot with queued edits applied, so the panel shows them before the next cook
+    pub fn with_pending(&self, queue: &ParameterEditQueue) -> Self {
+        let mut snapshot = self.clone();
+        for change in queue.pending_changes() {
+            snapshot.values.insert(change.parameter, change.value);
+        }
+        snapshot
+    }
+}
+
+/// Edit made in a snapshot-based panel, waiting to be applied to the node
+#[derive(Debug, Clone)]
+pub enum QueuedEdit {
+    /// Set a parameter
+    Parameter(ParameterChange),
+    /// Forward a UI action to `PluginNode::handle_ui_action`
+    Action(UIAction),
+}
+
+/// Outcome of applying queued edits or sidecar parameters
+///
+/// Returned by `PluginNodeHandle::safe_apply_edits` and `safe_import_parameters`.
+#[derive(Debug, Default)]
+pub struct AppliedEdits {
+    /// Parameter changes rejected by validation, with the reason
+    pub rejected: Vec<(String, ValidationError)>,
+    /// Edits the node failed to apply (e.g. it panicked), in order
+    pub failed: Vec<PluginError>,
+}
+
+impl AppliedEdits {
+    /// Check if every edit was applied
+    pub fn is_ok(&self) -> bool {
+        self.rejected.is_empty() && self.failed.is_empty()
+    }
+}
+
+/// Queue of panel edits applied between cooks
+///
+/// Clones share the same queue, so the UI thread can push while the host
+/// holds another clone to drain.
+#[derive(Debug, Clone, Default)]
+pub struct ParameterEditQueue {
+    edits: Arc<Mutex<Vec<QueuedEdit>>>,
+}
+
+impl ParameterEditQueue {
+    /// Create an empty queue
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Queue a parameter change
+    ///
+    /// Replaces a queued change of the same parameter, so dragging a slider
+    /// applies only the latest value.
+    pub fn set(&self, parameter: impl Into<String>, value: NodeData) {
+        let parameter = parameter.into();
+        let mut edits = self.lock();
+        // Only coalesce with changes queued after the last action
+        let queued = edits
+            .iter_mut()
+            .rev()
+            .map_while(|edit| match edit {
+                QueuedEdit::Parameter(change) => Some(change),
+                QueuedEdit::Action(_) => None,
+            })
+            .find(|change| change.parameter == parameter);
+        match queued {
+            Some(change) => change.value = value,
+            None => edits.push(QueuedEdit::Parameter(ParameterChange { parameter, value })),
+        }
+    }
+
+    /// Queue a panel interaction
+    ///
+    /// Parameter changes and file selections are queued as parameter changes.
+    /// Other actions are forwarded to the node in order with the changes
+    /// around them.
+    pub fn push_action(&self, action: UIAction) {
+        match action {
+            UIAction::ParameterChanged { parameter, value } => self.set(parameter, value),
+            UIAction::FileSelected { parameter, path } => self.set(parameter, NodeData::String(path)),
+            other => self.lock().push(QueuedEdit::Action(other)),
+        }
+    }
+
+    /// Queued parameter changes, in order
+    pub fn pending_changes(&self) -> Vec<ParameterChange> {
+        self.lock()
+            .iter()
+            .filter_map(|edit| match edit {
+                QueuedEdit::Parameter(change) => Some(change.clone()),
+                QueuedEdit::Action(_) => None,
+            })
+            .collect()
+    }
+
+    /// Check if nothing is queued
+    pub fn is_empty(&self) -> bool {
+        self.lock().is_empty()
+    }
+
+    /// Take all queued edits, in order
+    pub fn drain(&self) -> Vec<QueuedEdit> {
+        std::mem::take(&mut *self.lock())
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, Vec<QueuedEdit>> {
+        self.edits.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
@@ -0,0 +1,111 @@
+//! Units for scalar parameters and ports
+//!
+//! Floats carrying meters vs centimeters vs degrees silently mismatch. Ports and
+//! float parameters can declare a `Unit`; the UI displays values with their unit
+//! (angles stored in radians are shown in degrees) and nodes convert values with
+//! `Unit::convert` at process time.
+
+use serde::{Deserialize, Serialize};
+
+/// Physical quantity measured by a unit
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum UnitKind {
+    Length,
+    Angle,
+    Time,
+    Ratio,
+}
+
+/// Unit of a scalar value
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum Unit {
+    Millimeters,
+    Centimeters,
+    Meters,
+    Kilometers,
+    Inches,
+    Feet,
+    Degrees,
+    Radians,
+    Milliseconds,
+    Seconds,
+    /// Fraction in 0-1
+    Fraction,
+    /// Percentage in 0-100
+    Percent,
+}
+
+impl Unit {
+    /// Get the kind of quantity this unit measures
+    pub fn kind(&self) -> UnitKind {
+        match self {
+            Unit::Millimeters | Unit::Centimeters | Unit::Meters | Unit::Kilometers | Unit::Inches | Unit::Feet => {
+                UnitKind::Length
+            }
+            Unit::Degrees | Unit::Radians => UnitKind::Angle,
+            Unit::Milliseconds | Unit::Seconds => UnitKind::Time,
+            Unit::Fraction | Unit::Percent => UnitKind::Ratio,
+        }
+    }
+
+    /// Get the short suffix shown in the UI
+    pub fn suffix(&self) -> &'static str {
+        match self {
+            Unit::Millimeters => "mm",
+            Unit::Centimeters => "cm",
+            Unit::Meters => "m",
+            Unit::Kilometers => "km",
+            Unit::Inches => "in",
+            Unit::Feet => "ft",
+            Unit::Degrees => "°",
+            Unit::Radians => "rad",
+            Unit::Milliseconds => "ms",
+            Unit::Seconds => "s",
+            Unit::Fraction => "",
+            Unit::Percent => "%",
+        }
+    }
+
+    /// Get the unit values are displayed in (radians display as degrees)
+    pub fn display_unit(&self) -> Unit {
+        match self {
+            Unit::Radians => Unit::Degrees,
+            other => *other,
+        }
+    }
+
+    /// Scale factor to the base unit of this kind (meters, radians, seconds, fraction)
+    fn to_base(self) -> f32 {
+        match self {
+            Unit::Millimeters => 0.001,
+            Unit::Centimeters => 0.01,
+            Unit::Meters => 1.0,
+            Unit::Kilometers => 1000.0,
+            Unit::Inches => 0.0254,
+            Unit::Feet => 0.3048,
+            Unit::Degrees => std::f32::consts::PI / 180.0,
+            Unit::Radians => 1.0,
+            Unit::Milliseconds => 0.001,
+            Unit::Seconds => 1.0,
+            Unit::Fraction => 1.0,
+            Unit::Percent => 0.01,
+        }
+    }
+
+    /// Convert a value from this unit to another of the same kind
+    ///
+    /// Returns None when the units measure different quantities.
+    pub fn convert(&self, value: f32, to: Unit) -> Option<f32> {
+        if self.kind() != to.kind() {
+            return None;
+        }
+        Some(value * self.to_base() / to.to_base())
+    }
+
+    /// Format a value stored in this unit for display
+    pub fn format(&self, value: f32, decimals: usize) -> String {
+        let display = self.display_unit();
+        let shown = self.convert(value, display).unwrap_or(value);
+        format!("{:.*}{}", decimals, shown, display.suffix())
+    }
+}
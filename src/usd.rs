@@ -0,0 +1,436 @@
+//! USD helpers for plugins
+//!
+//! This module provides validated builders for USD scene data and the lazy
+//! payload loading contract.
+//!
+//! Huge stages should not be fully loaded up front. A USD reader plugin can emit
+//! lightweight `USDScenegraphMetadata` whose prims carry `USDPayloadRef` handles,
+//! and the host resolves the heavy geometry on demand (e.g. when a branch is
+//! expanded in the tree panel) through a `USDPayloadResolver`.
+
+use crate::{
+    MotionSample, PluginError, SubdivisionSurface, UVInterpolation, UVSet, USDLight, USDMaterial, USDMeshGeometry, USDPrimvar,
+    USDSceneData, ValidationError,
+};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// Identity transform matrix
+pub const IDENTITY_TRANSFORM: [[f32; 4]; 4] = [
+    [1.0, 0.0, 0.0, 0.0],
+    [0.0, 1.0, 0.0, 0.0],
+    [0.0, 0.0, 1.0, 0.0],
+    [0.0, 0.0, 0.0, 1.0],
+];
+
+/// Validated builder for `USDMeshGeometry`
+///
+/// ```
+/// # use nodle_plugin_sdk::USDMeshGeometryBuilder;
+/// # fn main() -> Result<(), nodle_plugin_sdk::ValidationError> {
+/// let mesh = USDMeshGeometryBuilder::new("/World/Triangle")
+///     .vertices(vec![[0.0, 0.0, 0.0], [1.0, 0.0, 0.0], [0.0, 1.0, 0.0]])
+///     .indices(vec![0, 1, 2])
+///     .normals(vec![[0.0, 0.0, 1.0]; 3])
+///     .build()?;
+/// # assert_eq!(mesh.prim_path, "/World/Triangle");
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone)]
+pub struct USDMeshGeometryBuilder {
+    prim_path: String,
+    display_name: Option<String>,
+    vertices: Vec<[f32; 3]>,
+    indices: Vec<u32>,
+    normals: Vec<[f32; 3]>,
+    uvs: Vec<[f32; 2]>,
+    uv_sets: Vec<UVSet>,
+    vertex_colors: Vec<[f32; 3]>,
+    transform: [[f32; 4]; 4],
+    material_path: Option<String>,
+    primvars: HashMap<String, USDPrimvar>,
+    motion_samples: Vec<MotionSample>,
+    subdivision: Option<SubdivisionSurface>,
+}
+
+impl USDMeshGeometryBuilder {
+    /// Start building a mesh for a prim path
+    pub fn new(prim_path: impl Into<String>) -> Self {
+        Self {
+            prim_path: prim_path.into(),
+            display_name: None,
+            vertices: Vec::new(),
+            indices: Vec::new(),
+            normals: Vec::new(),
+            uvs: Vec::new(),
+            uv_sets: Vec::new(),
+            vertex_colors: Vec::new(),
+            transform: IDENTITY_TRANSFORM,
+            material_path: None,
+            primvars: HashMap::new(),
+            motion_samples: Vec::new(),
+            subdivision: None,
+        }
+    }
+
+    /// Set the display name (defaults to the last prim path component)
+    pub fn display_name(mut self, name: impl Into<String>) -> Self {
+        self.display_name = Some(name.into());
+        self
+    }
+
+    /// Set vertex positions
+    pub fn vertices(mut self, vertices: Vec<[f32; 3]>) -> Self {
+        self.vertices = vertices;
+        self
+    }
+
+    /// Set triangle indices
+    pub fn indices(mut self, indices: Vec<u32>) -> Self {
+        self.indices = indices;
+        self
+    }
+
+    /// Set per-vertex normals
+    pub fn normals(mut self, normals: Vec<[f32; 3]>) -> Self {
+        self.normals = normals;
+        self
+    }
+
+    /// Set per-vertex UV coordinates
+    pub fn uvs(mut self, uvs: Vec<[f32; 2]>) -> Self {
+        self.uvs = uvs;
+        self
+    }
+
+    /// Add a named UV set
+    pub fn uv_set(mut self, set: UVSet) -> Self {
+        self.uv_sets.push(set);
+        self
+    }
+
+    /// Set per-vertex colors
+    pub fn vertex_colors(mut self, colors: Vec<[f32; 3]>) -> Self {
+        self.vertex_colors = colors;
+        self
+    }
+
+    /// Set the transform matrix (defaults to identity)
+    pub fn transform(mut self, transform: [[f32; 4]; 4]) -> Self {
+        self.transform = transform;
+        self
+    }
+
+    /// Bind a material
+    pub fn material_path(mut self, path: impl Into<String>) -> Self {
+        self.material_path = Some(path.into());
+        self
+    }
+
+    /// Add a primvar
+    pub fn primvar(mut self, primvar: USDPrimvar) -> Self {
+        self.primvars.insert(primvar.name.clone(), primvar);
+        self
+    }
+
+    /// Add a motion blur sample
+    pub fn motion_sample(mut self, sample: MotionSample) -> Self {
+        self.motion_samples.push(sample);
+        self
+    }
+
+    /// Set subdivision metadata (with the original polygons as control cage)
+    pub fn subdivision(mut self, subdivision: SubdivisionSurface) -> Self {
+        self.subdivision = Some(subdivision);
+        self
+    }
+
+    /// Validate and build the mesh
+    pub fn build(mut self) -> Result<USDMeshGeometry, ValidationError> {
+        if self.prim_path.is_empty() {
+            return Err(ValidationError::MissingField("prim_path".to_string()));
+        }
+        if self.vertices.is_empty() {
+            return Err(ValidationError::MissingField("vertices".to_string()));
+        }
+        if self.indices.len() % 3 != 0 {
+            return Err(ValidationError::InvalidIndexCount(self.indices.len()));
+        }
+
+        let vertex_count = self.vertices.len();
+        if let Some(&index) = self.indices.iter().find(|&&i| i as usize >= vertex_count) {
+            return Err(ValidationError::IndexOutOfBounds { index, vertex_count });
+        }
+        check_attribute_length("normals", self.normals.len(), vertex_count)?;
+        check_attribute_length("uvs", self.uvs.len(), vertex_count)?;
+        check_attribute_length("vertex_colors", self.vertex_colors.len(), vertex_count)?;
+        for set in &self.uv_sets {
+            let expected = match set.interpolation {
+                UVInterpolation::Vertex => vertex_count,
+                UVInterpolation::FaceVarying => self.indices.len(),
+            };
+            check_attribute_length(&format!("uv_sets.{}", set.name), set.uvs.len(), expected)?;
+        }
+
+        if self.vertices.iter().flatten().any(|v| !v.is_finite()) {
+            return Err(ValidationError::InvalidValue("non-finite vertex position".to_string()));
+        }
+        if self.transform.iter().flatten().any(|v| !v.is_finite()) {
+            return Err(ValidationError::InvalidValue("non-finite transform".to_string()));
+        }
+        for sample in &self.motion_samples {
+            check_attribute_length("motion_samples.positions", sample.positions.len(), vertex_count)?;
+        }
+        MotionSample::sort_samples(&mut self.motion_samples);
+        if let Some(subdivision) = &self.subdivision {
+            subdivision
+                .validate(vertex_count)
+                .map_err(|e| ValidationError::InvalidValue(format!("subdivision: {}", e)))?;
+        }
+
+        let display_name = self.display_name.unwrap_or_else(|| {
+            self.prim_path.rsplit('/').next().unwrap_or(&self.prim_path).to_string()
+        });
+
+        Ok(USDMeshGeometry {
+            prim_path: self.prim_path,
+            display_name,
+            vertices: self.vertices,
+            indices: self.indices,
+            normals: self.normals,
+            uvs: self.uvs,
+            uv_sets: self.uv_sets,
+            vertex_colors: self.vertex_colors,
+            transform: self.transform,
+            material_path: self.material_path,
+            primvars: self.primvars,
+            motion_samples: self.motion_samples,
+            subdivision: self.subdivision,
+        })
+    }
+}
+
+/// Optional per-vertex attributes must be empty or match the vertex count
+fn check_attribute_length(field: &str, actual: usize, expected: usize) -> Result<(), ValidationError> {
+    if actual != 0 && actual != expected {
+        return Err(ValidationError::LengthMismatch {
+            field: field.to_string(),
+            expected,
+            actual,
+        });
+    }
+    Ok(())
+}
+
+/// Validated builder for `USDSceneData`
+#[derive(Debug, Clone)]
+pub struct USDSceneDataBuilder {
+    up_axis: String,
+    meshes: Vec<USDMeshGeometry>,
+    lights: Vec<USDLight>,
+    materials: Vec<USDMaterial>,
+    bounds: Option<([f32; 3], [f32; 3])>,
+}
+
+impl USDSceneDataBuilder {
+    /// Start building a Y-up scene
+    pub fn new() -> Self {
+        Self {
+            up_axis: "Y".to_string(),
+            meshes: Vec::new(),
+            lights: Vec::new(),
+            materials: Vec::new(),
+            bounds: None,
+        }
+    }
+
+    /// Set the up axis ("X", "Y" or "Z")
+    pub fn up_axis(mut self, up_axis: impl Into<String>) -> Self {
+        self.up_axis = up_axis.into();
+        self
+    }
+
+    /// Add a mesh
+    pub fn mesh(mut self, mesh: USDMeshGeometry) -> Self {
+        self.meshes.push(mesh);
+        self
+    }
+
+    /// Add a light
+    pub fn light(mut self, light: USDLight) -> Self {
+        self.lights.push(light);
+        self
+    }
+
+    /// Add a material
+    pub fn material(mut self, material: USDMaterial) -> Self {
+        self.materials.push(material);
+        self
+    }
+
+    /// Set explicit scene bounds (computed from mesh vertices if not set)
+    pub fn bounds(mut self, min: [f32; 3], max: [f32; 3]) -> Self {
+        self.bounds = Some((min, max));
+        self
+    }
+
+    /// Validate and build the scene
+    pub fn build(self) -> Result<USDSceneData, ValidationError> {
+        if !matches!(self.up_axis.as_str(), "X" | "Y" | "Z") {
+            return Err(ValidationError::InvalidValue(format!("up axis '{}'", self.up_axis)));
+        }
+
+        for mesh in &self.meshes {
+            if let Some(material_path) = &mesh.material_path {
+                if !self.materials.iter().any(|m| &m.prim_path == material_path) {
+                    return Err(ValidationError::InvalidValue(format!(
+                        "mesh '{}' is bound to unknown material '{}'",
+                        mesh.prim_path, material_path
+                    )));
+                }
+            }
+        }
+
+        if let Some((min, max)) = self.bounds {
+            if (0..3).any(|i| min[i] > max[i]) {
+                return Err(ValidationError::InvalidValue("bounds min exceeds max".to_string()));
+            }
+        }
+
+        let bounds = self.bounds.or_else(|| compute_bounds(&self.meshes));
+
+        Ok(USDSceneData {
+            up_axis: self.up_axis,
+            meshes: self.meshes,
+            lights: self.lights,
+            materials: self.materials,
+            bounds,
+        })
+    }
+}
+
+impl Default for USDSceneDataBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Compute the scene-space bounds of all mesh vertices, applying each mesh's transform
+fn compute_bounds(meshes: &[USDMeshGeometry]) -> Option<([f32; 3], [f32; 3])> {
+    let mut vertices = meshes
+        .iter()
+        .flat_map(|m| m.vertices.iter().map(|v| crate::transform::transform_point(&m.transform, *v)));
+    let first = vertices.next()?;
+    Some(vertices.fold((first, first), |(mut min, mut max), v| {
+        for i in 0..3 {
+            min[i] = min[i].min(v[i]);
+            max[i] = max[i].max(v[i]);
+        }
+        (min, max)
+    }))
+}
+
+/// Handle to an unloaded USD payload or reference
+#[derive(Debug, Clone, Hash, PartialEq, Eq, Serialize, Deserialize)]
+pub struct USDPayloadRef {
+    /// Stage identifier (usually the root layer path)
+    pub stage_id: String,
+    /// Prim path the payload is attached to
+    pub prim_path: String,
+    /// Asset path of the payload layer, if known
+    pub asset_path: Option<String>,
+    /// Kind of composition arc
+    pub kind: USDPayloadKind,
+}
+
+/// Kind of composition arc a payload handle refers to
+#[derive(Debug, Clone, Copy, Hash, PartialEq, Eq, Serialize, Deserialize)]
+pub enum USDPayloadKind {
+    /// USD payload arc (deferred loading)
+    Payload,
+    /// USD reference arc
+    Reference,
+}
+
+impl USDPayloadRef {
+    /// Create a payload handle for a prim
+    pub fn payload(stage_id: impl Into<String>, prim_path: impl Into<String>) -> Self {
+        Self {
+            stage_id: stage_id.into(),
+            prim_path: prim_path.into(),
+            asset_path: None,
+            kind: USDPayloadKind::Payload,
+        }
+    }
+
+    /// Create a reference handle for a prim
+    pub fn reference(stage_id: impl Into<String>, prim_path: impl Into<String>) -> Self {
+        Self {
+            stage_id: stage_id.into(),
+            prim_path: prim_path.into(),
+            asset_path: None,
+            kind: USDPayloadKind::Reference,
+        }
+    }
+
+    /// Set the asset path of the payload layer
+    pub fn with_asset_path(mut self, asset_path: impl Into<String>) -> Self {
+        self.asset_path = Some(asset_path.into());
+        self
+    }
+}
+
+/// Loading state of a payload
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum USDPayloadState {
+    /// Not loaded yet
+    Unloaded,
+    /// Load in progress (0.0 - 1.0 if known)
+    Loading(Option<f32>),
+    /// Loaded and available
+    Loaded,
+    /// Loading failed
+    Failed(String),
+}
+
+/// Resolver for USD payloads
+///
+/// USD reader plugins implement this trait and hand it to the host through
+/// `PluginNode::get_payload_resolver`. The host decides when payloads are needed
+/// and calls `resolve_payload`; the plugin never has to load everything up front.
+pub trait USDPayloadResolver: Send + Sync {
+    /// Load the geometry behind a payload handle
+    fn resolve_payload(&mut self, payload: &USDPayloadRef) -> Result<USDSceneData, PluginError>;
+
+    /// Release any resources held for a payload (called when a branch is collapsed)
+    fn unload_payload(&mut self, _payload: &USDPayloadRef) {
+        // Default: nothing to release
+    }
+
+    /// Get the loading state of a payload
+    fn payload_state(&self, payload: &USDPayloadRef) -> USDPayloadState;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn computed_bounds_apply_mesh_transforms() {
+        let mut translate = IDENTITY_TRANSFORM;
+        translate[3] = [10.0, 0.0, 0.0, 1.0];
+        let moved = USDMeshGeometryBuilder::new("/moved")
+            .vertices(vec![[0.0, 0.0, 0.0], [1.0, 1.0, 1.0]])
+            .transform(translate)
+            .build()
+            .unwrap();
+        let origin = USDMeshGeometryBuilder::new("/origin")
+            .vertices(vec![[-1.0, 0.0, 0.0]])
+            .build()
+            .unwrap();
+
+        let scene = USDSceneDataBuilder::new().mesh(moved).mesh(origin).build().unwrap();
+        assert_eq!(scene.bounds, Some(([-1.0, 0.0, 0.0], [11.0, 1.0, 1.0])));
+    }
+}
@@ -0,0 +1,68 @@
+//! Persistent user notes and metadata on nodes
+//!
+//! Nodes that embed a `NodeUserData` and return it from
+//! `PluginNode::user_data`/`user_data_mut` get free-form notes and arbitrary
+//! tagged values (shot, asset, owner, ...) that the host saves with the project
+//! and restores on load. The host shows the notes field in the parameter panel.
+
+use crate::NodeData;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// Standard user data keys shared by pipeline plugins
+pub mod keys {
+    /// Shot name or code
+    pub const SHOT: &str = "shot";
+    /// Asset name
+    pub const ASSET: &str = "asset";
+    /// Responsible artist or department
+    pub const OWNER: &str = "owner";
+}
+
+/// User-editable notes and metadata persisted with a node
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct NodeUserData {
+    /// Free-form notes shown in the parameter panel
+    #[serde(default)]
+    pub notes: String,
+    /// Arbitrary tagged values, ordered by key
+    #[serde(default)]
+    pub entries: BTreeMap<String, NodeData>,
+}
+
+impl NodeUserData {
+    /// Create empty user data
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Get a value by key
+    pub fn get(&self, key: &str) -> Option<&NodeData> {
+        self.entries.get(key)
+    }
+
+    /// Set a value, returning the previous one
+    pub fn set(&mut self, key: impl Into<String>, value: NodeData) -> Option<NodeData> {
+        self.entries.insert(key.into(), value)
+    }
+
+    /// Remove a value
+    pub fn remove(&mut self, key: &str) -> Option<NodeData> {
+        self.entries.remove(key)
+    }
+
+    /// Get a string value by key (e.g. `keys::SHOT`)
+    pub fn get_string(&self, key: &str) -> Option<&str> {
+        self.get(key).and_then(|value| value.as_string())
+    }
+
+    /// Iterate over all keys
+    pub fn keys(&self) -> impl Iterator<Item = &str> {
+        self.entries.keys().map(String::as_str)
+    }
+
+    /// Check if there are no notes and no entries
+    pub fn is_empty(&self) -> bool {
+        self.notes.is_empty() && self.entries.is_empty()
+    }
+}
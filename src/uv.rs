@@ -0,0 +1,319 @@
+//! Named UV sets
+//!
+//! `GeometryData::uvs` and `USDMeshGeometry::uvs` hold the primary set ("st" in
+//! USD). Additional channels (lightmaps, per-layer projections) live in
+//! `uv_sets`, and materials choose a set per texture slot through
+//! `MaterialData::uv_set_bindings`. UDIM layouts are detected from the UV
+//! range, with tiles numbered 1001 + u + 10 * v.
+//!
+//! Tiled texture sets are described by `UdimTexture`: a path pattern containing
+//! `<UDIM>` or `<UVTILE>` plus the tiles found on disk.
+
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+
+/// Name of the primary UV set (matches USD's `st` primvar)
+pub const PRIMARY_UV_SET: &str = "st";
+
+/// Conventional name for a lightmap UV set
+pub const LIGHTMAP_UV_SET: &str = "lightmap";
+
+/// How UV values map onto the mesh
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum UVInterpolation {
+    /// One value per vertex
+    #[default]
+    Vertex,
+    /// One value per face-vertex (entry in `indices`)
+    FaceVarying,
+}
+
+/// A named texture coordinate channel
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct UVSet {
+    pub name: String,
+    pub uvs: Vec<[f32; 2]>,
+    #[serde(default)]
+    pub interpolation: UVInterpolation,
+}
+
+impl UVSet {
+    /// Create a per-vertex UV set
+    pub fn new(name: impl Into<String>, uvs: Vec<[f32; 2]>) -> Self {
+        Self {
+            name: name.into(),
+            uvs,
+            interpolation: UVInterpolation::Vertex,
+        }
+    }
+
+    /// Create a face-varying UV set
+    pub fn face_varying(name: impl Into<String>, uvs: Vec<[f32; 2]>) -> Self {
+        Self {
+            name: name.into(),
+            uvs,
+            interpolation: UVInterpolation::FaceVarying,
+        }
+    }
+
+    /// Check if the UVs extend past the 0-1 tile (UDIM layout)
+    pub fn is_udim(&self) -> bool {
+        self.uvs.iter().any(|uv| udim_tile(*uv) != Some(1001))
+    }
+
+    /// Sorted list of UDIM tiles covered by the UVs
+    ///
+    /// UVs outside the UDIM range (see `udim_tile`) are skipped.
+    pub fn udim_tiles(&self) -> Vec<u32> {
+        let mut tiles: Vec<u32> = self.uvs.iter().filter_map(|uv| udim_tile(*uv)).collect();
+        tiles.sort_unstable();
+        tiles.dedup();
+        tiles
+    }
+}
+
+/// Highest tile number a four-digit UDIM can name
+const MAX_UDIM_TILE: u32 = 9999;
+
+/// UDIM tile number of a UV coordinate (1001 for the 0-1 tile)
+///
+/// Values exactly on an upper tile edge count toward the lower tile. Returns
+/// None for non-finite coordinates and for V values above the last UDIM row.
+pub fn udim_tile(uv: [f32; 2]) -> Option<u32> {
+    if !uv[0].is_finite() || !uv[1].is_finite() {
+        return None;
+    }
+    // Float-to-int casts saturate, so clamp the row before scaling it
+    let index = |value: f32| (value.ceil() as i64).saturating_sub(1).clamp(0, i64::from(MAX_UDIM_TILE));
+    let tile = 1001 + index(uv[0]).min(9) + 10 * index(uv[1]);
+    u32::try_from(tile).ok().filter(|tile| *tile <= MAX_UDIM_TILE)
+}
+
+/// Check if a texture path contains a UDIM tile token
+pub fn is_udim_pattern(path: &str) -> bool {
+    path.contains("<UDIM>") || path.contains("<UVTILE>")
+}
+
+/// Substitute a tile number into a texture path pattern
+///
+/// Supports the `<UDIM>` token (Mari/USD) and `<UVTILE>` (`u1_v1` style).
+pub fn resolve_udim_path(pattern: &str, tile: u32) -> String {
+    let index = tile.saturating_sub(1001);
+    pattern
+        .replace("<UDIM>", &tile.to_string())
+        .replace("<UVTILE>", &format!("u{}_v{}", index % 10 + 1, index / 10 + 1))
+}
+
+/// One tile of a UDIM texture set
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct UdimTile {
+    /// Tile number (1001, 1002, ...)
+    pub tile: u32,
+    /// Resolved file path
+    pub path: String,
+    /// Tile resolution (0 if not yet known)
+    #[serde(default)]
+    pub width: u32,
+    #[serde(default)]
+    pub height: u32,
+}
+
+/// Texture reference spanning a set of UDIM tiles
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct UdimTexture {
+    /// Path pattern with a `<UDIM>` or `<UVTILE>` token
+    pub pattern: String,
+    /// Known tiles, sorted by tile number
+    #[serde(default)]
+    pub tiles: Vec<UdimTile>,
+}
+
+impl UdimTexture {
+    /// Create a texture reference with no known tiles
+    pub fn new(pattern: impl Into<String>) -> Self {
+        Self {
+            pattern: pattern.into(),
+            tiles: Vec::new(),
+        }
+    }
+
+    /// Create a texture reference and discover its tiles on disk
+    pub fn discover(pattern: impl Into<String>) -> Result<Self, String> {
+        let mut texture = Self::new(pattern);
+        texture.discover_tiles()?;
+        Ok(texture)
+    }
+
+    /// Scan the pattern's directory for existing tiles, replacing the tile list
+    ///
+    /// Resolutions are left at 0; readers fill them in with `set_tile_resolution`.
+    pub fn discover_tiles(&mut self) -> Result<(), String> {
+        let pattern = self.pattern.clone();
+        let path = Path::new(&pattern);
+        let dir = match path.parent() {
+            Some(dir) if !dir.as_os_str().is_empty() => dir,
+            _ => Path::new("."),
+        };
+        let file_pattern = path
+            .file_name()
+            .and_then(|name| name.to_str())
+            .ok_or_else(|| format!("Invalid UDIM pattern: {}", pattern))?;
+        let entries = std::fs::read_dir(dir)
+            .map_err(|e| format!("Failed to read directory {}: {}", dir.display(), e))?;
+
+        self.tiles.clear();
+        for entry in entries.flatten() {
+            let name = entry.file_name();
+            let Some(tile) = name.to_str().and_then(|name| match_tile(file_pattern, name)) else {
+                continue;
+            };
+            self.add_tile(tile, entry.path().to_string_lossy().into_owned());
+        }
+        Ok(())
+    }
+
+    /// Add or replace a tile, keeping the list sorted
+    pub fn add_tile(&mut self, tile: u32, path: impl Into<String>) {
+        let entry = UdimTile {
+            tile,
+            path: path.into(),
+            width: 0,
+            height: 0,
+        };
+        match self.tiles.binary_search_by_key(&tile, |t| t.tile) {
+            Ok(index) => self.tiles[index] = entry,
+            Err(index) => self.tiles.insert(index, entry),
+        }
+    }
+
+    /// Record the resolution of a known tile
+    pub fn set_tile_resolution(&mut self, tile: u32, width: u32, height: u32) -> bool {
+        match self.tiles.iter_mut().find(|t| t.tile == tile) {
+            Some(entry) => {
+                entry.width = width;
+                entry.height = height;
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Get a known tile
+    pub fn tile(&self, tile: u32) -> Option<&UdimTile> {
+        self.tiles
+            .binary_search_by_key(&tile, |t| t.tile)
+            .ok()
+            .map(|index| &self.tiles[index])
+    }
+
+    /// Resolve the pattern for a tile (whether or not it exists)
+    pub fn tile_path(&self, tile: u32) -> String {
+        resolve_udim_path(&self.pattern, tile)
+    }
+
+    /// Get the tile file covering a UV coordinate, if present
+    pub fn path_for_uv(&self, uv: [f32; 2]) -> Option<&str> {
+        self.tile(udim_tile(uv)?).map(|t| t.path.as_str())
+    }
+
+    /// Largest known tile resolution (for allocating texture caches)
+    pub fn max_resolution(&self) -> (u32, u32) {
+        self.tiles
+            .iter()
+            .fold((0, 0), |(w, h), t| (w.max(t.width), h.max(t.height)))
+    }
+
+    /// Tiles needed by a UV set that are missing from this texture
+    pub fn missing_tiles(&self, uv_set: &UVSet) -> Vec<u32> {
+        uv_set
+            .udim_tiles()
+            .into_iter()
+            .filter(|tile| self.tile(*tile).is_none())
+            .collect()
+    }
+}
+
+/// Match a file name against a pattern, returning the tile number
+fn match_tile(pattern: &str, name: &str) -> Option<u32> {
+    let (token, start) = ["<UDIM>", "<UVTILE>"]
+        .iter()
+        .find_map(|token| pattern.find(token).map(|start| (*token, start)))?;
+    let prefix = &pattern[..start];
+    let suffix = &pattern[start + token.len()..];
+    let middle = name.strip_prefix(prefix)?.strip_suffix(suffix)?;
+
+    if token == "<UDIM>" {
+        if middle.len() != 4 || !middle.bytes().all(|b| b.is_ascii_digit()) {
+            return None;
+        }
+        middle.parse().ok().filter(|tile| *tile >= 1001)
+    } else {
+        let (u, v) = middle.strip_prefix('u')?.split_once("_v")?;
+        let (u, v): (u32, u32) = (u.parse().ok()?, v.parse().ok()?);
+        if !(1..=10).contains(&u) || v < 1 {
+            return None;
+        }
+        (v - 1)
+            .checked_mul(10)
+            .and_then(|row| row.checked_add(1000 + u))
+            .filter(|tile| *tile <= MAX_UDIM_TILE)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn tiles_for_uv_coordinates() {
+        assert_eq!(udim_tile([0.5, 0.5]), Some(1001));
+        assert_eq!(udim_tile([1.0, 1.0]), Some(1001));
+        assert_eq!(udim_tile([1.5, 0.5]), Some(1002));
+        assert_eq!(udim_tile([0.5, 1.5]), Some(1011));
+        // U is clamped to the ten columns, negative values to the first tile
+        assert_eq!(udim_tile([12.5, 0.5]), Some(1010));
+        assert_eq!(udim_tile([-3.0, -3.0]), Some(1001));
+    }
+
+    #[test]
+    fn out_of_range_uvs_have_no_tile() {
+        assert_eq!(udim_tile([0.5, 899.5]), Some(9991));
+        assert_eq!(udim_tile([0.5, 900.5]), None);
+        assert_eq!(udim_tile([0.5, 1.0e30]), None);
+        assert_eq!(udim_tile([f32::NAN, 0.5]), None);
+        assert_eq!(udim_tile([0.5, f32::INFINITY]), None);
+
+        let uvs = UVSet::new("st", vec![[0.5, 0.5], [1.5, 0.5], [0.5, 1.0e30], [0.5, 0.5]]);
+        assert_eq!(uvs.udim_tiles(), vec![1001, 1002]);
+        assert!(uvs.is_udim());
+    }
+
+    #[test]
+    fn match_tile_names() {
+        assert_eq!(match_tile("color.<UDIM>.exr", "color.1012.exr"), Some(1012));
+        assert_eq!(match_tile("color.<UDIM>.exr", "color.0999.exr"), None);
+        assert_eq!(match_tile("color.<UDIM>.exr", "color.101.exr"), None);
+        assert_eq!(match_tile("color.<UDIM>.exr", "other.1001.exr"), None);
+        assert_eq!(match_tile("color_<UVTILE>.tx", "color_u2_v3.tx"), Some(1022));
+        assert_eq!(match_tile("color_<UVTILE>.tx", "color_u11_v1.tx"), None);
+        assert_eq!(match_tile("color_<UVTILE>.tx", "color_u1_v0.tx"), None);
+        assert_eq!(match_tile("color_<UVTILE>.tx", "color_u1_v4294967295.tx"), None);
+        assert_eq!(match_tile("color_<UVTILE>.tx", "color_u1_v1000.tx"), None);
+    }
+
+    #[test]
+    fn resolve_paths_and_lookup() {
+        assert_eq!(resolve_udim_path("c.<UDIM>.exr", 1022), "c.1022.exr");
+        assert_eq!(resolve_udim_path("c_<UVTILE>.exr", 1022), "c_u2_v3.exr");
+
+        let mut texture = UdimTexture::new("c.<UDIM>.exr");
+        texture.add_tile(1002, "c.1002.exr");
+        texture.add_tile(1001, "c.1001.exr");
+        assert_eq!(texture.tiles.iter().map(|t| t.tile).collect::<Vec<_>>(), vec![1001, 1002]);
+        assert_eq!(texture.path_for_uv([1.5, 0.5]), Some("c.1002.exr"));
+        assert_eq!(texture.path_for_uv([0.5, f32::NAN]), None);
+
+        let uvs = UVSet::new("st", vec![[0.5, 0.5], [2.5, 0.5]]);
+        assert_eq!(texture.missing_tiles(&uvs), vec![1003]);
+    }
+}
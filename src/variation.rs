@@ -0,0 +1,294 @@
+//! Parameter variation sets for wedging
+//!
+//! Look development often cooks one node many times with slightly different
+//! parameters and compares the results side by side (a "wedge"). A
+//! `VariationSet` defines ranges over chosen parameters and generates labeled,
+//! reproducible variations. `cook_variations` applies each one to a node,
+//! cooks it and restores the original values:
+//!
+//! ```rust,ignore
+//! let set = VariationSet::new(42)
+//!     .vary("roughness", VariationRange::Float { min: 0.1, max: 0.6 })
+//!     .vary("mode", VariationRange::Choice(vec!["Fast".into(), "Accurate".into()]));
+//! let results = unsafe { cook_variations(&mut handle, &params, &set.generate(8), &inputs, &cancel)? };
+//! for result in &results {
+//!     println!("{}: {}", result.variation.label, result.variation.describe());
+//! }
+//! ```
+//!
+//! Each value depends only on the seed, the variation index and the parameter
+//! name, so adding a parameter to a set does not change the values generated
+//! for the others.
+
+use crate::{
+    CancellationToken, NodeData, ParamDescriptor, ParamKind, ParamSet, PluginError, PluginNodeHandle, ProcessResult,
+};
+use std::collections::BTreeMap;
+
+/// Values a parameter takes across a variation set
+#[derive(Debug, Clone)]
+pub enum VariationRange {
+    /// Float between `min` and `max` (inclusive)
+    Float { min: f32, max: f32 },
+    /// Integer between `min` and `max` (inclusive)
+    Integer { min: i32, max: i32 },
+    /// Vector with each component between `min` and `max`
+    Vector3 { min: [f32; 3], max: [f32; 3] },
+    /// Color with each channel between `min` and `max`
+    Color { min: [f32; 4], max: [f32; 4] },
+    Boolean,
+    /// One of a list of values
+    Choice(Vec<NodeData>),
+}
+
+impl VariationRange {
+    /// Range covering a declared parameter
+    ///
+    /// Numbers use the declared range (0..=1 if none), choices use the
+    /// declared options. Returns None for strings, paths, frame ranges,
+    /// timecodes and gradients.
+    pub fn from_descriptor(descriptor: &ParamDescriptor) -> Option<Self> {
+        let min = descriptor.min.unwrap_or(0.0);
+        let max = descriptor.max.unwrap_or(1.0);
+        Some(match descriptor.kind {
+            ParamKind::Float => VariationRange::Float { min, max },
+            ParamKind::Integer => VariationRange::Integer {
+                min: min.ceil() as i32,
+                max: max.floor() as i32,
+            },
+            ParamKind::Boolean => VariationRange::Boolean,
+            ParamKind::Vector3 => VariationRange::Vector3 {
+                min: [min; 3],
+                max: [max; 3],
+            },
+            ParamKind::Color => VariationRange::Color {
+                min: [0.0; 4],
+                max: [1.0; 4],
+            },
+            ParamKind::Choice => VariationRange::Choice(
+                descriptor.options.iter().map(|option| NodeData::String(option.clone())).collect(),
+            ),
+            ParamKind::String
+            | ParamKind::Path
+            | ParamKind::FrameRange
+            | ParamKind::Timecode
+            | ParamKind::Gradient => return None,
+        })
+    }
+
+    /// Value at position `t` (0..=1) through the range
+    ///
+    /// Random variations sample `t` uniformly; sweeps step it evenly.
+    pub fn value_at(&self, t: f32) -> NodeData {
+        let t = t.clamp(0.0, 1.0);
+        let lerp = |min: f32, max: f32| min + (max - min) * t;
+        match self {
+            VariationRange::Float { min, max } => NodeData::Float(lerp(*min, *max)),
+            VariationRange::Integer { min, max } => NodeData::Integer(pick(*min as i64, *max as i64, t) as i32),
+            VariationRange::Vector3 { min, max } => NodeData::Vector3(std::array::from_fn(|i| lerp(min[i], max[i]))),
+            VariationRange::Color { min, max } => NodeData::Color(std::array::from_fn(|i| lerp(min[i], max[i]))),
+            VariationRange::Boolean => NodeData::Boolean(t >= 0.5),
+            VariationRange::Choice(values) if values.is_empty() => NodeData::None,
+            VariationRange::Choice(values) => values[pick(0, values.len() as i64 - 1, t) as usize].clone(),
+        }
+    }
+}
+
+/// Evenly split `min..=max` into buckets and return the one containing `t`
+fn pick(min: i64, max: i64, t: f32) -> i64 {
+    if max <= min {
+        return min;
+    }
+    let buckets = (max - min + 1) as f64;
+    min + ((t as f64 * buckets) as i64).min(max - min)
+}
+
+/// Ranges over chosen parameters of one node
+#[derive(Debug, Clone)]
+pub struct VariationSet {
+    /// Seed for random variations
+    pub seed: u64,
+    /// Range of each varied parameter
+    pub ranges: BTreeMap<String, VariationRange>,
+}
+
+impl VariationSet {
+    /// Create an empty set
+    pub fn new(seed: u64) -> Self {
+        Self {
+            seed,
+            ranges: BTreeMap::new(),
+        }
+    }
+
+    /// Vary a parameter over a range
+    pub fn vary(mut self, parameter: impl Into<String>, range: VariationRange) -> Self {
+        self.ranges.insert(parameter.into(), range);
+        self
+    }
+
+    /// Vary declared parameters over their declared ranges
+    ///
+    /// Names that are not declared, or cannot be varied, are ignored.
+    pub fn vary_declared(mut self, params: &ParamSet, names: &[&str]) -> Self {
+        for name in names {
+            if let Some(range) = params.get(name).and_then(VariationRange::from_descriptor) {
+                self.ranges.insert(name.to_string(), range);
+            }
+        }
+        self
+    }
+
+    /// Generate `count` random variations
+    pub fn generate(&self, count: usize) -> Vec<Variation> {
+        (0..count)
+            .map(|index| {
+                let seed = splitmix64(self.seed ^ (index as u64).wrapping_mul(0x9e37_79b9_7f4a_7c15));
+                let values = self
+                    .ranges
+                    .iter()
+                    .map(|(name, range)| {
+                        let bits = splitmix64(seed ^ crate::hashing::content_hash(name.as_str()));
+                        // Top 24 bits give a uniform float in [0, 1)
+                        let t = (bits >> 40) as f32 / (1u64 << 24) as f32;
+                        (name.clone(), range.value_at(t))
+                    })
+                    .collect();
+                Variation::new(index, seed, values)
+            })
+            .collect()
+    }
+
+    /// Generate `count` variations stepping every range evenly from min to max
+    pub fn sweep(&self, count: usize) -> Vec<Variation> {
+        (0..count)
+            .map(|index| {
+                let t = if count > 1 { index as f32 / (count - 1) as f32 } else { 0.0 };
+                let values = self
+                    .ranges
+                    .iter()
+                    .map(|(name, range)| (name.clone(), range.value_at(t)))
+                    .collect();
+                Variation::new(index, self.seed, values)
+            })
+            .collect()
+    }
+}
+
+/// One generated set of parameter values
+#[derive(Debug, Clone)]
+pub struct Variation {
+    /// Position in the generated list
+    pub index: usize,
+    /// Seed the values were generated from (regenerate with the set's seed and index)
+    pub seed: u64,
+    /// Short label (e.g. "v003")
+    pub label: String,
+    /// Value of each varied parameter
+    pub values: BTreeMap<String, NodeData>,
+}
+
+impl Variation {
+    fn new(index: usize, seed: u64, values: BTreeMap<String, NodeData>) -> Self {
+        Self {
+            index,
+            seed,
+            label: format!("v{:03}", index + 1),
+            values,
+        }
+    }
+
+    /// Human-readable summary of the values (e.g. "mode=Fast roughness=0.42")
+    pub fn describe(&self) -> String {
+        self.values
+            .iter()
+            .map(|(name, value)| format!("{}={}", name, describe_value(value)))
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+}
+
+fn describe_value(value: &NodeData) -> String {
+    let list = |values: &[f32]| values.iter().map(|v| format!("{:.3}", v)).collect::<Vec<_>>().join(",");
+    match value {
+        NodeData::Float(v) => format!("{:.3}", v),
+        NodeData::Integer(v) => v.to_string(),
+        NodeData::Boolean(v) => v.to_string(),
+        NodeData::String(v) => v.clone(),
+        NodeData::Vector3(v) => format!("({})", list(v)),
+        NodeData::Color(v) => format!("({})", list(v)),
+        other => other.type_name().to_string(),
+    }
+}
+
+/// Result of cooking one variation
+#[derive(Debug)]
+pub struct VariationResult {
+    pub variation: Variation,
+    /// Outputs of the cook, or why applying or cooking the variation failed
+    pub result: Result<ProcessResult, PluginError>,
+}
+
+/// Cook a node once per variation, restoring its parameters afterwards
+///
+/// Values are validated against `params` (unchecked if it is empty). A
+/// variation that fails validation or cooking is reported in its result and
+/// the remaining variations still run. Stops early, returning the results so
+/// far, when `cancel` is cancelled. If restoring a parameter fails, the others
+/// are still restored and the first error is returned.
+///
+/// # Safety
+/// Same requirements as [`PluginNodeHandle::as_node_mut`].
+pub unsafe fn cook_variations(
+    handle: &mut PluginNodeHandle,
+    params: &ParamSet,
+    variations: &[Variation],
+    inputs: &std::collections::HashMap<String, NodeData>,
+    cancel: &CancellationToken,
+) -> Result<Vec<VariationResult>, PluginError> {
+    let names: Vec<&String> = variations.iter().flat_map(|v| v.values.keys()).collect();
+    let mut originals = BTreeMap::new();
+    for name in names {
+        if !originals.contains_key(name) {
+            let value = handle.call("get_parameter", |n| n.get_parameter(name))?;
+            originals.insert(name.clone(), value);
+        }
+    }
+
+    let mut results = Vec::with_capacity(variations.len());
+    for variation in variations {
+        if cancel.is_cancelled() {
+            break;
+        }
+        let applied = variation
+            .values
+            .iter()
+            .try_for_each(|(name, value)| handle.safe_set_parameter(params, name, value.clone()));
+        let result = applied.and_then(|()| handle.safe_try_process(inputs));
+        results.push(VariationResult {
+            variation: variation.clone(),
+            result,
+        });
+    }
+
+    // Restore every parameter, even if an earlier one fails
+    let mut restore_error = None;
+    for (name, value) in originals {
+        if let Some(value) = value {
+            if let Err(e) = handle.call_mut("set_parameter", |n| n.set_parameter(&name, value)) {
+                restore_error.get_or_insert(e);
+            }
+        }
+    }
+    match restore_error {
+        Some(e) => Err(e),
+        None => Ok(results),
+    }
+}
+
+fn splitmix64(seed: u64) -> u64 {
+    let mut z = seed.wrapping_add(0x9e37_79b9_7f4a_7c15);
+    z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+    z ^ (z >> 31)
+}
@@ -5,6 +5,9 @@
 
 use serde::{Deserialize, Serialize};
 
+// Materials and lights are shared with node data; see data_types.rs
+pub use crate::data_types::{LightData, LightLinking, LightType, MaterialData};
+
 /// 3D camera state data
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct CameraData {
@@ -57,58 +60,9 @@ pub struct MeshData {
     pub material_id: Option<String>,
     /// Transformation matrix for this mesh
     pub transform: [[f32; 4]; 4],
-}
-
-/// Material data for 3D rendering
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct MaterialData {
-    /// Unique identifier for this material
-    pub id: String,
-    /// Material name
-    pub name: String,
-    /// Base color (RGBA)
-    pub base_color: [f32; 4],
-    /// Metallic factor (0.0 - 1.0)
-    pub metallic: f32,
-    /// Roughness factor (0.0 - 1.0)
-    pub roughness: f32,
-    /// Emission color (RGB)
-    pub emission: [f32; 3],
-    /// Texture paths
-    pub diffuse_texture: Option<String>,
-    pub normal_texture: Option<String>,
-    pub roughness_texture: Option<String>,
-    pub metallic_texture: Option<String>,
-}
-
-/// Light data for 3D scene
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct LightData {
-    /// Unique identifier for this light
-    pub id: String,
-    /// Light type
-    pub light_type: LightType,
-    /// Light position (for point/spot lights)
-    pub position: [f32; 3],
-    /// Light direction (for directional/spot lights)
-    pub direction: [f32; 3],
-    /// Light color (RGB)
-    pub color: [f32; 3],
-    /// Light intensity
-    pub intensity: f32,
-    /// Light range (for point/spot lights)
-    pub range: f32,
-    /// Spot light cone angle in radians
-    pub spot_angle: f32,
-}
-
-/// Types of lights
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub enum LightType {
-    Directional,
-    Point,
-    Spot,
-    Area,
+    /// Sub-frame samples for motion blur (see `MotionSample` for the layout convention)
+    #[serde(default)]
+    pub motion_samples: Vec<crate::data_types::MotionSample>,
 }
 
 /// Complete 3D scene data that plugins provide to the core for rendering
@@ -128,6 +82,15 @@ pub struct SceneData {
     pub bounding_box: Option<([f32; 3], [f32; 3])>,
 }
 
+impl SceneData {
+    /// Lights that illuminate a mesh, honoring light linking
+    /// 
+    /// Preview shading should only accumulate these lights for the mesh.
+    pub fn lights_for_mesh<'a>(&'a self, mesh: &'a MeshData) -> impl Iterator<Item = &'a LightData> + 'a {
+        self.lights.iter().filter(move |light| light.illuminates(&mesh.id))
+    }
+}
+
 impl Default for SceneData {
     fn default() -> Self {
         Self {
@@ -158,6 +121,9 @@ pub struct ViewportSettings {
     pub aa_samples: u32,
     /// Shading mode
     pub shading_mode: ShadingMode,
+    /// How scene-linear colors are turned into display colors
+    #[serde(default)]
+    pub color_management: ColorManagement,
 }
 
 /// Shading modes for viewport rendering
@@ -179,10 +145,84 @@ impl Default for ViewportSettings {
             show_ground_plane: true,
             aa_samples: 4,
             shading_mode: ShadingMode::Smooth,
+            color_management: ColorManagement::default(),
         }
     }
 }
 
+/// Transform from scene-linear to display colors
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub enum ViewTransform {
+    /// sRGB display encoding, values above 1.0 clip
+    #[default]
+    Standard,
+    /// No transform (inspect raw values, e.g. normals or IDs)
+    Raw,
+    /// OpenColorIO display/view from the host's config
+    /// 
+    /// Hosts without OCIO support fall back to `Standard`.
+    Ocio {
+        display: String,
+        view: String,
+        #[serde(default)]
+        look: Option<String>,
+    },
+}
+
+/// Viewport color management: exposure, view transform and gamma
+/// 
+/// Plugins deliver scene-linear colors; the core applies these settings when
+/// displaying them, so look-dev is judged on managed colors. `apply` is the
+/// reference implementation (without OCIO) for plugins that draw their own
+/// previews.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ColorManagement {
+    pub view_transform: ViewTransform,
+    /// Exposure adjustment in stops, applied before the view transform
+    pub exposure: f32,
+    /// Display gamma adjustment, applied after the view transform (1.0 = none)
+    pub gamma: f32,
+}
+
+impl Default for ColorManagement {
+    fn default() -> Self {
+        Self {
+            view_transform: ViewTransform::Standard,
+            exposure: 0.0,
+            gamma: 1.0,
+        }
+    }
+}
+
+impl ColorManagement {
+    /// Convert a color to display values
+    /// 
+    /// The color is first converted from `space` to linear sRGB. `Raw` data is
+    /// never color-converted, only exposed and gamma-adjusted. Alpha passes
+    /// through unchanged.
+    pub fn apply(&self, color: [f32; 4], space: crate::ColorSpace) -> [f32; 4] {
+        let linear = if space == crate::ColorSpace::Raw {
+            color
+        } else {
+            space.convert(color, crate::ColorSpace::LinearSrgb)
+        };
+        let scale = 2f32.powf(self.exposure);
+        let transform = |value: f32| {
+            let value = value * scale;
+            let value = match (&self.view_transform, space) {
+                (ViewTransform::Raw, _) | (_, crate::ColorSpace::Raw) => value,
+                _ => crate::color::linear_to_srgb(value.clamp(0.0, 1.0)),
+            };
+            if self.gamma > 0.0 && self.gamma != 1.0 {
+                value.max(0.0).powf(1.0 / self.gamma)
+            } else {
+                value
+            }
+        };
+        [transform(linear[0]), transform(linear[1]), transform(linear[2]), color[3]]
+    }
+}
+
 /// Complete viewport data that plugins provide to the core
 #[derive(Debug, Clone)]
 pub struct ViewportData {